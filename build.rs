@@ -1,27 +1,114 @@
-//! Emits two convenience `cfg` aliases so the source never has to repeat the
-//! SIMD feature matrix at every gate:
+//! Emits convenience `cfg` aliases so the source never has to repeat the SIMD
+//! feature matrix at every gate:
 //!
 //! * `unsafe_simd` — at least one kernel that uses `unsafe` is compiled in
-//!   (any x86 AVX kernel, or NEON on aarch64). When it is absent the crate is
+//!   (any x86 SIMD kernel, or NEON on aarch64). When it is absent the crate is
 //!   pure safe scalar Rust and carries `#![forbid(unsafe_code)]`.
-//! * `x86_simd` — at least one x86 AVX kernel is compiled in, i.e. runtime CPU
-//!   detection is needed.
+//! * `x86_ssse3` / `x86_avx2` / `x86_avx512bw` / `x86_avx512_vbmi` — that x86
+//!   kernel is compiled in.
+//! * `x86_simd` — at least one x86 SIMD kernel is compiled in.
+//! * `x86_static` — the most capable compiled-in x86 kernel is guaranteed by
+//!   the target, so dispatch is decided at compile time and no CPU detection
+//!   is compiled at all.
+//! * `x86_detect` — an x86 kernel is compiled in that the target does not
+//!   guarantee, i.e. runtime CPU detection is needed (`is_x86_feature_detected!`
+//!   with `std`, raw `cpuid` without).
+//! * `x86_cached` — the x86 tier is decided on first use and cached in a byte:
+//!   under `x86_detect`, or under `x86_static` with `std`, where the byte is
+//!   all that remains so the cap can still lower the guaranteed kernel.
+//! * `wasm_simd` — the `wasm-simd` feature is on and the target is `wasm32`
+//!   with `simd128` enabled. WASM has no runtime detection, so this is the
+//!   only way the kernel is ever compiled.
+//! * `portable_simd` — the `portable-simd` feature is on and the compiler is a
+//!   nightly one, so `core::simd` is there to build the portable kernel with.
+//!   On a stable compiler the feature does nothing (with a build warning), so
+//!   `--all-features` builds keep working there.
 
 fn main() {
-    println!("cargo::rustc-check-cfg=cfg(unsafe_simd)");
-    println!("cargo::rustc-check-cfg=cfg(x86_simd)");
+    for cfg in [
+        "unsafe_simd",
+        "x86_ssse3",
+        "x86_avx2",
+        "x86_avx512bw",
+        "x86_avx512_vbmi",
+        "x86_simd",
+        "x86_static",
+        "x86_detect",
+        "x86_cached",
+        "wasm_simd",
+        "portable_simd",
+    ] {
+        println!("cargo::rustc-check-cfg=cfg({cfg})");
+    }
 
     let arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let target_features = std::env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
     let feat = |name: &str| std::env::var_os(name).is_some();
+    let guaranteed = |names: &[&str]| {
+        names
+            .iter()
+            .all(|name| target_features.split(',').any(|f| f == *name))
+    };
 
     let x86 = matches!(arch.as_str(), "x86" | "x86_64");
-    let x86_simd = x86 && (feat("CARGO_FEATURE_AVX2") || feat("CARGO_FEATURE_AVX512_VBMI"));
-    let unsafe_simd = x86_simd || (arch == "aarch64" && feat("CARGO_FEATURE_NEON"));
+    let static_ssse3 = guaranteed(&["ssse3"]);
+    let static_avx2 = guaranteed(&["avx2"]);
+    let static_bw = guaranteed(&["avx512f", "avx512bw"]);
+    let static_vbmi = guaranteed(&["avx512f", "avx512bw", "avx512vbmi"]);
 
-    if x86_simd {
-        println!("cargo::rustc-cfg=x86_simd");
+    let ssse3 = x86 && feat("CARGO_FEATURE_SSSE3");
+    let avx2 = x86 && feat("CARGO_FEATURE_AVX2");
+    let bw = x86 && feat("CARGO_FEATURE_AVX512BW");
+    let vbmi = x86 && feat("CARGO_FEATURE_AVX512_VBMI");
+    let x86_simd = ssse3 || avx2 || bw || vbmi;
+    // Only the best kernel needs to be guaranteed: dispatch never looks past it.
+    let x86_static = if vbmi {
+        static_vbmi
+    } else if bw {
+        static_bw
+    } else if avx2 {
+        static_avx2
+    } else {
+        ssse3 && static_ssse3
+    };
+    let std = feat("CARGO_FEATURE_STD");
+    let wasm = arch == "wasm32" && guaranteed(&["simd128"]) && feat("CARGO_FEATURE_WASM_SIMD");
+    let portable = feat("CARGO_FEATURE_PORTABLE_SIMD") && nightly();
+    if feat("CARGO_FEATURE_PORTABLE_SIMD") && !portable {
+        println!("cargo::warning=`portable-simd` needs a nightly compiler; ignoring it");
     }
-    if unsafe_simd {
-        println!("cargo::rustc-cfg=unsafe_simd");
+    // The portable kernel itself is safe code, but dispatching to it goes
+    // through the same `unsafe` function-pointer tables as the others.
+    let unsafe_simd =
+        x86_simd || (arch == "aarch64" && feat("CARGO_FEATURE_NEON")) || wasm || portable;
+
+    for (cfg, on) in [
+        ("unsafe_simd", unsafe_simd),
+        ("x86_ssse3", ssse3),
+        ("x86_avx2", avx2),
+        ("x86_avx512bw", bw),
+        ("x86_avx512_vbmi", vbmi),
+        ("x86_simd", x86_simd),
+        ("x86_static", x86_static),
+        ("x86_detect", x86_simd && !x86_static),
+        ("x86_cached", x86_simd && (!x86_static || std)),
+        ("wasm_simd", wasm),
+        ("portable_simd", portable),
+    ] {
+        if on {
+            println!("cargo::rustc-cfg={cfg}");
+        }
     }
 }
+
+/// Whether `rustc` accepts `#![feature]`: a nightly or locally built compiler.
+fn nightly() -> bool {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    std::process::Command::new(rustc)
+        .arg("--version")
+        .output()
+        .is_ok_and(|out| {
+            let version = String::from_utf8_lossy(&out.stdout);
+            version.contains("-nightly") || version.contains("-dev")
+        })
+}
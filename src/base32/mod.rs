@@ -0,0 +1,397 @@
+//! Base32 encoding (RFC 4648 and three relatives), on the same
+//! kernels-and-dispatch footing as the Base64 [`Engine`](crate::Engine).
+//!
+//! | Engine | Alphabet | Padding | Decodes |
+//! | :--- | :--- | :---: | :--- |
+//! | [`BASE32`] / [`BASE32_NO_PAD`] | RFC 4648 `A-Z2-7` | yes / no | uppercase |
+//! | [`BASE32HEX`] / [`BASE32HEX_NO_PAD`] | RFC 4648 `0-9A-V` | yes / no | uppercase |
+//! | [`CROCKFORD`] | `0-9A-Z` less `ILOU` | no | either case, `I`/`L` as `1`, `O` as `0` |
+//! | [`ZBASE32`] | `ybndrfg8ejkmcpqxot1uwisza345h769` | no | lowercase |
+//!
+//! Each call runs the AVX512-VBMI kernel when that is the [`active_backend`]
+//! (or the backend pinned with [`Engine::with_backend`]), and the table-driven
+//! scalar kernel everywhere else.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use base64_turbo::base32::{BASE32, CROCKFORD};
+//!
+//! assert_eq!(BASE32.encode(b"hello"), "NBSWY3DP");
+//! assert_eq!(BASE32.encode(b"hi"), "NBUQ====");
+//! assert_eq!(CROCKFORD.decode("d1jprv3f").unwrap(), b"hello");
+//! # }
+//! ```
+
+use crate::{Backend, Error, Unsupported, active_backend};
+
+// Scalar implementation. The VBMI kernel lives with its Base64 sibling under
+// `crate::simd`, and hands its final group to this one.
+pub(crate) mod scalar;
+
+/// The four Base32 alphabets. Each engine pairs one with a padding choice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Alphabet {
+    /// RFC 4648 section 6.
+    Rfc4648,
+    /// RFC 4648 section 7, "base32hex": sorts like the data it encodes.
+    Hex,
+    /// Douglas Crockford's, which drops `I`, `L`, `O` and `U`.
+    Crockford,
+    /// z-base-32, ordered so the commonest characters are the easiest to read.
+    ZBase32,
+}
+
+impl Alphabet {
+    /// Every alphabet, in discriminant order, for tables indexed by one.
+    pub(crate) const ALL: [Self; 4] = [Self::Rfc4648, Self::Hex, Self::Crockford, Self::ZBase32];
+
+    /// The characters this alphabet encodes to, indexed by 5-bit value.
+    pub(crate) const fn symbols(self) -> &'static [u8; 32] {
+        match self {
+            Self::Rfc4648 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+            Self::Hex => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+            Self::Crockford => b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+            Self::ZBase32 => b"ybndrfg8ejkmcpqxot1uwisza345h769",
+        }
+    }
+}
+
+/// Compile-time reverse lookup for `alphabet`: ASCII back to 5-bit values,
+/// `0xFF` for anything else. Crockford's also takes lowercase and its
+/// look-alikes (`I`/`L` for `1`, `O` for `0`), as its spec asks of decoders.
+#[allow(clippy::cast_possible_truncation)] // `i` is always < 32, fits in u8
+pub(crate) const fn decode_table(alphabet: Alphabet) -> [u8; 256] {
+    let symbols = alphabet.symbols();
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 32 {
+        table[symbols[i] as usize] = i as u8;
+        if matches!(alphabet, Alphabet::Crockford) {
+            table[symbols[i].to_ascii_lowercase() as usize] = i as u8;
+        }
+        i += 1;
+    }
+    if matches!(alphabet, Alphabet::Crockford) {
+        let mut k = 0;
+        while k < 6 {
+            let (alias, digit) = (b"IiLlOo"[k], b"111100"[k]);
+            table[alias as usize] = table[digit as usize];
+            k += 1;
+        }
+    }
+    table
+}
+
+/// Internal configuration for a Base32 engine, the counterpart of the Base64
+/// `Config`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Config {
+    /// Which of the four alphabets to encode to and decode from.
+    pub alphabet: Alphabet,
+    /// If true, pads the output to a multiple of 8 with `=`, and requires that
+    /// padding when decoding.
+    pub padding: bool,
+}
+
+/// A stateless Base32 encoder/decoder.
+///
+/// The Base32 counterpart of the Base64 [`Engine`](crate::Engine), with the
+/// same zero-allocation and allocating APIs and the same [`Error`] reporting.
+///
+/// # Examples
+///
+/// ```rust
+/// use base64_turbo::base32::BASE32_NO_PAD;
+///
+/// let mut buf = [0u8; 8];
+/// let n = BASE32_NO_PAD.encode_into(b"hi", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"NBUQ");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Engine {
+    pub(crate) config: Config,
+    /// The kernel pinned by [`Engine::with_backend`]; `None` follows
+    /// [`active_backend`].
+    pub(crate) backend: Option<Backend>,
+}
+
+// ======================================================================
+// Pre-defined Engines
+// ======================================================================
+
+/// Standard Base32 (RFC 4648) with padding (`=`), as TOTP secrets and most
+/// other uses expect.
+pub const BASE32: Engine = Engine {
+    config: Config {
+        alphabet: Alphabet::Rfc4648,
+        padding: true,
+    },
+    backend: None,
+};
+
+/// Standard Base32 (RFC 4648) **without** padding.
+pub const BASE32_NO_PAD: Engine = Engine {
+    config: Config {
+        alphabet: Alphabet::Rfc4648,
+        padding: false,
+    },
+    backend: None,
+};
+
+/// "Extended Hex" Base32 (RFC 4648 section 7) with padding. Encoded strings
+/// sort in the same order as the data, which is why DNSSEC (NSEC3) uses it.
+pub const BASE32HEX: Engine = Engine {
+    config: Config {
+        alphabet: Alphabet::Hex,
+        padding: true,
+    },
+    backend: None,
+};
+
+/// "Extended Hex" Base32 **without** padding, as DNS labels carry it.
+pub const BASE32HEX_NO_PAD: Engine = Engine {
+    config: Config {
+        alphabet: Alphabet::Hex,
+        padding: false,
+    },
+    backend: None,
+};
+
+/// Crockford's Base32, unpadded. Decoding ignores case and reads `I`/`L` as
+/// `1` and `O` as `0`; the optional hyphens and check symbol are not part of
+/// this engine.
+pub const CROCKFORD: Engine = Engine {
+    config: Config {
+        alphabet: Alphabet::Crockford,
+        padding: false,
+    },
+    backend: None,
+};
+
+/// z-base-32, unpadded. Encodes whole bytes; the bit-length form of the spec is
+/// not supported.
+pub const ZBASE32: Engine = Engine {
+    config: Config {
+        alphabet: Alphabet::ZBase32,
+        padding: false,
+    },
+    backend: None,
+};
+
+impl Engine {
+    // ======================================================================
+    // Backend Selection
+    // ======================================================================
+
+    /// Returns a copy of this engine that always runs `backend`'s kernel,
+    /// instead of following [`active_backend`].
+    ///
+    /// The check is the one [`Engine::with_backend`](crate::Engine::with_backend)
+    /// makes. Only [`Backend::Avx512Vbmi`] has a Base32 kernel of its own; every
+    /// other backend runs scalar.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Unsupported`] if `backend` is not compiled into this build or
+    /// the current CPU cannot run it.
+    #[inline]
+    pub fn with_backend(self, backend: Backend) -> Result<Self, Unsupported> {
+        Ok(Self {
+            backend: backend.pin()?,
+            ..self
+        })
+    }
+
+    /// The backend this engine dispatches to: the one pinned by
+    /// [`with_backend`](Self::with_backend), or [`active_backend`] otherwise.
+    #[inline]
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend.unwrap_or_else(active_backend)
+    }
+
+    // ======================================================================
+    // Length Calculators
+    // ======================================================================
+
+    /// Calculates the exact buffer size required to encode `input_len` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base32::{BASE32, BASE32_NO_PAD};
+    ///
+    /// assert_eq!(BASE32.encoded_len(5), 8);
+    /// assert_eq!(BASE32.encoded_len(1), 8); // With padding
+    /// assert_eq!(BASE32_NO_PAD.encoded_len(1), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        if self.config.padding {
+            input_len.div_ceil(5) * 8
+        } else {
+            (input_len * 8).div_ceil(5)
+        }
+    }
+
+    /// Calculates the **maximum** buffer size required to decode `input_len`
+    /// bytes.
+    ///
+    /// Exact for unpadded input; padded input decodes to a few bytes less. Rely
+    /// on the `usize` returned by [`decode_into`](Self::decode_into) for the
+    /// valid slice of the output buffer.
+    #[inline]
+    #[must_use]
+    pub const fn estimate_decoded_len(&self, input_len: usize) -> usize {
+        // `input_len * 5 / 8` without the overflow.
+        input_len / 8 * 5 + input_len % 8 * 5 / 8
+    }
+
+    // ======================================================================
+    // Zero-Allocation APIs
+    // ======================================================================
+
+    /// Encodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error::BufferTooSmall)`: If `output.len()` is less than [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is not large enough to hold the
+    /// encoded data (see [`encoded_len`](Self::encoded_len)).
+    #[inline]
+    pub fn encode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::encoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Self::encode_dispatch(self, input, &mut output[..req_len]);
+
+        Ok(req_len)
+    }
+
+    /// Decodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is shorter than
+    /// [`estimate_decoded_len`](Self::estimate_decoded_len), or
+    /// [`Error::InvalidLength`] / [`Error::InvalidCharacter`] if `input` is not
+    /// valid Base32 for this engine.
+    #[inline]
+    pub fn decode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::estimate_decoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Self::decode_dispatch(self, input, &mut output[..req_len])
+    }
+
+    // ========================================================================
+    // Allocating APIs (std)
+    // ========================================================================
+
+    /// Allocates a new `String` and encodes the input data into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base32::BASE32HEX;
+    /// assert_eq!(BASE32HEX.encode(b"hello"), "D1IMOR3F");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::encode`: the length is exact, every byte is
+        // overwritten, and the output is pure ASCII.
+        let mut out = crate::spare(Self::encoded_len(self, input.len()));
+        Self::encode_dispatch(self, input, &mut out);
+        crate::into_ascii_string(out)
+    }
+
+    /// Allocates a new `Vec<u8>` and decodes the input data into it.
+    ///
+    /// # Errors
+    /// Returns `Error` if the input contains invalid characters or has an invalid length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base32::ZBASE32;
+    /// assert_eq!(ZBASE32.decode("pb1sa5dx").unwrap(), b"hello");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::decode`: `truncate` drops the unwritten tail,
+        // and on error the whole buffer is dropped unread.
+        let mut out = crate::spare(Self::estimate_decoded_len(self, input.len()));
+        let written = Self::decode_into(self, input, &mut out)?;
+        out.truncate(written);
+        Ok(out)
+    }
+
+    // ========================================================================
+    // Internal Dispatchers
+    // ========================================================================
+
+    // As for hex, a match on the backend is all the dispatch Base32 needs. The
+    // 256-bit VBMI backend stays on scalar: it exists to keep code off ZMM
+    // registers, and the Base32 kernel is ZMM-only.
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn encode_dispatch(&self, input: &[u8], dst: &mut [u8]) {
+        match self.backend() {
+            // SAFETY: the backend is supported, so the CPU has VBMI.
+            #[cfg(x86_avx512_vbmi)]
+            Backend::Avx512Vbmi => unsafe {
+                crate::simd::encode_base32_avx512_vbmi(&self.config, input, dst);
+            },
+            _ => scalar::encode_slice(&self.config, input, dst),
+        }
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn decode_dispatch(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+        match self.backend() {
+            // SAFETY: as in `encode_dispatch`.
+            #[cfg(x86_avx512_vbmi)]
+            Backend::Avx512Vbmi => unsafe {
+                crate::simd::decode_base32_avx512_vbmi(&self.config, input, dst)
+            },
+            _ => scalar::decode_slice(&self.config, input, dst),
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Scalar (non-SIMD) Base32 encode/decode primitives.
+//!
+//! Like the Base64 scalar kernel, this is **100% safe Rust** and table-driven.
+//! Encode maps 10 input bits straight to the two characters they encode, so a
+//! 5-byte group is four lookups. Decode keeps one narrow table per alphabet:
+//! Base32's 8-character groups would need 16 KiB of pre-shifted tables per
+//! alphabet to match the Base64 trick, so a group is eight lookups folded into
+//! a 40-bit accumulator instead, with validation falling out of OR-ing the
+//! looked-up values together. The VBMI kernel hands its final group here, so
+//! it is also the reference that kernel is tested against.
+
+#![forbid(unsafe_code)]
+// Kept by-reference to match the Base64 kernels and the VBMI one.
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use super::{Alphabet, Config, decode_table};
+use crate::Error;
+
+/// Maps a 10-bit value to the two characters it encodes, packed little-endian
+/// so the first character lands in the low byte.
+const fn encode_pair_table(symbols: &[u8; 32]) -> [u16; 1024] {
+    let mut table = [0u16; 1024];
+    let mut i = 0;
+    while i < 1024 {
+        table[i] = (symbols[i >> 5] as u16) | ((symbols[i & 0x1F] as u16) << 8);
+        i += 1;
+    }
+    table
+}
+
+/// The pair tables, indexed by `Alphabet as usize`.
+static ENCODE_PAIRS: [[u16; 1024]; 4] = [
+    encode_pair_table(Alphabet::ALL[0].symbols()),
+    encode_pair_table(Alphabet::ALL[1].symbols()),
+    encode_pair_table(Alphabet::ALL[2].symbols()),
+    encode_pair_table(Alphabet::ALL[3].symbols()),
+];
+
+/// The reverse lookups, indexed by `Alphabet as usize`.
+static DECODE_TABLES: [[u8; 256]; 4] = [
+    decode_table(Alphabet::ALL[0]),
+    decode_table(Alphabet::ALL[1]),
+    decode_table(Alphabet::ALL[2]),
+    decode_table(Alphabet::ALL[3]),
+];
+
+/// The eight characters a 40-bit group encodes to, first character in the
+/// low byte.
+#[inline]
+#[allow(clippy::cast_possible_truncation)] // every index is masked to 10 bits
+fn encode_group(pairs: &[u16; 1024], n: u64) -> [u8; 8] {
+    let pair = |shift: u32| u64::from(pairs[((n >> shift) & 0x3FF) as usize]);
+    (pair(30) | (pair(20) << 16) | (pair(10) << 32) | (pair(0) << 48)).to_le_bytes()
+}
+
+/// Encodes `input` into Base32, writing the result into `dst`.
+///
+/// `dst` must be at least the encoded length for `input`:
+/// * padded:   `input.len().div_ceil(5) * 8`
+/// * unpadded: `(input.len() * 8).div_ceil(5)`
+///
+/// A `dst` that is too small will panic (bounds check) rather than corrupt
+/// memory.
+#[inline]
+pub(crate) fn encode_slice(config: &Config, input: &[u8], dst: &mut [u8]) {
+    let pairs = &ENCODE_PAIRS[config.alphabet as usize];
+
+    let groups = input.len() / 5;
+    let (in_main, in_tail) = input.split_at(groups * 5);
+    let (out_main, out_tail) = dst.split_at_mut(groups * 8);
+
+    // --- MAIN LOOP ---
+    // Process 5 input bytes -> 8 output characters per iteration.
+    for (chunk, out) in in_main.chunks_exact(5).zip(out_main.chunks_exact_mut(8)) {
+        let mut word = [0u8; 8];
+        word[3..].copy_from_slice(chunk);
+        out.copy_from_slice(&encode_group(pairs, u64::from_be_bytes(word)));
+    }
+
+    // --- TAIL HANDLING ---
+    // 1-4 bytes, zero-filled to a group: its first `ceil(8 * rem / 5)`
+    // characters are exact, and padding fills out the rest.
+    let rem = in_tail.len();
+    if rem > 0 {
+        let mut word = [0u8; 8];
+        word[3..3 + rem].copy_from_slice(in_tail);
+        let chars = (rem * 8).div_ceil(5);
+        out_tail[..chars].copy_from_slice(&encode_group(pairs, u64::from_be_bytes(word))[..chars]);
+        if config.padding {
+            out_tail[chars..8].fill(b'=');
+        }
+    }
+}
+
+/// Decodes a Base32 `input` into `dst`, returning the number of bytes written.
+///
+/// Writes exactly the decoded bytes, so `dst` only needs to be as large as the
+/// true decoded length. A `dst` that is too small will panic (bounds check)
+/// rather than corrupt memory.
+///
+/// # Errors
+/// Returns [`Error::InvalidLength`] for a length no Base32 string can have
+/// under `config`, and [`Error::InvalidCharacter`] for a character outside its
+/// alphabet (including `=` anywhere but the padding of the final group).
+#[inline]
+pub(crate) fn decode_slice(config: &Config, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+    let len = input.len();
+    if len == 0 {
+        return Ok(0);
+    }
+    if config.padding && !len.is_multiple_of(8) {
+        return Err(Error::InvalidLength);
+    }
+
+    let table = &DECODE_TABLES[config.alphabet as usize];
+
+    // Every group but the last, which may be partial or padded.
+    let body = (len - 1) / 8 * 8;
+    let out_body = body / 8 * 5;
+
+    // --- FAST LOOP ---
+    for (chars, out) in input[..body]
+        .chunks_exact(8)
+        .zip(dst[..out_body].chunks_exact_mut(5))
+    {
+        // Invalid characters map to 0xFF, so any of them sets a bit above the
+        // five a valid value occupies.
+        let (n, bad) = chars.iter().fold((0u64, 0u8), |(n, bad), &c| {
+            let v = table[usize::from(c)];
+            ((n << 5) | u64::from(v), bad | v)
+        });
+        if bad > 0x1F {
+            return Err(Error::InvalidCharacter);
+        }
+        out.copy_from_slice(&n.to_be_bytes()[3..]);
+    }
+
+    let written = decode_final_group(config, table, &input[body..], &mut dst[out_body..])?;
+    Ok(out_body + written)
+}
+
+/// Decodes the final 1-8 characters of a Base32 string, padding included, and
+/// returns the number of bytes written.
+#[inline]
+fn decode_final_group(
+    config: &Config,
+    table: &[u8; 256],
+    group: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    let data = if config.padding {
+        let pad = group.iter().rev().take_while(|&&c| c == b'=').count();
+        &group[..group.len() - pad]
+    } else {
+        group
+    };
+
+    // Only these character counts end on a whole byte (the padded forms are
+    // `8 - count` `=`s).
+    let bytes = match data.len() {
+        2 => 1,
+        4 => 2,
+        5 => 3,
+        7 => 4,
+        8 => 5,
+        _ => return Err(Error::InvalidLength),
+    };
+
+    let mut bad = 0u8;
+    let mut n = 0u64;
+    for &c in data {
+        let v = table[usize::from(c)];
+        bad |= v;
+        n = (n << 5) | u64::from(v);
+    }
+    if bad > 0x1F {
+        return Err(Error::InvalidCharacter);
+    }
+
+    // Left-align the bits read to a 40-bit group; the first `bytes` are data.
+    n <<= 5 * (8 - data.len());
+    dst[..bytes].copy_from_slice(&n.to_be_bytes()[3..3 + bytes]);
+    Ok(bytes)
+}
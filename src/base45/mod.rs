@@ -0,0 +1,209 @@
+//! Base45 encoding (RFC 9285), with the same API and [`Error`] reporting as
+//! the Base64 [`Engine`](crate::Engine).
+//!
+//! Base45 packs two bytes into three characters of the 45 a QR code's
+//! alphanumeric mode can hold (`0-9A-Z`, space and `$%*+-./:`), which is
+//! what EU digital COVID certificates and other QR payloads use it for. A
+//! final odd byte takes two characters. Digits run least significant first,
+//! and decoding rejects any triplet above 65535, or final pair above 255, as
+//! RFC 9285 requires.
+//!
+//! The whole module is safe scalar code under `#![forbid(unsafe_code)]`, on
+//! every build: QR scanners decode a few hundred bytes at a time, where a SIMD
+//! kernel would not pay for the `unsafe` it brings.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use base64_turbo::base45::BASE45;
+//!
+//! assert_eq!(BASE45.encode(b"Hello!!"), "%69 VD92EX0");
+//! assert_eq!(BASE45.decode("QED8WEX0").unwrap(), b"ietf!");
+//! # }
+//! ```
+
+#![forbid(unsafe_code)]
+
+use crate::Error;
+
+// Scalar implementation, the only one Base45 has.
+pub(crate) mod scalar;
+
+/// A stateless Base45 encoder/decoder.
+///
+/// The Base45 counterpart of the Base64 [`Engine`](crate::Engine), with the
+/// same zero-allocation and allocating APIs. RFC 9285 fixes the alphabet and
+/// has no padding, so [`BASE45`] is the only engine, and both length
+/// calculators are exact.
+///
+/// # Examples
+///
+/// ```rust
+/// use base64_turbo::base45::BASE45;
+///
+/// let mut buf = [0u8; 6];
+/// let n = BASE45.encode_into(b"AB", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"BB8");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Engine(());
+
+/// Base45 as RFC 9285 defines it.
+pub const BASE45: Engine = Engine(());
+
+impl Engine {
+    // ======================================================================
+    // Length Calculators
+    // ======================================================================
+
+    /// The exact number of characters `input_len` bytes encode to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base45::BASE45;
+    ///
+    /// assert_eq!(BASE45.encoded_len(2), 3);
+    /// assert_eq!(BASE45.encoded_len(5), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        input_len / 2 * 3 + input_len % 2 * 2
+    }
+
+    /// The exact number of bytes `input_len` characters decode to. A length
+    /// that leaves one character over is rounded down, though decoding it
+    /// fails with [`Error::InvalidLength`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base45::BASE45;
+    ///
+    /// assert_eq!(BASE45.decoded_len(3), 2);
+    /// assert_eq!(BASE45.decoded_len(8), 5);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn decoded_len(&self, input_len: usize) -> usize {
+        input_len / 3 * 2 + input_len % 3 / 2
+    }
+
+    // ======================================================================
+    // Zero-Allocation APIs
+    // ======================================================================
+
+    /// Encodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error::BufferTooSmall)`: If `output.len()` is less than [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is not large enough to hold the
+    /// encoded data (see [`encoded_len`](Self::encoded_len)).
+    #[inline]
+    pub fn encode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::encoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        scalar::encode_slice(input, &mut output[..req_len]);
+
+        Ok(req_len)
+    }
+
+    /// Decodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if `input.len() % 3 == 1`,
+    /// [`Error::BufferTooSmall`] if `output` is shorter than
+    /// [`decoded_len`](Self::decoded_len), and [`Error::InvalidCharacter`] for
+    /// a character outside the alphabet or a group whose value is out of
+    /// range.
+    #[inline]
+    pub fn decode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len % 3 == 1 {
+            return Err(Error::InvalidLength);
+        }
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::decoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        scalar::decode_slice(input, &mut output[..req_len])?;
+
+        Ok(req_len)
+    }
+
+    // ========================================================================
+    // Allocating APIs (std)
+    // ========================================================================
+
+    /// Allocates a new `String` and encodes the input data into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base45::BASE45;
+    /// assert_eq!(BASE45.encode(b"ietf!"), "QED8WEX0");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::encode`: the length is exact, every byte is
+        // overwritten, and the output is pure ASCII.
+        let mut out = crate::spare(Self::encoded_len(self, input.len()));
+        scalar::encode_slice(input, &mut out);
+        crate::into_ascii_string(out)
+    }
+
+    /// Allocates a new `Vec<u8>` and decodes the input data into it.
+    ///
+    /// # Errors
+    /// Returns `Error` if the input contains invalid characters or has an invalid length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base45::BASE45;
+    /// assert_eq!(BASE45.decode("%69 VD92EX0").unwrap(), b"Hello!!");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::decode`: on error the whole buffer is dropped
+        // unread.
+        let mut out = crate::spare(Self::decoded_len(self, input.len()));
+        Self::decode_into(self, input, &mut out)?;
+        Ok(out)
+    }
+}
@@ -0,0 +1,90 @@
+//! Scalar Base45 encode/decode primitives.
+//!
+//! Encode splits each 16-bit group into three base-45 digits by constant
+//! division. Decode looks each character up, keeps the largest value so one
+//! compare catches any character outside the alphabet, and range-checks the
+//! group it rebuilds.
+
+use crate::Error;
+
+/// The 45 characters of a QR code's alphanumeric mode, indexed by digit value.
+const ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Compile-time reverse lookup: ASCII back to digit values, `0xFF` for
+/// anything else.
+#[allow(clippy::cast_possible_truncation)] // `i` is always < 45, fits in u8
+static DECODE_TABLE: [u8; 256] = {
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 45 {
+        table[ALPHABET[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+};
+
+/// Encodes `input` into Base45, writing the result into `dst`.
+///
+/// `dst` must be at least `input.len() / 2 * 3 + input.len() % 2 * 2` bytes; a
+/// `dst` that is too small will panic (bounds check) rather than corrupt
+/// memory.
+#[inline]
+pub(crate) fn encode_slice(input: &[u8], dst: &mut [u8]) {
+    let (pairs, tail) = input.as_chunks::<2>();
+    let (out_main, out_tail) = dst.split_at_mut(pairs.len() * 3);
+
+    // --- MAIN LOOP ---
+    // Process 2 input bytes -> 3 output characters, least significant first.
+    for (&pair, out) in pairs.iter().zip(out_main.chunks_exact_mut(3)) {
+        let n = usize::from(u16::from_be_bytes(pair));
+        out.copy_from_slice(&[ALPHABET[n % 45], ALPHABET[n / 45 % 45], ALPHABET[n / 2025]]);
+    }
+
+    // --- TAIL HANDLING ---
+    // A final odd byte is two characters.
+    if let [byte] = *tail {
+        let n = usize::from(byte);
+        out_tail[..2].copy_from_slice(&[ALPHABET[n % 45], ALPHABET[n / 45]]);
+    }
+}
+
+/// Decodes a Base45 `input` into `dst`, whose length must be exactly the
+/// decoded length of `input`.
+///
+/// `input.len() % 3` must not be 1; a length mismatch will panic (bounds
+/// check) rather than corrupt memory.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the alphabet, a
+/// triplet above 65535 or a final pair above 255.
+#[inline]
+pub(crate) fn decode_slice(input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    let (triplets, tail) = input.as_chunks::<3>();
+    let (out_main, out_tail) = dst.split_at_mut(triplets.len() * 2);
+
+    // The digits of a group, least significant first, folded into its value.
+    let value = |chars: &[u8]| -> Result<u32, Error> {
+        let (n, max) = chars.iter().rev().fold((0u32, 0u8), |(n, max), &c| {
+            let v = DECODE_TABLE[usize::from(c)];
+            (n * 45 + u32::from(v), max.max(v))
+        });
+        if max < 45 {
+            Ok(n)
+        } else {
+            Err(Error::InvalidCharacter)
+        }
+    };
+
+    // --- FAST LOOP ---
+    for (chars, out) in triplets.iter().zip(out_main.as_chunks_mut::<2>().0) {
+        let n = u16::try_from(value(chars)?).map_err(|_| Error::InvalidCharacter)?;
+        *out = n.to_be_bytes();
+    }
+
+    // --- TAIL HANDLING ---
+    // A final pair is one byte.
+    if !tail.is_empty() {
+        out_tail[0] = u8::try_from(value(tail)?).map_err(|_| Error::InvalidCharacter)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,316 @@
+//! Base85 encoding (Z85, Adobe Ascii85 and RFC 1924), with the same API and
+//! [`Error`] reporting as the Base64 [`Engine`](crate::Engine).
+//!
+//! | Engine | Alphabet | Framing | Shortcuts |
+//! | :--- | :--- | :---: | :--- |
+//! | [`Z85`] | `ZeroMQ`'s `0-9a-zA-Z.-:+=^!/*?&<>()[]{}@%$#` | none | none |
+//! | [`ASCII85`] | `!`-`u` | `<~` … `~>`, optional when decoding | `z` for a zero group |
+//! | [`RFC1924`] | `0-9A-Za-z` and 23 punctuation characters | none | none |
+//!
+//! All three encode each 4-byte group as five base-85 digits, most
+//! significant first. Z85 only defines whole groups, so it takes binary in
+//! multiples of 4 bytes and text in multiples of 5 characters, and reports
+//! [`Error::InvalidLength`] for anything else. Ascii85 and git's/Python's RFC
+//! 1924 variant allow a final group of `n` < 4 bytes: it is zero-filled and
+//! cut to its first `n + 1` digits, and decoding restores the cut digits as
+//! the highest one (`84`). Any group whose value overflows 32 bits is
+//! rejected.
+//!
+//! Base85 has no SIMD kernel here: the conversion is a division chain per
+//! group rather than a table shuffle, so every backend runs the same safe
+//! scalar code.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use base64_turbo::base85::{ASCII85, RFC1924, Z85};
+//!
+//! assert_eq!(Z85.encode(b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B").unwrap(), "HelloWorld");
+//! assert_eq!(ASCII85.encode(b"\0\0\0\0hi").unwrap(), "<~zBP@~>");
+//! assert_eq!(RFC1924.decode("Xk~0{Zv").unwrap(), b"hello");
+//! # }
+//! ```
+
+use crate::Error;
+
+// Scalar implementation, the only one Base85 has.
+pub(crate) mod scalar;
+
+/// The three Base85 alphabets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Alphabet {
+    /// `ZeroMQ` RFC 32: source-code safe, no quotes or backslash, whole
+    /// groups only.
+    Z85,
+    /// Adobe's (PostScript and PDF): the 85 characters from `!` up.
+    Ascii85,
+    /// RFC 1924, as git binary patches and Python's `b85encode` use it.
+    Rfc1924,
+}
+
+impl Alphabet {
+    /// Every alphabet, in discriminant order, for tables indexed by one.
+    pub(crate) const ALL: [Self; 3] = [Self::Z85, Self::Ascii85, Self::Rfc1924];
+
+    /// The characters this alphabet encodes to, indexed by digit value.
+    pub(crate) const fn symbols(self) -> &'static [u8; 85] {
+        match self {
+            Self::Z85 => {
+                b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#"
+            }
+            Self::Ascii85 => {
+                b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu"
+            }
+            Self::Rfc1924 => {
+                b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~"
+            }
+        }
+    }
+}
+
+/// A stateless Base85 encoder/decoder.
+///
+/// The Base85 counterpart of the Base64 [`Engine`](crate::Engine), with the
+/// same zero-allocation and allocating APIs and the same [`Error`] reporting.
+///
+/// # Examples
+///
+/// ```rust
+/// use base64_turbo::base85::Z85;
+///
+/// let mut buf = [0u8; 5];
+/// let n = Z85.encode_into(b"\x86\x4F\xD2\x6F", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"Hello");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Engine {
+    pub(crate) alphabet: Alphabet,
+}
+
+// ======================================================================
+// Pre-defined Engines
+// ======================================================================
+
+/// Z85 (`ZeroMQ` RFC 32), as CURVE keys are printed.
+///
+/// Like the spec, takes only whole groups: binary a multiple of 4 bytes long
+/// and text a multiple of 5 characters long, [`Error::InvalidLength`]
+/// otherwise.
+pub const Z85: Engine = Engine {
+    alphabet: Alphabet::Z85,
+};
+
+/// Adobe Ascii85, as PDF and PostScript streams carry it.
+///
+/// Encodes inside `<~` … `~>` and writes an all-zero group as `z`. Decoding
+/// takes the input framed or not: as PDF streams omit the `<~` and many
+/// producers the `~>`, neither is required. It accepts `z` between groups and
+/// skips whitespace anywhere, as Adobe's spec asks of decoders.
+pub const ASCII85: Engine = Engine {
+    alphabet: Alphabet::Ascii85,
+};
+
+/// RFC 1924's alphabet, as git binary patches and Python's `b85encode` use it.
+pub const RFC1924: Engine = Engine {
+    alphabet: Alphabet::Rfc1924,
+};
+
+impl Engine {
+    // ======================================================================
+    // Length Calculators
+    // ======================================================================
+
+    /// Calculates the buffer size required to encode `input_len` bytes.
+    ///
+    /// Exact for [`Z85`] (of whole groups) and [`RFC1924`]. For [`ASCII85`]
+    /// it includes the four framing characters and is a maximum, as each `z`
+    /// saves four; rely on the `usize` returned by
+    /// [`encode_into`](Self::encode_into) there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base85::{ASCII85, RFC1924, Z85};
+    ///
+    /// assert_eq!(Z85.encoded_len(8), 10);
+    /// assert_eq!(ASCII85.encoded_len(8), 14);
+    /// assert_eq!(RFC1924.encoded_len(5), 7); // A 1-byte group takes 2 digits
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        let rem = input_len % 4;
+        let digits = input_len / 4 * 5 + if rem == 0 { 0 } else { rem + 1 };
+        match self.alphabet {
+            Alphabet::Ascii85 => digits + 4,
+            Alphabet::Z85 | Alphabet::Rfc1924 => digits,
+        }
+    }
+
+    /// Calculates the **maximum** buffer size required to decode `input_len`
+    /// bytes.
+    ///
+    /// Exact for [`Z85`] and [`RFC1924`] input of a valid length. [`ASCII85`]
+    /// input decodes to less, since its framing and any whitespace carry no
+    /// data, except where it uses `z`: each one decodes to four bytes, and
+    /// [`decode_into`](Self::decode_into) reports [`Error::BufferTooSmall`]
+    /// if those outgrow `output`. The allocating [`decode`](Self::decode)
+    /// sizes its buffer for them.
+    #[inline]
+    #[must_use]
+    pub const fn estimate_decoded_len(&self, input_len: usize) -> usize {
+        let rem = input_len % 5;
+        input_len / 5 * 4 + if rem == 0 { 0 } else { rem - 1 }
+    }
+
+    // ======================================================================
+    // Zero-Allocation APIs
+    // ======================================================================
+
+    /// Encodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is not whole groups for [`Z85`] or the
+    ///   buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if this is [`Z85`] and `input` is not
+    /// a multiple of 4 bytes long, and [`Error::BufferTooSmall`] if `output`
+    /// is not large enough to hold the encoded data (see
+    /// [`encoded_len`](Self::encoded_len)).
+    #[inline]
+    pub fn encode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if self.alphabet == Alphabet::Z85 && !len.is_multiple_of(4) {
+            return Err(Error::InvalidLength);
+        }
+        if len == 0 && self.alphabet != Alphabet::Ascii85 {
+            return Ok(0);
+        }
+
+        let req_len = Self::encoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Ok(scalar::encode_slice(
+            self.alphabet,
+            input,
+            &mut output[..req_len],
+        ))
+    }
+
+    /// Decodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is shorter than
+    /// [`estimate_decoded_len`](Self::estimate_decoded_len) (or, for
+    /// [`ASCII85`], than the data its `z`s expand to), [`Error::InvalidLength`]
+    /// for a final group of a single digit or [`Z85`] input that is not a
+    /// multiple of 5 characters long, and [`Error::InvalidCharacter`] for a
+    /// character outside the alphabet or a group whose value does not fit in
+    /// 32 bits.
+    #[inline]
+    pub fn decode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if self.alphabet == Alphabet::Z85 && !len.is_multiple_of(5) {
+            return Err(Error::InvalidLength);
+        }
+        if len == 0 && self.alphabet != Alphabet::Ascii85 {
+            return Ok(0);
+        }
+
+        let req_len = Self::estimate_decoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        match self.alphabet {
+            Alphabet::Ascii85 => scalar::decode_ascii85(input, output),
+            Alphabet::Z85 | Alphabet::Rfc1924 => {
+                scalar::decode_slice(self.alphabet, input, &mut output[..req_len])
+            }
+        }
+    }
+
+    // ========================================================================
+    // Allocating APIs (std)
+    // ========================================================================
+
+    /// Allocates a new `String` and encodes the input data into it.
+    ///
+    /// Unlike the other codecs' `encode`, this returns a `Result`, as [`Z85`]
+    /// cannot encode a partial group.
+    ///
+    /// # Errors
+    /// Returns [`Error::InvalidLength`] if this is [`Z85`] and `input` is not
+    /// a multiple of 4 bytes long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::{Error, base85::{ASCII85, Z85}};
+    /// assert_eq!(ASCII85.encode(b"hello").unwrap(), "<~BOu!rDZ~>");
+    /// assert_eq!(Z85.encode(b"hello"), Err(Error::InvalidLength));
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> Result<String, Error> {
+        let input = input.as_ref();
+        if self.alphabet == Alphabet::Z85 && !input.len().is_multiple_of(4) {
+            return Err(Error::InvalidLength);
+        }
+
+        // As in `crate::Engine::encode`, but `z` can end Ascii85 short of the
+        // estimate: `truncate` drops the unwritten tail.
+        let mut out = crate::spare(Self::encoded_len(self, input.len()));
+        let written = scalar::encode_slice(self.alphabet, input, &mut out);
+        out.truncate(written);
+        Ok(crate::into_ascii_string(out))
+    }
+
+    /// Allocates a new `Vec<u8>` and decodes the input data into it.
+    ///
+    /// # Errors
+    /// Returns `Error` if the input contains invalid characters or has an invalid length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::base85::Z85;
+    /// assert_eq!(Z85.decode("HelloWorld").unwrap(), b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    #[allow(clippy::naive_bytecount)] // counting `z`s is not worth a dependency
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // Each `z` is one character for four bytes, where the estimate
+        // budgets it under one.
+        let mut len = Self::estimate_decoded_len(self, input.len());
+        if self.alphabet == Alphabet::Ascii85 {
+            len += input.iter().filter(|&&c| c == b'z').count() * 4;
+        }
+
+        // As in `crate::Engine::decode`: `truncate` drops the unwritten tail,
+        // and on error the whole buffer is dropped unread.
+        let mut out = crate::spare(len);
+        let written = Self::decode_into(self, input, &mut out)?;
+        out.truncate(written);
+        Ok(out)
+    }
+}
@@ -0,0 +1,227 @@
+//! Scalar Base85 encode/decode primitives.
+//!
+//! **100% safe Rust**, like every scalar kernel in the crate. Encode peels five
+//! digits off each 32-bit group by constant division, which the compiler turns
+//! into multiplies. Decode folds five table lookups into a 64-bit accumulator,
+//! so a group too large for 32 bits is caught by one compare afterwards, and
+//! keeps the largest looked-up value so one compare catches any character
+//! outside the alphabet too.
+
+#![forbid(unsafe_code)]
+
+use super::Alphabet;
+use crate::Error;
+
+/// The highest digit, which stands in for the ones a partial group drops.
+const MAX_DIGIT: u8 = 84;
+
+/// Compile-time reverse lookup for `alphabet`: ASCII back to digit values,
+/// `0xFF` for anything else.
+#[allow(clippy::cast_possible_truncation)] // `i` is always < 85, fits in u8
+const fn decode_table(alphabet: Alphabet) -> [u8; 256] {
+    let symbols = alphabet.symbols();
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 85 {
+        table[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// The reverse lookups, indexed by `Alphabet as usize`.
+static DECODE_TABLES: [[u8; 256]; 3] = [
+    decode_table(Alphabet::ALL[0]),
+    decode_table(Alphabet::ALL[1]),
+    decode_table(Alphabet::ALL[2]),
+];
+
+/// The five digits a 32-bit group encodes to, most significant first.
+#[inline]
+fn encode_group(symbols: &[u8; 85], mut n: u32) -> [u8; 5] {
+    let mut out = [0u8; 5];
+    for digit in out.iter_mut().rev() {
+        *digit = symbols[(n % 85) as usize];
+        n /= 85;
+    }
+    out
+}
+
+/// Encodes `input` into Base85, writing the result into `dst` and returning
+/// the number of bytes written.
+///
+/// `dst` must be at least `Engine::encoded_len` for `input`. Ascii85 gets its
+/// `<~` … `~>` framing and `z` for each all-zero group; the final partial
+/// group never takes the shortcut. A `dst` that is too small will panic
+/// (bounds check) rather than corrupt memory.
+#[inline]
+pub(crate) fn encode_slice(alphabet: Alphabet, input: &[u8], dst: &mut [u8]) -> usize {
+    let symbols = alphabet.symbols();
+    let ascii85 = alphabet == Alphabet::Ascii85;
+
+    let mut pos = if ascii85 {
+        dst[..2].copy_from_slice(b"<~");
+        2
+    } else {
+        0
+    };
+
+    // --- MAIN LOOP ---
+    // Process 4 input bytes -> 5 output digits (or one `z`) per iteration.
+    let mut chunks = input.chunks_exact(4);
+    for chunk in &mut chunks {
+        let n = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        if ascii85 && n == 0 {
+            dst[pos] = b'z';
+            pos += 1;
+        } else {
+            dst[pos..pos + 5].copy_from_slice(&encode_group(symbols, n));
+            pos += 5;
+        }
+    }
+
+    // --- TAIL HANDLING ---
+    // 1-3 bytes, zero-filled to a group: its first `rem + 1` digits are
+    // enough to recover them.
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let mut word = [0u8; 4];
+        word[..tail.len()].copy_from_slice(tail);
+        let digits = tail.len() + 1;
+        dst[pos..pos + digits]
+            .copy_from_slice(&encode_group(symbols, u32::from_be_bytes(word))[..digits]);
+        pos += digits;
+    }
+
+    if ascii85 {
+        dst[pos..pos + 2].copy_from_slice(b"~>");
+        pos += 2;
+    }
+    pos
+}
+
+/// The digit values of up to five characters, in the first `chars.len()`
+/// entries.
+#[inline]
+fn lookup(table: &[u8; 256], chars: &[u8]) -> [u8; 5] {
+    let mut values = [0u8; 5];
+    for (v, &c) in values.iter_mut().zip(chars) {
+        *v = table[usize::from(c)];
+    }
+    values
+}
+
+/// The 32-bit value of a group of up to five digit values, the missing ones
+/// taken as [`MAX_DIGIT`].
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if a value is outside the alphabet or
+/// the group overflows 32 bits.
+#[inline]
+fn decode_group(values: &[u8]) -> Result<u32, Error> {
+    let (mut n, max) = values.iter().fold((0u64, 0u8), |(n, max), &v| {
+        (n * 85 + u64::from(v), max.max(v))
+    });
+    for _ in values.len()..5 {
+        n = n * 85 + u64::from(MAX_DIGIT);
+    }
+    if max > MAX_DIGIT {
+        return Err(Error::InvalidCharacter);
+    }
+    u32::try_from(n).map_err(|_| Error::InvalidCharacter)
+}
+
+/// Decodes Z85 or RFC 1924 `input` into `dst`, returning the number of bytes
+/// written.
+///
+/// `dst` must be at least `Engine::estimate_decoded_len` for `input`; a `dst`
+/// that is too small will panic (bounds check) rather than corrupt memory.
+///
+/// # Errors
+/// Returns [`Error::InvalidLength`] if the final group is a single digit, and
+/// [`Error::InvalidCharacter`] for a character outside the alphabet or a
+/// group that overflows 32 bits. Z85's whole-group rule is the caller's.
+#[inline]
+pub(crate) fn decode_slice(
+    alphabet: Alphabet,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    let table = &DECODE_TABLES[alphabet as usize];
+
+    let chunks = input.chunks_exact(5);
+    let tail = chunks.remainder();
+    if tail.len() == 1 {
+        return Err(Error::InvalidLength);
+    }
+
+    // --- FAST LOOP ---
+    let out_body = input.len() / 5 * 4;
+    for (chars, out) in chunks.zip(dst[..out_body].chunks_exact_mut(4)) {
+        out.copy_from_slice(&decode_group(&lookup(table, chars))?.to_be_bytes());
+    }
+
+    // --- TAIL HANDLING ---
+    // 2-4 digits, filled out with the highest: the first `len - 1` bytes are
+    // the ones encoded.
+    if tail.is_empty() {
+        return Ok(out_body);
+    }
+    let bytes = tail.len() - 1;
+    let n = decode_group(&lookup(table, tail)[..tail.len()])?;
+    dst[out_body..out_body + bytes].copy_from_slice(&n.to_be_bytes()[..bytes]);
+    Ok(out_body + bytes)
+}
+
+/// Decodes Adobe Ascii85 `input` into `dst`, returning the number of bytes
+/// written.
+///
+/// Takes `input` with or without its `<~` and `~>`, skips whitespace anywhere
+/// inside, and expands a `z` between groups to four zero bytes. Unlike [`decode_slice`], this checks `dst` as it goes, since `z`
+/// makes the decoded length depend on more than the input length.
+///
+/// # Errors
+/// Returns [`Error::InvalidLength`] if the final group is a single digit, [`Error::InvalidCharacter`] for a character outside the
+/// alphabet, a `z` inside a group or a group that overflows 32 bits, and
+/// [`Error::BufferTooSmall`] if `dst` runs out.
+pub(crate) fn decode_ascii85(input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+    let table = &DECODE_TABLES[Alphabet::Ascii85 as usize];
+
+    let input = input.trim_ascii();
+    let input = input.strip_suffix(b"~>").unwrap_or(input);
+    let input = input.strip_prefix(b"<~").unwrap_or(input);
+
+    let mut written = 0;
+    let mut push = |bytes: &[u8]| -> Result<(), Error> {
+        let out = dst
+            .get_mut(written..written + bytes.len())
+            .ok_or(Error::BufferTooSmall)?;
+        out.copy_from_slice(bytes);
+        written += bytes.len();
+        Ok(())
+    };
+
+    let mut group = [0u8; 5];
+    let mut len = 0;
+    for &c in input {
+        match c {
+            c if c.is_ascii_whitespace() => {}
+            b'z' if len == 0 => push(&[0; 4])?,
+            _ => {
+                group[len] = table[usize::from(c)];
+                len += 1;
+                if len == 5 {
+                    push(&decode_group(&group)?.to_be_bytes())?;
+                    len = 0;
+                }
+            }
+        }
+    }
+
+    match len {
+        0 => {}
+        1 => return Err(Error::InvalidLength),
+        _ => push(&decode_group(&group[..len])?.to_be_bytes()[..len - 1])?,
+    }
+    Ok(written)
+}
@@ -0,0 +1,332 @@
+//! CPU capability detection for the x86 kernels, resolved once and cached.
+//!
+//! `std::is_x86_feature_detected!` already caches its answer internally, but this
+//! collapses the whole *tier* decision — which of the compiled kernels to run —
+//! into a single load after the first call, instead of re-checking each feature
+//! bit on every encode/decode. Without `std` the feature bits come straight
+//! from `cpuid`/`xgetbv` (see [`cpuid_detected`]), so the kernels still run in
+//! kernel-mode and unikernel builds.
+//!
+//! The decision can be capped, so operators can rule out a kernel (say, VBMI on
+//! hosts where 512-bit code throttles neighbouring workloads) without a rebuild:
+//! by the [`MAX_BACKEND_ENV`] environment variable (with `std`), read once when
+//! the decision is made, and by [`crate::set_max_backend`] before the first
+//! encode/decode. When both are given, a kernel must pass both.
+//!
+//! When the target guarantees the best compiled-in kernel's features (built
+//! with `-C target-cpu=...` or `-C target-feature=...`, the `x86_static` cfg
+//! from `build.rs`), there is nothing left to detect. Without `std` the tier
+//! is then a constant, none of this machinery is compiled, and dispatch can
+//! inline straight into the kernel. With `std` the cached byte stays (the
+//! `x86_cached` cfg), so the environment variable and
+//! [`crate::set_max_backend`] can still lower the tier; `detect` folds to
+//! compile-time answers, and each call pays one load and a branch on it.
+
+use crate::Backend;
+#[cfg(x86_cached)]
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// Environment variable naming the most capable backend detection may pick.
+#[cfg(all(x86_cached, feature = "std"))]
+const MAX_BACKEND_ENV: &str = "BASE64_TURBO_MAX_BACKEND";
+
+/// The detected tier, once decided, as encoded by [`encode_tier`]; [`UNDECIDED`]
+/// until then. A plain atomic rather than a `OnceLock` so the cache works the
+/// same with and without `std`.
+#[cfg(x86_cached)]
+static CACHE: AtomicU8 = AtomicU8::new(UNDECIDED);
+
+#[cfg(x86_cached)]
+const UNDECIDED: u8 = 0;
+
+#[cfg(x86_cached)]
+const fn encode_tier(tier: Backend) -> u8 {
+    match tier {
+        Backend::Avx512Vbmi => 6,
+        Backend::Avx512Vbmi256 => 5,
+        Backend::Avx512Bw => 4,
+        Backend::Avx2 => 3,
+        Backend::Ssse3 => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(x86_cached)]
+const fn decode_tier(byte: u8) -> Backend {
+    match byte {
+        6 => Backend::Avx512Vbmi,
+        5 => Backend::Avx512Vbmi256,
+        4 => Backend::Avx512Bw,
+        3 => Backend::Avx2,
+        2 => Backend::Ssse3,
+        _ => Backend::Scalar,
+    }
+}
+
+/// Whether every listed target feature is available. A feature the target
+/// guarantees is a compile-time `true`; `is_x86_feature_detected!` folds that
+/// case away too, but only exists with `std`, so `no_std` builds ask
+/// [`cpuid_detected`] instead.
+macro_rules! detected {
+    ($($feature:tt),+) => {{
+        #[cfg(x86_static)]
+        let on = true $(&& cfg!(target_feature = $feature))+;
+        #[cfg(all(x86_detect, feature = "std"))]
+        let on = true $(&& std::is_x86_feature_detected!($feature))+;
+        #[cfg(all(x86_detect, not(feature = "std")))]
+        let on = true $(&& (cfg!(target_feature = $feature) || cpuid_detected($feature)))+;
+        on
+    }};
+}
+
+/// Whether the CPU implements every subset `backend`'s kernel issues. Only
+/// the x86 kernels compiled into this build can answer `true`; the caller
+/// handles the rest.
+#[inline]
+// Only `const`-eligible when the target guarantees every compiled-in kernel.
+#[allow(clippy::missing_const_for_fn)]
+pub(crate) fn has(backend: Backend) -> bool {
+    match backend {
+        // All three are required: `vpermb`/`vpermi2b`/`vpmultishiftqb` are
+        // VBMI, the masked `vmovdqu8` tiers are BW, the registers are F.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => detected!("avx512f", "avx512bw", "avx512vbmi"),
+        // The same, plus VL for the 256-bit encodings of all of it.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi256 => detected!("avx512f", "avx512bw", "avx512vl", "avx512vbmi"),
+        // The byte shuffles and masked `vmovdqu8` are BW, the registers are F.
+        #[cfg(x86_avx512bw)]
+        Backend::Avx512Bw => detected!("avx512f", "avx512bw"),
+        #[cfg(x86_avx2)]
+        Backend::Avx2 => detected!("avx2"),
+        #[cfg(x86_ssse3)]
+        Backend::Ssse3 => detected!("ssse3"),
+        _ => false,
+    }
+}
+
+/// XCR0 bits for the XMM and YMM register state: the OS saves the full 256-bit
+/// registers across context switches.
+#[cfg(all(x86_detect, not(feature = "std")))]
+const XCR0_YMM: u64 = 0b0000_0110;
+
+/// XCR0 bits for the AVX-512 state on top of [`XCR0_YMM`]: the opmask
+/// registers, the upper halves of ZMM0-15, and ZMM16-31.
+#[cfg(all(x86_detect, not(feature = "std")))]
+const XCR0_ZMM: u64 = 0b1110_0110;
+
+/// `is_x86_feature_detected!` for `no_std`, straight from `cpuid`, for the
+/// handful of features the kernels need. A CPU bit alone is not enough: the OS
+/// must also have enabled XSAVE and opted in to saving the wider registers,
+/// or their upper halves are lost (or `#UD`) on the first context switch.
+#[cfg(all(x86_detect, not(feature = "std")))]
+// `__cpuid` only became a safe function after our MSRV.
+#[allow(unused_unsafe)]
+fn cpuid_detected(feature: &str) -> bool {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::{__cpuid, __cpuid_count, has_cpuid};
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::{__cpuid, __cpuid_count};
+
+    #[cfg(target_arch = "x86")]
+    if !has_cpuid() {
+        return false;
+    }
+    let bit = |reg: u32, n: u32| (reg >> n) & 1 == 1;
+
+    // SAFETY: `cpuid` exists on every x86_64 CPU, and was checked for on x86.
+    // Leaves above the reported maximum are never queried.
+    let max_leaf = unsafe { __cpuid(0) }.eax;
+    if max_leaf < 1 {
+        return false;
+    }
+    // SAFETY: as above.
+    let leaf1 = unsafe { __cpuid(1) };
+    // XMM state predates XSAVE (the OS saves it with `fxsave`), so SSSE3 needs
+    // no further OS check.
+    if feature == "ssse3" {
+        return bit(leaf1.ecx, 9);
+    }
+    if max_leaf < 7 {
+        return false;
+    }
+    // OSXSAVE: the OS enabled XSAVE, which is also what makes `xgetbv` legal.
+    if !bit(leaf1.ecx, 27) {
+        return false;
+    }
+    // SAFETY: OSXSAVE is set, so `xgetbv` is supported and enabled.
+    let xcr0 = unsafe { xcr0() };
+    // SAFETY: as above; leaf 7 is within `max_leaf`.
+    let leaf7 = unsafe { __cpuid_count(7, 0) };
+
+    let (state, on) = match feature {
+        "avx2" => (XCR0_YMM, bit(leaf1.ecx, 28) && bit(leaf7.ebx, 5)),
+        "avx512f" => (XCR0_ZMM, bit(leaf7.ebx, 16)),
+        "avx512bw" => (XCR0_ZMM, bit(leaf7.ebx, 30)),
+        "avx512vl" => (XCR0_ZMM, bit(leaf7.ebx, 31)),
+        "avx512vbmi" => (XCR0_ZMM, bit(leaf7.ecx, 1)),
+        _ => return false,
+    };
+    on && xcr0 & state == state
+}
+
+/// Reads XCR0, the register state the OS has enabled for XSAVE.
+///
+/// # Safety
+/// The CPU must report OSXSAVE (`cpuid` leaf 1, ECX bit 27).
+#[cfg(all(x86_detect, not(feature = "std")))]
+#[target_feature(enable = "xsave")]
+unsafe fn xcr0() -> u64 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::_xgetbv;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::_xgetbv;
+
+    // SAFETY: the caller guarantees XSAVE is enabled.
+    unsafe { _xgetbv(0) }
+}
+
+/// The cap named by [`MAX_BACKEND_ENV`], if it is set to a backend name
+/// (case-insensitive, see [`Backend::name`]). Anything else is ignored: a
+/// typo'd override must not stop the process from encoding.
+#[cfg(all(x86_cached, feature = "std"))]
+fn env_cap() -> Option<Backend> {
+    let value = std::env::var(MAX_BACKEND_ENV).ok()?;
+    let value = value.trim();
+    [
+        Backend::Scalar,
+        Backend::Ssse3,
+        Backend::Avx2,
+        Backend::Avx512Bw,
+        Backend::Avx512Vbmi256,
+        Backend::Avx512Vbmi,
+        Backend::Neon,
+        Backend::WasmSimd,
+        Backend::Portable,
+    ]
+    .into_iter()
+    .find(|backend| backend.name().eq_ignore_ascii_case(value))
+}
+
+/// Without `std` there is no environment to read.
+#[cfg(all(x86_detect, not(feature = "std")))]
+const fn env_cap() -> Option<Backend> {
+    None
+}
+
+// Candidates, most- to least-capable. Only compiled-in kernels can pass
+// `has`, which keeps detection in lockstep with the feature set.
+#[cfg(x86_cached)]
+fn detect(cap: Option<Backend>) -> Backend {
+    let env = env_cap();
+    let admitted = |backend: Backend| {
+        [cap, env]
+            .into_iter()
+            .flatten()
+            .all(|max| backend.capped_by(max))
+    };
+    [
+        Backend::Avx512Vbmi,
+        Backend::Avx512Vbmi256,
+        Backend::Avx512Bw,
+        Backend::Avx2,
+        Backend::Ssse3,
+    ]
+    .into_iter()
+    .find(|&backend| admitted(backend) && has(backend))
+    .unwrap_or(Backend::Scalar)
+}
+
+/// The best compiled-in kernel the current CPU supports, within any cap.
+/// Detected on the first call and cached for the lifetime of the process.
+#[cfg(x86_cached)]
+#[inline]
+pub(crate) fn tier() -> Backend {
+    match CACHE.load(Ordering::Relaxed) {
+        UNDECIDED => decide(detect(None)).unwrap_or_else(|tier| tier),
+        byte => decode_tier(byte),
+    }
+}
+
+/// Runs detection now, under `max`, unless it already ran. Returns the tier
+/// that was locked in first if it did.
+#[cfg(x86_cached)]
+pub(crate) fn set_max(max: Backend) -> Result<(), Backend> {
+    match CACHE.load(Ordering::Relaxed) {
+        UNDECIDED => decide(detect(Some(max))).map(drop),
+        byte => Err(decode_tier(byte)),
+    }
+}
+
+/// Locks in `tier` unless another thread decided first, in which case its
+/// answer wins and is returned as the error. Only this byte is published,
+/// so relaxed ordering suffices.
+#[cfg(x86_cached)]
+#[cold]
+fn decide(tier: Backend) -> Result<Backend, Backend> {
+    match CACHE.compare_exchange(
+        UNDECIDED,
+        encode_tier(tier),
+        Ordering::Relaxed,
+        Ordering::Relaxed,
+    ) {
+        Ok(_) => Ok(tier),
+        Err(byte) => Err(decode_tier(byte)),
+    }
+}
+
+/// The best compiled-in kernel, which the target guarantees. Only without
+/// `std`: with it the cap can still lower the tier, as under detection.
+#[cfg(all(x86_static, not(x86_cached)))]
+#[inline]
+pub(crate) const fn tier() -> Backend {
+    if cfg!(x86_avx512_vbmi) {
+        Backend::Avx512Vbmi
+    } else if cfg!(x86_avx512bw) {
+        Backend::Avx512Bw
+    } else if cfg!(x86_avx2) {
+        Backend::Avx2
+    } else {
+        Backend::Ssse3
+    }
+}
+
+/// The tier was fixed at compile time, so there is nothing left to cap.
+#[cfg(all(x86_static, not(x86_cached)))]
+pub(crate) const fn set_max(_max: Backend) -> Result<(), Backend> {
+    Err(tier())
+}
+
+#[cfg(all(test, x86_detect, not(feature = "std")))]
+mod tests {
+    /// The `cpuid` path must agree with `std`'s detection, which the test
+    /// harness still links, on every feature it answers for.
+    #[test]
+    fn cpuid_matches_std_detection() {
+        assert_eq!(
+            super::cpuid_detected("ssse3"),
+            std::is_x86_feature_detected!("ssse3")
+        );
+        assert_eq!(
+            super::cpuid_detected("avx2"),
+            std::is_x86_feature_detected!("avx2")
+        );
+        assert_eq!(
+            super::cpuid_detected("avx512f"),
+            std::is_x86_feature_detected!("avx512f")
+        );
+        assert_eq!(
+            super::cpuid_detected("avx512bw"),
+            std::is_x86_feature_detected!("avx512bw")
+        );
+        assert_eq!(
+            super::cpuid_detected("avx512vl"),
+            std::is_x86_feature_detected!("avx512vl")
+        );
+        assert_eq!(
+            super::cpuid_detected("avx512vbmi"),
+            std::is_x86_feature_detected!("avx512vbmi")
+        );
+        assert!(!super::cpuid_detected("sse9"));
+    }
+}
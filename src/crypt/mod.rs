@@ -0,0 +1,338 @@
+//! The Base64 dialects of password hashes, on the same kernels-and-dispatch
+//! footing as the Base64 [`Engine`](crate::Engine).
+//!
+//! | Engine | Alphabet | Bit order | Used by |
+//! | :--- | :--- | :--- | :--- |
+//! | [`CRYPT`] | `./0-9A-Za-z` | little-endian | md5-crypt (`$1$`), sha256-crypt (`$5$`), sha512-crypt (`$6$`) |
+//! | [`BCRYPT`] | `./A-Za-z0-9` | big-endian | bcrypt (`$2a$`, `$2b$`, `$2y$`) |
+//!
+//! Neither pads. The crypt(3) family reads each 3-byte group as a
+//! little-endian 24-bit number and writes its 6-bit fields least significant
+//! first, so `CRYPT` is not a remapped RFC 4648 alphabet the way `BCRYPT` is.
+//! A final one or two bytes take two or three characters, and decoding ignores
+//! the spare bits of a final partial group, as the hash implementations do.
+//!
+//! The engines stop at the encoding: md5-crypt and sha-crypt shuffle the
+//! digest's bytes into a scheme-specific order before encoding them, and that
+//! permutation is the hash parser's to apply or undo.
+//!
+//! Each call runs the AVX512-VBMI kernel when that is the [`active_backend`]
+//! (or the backend pinned with [`Engine::with_backend`]), and the table-driven
+//! scalar kernel everywhere else.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use base64_turbo::crypt::{BCRYPT, CRYPT};
+//!
+//! assert_eq!(CRYPT.encode(b"hello"), "cJ4Pgx4");
+//! assert_eq!(BCRYPT.encode(b"hello"), "YETqZE6");
+//!
+//! // The salt of a bcrypt hash: 22 characters, 16 bytes.
+//! let salt = BCRYPT.decode("N9qo8uLOickgx2ZMRZoMye").unwrap();
+//! assert_eq!(salt.len(), 16);
+//! # }
+//! ```
+
+use crate::{Backend, Error, Unsupported, active_backend};
+
+// Scalar implementation. The VBMI kernel lives with its Base64 sibling under
+// `crate::simd`, and hands its final group to this one.
+pub(crate) mod scalar;
+
+/// The two password-hash dialects. Each fixes both an alphabet and a bit
+/// order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Scheme {
+    /// crypt(3)'s "hash64": `./0-9A-Za-z`, groups read little-endian.
+    Crypt,
+    /// bcrypt's: `./A-Za-z0-9`, groups read big-endian as in RFC 4648.
+    Bcrypt,
+}
+
+impl Scheme {
+    /// Every scheme, in discriminant order, for tables indexed by one.
+    pub(crate) const ALL: [Self; 2] = [Self::Crypt, Self::Bcrypt];
+
+    /// The characters this scheme encodes to, indexed by 6-bit value.
+    pub(crate) const fn symbols(self) -> &'static [u8; 64] {
+        match self {
+            Self::Crypt => b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+            Self::Bcrypt => b"./ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+        }
+    }
+}
+
+/// Compile-time reverse lookup for `scheme`: ASCII back to 6-bit values,
+/// `0xFF` for anything else.
+#[allow(clippy::cast_possible_truncation)] // `i` is always < 64, fits in u8
+pub(crate) const fn decode_table(scheme: Scheme) -> [u8; 256] {
+    let symbols = scheme.symbols();
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// A stateless crypt(3) or bcrypt Base64 encoder/decoder.
+///
+/// The password-hash counterpart of the Base64 [`Engine`](crate::Engine), with
+/// the same zero-allocation and allocating APIs and the same [`Error`]
+/// reporting. Neither dialect pads, so both length calculators are exact.
+///
+/// # Examples
+///
+/// ```rust
+/// use base64_turbo::crypt::CRYPT;
+///
+/// let mut buf = [0u8; 4];
+/// let n = CRYPT.encode_into(b"hi", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"cZ4");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Engine {
+    pub(crate) scheme: Scheme,
+    /// The kernel pinned by [`Engine::with_backend`]; `None` follows
+    /// [`active_backend`].
+    pub(crate) backend: Option<Backend>,
+}
+
+// ======================================================================
+// Pre-defined Engines
+// ======================================================================
+
+/// crypt(3)'s Base64, as md5-crypt, sha256-crypt and sha512-crypt write their
+/// salts and digests: `./0-9A-Za-z`, little-endian groups, no padding.
+pub const CRYPT: Engine = Engine {
+    scheme: Scheme::Crypt,
+    backend: None,
+};
+
+/// bcrypt's Base64: `./A-Za-z0-9`, big-endian groups, no padding.
+pub const BCRYPT: Engine = Engine {
+    scheme: Scheme::Bcrypt,
+    backend: None,
+};
+
+impl Engine {
+    // ======================================================================
+    // Backend Selection
+    // ======================================================================
+
+    /// Returns a copy of this engine that always runs `backend`'s kernel,
+    /// instead of following [`active_backend`].
+    ///
+    /// The check is the one [`Engine::with_backend`](crate::Engine::with_backend)
+    /// makes. Only [`Backend::Avx512Vbmi`] has a kernel of its own for these
+    /// dialects; every other backend runs scalar.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Unsupported`] if `backend` is not compiled into this build or
+    /// the current CPU cannot run it.
+    #[inline]
+    pub fn with_backend(self, backend: Backend) -> Result<Self, Unsupported> {
+        Ok(Self {
+            backend: backend.pin()?,
+            ..self
+        })
+    }
+
+    /// The backend this engine dispatches to: the one pinned by
+    /// [`with_backend`](Self::with_backend), or [`active_backend`] otherwise.
+    #[inline]
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend.unwrap_or_else(active_backend)
+    }
+
+    // ======================================================================
+    // Length Calculators
+    // ======================================================================
+
+    /// The exact number of characters `input_len` bytes encode to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::crypt::BCRYPT;
+    ///
+    /// assert_eq!(BCRYPT.encoded_len(16), 22); // a bcrypt salt
+    /// assert_eq!(BCRYPT.encoded_len(23), 31); // a bcrypt digest
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        input_len / 3 * 4 + (input_len % 3 * 4).div_ceil(3)
+    }
+
+    /// The exact number of bytes `input_len` characters decode to. A length
+    /// that leaves one character over is rounded down, though decoding it
+    /// fails with [`Error::InvalidLength`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::crypt::CRYPT;
+    ///
+    /// assert_eq!(CRYPT.decoded_len(86), 64); // a sha512-crypt digest
+    /// assert_eq!(CRYPT.decoded_len(22), 16); // an md5-crypt digest
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn decoded_len(&self, input_len: usize) -> usize {
+        input_len / 4 * 3 + input_len % 4 * 3 / 4
+    }
+
+    // ======================================================================
+    // Zero-Allocation APIs
+    // ======================================================================
+
+    /// Encodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error::BufferTooSmall)`: If `output.len()` is less than [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is not large enough to hold the
+    /// encoded data (see [`encoded_len`](Self::encoded_len)).
+    #[inline]
+    pub fn encode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::encoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Self::encode_dispatch(self, input, &mut output[..req_len]);
+
+        Ok(req_len)
+    }
+
+    /// Decodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if `input.len() % 4 == 1`,
+    /// [`Error::BufferTooSmall`] if `output` is shorter than
+    /// [`decoded_len`](Self::decoded_len), and [`Error::InvalidCharacter`] for
+    /// a character outside the alphabet.
+    #[inline]
+    pub fn decode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len % 4 == 1 {
+            return Err(Error::InvalidLength);
+        }
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::decoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Self::decode_dispatch(self, input, &mut output[..req_len])?;
+
+        Ok(req_len)
+    }
+
+    // ========================================================================
+    // Allocating APIs (std)
+    // ========================================================================
+
+    /// Allocates a new `String` and encodes the input data into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::crypt::CRYPT;
+    /// assert_eq!(CRYPT.encode(b"hello world"), "cJ4Pgx46rxaQgF4");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::encode`: the length is exact, every byte is
+        // overwritten, and the output is pure ASCII.
+        let mut out = crate::spare(Self::encoded_len(self, input.len()));
+        Self::encode_dispatch(self, input, &mut out);
+        crate::into_ascii_string(out)
+    }
+
+    /// Allocates a new `Vec<u8>` and decodes the input data into it.
+    ///
+    /// # Errors
+    /// Returns `Error` if the input contains invalid characters or has an invalid length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::crypt::BCRYPT;
+    /// assert_eq!(BCRYPT.decode("YETqZE6eb07wZEO").unwrap(), b"hello world");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::decode`: on error the whole buffer is dropped
+        // unread.
+        let mut out = crate::spare(Self::decoded_len(self, input.len()));
+        Self::decode_into(self, input, &mut out)?;
+        Ok(out)
+    }
+
+    // ========================================================================
+    // Internal Dispatchers
+    // ========================================================================
+
+    // As for Base32: a match on the backend, with the 256-bit VBMI backend left
+    // on scalar because the kernel is ZMM-only.
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn encode_dispatch(&self, input: &[u8], dst: &mut [u8]) {
+        match self.backend() {
+            // SAFETY: the backend is supported, so the CPU has VBMI.
+            #[cfg(x86_avx512_vbmi)]
+            Backend::Avx512Vbmi => unsafe {
+                crate::simd::encode_crypt_avx512_vbmi(self.scheme, input, dst);
+            },
+            _ => scalar::encode_slice(self.scheme, input, dst),
+        }
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn decode_dispatch(&self, input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+        match self.backend() {
+            // SAFETY: as in `encode_dispatch`.
+            #[cfg(x86_avx512_vbmi)]
+            Backend::Avx512Vbmi => unsafe {
+                crate::simd::decode_crypt_avx512_vbmi(self.scheme, input, dst)
+            },
+            _ => scalar::decode_slice(self.scheme, input, dst),
+        }
+    }
+}
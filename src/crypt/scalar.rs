@@ -0,0 +1,147 @@
+//! Scalar (non-SIMD) crypt(3) and bcrypt encode/decode primitives.
+//!
+//! **100% safe Rust**, table-driven like the Base32 kernel. Each group is read
+//! as a 24-bit number in the scheme's byte order, and its four 6-bit fields
+//! come out in the scheme's character order: most significant first for
+//! bcrypt, least significant first for crypt(3). Encode maps 12 bits straight
+//! to the two characters they encode, with the pair tables built in that
+//! character order, so a group is two lookups either way. The VBMI kernel
+//! hands its final group here, so it is also the reference that kernel is
+//! tested against.
+
+#![forbid(unsafe_code)]
+
+use super::{Scheme, decode_table};
+use crate::Error;
+
+/// Maps a 12-bit value to the two characters it encodes, packed little-endian
+/// so the first character lands in the low byte. crypt(3) writes the low
+/// field first, bcrypt the high one.
+const fn encode_pair_table(scheme: Scheme) -> [u16; 4096] {
+    let symbols = scheme.symbols();
+    let mut table = [0u16; 4096];
+    let mut i = 0;
+    while i < 4096 {
+        let (first, second) = match scheme {
+            Scheme::Crypt => (i & 0x3F, i >> 6),
+            Scheme::Bcrypt => (i >> 6, i & 0x3F),
+        };
+        table[i] = (symbols[first] as u16) | ((symbols[second] as u16) << 8);
+        i += 1;
+    }
+    table
+}
+
+/// The pair tables, indexed by `Scheme as usize`.
+static ENCODE_PAIRS: [[u16; 4096]; 2] = [
+    encode_pair_table(Scheme::ALL[0]),
+    encode_pair_table(Scheme::ALL[1]),
+];
+
+/// The reverse lookups, indexed by `Scheme as usize`.
+static DECODE_TABLES: [[u8; 256]; 2] = [decode_table(Scheme::ALL[0]), decode_table(Scheme::ALL[1])];
+
+/// The four characters a group encodes to, first character in the low byte.
+/// Trailing zero bytes only reach the trailing characters, so a partial group
+/// zero-filled to three bytes keeps its leading ones exact.
+#[inline]
+fn encode_group(scheme: Scheme, group: [u8; 3]) -> [u8; 4] {
+    let pairs = &ENCODE_PAIRS[scheme as usize];
+    let (first, second) = match scheme {
+        Scheme::Crypt => {
+            let n = u32::from_le_bytes([group[0], group[1], group[2], 0]);
+            (n & 0xFFF, n >> 12)
+        }
+        Scheme::Bcrypt => {
+            let n = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+            (n >> 12, n & 0xFFF)
+        }
+    };
+    let pair = |bits: u32| u32::from(pairs[bits as usize]);
+    (pair(first) | (pair(second) << 16)).to_le_bytes()
+}
+
+/// Encodes `input` under `scheme`, writing the result into `dst`.
+///
+/// `dst` must be at least [`encoded_len`](super::Engine::encoded_len) for
+/// `input`; a `dst` that is too small will panic (bounds check) rather than
+/// corrupt memory.
+#[inline]
+pub(crate) fn encode_slice(scheme: Scheme, input: &[u8], dst: &mut [u8]) {
+    let (groups, tail) = input.as_chunks::<3>();
+    let (out_main, out_tail) = dst.split_at_mut(groups.len() * 4);
+
+    // --- MAIN LOOP ---
+    // Process 3 input bytes -> 4 output characters per iteration.
+    for (&group, out) in groups.iter().zip(out_main.as_chunks_mut::<4>().0) {
+        *out = encode_group(scheme, group);
+    }
+
+    // --- TAIL HANDLING ---
+    // 1-2 bytes, zero-filled to a group: its first `rem + 1` characters.
+    let rem = tail.len();
+    if rem > 0 {
+        let mut group = [0u8; 3];
+        group[..rem].copy_from_slice(tail);
+        out_tail[..=rem].copy_from_slice(&encode_group(scheme, group)[..=rem]);
+    }
+}
+
+/// Decodes up to four characters under `scheme`, the missing ones read as
+/// zero, into the group's three bytes. Returns `None` for a character outside
+/// the alphabet.
+#[inline]
+fn decode_group(scheme: Scheme, chars: &[u8]) -> Option<[u8; 3]> {
+    let table = &DECODE_TABLES[scheme as usize];
+    let mut values = [0u8; 4];
+    // Invalid characters map to 0xFF, so any of them sets a bit above the six
+    // a valid value occupies.
+    let mut bad = 0u8;
+    for (value, &c) in values.iter_mut().zip(chars) {
+        *value = table[usize::from(c)];
+        bad |= *value;
+    }
+    if bad > 0x3F {
+        return None;
+    }
+
+    let [v0, v1, v2, v3] = values.map(u32::from);
+    Some(match scheme {
+        Scheme::Crypt => {
+            let [b0, b1, b2, _] = (v0 | (v1 << 6) | (v2 << 12) | (v3 << 18)).to_le_bytes();
+            [b0, b1, b2]
+        }
+        Scheme::Bcrypt => {
+            let [_, b0, b1, b2] = ((v0 << 18) | (v1 << 12) | (v2 << 6) | v3).to_be_bytes();
+            [b0, b1, b2]
+        }
+    })
+}
+
+/// Decodes `input` under `scheme` into `dst`, whose length must be exactly
+/// the decoded length of `input`.
+///
+/// `input.len() % 4` must not be 1; a length mismatch will panic (bounds
+/// check) rather than corrupt memory. The spare bits of a final partial group
+/// are ignored.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the alphabet.
+#[inline]
+pub(crate) fn decode_slice(scheme: Scheme, input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    let (groups, tail) = input.as_chunks::<4>();
+    let (out_main, out_tail) = dst.split_at_mut(groups.len() * 3);
+
+    // --- FAST LOOP ---
+    for (chars, out) in groups.iter().zip(out_main.as_chunks_mut::<3>().0) {
+        *out = decode_group(scheme, chars).ok_or(Error::InvalidCharacter)?;
+    }
+
+    // --- TAIL HANDLING ---
+    // 2-3 characters are 1-2 bytes.
+    if !tail.is_empty() {
+        let bytes = decode_group(scheme, tail).ok_or(Error::InvalidCharacter)?;
+        out_tail.copy_from_slice(&bytes[..tail.len() - 1]);
+    }
+    Ok(())
+}
@@ -0,0 +1,358 @@
+//! Kernel dispatch, resolved once per process in the style of an ELF `ifunc`.
+//!
+//! Each backend is described by a static [`Kernels`] table: its encode/decode
+//! entry points plus the shortest input each is worth entering for. Default
+//! engines dispatch through [`ACTIVE`], an atomic pointer that starts out at
+//! [`RESOLVER`], whose entry points pick the real table on first use, install
+//! it, and forward the call. From then on every call is two relaxed loads (the
+//! table, then its threshold), one length compare, and an indirect call — no
+//! tier re-check and no per-backend branch chain. Inputs below the threshold go to the table's short-input
+//! kernel where it has one, and otherwise take a direct (inlinable) call to the
+//! scalar kernel. The thresholds ship with measured defaults, and
+//! [`crate::calibrate`] replaces them with the host's own crossovers.
+//!
+//! Builds whose target guarantees the best x86 kernel (`x86_static`) skip the
+//! resolver: [`active`] names that kernel's table directly, so without `std`
+//! the whole dispatch folds away at compile time. With `std` it still goes
+//! through the cached tier byte, which a cap can lower.
+//!
+//! The kernels themselves are untouched, stand-alone functions; the tables only
+//! point at them. The Kani harnesses and Miri suites keep calling each one
+//! directly, so nothing here sits between a kernel and its proofs.
+
+// Every kernel takes `&Config`, and the entry points here must match them.
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use crate::{Backend, Config, Error, scalar};
+#[cfg(not(x86_static))]
+use core::sync::atomic::AtomicPtr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Signature shared by every encode kernel.
+pub(crate) type EncodeFn = unsafe fn(&Config, &[u8], &mut [u8]);
+/// Signature shared by every decode kernel.
+pub(crate) type DecodeFn = unsafe fn(&Config, &[u8], &mut [u8]) -> Result<usize, Error>;
+
+/// The shortest input a kernel is entered for: a default, the value currently
+/// installed, and a ceiling no installed value may pass.
+#[derive(Debug)]
+pub(crate) struct Threshold {
+    default: usize,
+    max: usize,
+    current: AtomicUsize,
+}
+
+impl Threshold {
+    /// A threshold starting out at `default`. `max` is one past the longest
+    /// input the table's short-input kernel takes, so that inputs below any
+    /// installed value still fit it; `usize::MAX` where scalar takes them.
+    const fn new(default: usize, max: usize) -> Self {
+        Self {
+            default,
+            max,
+            current: AtomicUsize::new(default),
+        }
+    }
+
+    pub(crate) const fn default(&self) -> usize {
+        self.default
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) const fn max(&self) -> usize {
+        self.max
+    }
+
+    #[inline]
+    pub(crate) fn get(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// Installs `len`, lowered to the ceiling if above it.
+    pub(crate) fn set(&self, len: usize) {
+        self.current.store(len.min(self.max), Ordering::Relaxed);
+    }
+}
+
+/// One backend's entry points.
+///
+/// # Safety
+///
+/// Calling `encode`/`decode` requires the CPU to support the table's backend. Tables are
+/// only reachable through [`active`] and [`for_backend`], which hand them out
+/// for backends that passed [`Backend::is_supported`] (or, for [`RESOLVER`],
+/// that pick such a table themselves), so the call sites below rely on that.
+#[derive(Debug)]
+pub(crate) struct Kernels {
+    /// Shortest input `encode` is entered for; shorter inputs run
+    /// `encode_short`, or scalar without one.
+    pub(crate) encode_min: Threshold,
+    pub(crate) encode: EncodeFn,
+    /// Encoder for every input below `encode_min`.
+    pub(crate) encode_short: Option<EncodeFn>,
+    /// Shortest input `decode` is entered for; shorter inputs run
+    /// `decode_short`, or scalar without one.
+    pub(crate) decode_min: Threshold,
+    pub(crate) decode: DecodeFn,
+    /// Decoder for every input below `decode_min`.
+    pub(crate) decode_short: Option<DecodeFn>,
+    /// Whether `decode` and `decode_short` read [`Config::lenient`]. The
+    /// kernels that look characters up in a table merge both alphabets into
+    /// it; the rest decode lenient configs through scalar.
+    pub(crate) lenient: bool,
+}
+
+/// The scalar kernels. Their thresholds are never met, so dispatch always
+/// takes the direct call and the pointers are only here for completeness.
+static SCALAR: Kernels = Kernels {
+    encode_min: Threshold::new(usize::MAX, usize::MAX),
+    encode: scalar::encode_slice,
+    encode_short: None,
+    decode_min: Threshold::new(usize::MAX, usize::MAX),
+    decode: scalar::decode_slice,
+    decode_short: None,
+    lenient: true,
+};
+
+// Smart degrade by length: a kernel is only worth entering once the input
+// fills its vector width. The masked tails let VBMI start decoding earlier
+// than AVX2, whose single tier needs a 32-byte block plus a 4-byte read-ahead.
+// Below the threshold, the backends with a short-input kernel run the whole
+// input through one or two vectors rather than the scalar tables.
+
+#[cfg(x86_avx512_vbmi)]
+static AVX512_VBMI: Kernels = Kernels {
+    encode_min: Threshold::new(32, crate::simd::ENC_SHORT_MAX_AVX512_VBMI + 1),
+    // VBMI fast-path: vpermb replaces the 8-instruction char mapping.
+    encode: crate::simd::encode_slice_avx512_vbmi,
+    encode_short: Some(crate::simd::encode_short_avx512_vbmi),
+    decode_min: Threshold::new(32, crate::simd::DEC_SHORT_MAX_AVX512_VBMI + 1),
+    // VBMI fast-path: vpermi2b collapses decode+validate to ~4 instructions.
+    decode: crate::simd::decode_slice_avx512_vbmi,
+    decode_short: Some(crate::simd::decode_short_avx512_vbmi),
+    lenient: true,
+};
+
+// Half the width of the ZMM kernel, with the same masked tails.
+#[cfg(x86_avx512_vbmi)]
+static AVX512_VBMI_256: Kernels = Kernels {
+    encode_min: Threshold::new(16, usize::MAX),
+    encode: crate::simd::encode_slice_avx512_vbmi_256,
+    encode_short: None,
+    decode_min: Threshold::new(16, usize::MAX),
+    decode: crate::simd::decode_slice_avx512_vbmi_256,
+    decode_short: None,
+    lenient: true,
+};
+
+// The same tiers as VBMI, masked tails included, so the same thresholds.
+#[cfg(x86_avx512bw)]
+static AVX512BW: Kernels = Kernels {
+    encode_min: Threshold::new(32, usize::MAX),
+    encode: crate::simd::encode_slice_avx512bw,
+    encode_short: None,
+    decode_min: Threshold::new(32, usize::MAX),
+    decode: crate::simd::decode_slice_avx512bw,
+    decode_short: None,
+    lenient: true,
+};
+
+#[cfg(x86_avx2)]
+static AVX2: Kernels = Kernels {
+    encode_min: Threshold::new(32, crate::simd::ENC_SHORT_MAX_AVX2 + 1),
+    encode: crate::simd::encode_slice_avx2,
+    encode_short: Some(crate::simd::encode_short_avx2),
+    decode_min: Threshold::new(36, crate::simd::DEC_SHORT_MAX_AVX2 + 1),
+    decode: crate::simd::decode_slice_avx2,
+    decode_short: Some(crate::simd::decode_short_avx2),
+    lenient: true,
+};
+
+// Same shape as AVX2 at half the width: a 16-character block plus the margin.
+#[cfg(x86_ssse3)]
+static SSSE3: Kernels = Kernels {
+    encode_min: Threshold::new(16, usize::MAX),
+    encode: crate::simd::encode_slice_ssse3,
+    encode_short: None,
+    decode_min: Threshold::new(20, usize::MAX),
+    decode: crate::simd::decode_slice_ssse3,
+    decode_short: None,
+    lenient: true,
+};
+
+// NEON's decode tier is a 16-in/12-out block plus a 4-byte read-ahead margin
+// (see `neon::decode_slice_neon`), so it needs 20 bytes to run.
+#[cfg(all(target_arch = "aarch64", feature = "neon"))]
+static NEON: Kernels = Kernels {
+    encode_min: Threshold::new(16, crate::simd::ENC_SHORT_MAX_NEON + 1),
+    encode: crate::simd::encode_slice_neon,
+    encode_short: Some(crate::simd::encode_short_neon),
+    decode_min: Threshold::new(20, crate::simd::DEC_SHORT_MAX_NEON + 1),
+    decode: crate::simd::decode_slice_neon,
+    decode_short: Some(crate::simd::decode_short_neon),
+    lenient: true,
+};
+
+// The SSSE3 block shapes. Decode needs no read-ahead margin of its own (the
+// lane stores write exactly 12 bytes), but still leaves the last group to the
+// scalar tail, so it needs 20 bytes to run.
+#[cfg(wasm_simd)]
+static WASM_SIMD: Kernels = Kernels {
+    encode_min: Threshold::new(16, usize::MAX),
+    encode: crate::simd::encode_slice_wasm,
+    encode_short: None,
+    decode_min: Threshold::new(20, usize::MAX),
+    decode: crate::simd::decode_slice_wasm,
+    decode_short: None,
+    lenient: false,
+};
+
+// The same block shapes as NEON, so the same thresholds.
+#[cfg(portable_simd)]
+static PORTABLE: Kernels = Kernels {
+    encode_min: Threshold::new(16, usize::MAX),
+    encode: crate::simd::encode_slice_portable,
+    encode_short: None,
+    decode_min: Threshold::new(20, usize::MAX),
+    decode: crate::simd::decode_slice_portable,
+    decode_short: None,
+    lenient: false,
+};
+
+/// The table for `backend`, falling back to scalar for one that is not
+/// compiled in. Callers must only pass supported backends (see [`Kernels`]).
+#[inline]
+pub(crate) fn for_backend(backend: Backend) -> &'static Kernels {
+    table(backend).unwrap_or(&SCALAR)
+}
+
+/// The table for `backend`'s SIMD kernels, if they are compiled in.
+#[inline]
+pub(crate) const fn table(backend: Backend) -> Option<&'static Kernels> {
+    match backend {
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => Some(&AVX512_VBMI),
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi256 => Some(&AVX512_VBMI_256),
+        #[cfg(x86_avx512bw)]
+        Backend::Avx512Bw => Some(&AVX512BW),
+        #[cfg(x86_avx2)]
+        Backend::Avx2 => Some(&AVX2),
+        #[cfg(x86_ssse3)]
+        Backend::Ssse3 => Some(&SSSE3),
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        Backend::Neon => Some(&NEON),
+        #[cfg(wasm_simd)]
+        Backend::WasmSimd => Some(&WASM_SIMD),
+        #[cfg(portable_simd)]
+        Backend::Portable => Some(&PORTABLE),
+        _ => None,
+    }
+}
+
+/// Stand-in installed until the first call: both entry points resolve the
+/// real table, install it, and forward to it. A threshold of 0 routes every
+/// call through them.
+#[cfg(not(x86_static))]
+static RESOLVER: Kernels = Kernels {
+    encode_min: Threshold::new(0, 0),
+    encode: resolve_encode,
+    encode_short: None,
+    decode_min: Threshold::new(0, 0),
+    decode: resolve_decode,
+    decode_short: None,
+    // The real table's flag is checked once the call is forwarded to it.
+    lenient: true,
+};
+
+/// The table default engines dispatch through. Every table it can point at is
+/// an immutable static, so the loads need no ordering: a thread that still
+/// sees [`RESOLVER`] merely resolves (to the same answer) again.
+#[cfg(not(x86_static))]
+static ACTIVE: AtomicPtr<Kernels> = AtomicPtr::new(core::ptr::from_ref(&RESOLVER).cast_mut());
+
+/// Picks the table for [`crate::active_backend`] and installs it.
+#[cfg(not(x86_static))]
+fn install() -> &'static Kernels {
+    let kernels = for_backend(crate::active_backend());
+    ACTIVE.store(core::ptr::from_ref(kernels).cast_mut(), Ordering::Relaxed);
+    kernels
+}
+
+/// Makes the next call resolve again, for a cap that changed after first use
+/// on builds that allow it.
+#[cfg(not(x86_simd))]
+pub(crate) fn reset() {
+    ACTIVE.store(core::ptr::from_ref(&RESOLVER).cast_mut(), Ordering::Relaxed);
+}
+
+/// The table default engines dispatch through.
+#[cfg(not(x86_static))]
+#[inline]
+pub(crate) fn active() -> &'static Kernels {
+    // SAFETY: `ACTIVE` only ever holds pointers to the statics above.
+    unsafe { &*ACTIVE.load(Ordering::Relaxed) }
+}
+
+/// The table default engines dispatch through, chosen without detection.
+/// Without `std` the tier is a constant, so this folds to one static and its
+/// pointers to direct, inlinable calls; with it, to a branch on the cached
+/// tier byte.
+#[cfg(x86_static)]
+#[inline]
+pub(crate) fn active() -> &'static Kernels {
+    for_backend(crate::cpu::tier())
+}
+
+#[cfg(not(x86_static))]
+unsafe fn resolve_encode(config: &Config, input: &[u8], dst: &mut [u8]) {
+    encode(install(), config, input, dst);
+}
+
+#[cfg(not(x86_static))]
+unsafe fn resolve_decode(config: &Config, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+    decode(install(), config, input, dst)
+}
+
+/// Runs `kernels`' encoder, or its short-input encoder or scalar below its
+/// threshold.
+#[inline]
+pub(crate) fn encode(kernels: &Kernels, config: &Config, input: &[u8], dst: &mut [u8]) {
+    if input.len() >= kernels.encode_min.get() {
+        // SAFETY: `kernels` came from `active`/`for_backend`, so the CPU
+        // supports its backend.
+        unsafe { (kernels.encode)(config, input, dst) }
+    } else if let Some(encode_short) = kernels.encode_short {
+        // SAFETY: as above; the input is below `encode_min`, which never
+        // passes one past the longest input the short kernel takes.
+        unsafe { encode_short(config, input, dst) }
+    } else {
+        // Fallback: Scalar / short inputs.
+        scalar::encode_slice(config, input, dst);
+    }
+}
+
+/// Runs `kernels`' decoder, or its short-input decoder or scalar below its
+/// threshold.
+#[inline]
+pub(crate) fn decode(
+    kernels: &Kernels,
+    config: &Config,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    if config.lenient && !kernels.lenient {
+        scalar::decode_slice(config, input, dst)
+    } else if input.len() >= kernels.decode_min.get() {
+        // SAFETY: `kernels` came from `active`/`for_backend`, so the CPU
+        // supports its backend.
+        unsafe { (kernels.decode)(config, input, dst) }
+    } else if let Some(decode_short) = kernels.decode_short {
+        // SAFETY: as above; the input is below `decode_min`, which never
+        // passes one past the longest input the short kernel takes.
+        unsafe { decode_short(config, input, dst) }
+    } else {
+        // Fallback: Scalar / short inputs.
+        scalar::decode_slice(config, input, dst)
+    }
+}
@@ -0,0 +1,108 @@
+//! Ignore-garbage decoding: drop every byte outside the alphabet and decode
+//! the rest, as GNU `base64 --ignore-garbage` and Java's MIME decoder do.
+//!
+//! The input goes through in pieces of [`PIECE`] bytes, each compacted to its
+//! alphabet characters and `'='` in a stack buffer, by the validity mask the
+//! backend's own decoder computes: on AVX512-VBMI from its `vpermi2b` lookup,
+//! driving a `vpermb` that packs the kept lanes together; on AVX2 (and the
+//! AVX-512 backends without VBMI) from its nibble lookup, driving a `vpshufb`;
+//! on NEON from its nibble lookup, driving a `tbl`. Other backends compact in
+//! scalar. The whole groups that builds up are decoded by the engine's own
+//! kernel, so every backend still decodes in vectors. The last group is held
+//! back until the input ends, so the padding and length rules only ever see
+//! it.
+use crate::{Backend, Engine, Error};
+
+// Scalar kernel. The SIMD ones live with their Base64 siblings under
+// `crate::simd`.
+pub(crate) mod scalar;
+
+/// Input bytes compacted per pass. The stack buffer holds one piece plus the
+/// group held back from the one before.
+const PIECE: usize = 4096;
+
+/// Compacts `src` into `dst` on `backend`'s kernel.
+#[inline]
+fn compact(backend: Backend, engine: Engine, src: &[u8], dst: &mut [u8]) -> usize {
+    match backend {
+        // SAFETY: the backend is supported, so the CPU has VBMI.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => unsafe {
+            crate::simd::compact_avx512_vbmi(&engine.config, src, dst)
+        },
+        // SAFETY: the backend is supported, so the CPU has AVX2; the AVX-512
+        // ones imply it, as for hex.
+        #[cfg(x86_avx2)]
+        Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+            crate::simd::compact_avx2(&engine.config, src, dst)
+        },
+        // SAFETY: NEON is mandatory on aarch64.
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        Backend::Neon => unsafe { crate::simd::compact_neon(&engine.config, src, dst) },
+        _ => scalar::compact(&engine.config, src, dst),
+    }
+}
+
+/// Decodes `chars` into `output` at `written`, through a window of
+/// `estimate_decoded_len` bytes, as `decode_into` gives the kernels. Where
+/// `output` has less than that left, see [`decode_bounced`].
+#[inline]
+fn decode_at(
+    engine: Engine,
+    chars: &[u8],
+    output: &mut [u8],
+    written: usize,
+) -> Result<usize, Error> {
+    if chars.is_empty() {
+        return Ok(0);
+    }
+    let window = engine.estimate_decoded_len(chars.len());
+    let out = &mut output[written..];
+    if out.len() < window {
+        return decode_bounced(engine, chars, out);
+    }
+    engine.decode_dispatch(chars, &mut out[..window])
+}
+
+/// Decodes `chars`, at most a piece's worth, through a stack buffer with the
+/// kernel's full window and copies the bytes over, so `out` only has to hold
+/// what they decode to. Only the last window or two of a tight `output` come
+/// here.
+#[cold]
+fn decode_bounced(engine: Engine, chars: &[u8], out: &mut [u8]) -> Result<usize, Error> {
+    let mut bounce = [0u8; PIECE / 4 * 3 + 3];
+    let window = engine.estimate_decoded_len(chars.len());
+    let n = engine.decode_dispatch(chars, &mut bounce[..window])?;
+    out.get_mut(..n)
+        .ok_or(Error::BufferTooSmall)?
+        .copy_from_slice(&bounce[..n]);
+    Ok(n)
+}
+
+/// The body of [`Engine::decode_ignore_garbage_into`]. The output is
+/// bound-checked as it is written, so [`Error::BufferTooSmall`] means the
+/// bytes decoded so far did not fit.
+pub(crate) fn decode(engine: Engine, input: &[u8], output: &mut [u8]) -> Result<usize, Error> {
+    let backend = engine.backend();
+    let mut buf = [0u8; PIECE + 4];
+    let mut held = 0;
+    let mut written = 0;
+
+    for piece in input.chunks(PIECE) {
+        held += compact(backend, engine, piece, &mut buf[held..]);
+
+        // Everything but the last 1 to 4 characters, in whole groups.
+        let body = held.saturating_sub(1) / 4 * 4;
+        let n = body / 4 * 3;
+        if decode_at(engine, &buf[..body], output, written)? != n {
+            // Fewer bytes than groups: the decoder stopped at a padded one
+            // that is not the last, and would drop what follows it.
+            return Err(Error::InvalidCharacter);
+        }
+        written += n;
+        buf.copy_within(body..held, 0);
+        held -= body;
+    }
+
+    Ok(written + decode_at(engine, &buf[..held], output, written)?)
+}
@@ -0,0 +1,32 @@
+//! Scalar (non-SIMD) compaction for ignore-garbage decoding.
+//!
+//! **100% safe Rust.** Every byte is written to the next free slot and the
+//! slot only advances when the byte is kept, so the loop has no branch on the
+//! data. The VBMI kernel is tested against this one.
+
+#![forbid(unsafe_code)]
+// `&Config`, as in the Base64 scalar kernel and the SIMD one beside this.
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use crate::{Config, LENIENT_DECODE_TABLE, STANDARD_DECODE_TABLE, URL_SAFE_DECODE_TABLE};
+
+/// Copies the bytes of `src` that `config` decodes, plus `'='`, to the front
+/// of `dst`, which must be at least as long, and returns how many there were.
+#[inline]
+pub(crate) fn compact(config: &Config, src: &[u8], dst: &mut [u8]) -> usize {
+    let table = if config.lenient {
+        &LENIENT_DECODE_TABLE
+    } else if config.url_safe {
+        &URL_SAFE_DECODE_TABLE
+    } else {
+        &STANDARD_DECODE_TABLE
+    };
+    // `kept` never passes the index of the byte being read, so the write is in
+    // bounds.
+    let mut kept = 0;
+    for &c in src {
+        dst[kept] = c;
+        kept += usize::from(table[usize::from(c)] < 64 || c == b'=');
+    }
+    kept
+}
@@ -0,0 +1,311 @@
+//! Base16 (hex) encoding, on the same kernels-and-dispatch footing as the
+//! Base64 [`Engine`](crate::Engine).
+//!
+//! [`HEX_LOWER`] and [`HEX_UPPER`] only differ in the case they encode to;
+//! both decode either case. Each call runs the hex kernel matching
+//! [`active_backend`] (or the backend pinned with [`Engine::with_backend`]):
+//! AVX512-VBMI, AVX2 or NEON where one is compiled in, scalar everywhere else.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use base64_turbo::hex::{HEX_LOWER, HEX_UPPER};
+//!
+//! assert_eq!(HEX_LOWER.encode(b"\xde\xad\xbe\xef"), "deadbeef");
+//! assert_eq!(HEX_UPPER.encode(b"\xde\xad\xbe\xef"), "DEADBEEF");
+//! assert_eq!(HEX_LOWER.decode("DeadBeef").unwrap(), b"\xde\xad\xbe\xef");
+//! # }
+//! ```
+
+use crate::{Backend, Error, Unsupported, active_backend};
+
+// Scalar implementation. The SIMD kernels live with their Base64 siblings
+// under `crate::simd`, and hand their remainder to this one.
+pub(crate) mod scalar;
+
+/// The lowercase hex digits, indexed by nibble.
+const LOWER_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+/// The uppercase hex digits, indexed by nibble.
+const UPPER_ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+
+/// The digits an engine encodes to.
+pub(crate) const fn alphabet(upper: bool) -> &'static [u8; 16] {
+    if upper {
+        UPPER_ALPHABET
+    } else {
+        LOWER_ALPHABET
+    }
+}
+
+/// A stateless hex encoder/decoder.
+///
+/// The hex counterpart of the Base64 [`Engine`](crate::Engine), with the same
+/// zero-allocation and allocating APIs. Two characters encode each byte, so
+/// both length calculators are exact.
+///
+/// # Examples
+///
+/// ```rust
+/// use base64_turbo::hex::HEX_LOWER;
+///
+/// let mut buf = [0u8; 8];
+/// let n = HEX_LOWER.encode_into(b"\x00\x7f\x80\xff", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"007f80ff");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Engine {
+    /// If true, encodes to `A`-`F` rather than `a`-`f`.
+    pub(crate) upper: bool,
+    /// The kernel pinned by [`Engine::with_backend`]; `None` follows
+    /// [`active_backend`].
+    pub(crate) backend: Option<Backend>,
+}
+
+/// Lowercase hex (`0-9a-f`), as `sha256sum` and most trace IDs print it.
+///
+/// Decodes either case.
+pub const HEX_LOWER: Engine = Engine {
+    upper: false,
+    backend: None,
+};
+
+/// Uppercase hex (`0-9A-F`).
+///
+/// Decodes either case.
+pub const HEX_UPPER: Engine = Engine {
+    upper: true,
+    backend: None,
+};
+
+impl Engine {
+    // ======================================================================
+    // Backend Selection
+    // ======================================================================
+
+    /// Returns a copy of this engine that always runs `backend`'s kernel,
+    /// instead of following [`active_backend`].
+    ///
+    /// The check is the one [`Engine::with_backend`](crate::Engine::with_backend)
+    /// makes. Backends without a hex kernel of their own run the closest one
+    /// they can: the AVX-512BW and 256-bit VBMI backends run the AVX2 kernel,
+    /// and the rest run scalar.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Unsupported`] if `backend` is not compiled into this build or
+    /// the current CPU cannot run it.
+    #[inline]
+    pub fn with_backend(self, backend: Backend) -> Result<Self, Unsupported> {
+        Ok(Self {
+            backend: backend.pin()?,
+            ..self
+        })
+    }
+
+    /// The backend this engine dispatches to: the one pinned by
+    /// [`with_backend`](Self::with_backend), or [`active_backend`] otherwise.
+    #[inline]
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend.unwrap_or_else(active_backend)
+    }
+
+    // ======================================================================
+    // Length Calculators
+    // ======================================================================
+
+    /// The exact number of characters `input_len` bytes encode to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::hex::HEX_LOWER;
+    ///
+    /// assert_eq!(HEX_LOWER.encoded_len(32), 64);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        input_len * 2
+    }
+
+    /// The exact number of bytes `input_len` characters decode to. An odd
+    /// length is rounded down, though decoding it fails with
+    /// [`Error::InvalidLength`].
+    #[inline]
+    #[must_use]
+    pub const fn decoded_len(&self, input_len: usize) -> usize {
+        input_len / 2
+    }
+
+    // ======================================================================
+    // Zero-Allocation APIs
+    // ======================================================================
+
+    /// Encodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error::BufferTooSmall)`: If `output.len()` is less than [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is not large enough to hold the
+    /// encoded data (see [`encoded_len`](Self::encoded_len)).
+    #[inline]
+    pub fn encode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::encoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Self::encode_dispatch(self, input, &mut output[..req_len]);
+
+        Ok(req_len)
+    }
+
+    /// Decodes `input`, in either case, into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] if `input` has an odd length,
+    /// [`Error::BufferTooSmall`] if `output` is shorter than
+    /// [`decoded_len`](Self::decoded_len), or [`Error::InvalidCharacter`] if
+    /// `input` holds anything but hex digits.
+    #[inline]
+    pub fn decode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+        if !len.is_multiple_of(2) {
+            return Err(Error::InvalidLength);
+        }
+
+        let req_len = Self::decoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Self::decode_dispatch(self, input, &mut output[..req_len])?;
+
+        Ok(req_len)
+    }
+
+    // ========================================================================
+    // Allocating APIs (std)
+    // ========================================================================
+
+    /// Allocates a new `String` and encodes the input data into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::hex::HEX_LOWER;
+    /// assert_eq!(HEX_LOWER.encode(b"hello"), "68656c6c6f");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::encode`: the length is exact, every byte is
+        // overwritten, and the output is pure ASCII.
+        let mut out = crate::spare(Self::encoded_len(self, input.len()));
+        Self::encode_dispatch(self, input, &mut out);
+        crate::into_ascii_string(out)
+    }
+
+    /// Allocates a new `Vec<u8>` and decodes the input data, in either case,
+    /// into it.
+    ///
+    /// # Errors
+    /// Returns `Error` if the input has an odd length or contains anything but
+    /// hex digits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::hex::HEX_LOWER;
+    /// assert_eq!(HEX_LOWER.decode("68656C6C6F").unwrap(), b"hello");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // The length is exact, so on success every byte has been written; on
+        // error the whole buffer is dropped unread.
+        let mut out = crate::spare(Self::decoded_len(self, input.len()));
+        Self::decode_into(self, input, &mut out)?;
+        Ok(out)
+    }
+
+    // ========================================================================
+    // Internal Dispatchers
+    // ========================================================================
+
+    // The Base64 kernels go through `dispatch`'s tables; hex has three kernels
+    // and no length thresholds to calibrate, so a match on the backend is all
+    // the dispatch it needs. `backend` is one relaxed load once detection has
+    // run, and always names a backend that passed `Backend::is_supported`.
+    //
+    // The AVX-512 backends without a hex kernel run the AVX2 one: rustc treats
+    // `avx512f` as implying `avx2`, so the Base64 AVX-512 kernels already count
+    // on AVX2 being there whenever they are.
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn encode_dispatch(&self, input: &[u8], dst: &mut [u8]) {
+        match self.backend() {
+            // SAFETY: the backend is supported, so the CPU has VBMI.
+            #[cfg(x86_avx512_vbmi)]
+            Backend::Avx512Vbmi => unsafe {
+                crate::simd::encode_hex_avx512_vbmi(self.upper, input, dst);
+            },
+            // SAFETY: the backend is supported, so the CPU has AVX2 (see above).
+            #[cfg(x86_avx2)]
+            Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+                crate::simd::encode_hex_avx2(self.upper, input, dst);
+            },
+            // SAFETY: NEON is mandatory on aarch64.
+            #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+            Backend::Neon => unsafe { crate::simd::encode_hex_neon(self.upper, input, dst) },
+            _ => scalar::encode_slice(self.upper, input, dst),
+        }
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn decode_dispatch(&self, input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+        match self.backend() {
+            // SAFETY: as in `encode_dispatch`.
+            #[cfg(x86_avx512_vbmi)]
+            Backend::Avx512Vbmi => unsafe { crate::simd::decode_hex_avx512_vbmi(input, dst) },
+            // SAFETY: as in `encode_dispatch`.
+            #[cfg(x86_avx2)]
+            Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+                crate::simd::decode_hex_avx2(input, dst)
+            },
+            // SAFETY: as in `encode_dispatch`.
+            #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+            Backend::Neon => unsafe { crate::simd::decode_hex_neon(input, dst) },
+            _ => scalar::decode_slice(input, dst),
+        }
+    }
+}
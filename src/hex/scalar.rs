@@ -0,0 +1,85 @@
+//! Scalar (non-SIMD) hex encode/decode primitives.
+//!
+//! Like the Base64 scalar kernel, this is **100% safe Rust** and table-driven:
+//! encode maps a byte straight to its two digits in one lookup, and decode
+//! folds the high nibble's `<< 4` into its table, so a pair of digits is two
+//! loads OR-ed together, with validation falling out of the same OR. The SIMD
+//! kernels hand their remainder here, so it is also the reference they are
+//! tested against.
+
+#![forbid(unsafe_code)]
+
+use super::{LOWER_ALPHABET, UPPER_ALPHABET, alphabet};
+use crate::Error;
+
+/// Maps a byte to its two digits, packed little-endian so the high nibble's
+/// digit lands in the low byte.
+const fn encode_pair_table(alphabet: &[u8; 16]) -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (alphabet[i >> 4] as u16) | ((alphabet[i & 0xF] as u16) << 8);
+        i += 1;
+    }
+    table
+}
+
+static LOWER_ENCODE_PAIRS: [u16; 256] = encode_pair_table(alphabet(false));
+static UPPER_ENCODE_PAIRS: [u16; 256] = encode_pair_table(alphabet(true));
+
+/// Reverse lookup for either case, with the nibble pre-shifted by `shift`.
+/// Invalid characters map to `u16::MAX`, so OR-ing a pair together pushes the
+/// result above `0xFF` if either was bad.
+const fn decode_shift_table(shift: u32) -> [u16; 256] {
+    let mut table = [u16::MAX; 256];
+    let mut i: u16 = 0;
+    while i < 16 {
+        table[LOWER_ALPHABET[i as usize] as usize] = i << shift;
+        table[UPPER_ALPHABET[i as usize] as usize] = i << shift;
+        i += 1;
+    }
+    table
+}
+
+/// The high- and low-nibble tables, indexed by a digit's position in its pair.
+static DECODE_SHIFTED: [[u16; 256]; 2] = [decode_shift_table(4), decode_shift_table(0)];
+
+/// Encodes `input` as hex, writing `2 * input.len()` digits into `dst`.
+///
+/// A `dst` that is too small will panic (bounds check) rather than corrupt
+/// memory.
+#[inline]
+pub(crate) fn encode_slice(upper: bool, input: &[u8], dst: &mut [u8]) {
+    let pairs = if upper {
+        &UPPER_ENCODE_PAIRS
+    } else {
+        &LOWER_ENCODE_PAIRS
+    };
+
+    for (&byte, out) in input.iter().zip(dst[..2 * input.len()].chunks_exact_mut(2)) {
+        out.copy_from_slice(&pairs[usize::from(byte)].to_le_bytes());
+    }
+}
+
+/// Decodes hex `input`, in either case, into the first `input.len() / 2`
+/// bytes of `dst`.
+///
+/// A `dst` that is too small will panic (bounds check) rather than corrupt
+/// memory.
+///
+/// # Errors
+/// Returns [`Error::InvalidLength`] if `input` has an odd length, and
+/// [`Error::InvalidCharacter`] if it holds anything but hex digits.
+#[inline]
+pub(crate) fn decode_slice(input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    if !input.len().is_multiple_of(2) {
+        return Err(Error::InvalidLength);
+    }
+
+    for (digits, out) in input.chunks_exact(2).zip(&mut dst[..input.len() / 2]) {
+        let n =
+            DECODE_SHIFTED[0][usize::from(digits[0])] | DECODE_SHIFTED[1][usize::from(digits[1])];
+        *out = u8::try_from(n).map_err(|_| Error::InvalidCharacter)?;
+    }
+    Ok(())
+}
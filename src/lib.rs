@@ -17,8 +17,9 @@
 //! not claim to be; within the narrower set of crates combining SIMD-accelerated Base64 with
 //! Kani + MIRI verification, we are not aware of another one that reaches AVX-512 VBMI speeds.
 //!
-//! This crate provides runtime CPU detection to utilize **AVX-512 VBMI** or **AVX2** intrinsics on `x86_64`,
-//! and compile-time **NEON** acceleration on `aarch64`.
+//! This crate provides runtime CPU detection to utilize **AVX-512 VBMI**, **AVX-512BW**, **AVX2** or **SSSE3** intrinsics on `x86_64`,
+//! and compile-time **NEON** acceleration on `aarch64` and **`simd128`** on `wasm32`; on
+//! nightly, the `portable-simd` feature adds a `core::simd` kernel for every other target.
 //! It includes a highly optimized scalar fallback for non-SIMD targets and supports `no_std` environments.
 //!
 //! ### Basic API (Allocating)
@@ -59,23 +60,107 @@
 //! assert_eq!(&output[..len], b"UmF3IGJ5dGVz");
 //! ```
 //!
+//! ### Backend Selection
+//!
+//! [`active_backend`] reports which kernel the engines dispatch to, and
+//! [`Engine::with_backend`] pins a specific one after checking the CPU can run it.
+//!
+//! ```rust
+//! use base64_turbo::{Backend, STANDARD};
+//!
+//! println!("using {}", base64_turbo::active_backend());
+//!
+//! // Every build can fall back to scalar; SIMD kernels depend on the host.
+//! let scalar = STANDARD.with_backend(Backend::Scalar).unwrap();
+//! assert_eq!(scalar.backend(), Backend::Scalar);
+//! ```
+//!
+//! ### Lenient Decoding
+//!
+//! [`LENIENT`] decodes either alphabet, even mixed in one input, with or
+//! without padding, mapping `+`/`-` to 62 and `/`/`_` to 63 in one table.
+//!
+//! ### Ignoring Garbage
+//!
+//! [`Engine::decode_ignore_garbage_into`] drops every byte outside the
+//! alphabet, such as line breaks or quoting, and decodes the rest, as GNU
+//! `base64 --ignore-garbage` does.
+//!
+//! ### Transcoding
+//!
+//! [`transcode`] converts Base64 from one engine's alphabet and padding to
+//! another's without decoding it, validating as it goes, and
+//! [`transcode_in_place`] does so where it lies.
+//!
+//! ```rust
+//! use base64_turbo::{STANDARD, URL_SAFE_NO_PAD, transcode};
+//!
+//! let mut buf = [0u8; 8];
+//! let n = transcode(&STANDARD, &URL_SAFE_NO_PAD, "PDw/Pz4+", &mut buf).unwrap();
+//! assert_eq!(&buf[..n], b"PDw_Pz4-");
+//! ```
+//!
+//! [`base64_to_hex`] and [`hex_to_base64`] do the same between Base64 and
+//! [`hex`], with no buffer of bytes in between.
+//!
+//! ### Hex
+//!
+//! The [`hex`] module applies the same kernels and dispatch to Base16:
+//! [`hex::HEX_LOWER`] and [`hex::HEX_UPPER`] have the same API as the Base64
+//! engines, and decode either case.
+//!
+//! ### Base32
+//!
+//! The [`base32`] module does the same for RFC 4648 Base32, base32hex,
+//! Crockford and z-base-32, with [`base32::BASE32`] and its siblings.
+//!
+//! ### Password hashes
+//!
+//! The [`crypt`] module decodes and encodes the Base64 dialects of password
+//! hashes: [`crypt::CRYPT`] for md5-crypt and sha-crypt, and
+//! [`crypt::BCRYPT`] for bcrypt, each with its own alphabet and bit order.
+//!
+//! ### Base85
+//!
+//! The [`base85`] module offers [`base85::Z85`], Adobe [`base85::ASCII85`] and
+//! [`base85::RFC1924`] with the same API. They have no SIMD kernels.
+//!
+//! ### Base45
+//!
+//! The [`base45`] module's [`base45::BASE45`] is RFC 9285 Base45, for QR
+//! payloads. It is safe scalar code under `#![forbid(unsafe_code)]` on every
+//! build, and works on `no_std`.
+//!
+//! ### Base58 and Base62
+//!
+//! The [`radix`] module has Base58 ([`radix::BASE58`] with Bitcoin's alphabet,
+//! plus the Ripple and Flickr ones) and [`radix::BASE62`], with the same API.
+//!
 //! ## Feature Flags
 //!
 //! Each x86 SIMD kernel is an independent knob, so a target can compile in only
 //! what its CPUs are likely to support. Runtime detection still gates every call,
-//! so a kernel the host lacks simply falls back to scalar.
+//! so a kernel the host lacks simply falls back to scalar. When the target itself
+//! guarantees a kernel's instructions (`-C target-cpu=...`), that kernel is
+//! chosen at compile time instead, with no detection at all (with `std`, a
+//! [`set_max_backend`] cap can still lower it). Neither needs `std`:
+//! `no_std` builds detect through `cpuid`/`xgetbv` directly.
 //!
 //! | Feature | Default | Description |
 //! |---------|---------|-------------|
 //! | **`std`** | **Yes** | Enables `String` and `Vec` support. Disable this for `no_std` environments. |
-//! | **`avx2`** | **Yes** | AVX2 kernel + runtime detection on `x86`/`x86_64`. Implies `std`. |
-//! | **`avx512-vbmi`** | **Yes** | AVX-512 VBMI fast-path kernel on `x86`/`x86_64`. Implies `std`. |
-//! | **`simd`** | **Yes** | Convenience meta-feature: enables `avx2` + `avx512-vbmi` at once. |
+//! | **`avx2`** | **Yes** | AVX2 kernel + runtime detection on `x86`/`x86_64` (`cpuid` without `std`), or static dispatch when the target enables `avx2`. |
+//! | **`avx512bw`** | **Yes** | AVX-512BW kernel for `x86`/`x86_64` CPUs with AVX-512 but no VBMI (Skylake-SP, Cascade Lake). Detected or static, like `avx2`. |
+//! | **`avx512-vbmi`** | **Yes** | AVX-512 VBMI fast-path kernel on `x86`/`x86_64`, plus its 256-bit AVX-512VL variant ([`Backend::Avx512Vbmi256`]). Detected or static, like `avx2`. |
+//! | **`ssse3`** | **Yes** | 128-bit SSSE3 kernel for `x86`/`x86_64` CPUs without AVX2. Detected or static, like `avx2`. |
+//! | **`simd`** | **Yes** | Convenience meta-feature: enables `ssse3` + `avx2` + `avx512bw` + `avx512-vbmi` at once. |
 //! | **`neon`** | **Yes** | **NEON** acceleration on aarch64 (ARM64). No `std` required — compile-time dispatch. |
+//! | **`wasm-simd`** | **Yes** | **`simd128`** acceleration on `wasm32`, when the target enables it (`-C target-feature=+simd128`). Compile-time dispatch. |
+//! | **`portable-simd`** | **No** | Nightly only: a `core::simd` kernel ([`Backend::Portable`]) for targets without a hand-written one (RISC-V, `LoongArch`, `PowerPC`, WASM). Ignored on stable. |
 //! | **`unstable`** | **No** | Exposes the raw internal kernels (e.g. `encode_avx2`; the `*_scalar` accessors are safe). |
 //!
-//! If **no** SIMD kernel is enabled (no `avx2`/`avx512-vbmi` on x86, no
-//! `neon` on aarch64), the build is pure scalar Rust and the crate carries
+//! If **no** SIMD kernel is enabled (no `ssse3`/`avx2`/`avx512bw`/`avx512-vbmi` on x86, no
+//! `neon` on aarch64, no `wasm-simd` on wasm32 with `simd128`, no `portable-simd`), the build is pure scalar Rust and the crate carries
 //! `#![forbid(unsafe_code)]` — memory safety then holds by construction, with no
 //! `unsafe` anywhere to audit.
 //!
@@ -84,15 +169,15 @@
 //! This crate utilizes `unsafe` code for SIMD intrinsics and pointer arithmetic to achieve maximum performance.
 //! To ensure safety, we employ a "Swiss Cheese" model of verification layers:
 //!
-//! *   **Model checking (Kani):** For the Scalar and AVX2 kernels, Kani explores
+//! *   **Model checking (Kani):** For the Scalar, AVX2, SSSE3 and NEON kernels, Kani explores
 //!     *every possible input byte value* at lengths chosen to exercise each loop tier and the
 //!     scalar-tail handoff, proving the kernel does not panic, does not read or write out of
-//!     bounds, and round-trips exactly. On AVX2 a second layer of proofs takes the loop
+//!     bounds, and round-trips exactly. On AVX2, SSSE3 and NEON a second layer of proofs takes the loop
 //!     arithmetic on its own, over an unbounded symbolic length and an arbitrary iteration, so
 //!     the in-bounds result there is a machine-checked induction covering every length rather
 //!     than the ones a harness happens to pin. The README spells out what that does and does not
-//!     buy you, along with the AVX512-VBMI and NEON gaps.
-//! *   **MIRI Audited:** All SIMD paths (AVX512-VBMI, AVX2, NEON) and Scalar fallbacks are run under
+//!     buy you, along with the AVX-512 gaps.
+//! *   **MIRI Audited:** All SIMD paths (AVX512-VBMI, AVX-512BW, AVX2, SSSE3, NEON) and Scalar fallbacks are run under
 //!     **MIRI** (Undefined Behavior checker) in CI, covering every distinct code path at least once.
 //! *   **`MemorySanitizer`:** The codebase is audited with `MSan` to prevent logic errors derived from reading uninitialized memory.
 //! *   **Fuzzing:** The codebase is fuzz-tested via `cargo-fuzz` (2.5B+ iterations).
@@ -112,6 +197,7 @@
 #![allow(clippy::cast_ptr_alignment)]
 #![cfg_attr(test, allow(clippy::unwrap_used, clippy::expect_used))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(portable_simd, feature(portable_simd))]
 
 #[cfg(all(doctest, feature = "std"))]
 #[doc = include_str!("../README.md")]
@@ -123,65 +209,55 @@ mod scalar;
 #[cfg(unsafe_simd)]
 mod simd;
 
-/// Runtime CPU capability detection for the x86 kernels, resolved once and cached.
-///
-/// `std::is_x86_feature_detected!` already caches its answer internally, but this
-/// collapses the whole *tier* decision — which of the compiled kernels to run —
-/// into a single load after the first call, instead of re-checking each feature
-/// bit on every encode/decode.
+// Runtime CPU detection for the x86 kernels.
 #[cfg(x86_simd)]
-mod cpu {
-    use std::sync::OnceLock;
-
-    // Tier levels, ordered least- to most-capable so callers compare with `>=`.
-    // Each level exists only when its kernel was compiled in, which keeps the
-    // detection and dispatch arms in lockstep with the feature set.
-    #[cfg(feature = "avx2")]
-    pub(crate) const AVX2: u8 = 1;
-    #[cfg(feature = "avx512-vbmi")]
-    pub(crate) const AVX512_VBMI: u8 = 2;
-
-    fn detect() -> u8 {
-        #[cfg(feature = "avx512-vbmi")]
-        if std::is_x86_feature_detected!("avx512f")
-            && std::is_x86_feature_detected!("avx512bw")
-            && std::is_x86_feature_detected!("avx512vbmi")
-        {
-            return AVX512_VBMI;
-        }
-        #[cfg(feature = "avx2")]
-        if std::is_x86_feature_detected!("avx2") {
-            return AVX2;
-        }
-        0 // scalar
-    }
+mod cpu;
+// Kernel selection, resolved once and cached as a table of function pointers.
+#[cfg(unsafe_simd)]
+mod dispatch;
+// The length thresholds dispatch switches kernels at, and their calibration.
+mod thresholds;
+// Base64-to-Base64 and Base64-to-hex conversion without decoding to bytes.
+mod transcode;
+// Decoding that skips every byte outside the alphabet (`base64 -i`).
+mod garbage;
+
+// Base16, on the same detection and dispatch.
+pub mod hex;
+// Base32, likewise.
+pub mod base32;
+// The crypt(3) and bcrypt Base64 dialects, likewise.
+pub mod crypt;
+// Base85, scalar only.
+pub mod base85;
+// Base45, safe scalar code on every build.
+pub mod base45;
+// Base58 and Base62, scalar only.
+pub mod radix;
 
-    /// The best compiled-in kernel tier the current CPU supports. Detected on the
-    /// first call and cached for the lifetime of the process.
-    #[inline]
-    pub(crate) fn tier() -> u8 {
-        static CACHE: OnceLock<u8> = OnceLock::new();
-        *CACHE.get_or_init(detect)
-    }
-}
+#[cfg(feature = "std")]
+pub use thresholds::calibrate;
+pub use thresholds::{ParseThresholdsError, Thresholds, set_thresholds, thresholds};
+pub use transcode::{base64_to_hex, hex_to_base64, transcode, transcode_in_place};
 
 // ======================================================================
 // ERROR DEFINITION
 // ======================================================================
 
-/// Errors that can occur during Base64 encoding or decoding operations.
+/// Errors that can occur while encoding or decoding, shared by every codec in
+/// the crate.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Error {
-    /// The input length is invalid for Base64 decoding.
+    /// The input length is invalid for the codec.
     ///
-    /// Base64 encoded data (with padding) must strictly have a length divisible by 4.
-    /// If the input string is truncated or has incorrect padding length, this error is returned.
+    /// Returned when the input is truncated or cannot be a whole encoding, e.g.
+    /// padded Base64 whose length is not divisible by 4, or odd-length hex.
     InvalidLength,
 
     /// An invalid character was encountered during decoding.
     ///
     /// This occurs if the input contains bytes that do not belong to the
-    /// selected Base64 alphabet (e.g., symbols not in the standard set) or
+    /// selected alphabet (e.g., symbols not in the standard Base64 set) or
     /// if padding characters (`=`) appear in invalid positions.
     InvalidCharacter,
 
@@ -210,6 +286,302 @@ impl core::fmt::Display for Error {
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
+/// Returned by [`Engine::with_backend`] when the requested kernel cannot run.
+///
+/// Either the kernel was not compiled in (its cargo feature is off, or it
+/// targets another architecture) or the current CPU lacks an instruction
+/// subset it issues.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsupported {
+    backend: Backend,
+}
+
+impl Unsupported {
+    /// The backend that was requested.
+    #[inline]
+    #[must_use]
+    pub const fn backend(&self) -> Backend {
+        self.backend
+    }
+}
+
+impl core::fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "The {} backend is not available on this build or CPU",
+            self.backend
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Unsupported {}
+
+// ======================================================================
+// Backend Selection
+// ======================================================================
+
+/// A kernel family that can run the encode/decode loops.
+///
+/// By default every [`Engine`] dispatches to [`active_backend`], the fastest
+/// kernel that is both compiled in and supported by the current CPU.
+/// [`Engine::with_backend`] pins a specific one instead, e.g. for an A/B test.
+///
+/// Whichever backend is selected, inputs too short to fill its vector width
+/// run on its short-input kernel where it has one, and on the scalar kernel
+/// otherwise; [`Thresholds`] has the lengths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// The table-driven scalar kernel. Always available.
+    Scalar,
+    /// The 128-bit SSSE3 kernel on `x86`/`x86_64` (feature `ssse3`), for CPUs
+    /// without AVX2.
+    Ssse3,
+    /// The AVX2 kernel on `x86`/`x86_64` (feature `avx2`).
+    Avx2,
+    /// The AVX-512BW kernel on `x86`/`x86_64` (feature `avx512bw`), for
+    /// AVX-512 CPUs without VBMI.
+    Avx512Bw,
+    /// The AVX-512 VBMI kernel at 256 bits on `x86`/`x86_64` (feature
+    /// `avx512-vbmi`, plus AVX-512VL at runtime). It keeps to YMM registers, so
+    /// it never asks for the lower frequency licence some Intel parts impose on
+    /// 512-bit code. Detection ranks it just below [`Backend::Avx512Vbmi`]:
+    /// select it by capping there (see [`set_max_backend`]) or by pinning it
+    /// with [`Engine::with_backend`].
+    Avx512Vbmi256,
+    /// The AVX-512 VBMI kernel on `x86`/`x86_64` (feature `avx512-vbmi`).
+    Avx512Vbmi,
+    /// The NEON kernel on `aarch64` (feature `neon`).
+    Neon,
+    /// The `simd128` kernel on `wasm32` (feature `wasm-simd`), when the target
+    /// enables `simd128`. Chosen at compile time.
+    WasmSimd,
+    /// The portable `core::simd` kernel (feature `portable-simd`, nightly
+    /// only), for targets without a hand-written one. It is picked at compile
+    /// time wherever no other kernel is compiled in; x86 detection never
+    /// picks it, but it can still be pinned with [`Engine::with_backend`].
+    Portable,
+}
+
+impl Backend {
+    /// A short lowercase name, matching the cargo feature that compiles the
+    /// kernel in (`"scalar"` for the scalar kernel, and `"avx512-vbmi-256"` for
+    /// the 256-bit variant `avx512-vbmi` also compiles).
+    #[inline]
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Scalar => "scalar",
+            Self::Ssse3 => "ssse3",
+            Self::Avx2 => "avx2",
+            Self::Avx512Bw => "avx512bw",
+            Self::Avx512Vbmi256 => "avx512-vbmi-256",
+            Self::Avx512Vbmi => "avx512-vbmi",
+            Self::Neon => "neon",
+            Self::WasmSimd => "wasm-simd",
+            Self::Portable => "portable-simd",
+        }
+    }
+
+    /// Whether this backend's kernel is compiled in and the current CPU can
+    /// run it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::Backend;
+    ///
+    /// assert!(Backend::Scalar.is_supported());
+    /// ```
+    #[must_use]
+    // Only `const`-eligible in builds without an x86 kernel to detect.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Scalar => true,
+            Self::Neon => cfg!(all(target_arch = "aarch64", feature = "neon")),
+            Self::WasmSimd => cfg!(wasm_simd),
+            Self::Portable => cfg!(portable_simd),
+            #[cfg(x86_simd)]
+            Self::Ssse3 | Self::Avx2 | Self::Avx512Bw | Self::Avx512Vbmi256 | Self::Avx512Vbmi => {
+                cpu::has(self)
+            }
+            #[cfg(not(x86_simd))]
+            Self::Ssse3 | Self::Avx2 | Self::Avx512Bw | Self::Avx512Vbmi256 | Self::Avx512Vbmi => {
+                false
+            }
+        }
+    }
+}
+
+impl Backend {
+    /// Whether a cap of `max` admits this backend. Scalar is always admitted;
+    /// a SIMD kernel only when `max` is it or a more capable kernel for the
+    /// same architecture, so capping at another architecture's kernel leaves
+    /// scalar alone. The portable kernel belongs to every architecture and
+    /// ranks below all of them, so any SIMD cap admits it.
+    #[cfg(any(not(x86_static), x86_cached))]
+    const fn capped_by(self, max: Self) -> bool {
+        matches!(
+            (self, max),
+            (Self::Scalar, _)
+                | (
+                    Self::Ssse3,
+                    Self::Ssse3
+                        | Self::Avx2
+                        | Self::Avx512Bw
+                        | Self::Avx512Vbmi256
+                        | Self::Avx512Vbmi
+                )
+                | (
+                    Self::Avx2,
+                    Self::Avx2 | Self::Avx512Bw | Self::Avx512Vbmi256 | Self::Avx512Vbmi
+                )
+                | (
+                    Self::Avx512Bw,
+                    Self::Avx512Bw | Self::Avx512Vbmi256 | Self::Avx512Vbmi
+                )
+                | (Self::Avx512Vbmi256, Self::Avx512Vbmi256 | Self::Avx512Vbmi)
+                | (Self::Avx512Vbmi, Self::Avx512Vbmi)
+                | (Self::Neon, Self::Neon)
+                | (Self::WasmSimd, Self::WasmSimd)
+                | (
+                    Self::Portable,
+                    Self::Ssse3
+                        | Self::Avx2
+                        | Self::Avx512Bw
+                        | Self::Avx512Vbmi256
+                        | Self::Avx512Vbmi
+                        | Self::Neon
+                        | Self::WasmSimd
+                        | Self::Portable
+                )
+        )
+    }
+
+    /// The `backend` an engine pinned to this backend stores, or
+    /// [`Unsupported`] if this build or CPU cannot run it. Every codec's
+    /// `with_backend` pins through here.
+    #[inline]
+    pub(crate) fn pin(self) -> Result<Option<Self>, Unsupported> {
+        if self.is_supported() {
+            Ok(Some(self))
+        } else {
+            Err(Unsupported { backend: self })
+        }
+    }
+}
+
+impl core::fmt::Display for Backend {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The backend every default-configured [`Engine`] dispatches to: the fastest
+/// kernel that is compiled in and supported by the current CPU.
+///
+/// On `x86`/`x86_64` this runs CPU detection on the first call and caches the
+/// answer, unless the target already guarantees the best compiled-in kernel
+/// (e.g. `-C target-cpu=native`): then that kernel is the answer, lowered only
+/// by a cap (see [`set_max_backend`]), and without `std` a compile-time constant;
+/// on `aarch64` NEON is chosen at compile time, as are `simd128` on `wasm32`
+/// and the portable kernel on targets with nothing better.
+///
+/// # Examples
+///
+/// ```
+/// let backend = base64_turbo::active_backend();
+/// assert!(backend.is_supported());
+/// println!("base64-turbo is using the {backend} kernel");
+/// ```
+#[inline]
+#[must_use]
+// Only `const`-eligible in builds whose x86 tier is fixed at compile time.
+#[allow(clippy::missing_const_for_fn)]
+pub fn active_backend() -> Backend {
+    #[cfg(x86_simd)]
+    {
+        cpu::tier()
+    }
+    #[cfg(not(x86_simd))]
+    {
+        let skip = CAPPED.load(core::sync::atomic::Ordering::Relaxed);
+        FIXED
+            .into_iter()
+            .skip(usize::from(skip))
+            .find(|backend| backend.is_supported())
+            .unwrap_or(Backend::Scalar)
+    }
+}
+
+/// The kernels builds without x86 detection pick between at compile time,
+/// most capable first.
+#[cfg(not(x86_simd))]
+const FIXED: [Backend; 3] = [Backend::Neon, Backend::WasmSimd, Backend::Portable];
+
+/// How many of [`FIXED`] the cap from [`set_max_backend`] rules out.
+#[cfg(not(x86_simd))]
+static CAPPED: core::sync::atomic::AtomicU8 = core::sync::atomic::AtomicU8::new(0);
+
+/// Caps the backend that [`active_backend`] may pick at `max`.
+///
+/// Kernels more capable than `max` are skipped even when the CPU supports
+/// them; `Backend::Scalar` turns every SIMD kernel off. Capping at another
+/// architecture's kernel leaves only scalar, or the portable kernel where it is
+/// the fallback. Engines pinned with
+/// [`Engine::with_backend`] are not affected.
+///
+/// On `x86`/`x86_64` the choice is made once per process, so this must run
+/// before the first encode/decode (or [`active_backend`] call). Operators can
+/// apply the same cap without touching code through the
+/// `BASE64_TURBO_MAX_BACKEND` environment variable (e.g. `avx2`), read when the
+/// choice is made if the `std` feature is on; when both are set, the lower cap
+/// wins. On targets that pick their kernel at compile time, the cap applies
+/// from the next call and the environment variable is not consulted.
+///
+/// # Errors
+///
+/// Returns `Err` with the backend already in use if the `x86` detection has
+/// already run. Nothing changes in that case.
+///
+/// When the target guarantees the best `x86` kernel, that kernel stands in
+/// for detection and a cap applies as above, at the cost of one cached byte
+/// checked per call. Without `std` the kernel is instead fixed at compile
+/// time, so dispatch can inline into it: every cap is refused with `Err`.
+///
+/// # Examples
+///
+/// ```
+/// use base64_turbo::{Backend, set_max_backend};
+///
+/// // Rule out the AVX-512 kernel for this process, if it is still undecided.
+/// if let Err(active) = set_max_backend(Backend::Avx2) {
+///     println!("too late: already using {active}");
+/// }
+/// ```
+#[inline]
+// Only `const`-eligible in builds whose x86 tier is fixed at compile time.
+#[allow(clippy::missing_const_for_fn)]
+pub fn set_max_backend(max: Backend) -> Result<(), Backend> {
+    #[cfg(x86_simd)]
+    {
+        cpu::set_max(max)
+    }
+    #[cfg(not(x86_simd))]
+    {
+        let admitted = FIXED.iter().position(|backend| backend.capped_by(max));
+        #[allow(clippy::cast_possible_truncation)] // `FIXED` has three entries
+        let skip = admitted.unwrap_or(FIXED.len()) as u8;
+        CAPPED.store(skip, core::sync::atomic::Ordering::Relaxed);
+        #[cfg(unsafe_simd)]
+        dispatch::reset();
+        Ok(())
+    }
+}
+
 // ======================================================================
 // Internal Lookup Tables
 // ======================================================================
@@ -250,6 +622,15 @@ const URL_SAFE_DECODE_TABLE: [u8; 256] = {
     table
 };
 
+/// Computed compile-time reverse lookup table for either alphabet: the
+/// Standard table plus the URL-Safe `-` and `_`. Used for `LENIENT`.
+const LENIENT_DECODE_TABLE: [u8; 256] = {
+    let mut table = STANDARD_DECODE_TABLE;
+    table[b'-' as usize] = 62;
+    table[b'_' as usize] = 63;
+    table
+};
+
 // ======================================================================
 // Configuration & Types
 // ======================================================================
@@ -261,6 +642,10 @@ pub(crate) struct Config {
     pub url_safe: bool,
     /// If true, writes `=` padding characters to the output.
     pub padding: bool,
+    /// If true, decodes `-` and `_` as well as `+` and `/`. The scalar and
+    /// table-driven SIMD decoders read it (see `dispatch::Kernels::lenient`);
+    /// encoders ignore it.
+    pub lenient: bool,
 }
 
 /// A high-performance, stateless Base64 encoder/decoder.
@@ -289,6 +674,9 @@ pub(crate) struct Config {
 #[derive(Debug, Clone, Copy)]
 pub struct Engine {
     pub(crate) config: Config,
+    /// The kernel pinned by [`Engine::with_backend`]; `None` follows
+    /// [`active_backend`].
+    pub(crate) backend: Option<Backend>,
 }
 
 // ======================================================================
@@ -302,7 +690,9 @@ pub const STANDARD: Engine = Engine {
     config: Config {
         url_safe: false,
         padding: true,
+        lenient: false,
     },
+    backend: None,
 };
 
 /// Standard Base64 (RFC 4648) **without** padding.
@@ -313,7 +703,9 @@ pub const STANDARD_NO_PAD: Engine = Engine {
     config: Config {
         url_safe: false,
         padding: false,
+        lenient: false,
     },
+    backend: None,
 };
 
 /// URL-Safe Base64 with padding.
@@ -323,7 +715,9 @@ pub const URL_SAFE: Engine = Engine {
     config: Config {
         url_safe: true,
         padding: true,
+        lenient: false,
     },
+    backend: None,
 };
 
 /// URL-Safe Base64 **without** padding.
@@ -333,7 +727,40 @@ pub const URL_SAFE_NO_PAD: Engine = Engine {
     config: Config {
         url_safe: true,
         padding: false,
+        lenient: false,
+    },
+    backend: None,
+};
+
+/// Lenient Base64: decodes either alphabet, even mixed in one input, with or
+/// without padding.
+///
+/// `+` and `-` both decode to 62, and `/` and `_` both to 63, as Protobuf's
+/// JSON mapping and many REST APIs accept, in one pass rather than trying
+/// [`STANDARD`] and then [`URL_SAFE`]. Encoding is that of [`STANDARD_NO_PAD`].
+///
+/// The scalar, AVX512-VBMI (both widths), AVX512BW, AVX2, SSSE3 and NEON
+/// decoders take it as it is, their lookup tables mapping each pair of
+/// symbols to one index; on the WASM simd128 and portable-simd backends it
+/// decodes through scalar.
+///
+/// # Examples
+///
+/// ```
+/// use base64_turbo::LENIENT;
+///
+/// let mut buf = [0u8; LENIENT.estimate_decoded_len(8)];
+/// assert_eq!(LENIENT.decode_into("-_+/-_+/", &mut buf), Ok(6));
+/// assert_eq!(LENIENT.decode_into("+/+/+w==", &mut buf), Ok(4));
+/// assert_eq!(LENIENT.decode_into("+/+/+w", &mut buf), Ok(4));
+/// ```
+pub const LENIENT: Engine = Engine {
+    config: Config {
+        url_safe: false,
+        padding: false,
+        lenient: true,
     },
+    backend: None,
 };
 
 // ======================================================================
@@ -388,6 +815,54 @@ fn into_ascii_string(bytes: Vec<u8>) -> String {
 }
 
 impl Engine {
+    // ======================================================================
+    // Backend Selection
+    // ======================================================================
+
+    /// Returns a copy of this engine that always runs `backend`'s kernel,
+    /// instead of following [`active_backend`].
+    ///
+    /// The request is checked once, here: the kernel must be compiled in and
+    /// the current CPU must support it. That makes this the safe way to pin a
+    /// kernel (for an A/B test, or to rule one out while debugging), where the
+    /// `unstable` raw accessors such as `encode_avx2` leave the CPU check to
+    /// the caller. Inputs too short for the pinned kernel still run on its
+    /// short-input kernel or on scalar, and calibrated [`Thresholds`] apply to
+    /// it as to the default engines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Unsupported`] if `backend` is not compiled into this build or
+    /// the current CPU cannot run it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::{Backend, STANDARD};
+    ///
+    /// let scalar = STANDARD.with_backend(Backend::Scalar).unwrap();
+    /// assert_eq!(scalar.backend(), Backend::Scalar);
+    ///
+    /// let mut buf = [0u8; 8];
+    /// let n = scalar.encode_into(b"hello", &mut buf).unwrap();
+    /// assert_eq!(&buf[..n], b"aGVsbG8=");
+    /// ```
+    #[inline]
+    pub fn with_backend(self, backend: Backend) -> Result<Self, Unsupported> {
+        Ok(Self {
+            backend: backend.pin()?,
+            ..self
+        })
+    }
+
+    /// The backend this engine dispatches to: the one pinned by
+    /// [`with_backend`](Self::with_backend), or [`active_backend`] otherwise.
+    #[inline]
+    #[must_use]
+    pub fn backend(&self) -> Backend {
+        self.backend.unwrap_or_else(active_backend)
+    }
+
     // ======================================================================
     // Length Calculators
     // ======================================================================
@@ -508,6 +983,56 @@ impl Engine {
         Ok(real_len)
     }
 
+    /// Decodes `input` into `output`, skipping every byte outside the alphabet,
+    /// as GNU `base64 --ignore-garbage` and Java's MIME decoder do.
+    ///
+    /// Line breaks, `>` quoting, stray control bytes and anything else this
+    /// engine would reject are dropped; `'='` is kept, and what is left must be
+    /// valid Base64, as for [`decode_into`](Self::decode_into). The one
+    /// difference: characters after a padded group are rejected, where the
+    /// scalar decoder stops at the padding and ignores them.
+    ///
+    /// On AVX512-VBMI, AVX2 (which the AVX-512 backends without VBMI share)
+    /// and NEON the dropping is done in vectors, driven by the validity mask
+    /// the decoder already computes; elsewhere it is scalar. Every backend
+    /// decodes what is left on its own kernel.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If what is left is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if the decoded bytes do not fit in
+    /// `output`, which is checked as they are written: unlike
+    /// [`decode_into`](Self::decode_into), `output` need only hold the bytes
+    /// themselves, not an estimate from the input's length. Returns
+    /// [`Error::InvalidLength`] / [`Error::InvalidCharacter`] if what is left
+    /// is not valid Base64. On error the contents of `output` are unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::STANDARD;
+    ///
+    /// let mut buf = [0u8; 5];
+    /// let n = STANDARD.decode_ignore_garbage_into("> aGVs\r\n> bG8=\r\n", &mut buf).unwrap();
+    /// assert_eq!(&buf[..n], b"hello");
+    /// ```
+    #[inline]
+    pub fn decode_ignore_garbage_into<T: AsRef<[u8]>>(
+        &self,
+        input: T,
+        output: &mut [u8],
+    ) -> Result<usize, Error> {
+        let input = input.as_ref();
+        if input.is_empty() {
+            return Ok(0);
+        }
+        garbage::decode(*self, input, output)
+    }
+
     // ========================================================================
     // Allocating APIs (std)
     // ========================================================================
@@ -565,90 +1090,144 @@ impl Engine {
         Ok(out)
     }
 
+    /// Allocates a new `Vec<u8>` and decodes the input data into it, skipping
+    /// every byte outside the alphabet. See
+    /// [`decode_ignore_garbage_into`](Self::decode_ignore_garbage_into).
+    ///
+    /// # Errors
+    /// Returns `Error` if what is left after the garbage is dropped has invalid
+    /// characters or an invalid length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::STANDARD;
+    /// let bytes = STANDARD.decode_ignore_garbage("\"aGVs bG8=\"").unwrap();
+    /// assert_eq!(bytes, b"hello");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode_ignore_garbage<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // As in `decode`.
+        let mut out = spare(Self::estimate_decoded_len(self, input.len()));
+        let written = Self::decode_ignore_garbage_into(self, input, &mut out)?;
+        out.truncate(written);
+        Ok(out)
+    }
+
     // ========================================================================
     // Internal Dispatchers
     // ========================================================================
 
-    // `&self` (a 2-byte Copy `Engine`) is kept by-ref for consistency with the
+    // `&self` (a 3-byte Copy `Engine`) is kept by-ref for consistency with the
     // rest of the `Engine` methods, not because the reference is required.
+    //
+    // Default engines go through the table `dispatch` resolved on first use;
+    // pinned ones look theirs up directly. Either way the table belongs to a
+    // backend that passed `Backend::is_supported` — `Engine`'s fields are
+    // private, so no caller can pin an unchecked one.
+    #[cfg(unsafe_simd)]
     #[allow(clippy::trivially_copy_pass_by_ref)]
     #[inline]
-    fn encode_dispatch(&self, input: &[u8], dst: &mut [u8]) {
-        #[cfg(x86_simd)]
-        {
-            let len = input.len();
-            let tier = cpu::tier();
-
-            // Smart degrade by length: a kernel is only worth entering once the
-            // input fills its vector width. AVX2's single tier runs from 32
-            // bytes up.
-            #[cfg(feature = "avx512-vbmi")]
-            if len >= 32 && tier == cpu::AVX512_VBMI {
-                // VBMI fast-path: vpermb replaces the 8-instruction char mapping.
-                // SAFETY: tier() confirmed AVX-512F/BW/VBMI on this CPU.
-                unsafe { simd::encode_slice_avx512_vbmi(&self.config, input, dst) };
-                return;
-            }
-            #[cfg(feature = "avx2")]
-            if len >= 32 && tier >= cpu::AVX2 {
-                // SAFETY: tier() confirmed AVX2 on this CPU.
-                unsafe { simd::encode_slice_avx2(&self.config, input, dst) };
-                return;
-            }
-        }
-
-        // NEON path (aarch64): compile-time dispatch, no runtime detection.
-        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
-        if input.len() >= 16 {
-            // SAFETY: NEON is baseline on aarch64.
-            unsafe { simd::encode_slice_neon(&self.config, input, dst) };
-            return;
-        }
+    fn kernels(&self) -> &'static dispatch::Kernels {
+        self.backend
+            .map_or_else(dispatch::active, dispatch::for_backend)
+    }
 
-        // Fallback: Scalar / non-SIMD target / short inputs.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[inline]
+    fn encode_dispatch(&self, input: &[u8], dst: &mut [u8]) {
+        #[cfg(unsafe_simd)]
+        dispatch::encode(self.kernels(), &self.config, input, dst);
+        // Scalar-only build: there is nothing to select.
+        #[cfg(not(unsafe_simd))]
         scalar::encode_slice(&self.config, input, dst);
     }
 
     #[allow(clippy::trivially_copy_pass_by_ref)]
     #[inline]
     fn decode_dispatch(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
-        #[cfg(x86_simd)]
+        #[cfg(unsafe_simd)]
         {
-            let len = input.len();
-            let tier = cpu::tier();
-
-            // As in `encode_dispatch`, the masked tails let VBMI start earlier
-            // than AVX2.
-            #[cfg(feature = "avx512-vbmi")]
-            if len >= 32 && tier == cpu::AVX512_VBMI {
-                // VBMI fast-path: vpermi2b collapses decode+validate to ~4 instructions.
-                // SAFETY: tier() confirmed AVX-512F/BW/VBMI on this CPU.
-                return unsafe { simd::decode_slice_avx512_vbmi(&self.config, input, dst) };
-            }
-            #[cfg(feature = "avx2")]
-            if len >= 36 && tier >= cpu::AVX2 {
-                // SAFETY: tier() confirmed AVX2 on this CPU.
-                return unsafe { simd::decode_slice_avx2(&self.config, input, dst) };
-            }
+            dispatch::decode(self.kernels(), &self.config, input, dst)
         }
-
-        // NEON path (aarch64): compile-time dispatch, no runtime detection.
-        // Its single tier is a 16-in/12-out block plus a 4-byte read-ahead
-        // margin (see `neon::decode_slice_neon`), so it needs 20 bytes to run.
-        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
-        if input.len() >= 20 {
-            // SAFETY: NEON is baseline on aarch64.
-            return unsafe { simd::decode_slice_neon(&self.config, input, dst) };
+        #[cfg(not(unsafe_simd))]
+        {
+            scalar::decode_slice(&self.config, input, dst)
         }
-
-        // Fallback: Scalar / non-SIMD target / short inputs.
-        scalar::decode_slice(&self.config, input, dst)
     }
 
     // ========================================================================
     // Raw unsafe access (unstable feature)
     // ========================================================================
 
+    /// Encodes a byte slice into Base64 using the 128-bit SSSE3 SIMD implementation.
+    ///
+    /// This provides raw access to the direct SSSE3 encoding logic, the x86 kernel for
+    /// CPUs without AVX2.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   sufficient capacity. The required size depends on `config.padding`:
+    ///   - With padding: `input.len().div_ceil(3) * 4`
+    ///   - Without padding: `(input.len() * 4).div_ceil(3)`
+    ///   - Highly recommended: use `Engine::encoded_len` to compute length.
+    ///
+    /// - The caller **must** ensure the target CPU supports SSSE3 instructions at runtime.
+    ///   Executing this function on a CPU without SSSE3 support will cause an illegal
+    ///   instruction crash.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::encode`).
+    #[cfg(all(x86_ssse3, feature = "unstable"))]
+    pub unsafe fn encode_ssse3(&self, input: &[u8], dst: &mut [u8]) {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::encode_slice_ssse3(&self.config, input, dst) }
+    }
+
+    /// Decodes a Base64 byte slice using the 128-bit SSSE3 SIMD implementation.
+    ///
+    /// This provides raw access to the direct SSSE3 decoding logic.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   at least `(input.len() / 4 + 1) * 3` bytes of capacity. Each 12-byte block is written
+    ///   with a 16-byte store, so the extra space absorbs the last store's overhang.
+    ///   - Highly recommended: use `Engine::estimate_decoded_len` to compute length.
+    ///
+    /// - The caller **must** ensure the target CPU supports SSSE3 instructions at runtime.
+    ///   Executing this function on a CPU without SSSE3 support will cause an illegal
+    ///   instruction crash.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::decode`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
+    /// valid Base64.
+    #[cfg(all(x86_ssse3, feature = "unstable"))]
+    pub unsafe fn decode_ssse3(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::decode_slice_ssse3(&self.config, input, dst) }
+    }
+
     /// Encodes a byte slice into Base64 using a highly optimized AVX2 SIMD implementation.
     ///
     /// This provides raw access to the direct AVX2 encoding logic.
@@ -673,7 +1252,7 @@ impl Engine {
     /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
     /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
     /// (e.g., `Engine::encode`).
-    #[cfg(all(x86_simd, feature = "avx2", feature = "unstable"))]
+    #[cfg(all(x86_avx2, feature = "unstable"))]
     pub unsafe fn encode_avx2(&self, input: &[u8], dst: &mut [u8]) {
         // SAFETY: Caller must uphold the contracts documented on this function.
         unsafe { simd::encode_slice_avx2(&self.config, input, dst) }
@@ -709,12 +1288,77 @@ impl Engine {
     ///
     /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
     /// valid Base64.
-    #[cfg(all(x86_simd, feature = "avx2", feature = "unstable"))]
+    #[cfg(all(x86_avx2, feature = "unstable"))]
     pub unsafe fn decode_avx2(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
         // SAFETY: Caller must uphold the contracts documented on this function.
         unsafe { simd::decode_slice_avx2(&self.config, input, dst) }
     }
 
+    /// Encodes a byte slice into Base64 using the AVX-512BW SIMD implementation.
+    ///
+    /// This provides raw access to the direct AVX-512BW encoding logic, for AVX-512 CPUs
+    /// without VBMI.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   sufficient capacity. The required size depends on `config.padding`:
+    ///   - With padding: `input.len().div_ceil(3) * 4`
+    ///   - Without padding: `(input.len() * 4).div_ceil(3)`
+    ///   - Highly recommended: use `Engine::encoded_len` to compute length.
+    ///
+    /// - The caller **must** ensure the target CPU supports the `avx512f` and `avx512bw`
+    ///   instruction subsets at runtime. Executing this function on a CPU without both will
+    ///   cause an illegal instruction crash.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::encode`).
+    #[cfg(all(x86_avx512bw, feature = "unstable"))]
+    pub unsafe fn encode_avx512bw(&self, input: &[u8], dst: &mut [u8]) {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::encode_slice_avx512bw(&self.config, input, dst) }
+    }
+
+    /// Decodes a Base64 byte slice using the AVX-512BW SIMD implementation.
+    ///
+    /// This provides raw access to the direct AVX-512BW decoding logic.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   at least `(input.len() / 4 + 1) * 3` bytes of capacity. As with the VBMI kernel, the
+    ///   quad tier's first three stores are unmasked and overhang their 48 bytes by 16.
+    ///   - Highly recommended: use `Engine::estimate_decoded_len` to compute length.
+    ///
+    /// - The caller **must** ensure the target CPU supports the `avx512f` and `avx512bw`
+    ///   instruction subsets at runtime. Executing this function on a CPU without both will
+    ///   cause an illegal instruction crash.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::decode`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
+    /// valid Base64.
+    #[cfg(all(x86_avx512bw, feature = "unstable"))]
+    pub unsafe fn decode_avx512bw(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::decode_slice_avx512bw(&self.config, input, dst) }
+    }
+
     /// Encodes a byte slice into Base64 using the AVX-512-VBMI SIMD implementation.
     ///
     /// This provides raw access to the direct AVX-512-VBMI encoding logic, the
@@ -740,7 +1384,7 @@ impl Engine {
     /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
     /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
     /// (e.g., `Engine::encode`).
-    #[cfg(all(x86_simd, feature = "avx512-vbmi", feature = "unstable"))]
+    #[cfg(all(x86_avx512_vbmi, feature = "unstable"))]
     pub unsafe fn encode_avx512_vbmi(&self, input: &[u8], dst: &mut [u8]) {
         // SAFETY: Caller must uphold the contracts documented on this function.
         unsafe { simd::encode_slice_avx512_vbmi(&self.config, input, dst) }
@@ -775,12 +1419,81 @@ impl Engine {
     ///
     /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
     /// valid Base64.
-    #[cfg(all(x86_simd, feature = "avx512-vbmi", feature = "unstable"))]
+    #[cfg(all(x86_avx512_vbmi, feature = "unstable"))]
     pub unsafe fn decode_avx512_vbmi(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
         // SAFETY: Caller must uphold the contracts documented on this function.
         unsafe { simd::decode_slice_avx512_vbmi(&self.config, input, dst) }
     }
 
+    /// Encodes a byte slice into Base64 using the 256-bit AVX-512-VBMI SIMD implementation.
+    ///
+    /// This provides raw access to the direct AVX-512-VBMI encoding logic at YMM width,
+    /// which avoids the 512-bit frequency licence.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   sufficient capacity. The required size depends on `config.padding`:
+    ///   - With padding: `input.len().div_ceil(3) * 4`
+    ///   - Without padding: `(input.len() * 4).div_ceil(3)`
+    ///   - Highly recommended: use `Engine::encoded_len` to compute length.
+    ///
+    /// - The caller **must** ensure the target CPU supports the `avx512f`, `avx512bw`,
+    ///   `avx512vl` and `avx512vbmi` instruction subsets at runtime. Executing this function
+    ///   on a CPU without all four will cause an illegal instruction crash.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::encode`).
+    #[cfg(all(x86_avx512_vbmi, feature = "unstable"))]
+    pub unsafe fn encode_avx512_vbmi_256(&self, input: &[u8], dst: &mut [u8]) {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::encode_slice_avx512_vbmi_256(&self.config, input, dst) }
+    }
+
+    /// Decodes a Base64 byte slice using the 256-bit AVX-512-VBMI SIMD implementation.
+    ///
+    /// This provides raw access to the direct AVX-512-VBMI decoding logic at YMM width.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   at least `(input.len() / 4 + 1) * 3` bytes of capacity. As with the 512-bit kernel,
+    ///   the quad tier's first three stores are unmasked and overhang their 24 bytes by 8.
+    ///   - Highly recommended: use `Engine::estimate_decoded_len` to compute length.
+    ///
+    /// - The caller **must** ensure the target CPU supports the `avx512f`, `avx512bw`,
+    ///   `avx512vl` and `avx512vbmi` instruction subsets at runtime. Executing this function
+    ///   on a CPU without all four will cause an illegal instruction crash.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::decode`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
+    /// valid Base64.
+    #[cfg(all(x86_avx512_vbmi, feature = "unstable"))]
+    pub unsafe fn decode_avx512_vbmi_256(
+        &self,
+        input: &[u8],
+        dst: &mut [u8],
+    ) -> Result<usize, Error> {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::decode_slice_avx512_vbmi_256(&self.config, input, dst) }
+    }
+
     /// Encodes a byte slice into Base64 using the optimized scalar (non-SIMD) algorithm.
     ///
     /// This provides raw access to the direct scalar encoding logic. Unlike the SIMD
@@ -870,4 +1583,87 @@ impl Engine {
         // SAFETY: Caller must uphold the contracts documented on this function.
         unsafe { simd::decode_slice_neon(&self.config, input, dst) }
     }
+
+    /// Encodes a byte slice into Base64 using the WebAssembly `simd128` implementation.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   at least `Engine::encoded_len(input.len())` bytes of capacity.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::encode`).
+    #[cfg(all(wasm_simd, feature = "unstable"))]
+    pub unsafe fn encode_wasm(&self, input: &[u8], dst: &mut [u8]) {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::encode_slice_wasm(&self.config, input, dst) }
+    }
+
+    /// Decodes a Base64 byte slice using the WebAssembly `simd128` implementation.
+    ///
+    /// # Safety
+    ///
+    /// This function is **unsafe** and requires the caller to uphold strict memory contracts.
+    /// Failure to do so will result in **undefined behavior** (e.g., buffer overflow).
+    ///
+    /// - The destination pointer `dst` must be valid and point to a mutable memory region with
+    ///   at least the decoded length in capacity. Unlike the other 128-bit kernels, every
+    ///   store writes exactly the bytes it decodes, so no slack is needed.
+    ///   - Highly recommended: use `Engine::estimate_decoded_len` to compute length.
+    ///
+    /// # Warning
+    ///
+    /// This is a low-level, unsafe primitive. Misuse can lead to undefined behavior regardless
+    /// of other crate guarantees. For better memory safety, use the safe higher-level APIs
+    /// (e.g., `Engine::decode`).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
+    /// valid Base64.
+    #[cfg(all(wasm_simd, feature = "unstable"))]
+    pub unsafe fn decode_wasm(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+        // SAFETY: Caller must uphold the contracts documented on this function.
+        unsafe { simd::decode_slice_wasm(&self.config, input, dst) }
+    }
+
+    /// Encodes a byte slice into Base64 using the portable `core::simd` implementation.
+    ///
+    /// Like [`Engine::encode_scalar`], this is a **safe** function: the portable kernel
+    /// uses no `unsafe`, and runs on any target.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` is smaller than the encoded length. Size it with
+    /// [`Engine::encoded_len`].
+    #[cfg(all(portable_simd, feature = "unstable"))]
+    pub fn encode_portable(&self, input: &[u8], dst: &mut [u8]) {
+        simd::encode_slice_portable(&self.config, input, dst);
+    }
+
+    /// Decodes a Base64 byte slice using the portable `core::simd` implementation.
+    ///
+    /// Like [`Engine::decode_scalar`], this is a **safe** function, and every
+    /// block writes exactly the bytes it decodes, so `dst` needs no slack.
+    ///
+    /// Size `dst` with [`Engine::estimate_decoded_len`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dst` is too small to hold the decoded output.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLength`] or [`Error::InvalidCharacter`] if `input` is not
+    /// valid Base64.
+    #[cfg(all(portable_simd, feature = "unstable"))]
+    pub fn decode_portable(&self, input: &[u8], dst: &mut [u8]) -> Result<usize, Error> {
+        simd::decode_slice_portable(&self.config, input, dst)
+    }
 }
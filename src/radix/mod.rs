@@ -0,0 +1,299 @@
+//! Big-radix encodings (Base58 and Base62), with the same API and [`Error`]
+//! reporting as the Base64 [`Engine`](crate::Engine).
+//!
+//! | Engine | Alphabet | Used by |
+//! | :--- | :--- | :--- |
+//! | [`BASE58`] | `1-9A-HJ-NP-Za-km-z` | Bitcoin addresses, IPFS CIDs |
+//! | [`BASE58_RIPPLE`] | `rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz` | XRP Ledger addresses |
+//! | [`BASE58_FLICKR`] | `1-9a-km-zA-HJ-NP-Z` | Flickr short URLs |
+//! | [`BASE62`] | `0-9A-Za-z` | short URL IDs |
+//!
+//! Unlike Base64, these treat the whole input as one big-endian number and
+//! write it out in base 58 or 62, so no character maps to a fixed group of
+//! bits. Each leading zero byte is kept as one leading zero digit (`1` in
+//! Bitcoin's alphabet, `0` in Base62's), which the number alone would lose.
+//!
+//! The conversion works ten digits at a time on `u64` limbs, rather than one
+//! byte at a time: encode divides by `58^10` (or `62^10`), decode multiplies by
+//! it. It is still quadratic in the input length, as every radix conversion is,
+//! so these suit keys, hashes and IDs rather than bulk data. The limbs live in
+//! the output buffer, so nothing allocates: encode packs them into the room
+//! the longest result needs, while decode's working room is why
+//! [`Engine::estimate_decoded_len`] is a few bytes above the longest result
+//! for short inputs.
+//!
+//! ```rust
+//! # #[cfg(feature = "std")]
+//! # {
+//! use base64_turbo::radix::{BASE58, BASE62};
+//!
+//! assert_eq!(BASE58.encode(b"hello world"), "StV1DL6CwTryKyV");
+//! assert_eq!(BASE58.encode(b"\0\0\x01"), "112");
+//! assert_eq!(BASE62.decode("AAwf93rvy4aWQVw").unwrap(), b"hello world");
+//! # }
+//! ```
+
+use crate::Error;
+
+// Scalar implementation, the only one the radix codecs have.
+pub(crate) mod scalar;
+
+/// The four big-radix alphabets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Alphabet {
+    /// Bitcoin's Base58, which drops `0`, `O`, `I` and `l`.
+    Bitcoin,
+    /// The XRP Ledger's reordering of the same 58 characters.
+    Ripple,
+    /// Flickr's, with the cases swapped.
+    Flickr,
+    /// Base62: digits, then upper and lowercase letters.
+    Base62,
+}
+
+impl Alphabet {
+    /// Every alphabet, in discriminant order, for tables indexed by one.
+    pub(crate) const ALL: [Self; 4] = [Self::Bitcoin, Self::Ripple, Self::Flickr, Self::Base62];
+
+    /// The characters this alphabet encodes to, indexed by digit value.
+    pub(crate) const fn symbols(self) -> &'static [u8] {
+        match self {
+            Self::Bitcoin => b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            Self::Ripple => b"rpshnaf39wBUDNEGHJKLM4PQRST7VWXYZ2bcdeCg65jkm8oFqi1tuvAxyz",
+            Self::Flickr => b"123456789abcdefghijkmnopqrstuvwxyzABCDEFGHJKLMNPQRSTUVWXYZ",
+            Self::Base62 => b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz",
+        }
+    }
+
+    /// The radix, 58 or 62.
+    #[allow(clippy::cast_possible_truncation)] // 58 or 62, fits in u64
+    pub(crate) const fn radix(self) -> u64 {
+        self.symbols().len() as u64
+    }
+
+    /// Digits per input byte, `log(256) / log(radix)`, in 64-bit fixed
+    /// point and rounded up.
+    const fn digits_per_byte(self) -> u128 {
+        match self {
+            // 1.36565823730976103695...
+            Self::Bitcoin | Self::Ripple | Self::Flickr => 0x1_5d9b_c73a_c228_8343,
+            // 1.34359023165633553801...
+            Self::Base62 => 0x1_57f5_8788_3063_f20b,
+        }
+    }
+}
+
+/// A stateless Base58 or Base62 encoder/decoder.
+///
+/// The big-radix counterpart of the Base64 [`Engine`](crate::Engine), with
+/// the same zero-allocation and allocating APIs and the same [`Error`]
+/// reporting.
+///
+/// # Examples
+///
+/// ```rust
+/// use base64_turbo::radix::BASE58;
+///
+/// let mut buf = [0u8; 32];
+/// let n = BASE58.encode_into(b"\0hi", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"18wr");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Engine {
+    pub(crate) alphabet: Alphabet,
+}
+
+// ======================================================================
+// Pre-defined Engines
+// ======================================================================
+
+/// Base58 with Bitcoin's alphabet, the one nearly everything else uses too.
+///
+/// Plain Base58: the version byte and checksum of `Base58Check` are the
+/// caller's to add and verify.
+pub const BASE58: Engine = Engine {
+    alphabet: Alphabet::Bitcoin,
+};
+
+/// Base58 with the XRP Ledger's alphabet.
+pub const BASE58_RIPPLE: Engine = Engine {
+    alphabet: Alphabet::Ripple,
+};
+
+/// Base58 with Flickr's alphabet.
+pub const BASE58_FLICKR: Engine = Engine {
+    alphabet: Alphabet::Flickr,
+};
+
+/// Base62 (`0-9A-Za-z`), in the order the `base62` crate's standard alphabet
+/// uses.
+pub const BASE62: Engine = Engine {
+    alphabet: Alphabet::Base62,
+};
+
+impl Engine {
+    // ======================================================================
+    // Length Calculators
+    // ======================================================================
+
+    /// Calculates the buffer size required to encode `input_len` bytes.
+    ///
+    /// The longest encoding of `input_len` bytes, which is what all `0xFF`
+    /// bytes encode to: the smallest `d` with `radix^d >= 256^input_len`.
+    ///
+    /// A maximum: the encoded length depends on the input's value, not just
+    /// its length. Rely on the `usize` [`encode_into`](Self::encode_into)
+    /// returns for the valid slice of the output buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::radix::BASE58;
+    ///
+    /// // A 32-byte key is at most 44 characters.
+    /// assert_eq!(BASE58.encoded_len(32), 44);
+    /// ```
+    #[inline]
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)] // at most 1.37 * `input_len`
+    pub const fn encoded_len(&self, input_len: usize) -> usize {
+        // `input_len * log(256) / log(radix)`, rounded up. The ratio is itself
+        // rounded up, which could only tip a product just below a whole number
+        // over it; the tests check none does over the lengths they cover.
+        let digits = input_len as u128 * self.alphabet.digits_per_byte();
+        digits.div_ceil(1 << 64) as usize
+    }
+
+    /// Calculates the **maximum** buffer size required to decode `input_len`
+    /// bytes.
+    ///
+    /// A string of zero digits decodes to as many bytes, so this is at least
+    /// `input_len`; for short inputs it is a little more, as
+    /// [`decode_into`](Self::decode_into) uses the buffer as working room. Rely
+    /// on the `usize` it returns for the valid slice of the output buffer.
+    #[inline]
+    #[must_use]
+    pub const fn estimate_decoded_len(&self, input_len: usize) -> usize {
+        if input_len == 0 {
+            return 0;
+        }
+        // Enough `u64` limbs for `6 * input_len` bits, as log2(62) < 6.
+        let limbs = 8 * (input_len / 64 * 6 + input_len % 64 * 6 / 64 + 1);
+        if limbs > input_len { limbs } else { input_len }
+    }
+
+    // ======================================================================
+    // Zero-Allocation APIs
+    // ======================================================================
+
+    /// Encodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error::BufferTooSmall)`: If `output.len()` is less than [`encoded_len`](Self::encoded_len).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is not large enough to hold the
+    /// encoded data (see [`encoded_len`](Self::encoded_len)).
+    #[inline]
+    pub fn encode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::encoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        Ok(scalar::encode_slice(
+            self.alphabet,
+            input,
+            &mut output[..req_len],
+        ))
+    }
+
+    /// Decodes `input` into the provided `output` buffer.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(usize)`: The actual number of bytes written to `output`.
+    /// * `Err(Error)`: If the input is invalid or the buffer is too small.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::BufferTooSmall`] if `output` is shorter than
+    /// [`estimate_decoded_len`](Self::estimate_decoded_len), or
+    /// [`Error::InvalidCharacter`] for a character outside the alphabet. Any
+    /// length is valid.
+    #[inline]
+    pub fn decode_into<T: AsRef<[u8]>>(&self, input: T, output: &mut [u8]) -> Result<usize, Error> {
+        let input = input.as_ref();
+        let len = input.len();
+
+        if len == 0 {
+            return Ok(0);
+        }
+
+        let req_len = Self::estimate_decoded_len(self, len);
+        if output.len() < req_len {
+            return Err(Error::BufferTooSmall);
+        }
+
+        scalar::decode_slice(self.alphabet, input, &mut output[..req_len])
+    }
+
+    // ========================================================================
+    // Allocating APIs (std)
+    // ========================================================================
+
+    /// Allocates a new `String` and encodes the input data into it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::radix::BASE58_FLICKR;
+    /// assert_eq!(BASE58_FLICKR.encode(b"hello world"), "rTu1dk6cWsRYjYu");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn encode<T: AsRef<[u8]>>(&self, input: T) -> String {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::encode`, but the length is a maximum:
+        // `truncate` drops what a shorter encoding leaves unwritten.
+        let mut out = crate::spare(Self::encoded_len(self, input.len()));
+        let written = scalar::encode_slice(self.alphabet, input, &mut out);
+        out.truncate(written);
+        crate::into_ascii_string(out)
+    }
+
+    /// Allocates a new `Vec<u8>` and decodes the input data into it.
+    ///
+    /// # Errors
+    /// Returns `Error` if the input contains invalid characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use base64_turbo::radix::BASE58_RIPPLE;
+    /// assert_eq!(BASE58_RIPPLE.decode("StVrDLaUATiyKyV").unwrap(), b"hello world");
+    /// ```
+    #[inline]
+    #[cfg(feature = "std")]
+    pub fn decode<T: AsRef<[u8]>>(&self, input: T) -> Result<Vec<u8>, Error> {
+        let input = input.as_ref();
+
+        // As in `crate::Engine::decode`: `truncate` drops the unwritten tail,
+        // and on error the whole buffer is dropped unread.
+        let mut out = crate::spare(Self::estimate_decoded_len(self, input.len()));
+        let written = Self::decode_into(self, input, &mut out)?;
+        out.truncate(written);
+        Ok(out)
+    }
+}
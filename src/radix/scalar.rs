@@ -0,0 +1,223 @@
+//! Scalar Base58/Base62 encode/decode primitives.
+//!
+//! **100% safe Rust.** Both directions are schoolbook long arithmetic on `u64`
+//! limbs, ten digits at a time, with the limbs kept in `dst` itself:
+//!
+//! * Encode reads the input eight bytes at a time into a number held in base
+//!   `radix^10` (one limb per ten digits), multiplying it by `2^64` and adding
+//!   each new limb with `u128` intermediates. The limbs sit at the end of
+//!   `dst`, least significant last, and each then expands into its ten digits
+//!   from the most significant down, written from the front: a limb is 8
+//!   bytes and its digits 10, so the digits only ever overwrite limbs already
+//!   read, and `dst` needs no more room than the longest encoding.
+//! * Decode reads the digits ten at a time into a number held in base `2^64`,
+//!   multiplying it by `radix^10` (or less, for the first chunk) and adding
+//!   each chunk's value. Stored big-endian, those limbs already are the
+//!   decoded bytes.
+//!
+//! Either way, the leading zeros the number cannot carry are counted up front
+//! and written back once the digits or bytes are moved into place after them.
+
+#![forbid(unsafe_code)]
+// The `u128` products and quotients are narrowed only where the arithmetic
+// bounds them to 64 bits.
+#![allow(clippy::cast_possible_truncation)]
+
+use super::Alphabet;
+use crate::Error;
+
+/// Compile-time reverse lookup for `alphabet`: ASCII back to digit values,
+/// `0xFF` for anything else.
+const fn decode_table(alphabet: Alphabet) -> [u8; 256] {
+    let symbols = alphabet.symbols();
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < symbols.len() {
+        table[symbols[i] as usize] = i as u8;
+        i += 1;
+    }
+    table
+}
+
+/// The reverse lookups, indexed by `Alphabet as usize`.
+static DECODE_TABLES: [[u8; 256]; 4] = [
+    decode_table(Alphabet::ALL[0]),
+    decode_table(Alphabet::ALL[1]),
+    decode_table(Alphabet::ALL[2]),
+    decode_table(Alphabet::ALL[3]),
+];
+
+/// Reads the limb at byte offset `at` of `buf`.
+#[inline]
+fn load(buf: &[u8], at: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[at..at + 8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Writes the limb at byte offset `at` of `buf`.
+#[inline]
+fn store(buf: &mut [u8], at: usize, limb: u64) {
+    buf[at..at + 8].copy_from_slice(&limb.to_be_bytes());
+}
+
+/// The ten digits of `limb`, most significant first.
+#[inline]
+fn expand(symbols: &[u8], radix: u64, mut limb: u64) -> [u8; 10] {
+    let mut digits = [0u8; 10];
+    for digit in digits.iter_mut().rev() {
+        *digit = symbols[(limb % radix) as usize];
+        limb /= radix;
+    }
+    digits
+}
+
+/// Output length below which [`encode_slice`] works on the stack instead:
+/// from here up, the limbs of any number that fits in `dst` fit in it too, as
+/// `8 * ceil(len / 10) <= len`.
+const STACK_ROOM: usize = 40;
+
+/// Encodes `input` into `dst` and returns the number of characters written.
+///
+/// `dst` must be at least `Engine::encoded_len` for `input`; a `dst` that is
+/// too small will panic (bounds check) rather than corrupt memory.
+pub(crate) fn encode_slice(alphabet: Alphabet, input: &[u8], dst: &mut [u8]) -> usize {
+    if dst.len() >= STACK_ROOM {
+        return encode_in_place(alphabet, input, dst);
+    }
+    // Too short to hold the limbs of even a short number: encode into room
+    // that is, and copy out what fits.
+    let mut room = [0u8; STACK_ROOM];
+    let written = encode_in_place(alphabet, input, &mut room);
+    dst[..written].copy_from_slice(&room[..written]);
+    written
+}
+
+/// [`encode_slice`], for a `dst` of at least [`STACK_ROOM`] bytes.
+fn encode_in_place(alphabet: Alphabet, input: &[u8], dst: &mut [u8]) -> usize {
+    let symbols = alphabet.symbols();
+    let radix = alphabet.radix();
+    let base = radix.pow(10);
+
+    let lead = input.iter().take_while(|&&b| b == 0).count();
+    let number = &input[lead..];
+    if number.is_empty() {
+        dst[..lead].fill(symbols[0]);
+        return lead;
+    }
+
+    // --- LIMB LOOP ---
+    // The end of `dst` holds `limbs` limbs in base `radix^10`, most
+    // significant first; limb `k`, counting from the least significant, is at
+    // byte `limb_at(k)`.
+    let end = dst.len();
+    let limb_at = |k: usize| end - 8 * (k + 1);
+    let mut limbs = 0;
+
+    // Eight input bytes per step; the first step takes the odd few.
+    let (head, body) = number.split_at(number.len() % 8);
+    for chunk in core::iter::once(head)
+        .filter(|head| !head.is_empty())
+        .chain(body.chunks_exact(8))
+    {
+        let mut word = [0u8; 8];
+        word[8 - chunk.len()..].copy_from_slice(chunk);
+
+        let shift = 8 * chunk.len() as u32;
+        let mut carry = u64::from_be_bytes(word);
+        for k in 0..limbs {
+            let v = (u128::from(load(dst, limb_at(k))) << shift) | u128::from(carry);
+            store(dst, limb_at(k), (v % u128::from(base)) as u64);
+            carry = (v / u128::from(base)) as u64;
+        }
+        while carry > 0 {
+            store(dst, limb_at(limbs), carry % base);
+            carry /= base;
+            limbs += 1;
+        }
+    }
+
+    // --- EXPANSION ---
+    // Most significant limb first, its zero digits dropped, written after
+    // the leading zeros. Limb `k`'s digits end `10 * k` short of the
+    // encoding's end, which is no later than `end`, so no further than limb
+    // `k - 1`'s first byte.
+    let top = expand(symbols, radix, load(dst, limb_at(limbs - 1)));
+    let skip = top.iter().take_while(|&&c| c == symbols[0]).count();
+    let mut pos = lead + top.len() - skip;
+    dst[lead..pos].copy_from_slice(&top[skip..]);
+    for k in (0..limbs - 1).rev() {
+        let digits = expand(symbols, radix, load(dst, limb_at(k)));
+        dst[pos..pos + 10].copy_from_slice(&digits);
+        pos += 10;
+    }
+
+    // The input's leading zeros come back.
+    dst[..lead].fill(symbols[0]);
+    pos
+}
+
+/// Decodes `input` into `dst` and returns the number of bytes written.
+///
+/// `dst` must be at least `Engine::estimate_decoded_len` for `input`; a `dst`
+/// that is too small will panic (bounds check) rather than corrupt memory.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the alphabet.
+pub(crate) fn decode_slice(
+    alphabet: Alphabet,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    let table = &DECODE_TABLES[alphabet as usize];
+    let radix = alphabet.radix();
+
+    let zero = alphabet.symbols()[0];
+    let lead = input.iter().take_while(|&&c| c == zero).count();
+    let number = &input[lead..];
+    if number.is_empty() {
+        dst[..lead].fill(0);
+        return Ok(lead);
+    }
+
+    // --- LIMB LOOP ---
+    // `dst` holds limbs `top..limbs` in base `2^64`, most significant first.
+    // As `log2(radix) < 6`, six bits per digit is room enough.
+    let limbs = number.len() / 64 * 6 + number.len() % 64 * 6 / 64 + 1;
+    let mut top = limbs;
+
+    // Ten digits per step; the first step takes the odd few.
+    let (head, body) = number.split_at(number.len() % 10);
+    for chunk in core::iter::once(head)
+        .filter(|head| !head.is_empty())
+        .chain(body.chunks_exact(10))
+    {
+        let mut carry = 0u64;
+        let mut scale = 1u64;
+        for &c in chunk {
+            let digit = u64::from(table[usize::from(c)]);
+            if digit >= radix {
+                return Err(Error::InvalidCharacter);
+            }
+            carry = carry * radix + digit;
+            scale *= radix;
+        }
+
+        for k in (top..limbs).rev() {
+            let v = u128::from(load(dst, 8 * k)) * u128::from(scale) + u128::from(carry);
+            store(dst, 8 * k, v as u64);
+            carry = (v >> 64) as u64;
+        }
+        if carry > 0 {
+            top -= 1;
+            store(dst, 8 * top, carry);
+        }
+    }
+
+    // The top limb's zero bytes go; the input's leading zeros come back.
+    let end = 8 * limbs;
+    let start = 8 * top + dst[8 * top..end].iter().take_while(|&&b| b == 0).count();
+    dst.copy_within(start..end, lead);
+    dst[..lead].fill(0);
+    Ok(lead + end - start)
+}
@@ -27,8 +27,8 @@
 #![allow(clippy::trivially_copy_pass_by_ref)]
 
 use crate::{
-    Config, Error, STANDARD_ALPHABET, STANDARD_DECODE_TABLE, URL_SAFE_ALPHABET,
-    URL_SAFE_DECODE_TABLE,
+    Config, Error, LENIENT_DECODE_TABLE, STANDARD_ALPHABET, STANDARD_DECODE_TABLE,
+    URL_SAFE_ALPHABET, URL_SAFE_DECODE_TABLE,
 };
 
 /// Maps a 12-bit value to the two Base64 characters it encodes, packed
@@ -77,6 +77,21 @@ const fn decode_shift_tables(alphabet: &[u8; 64]) -> [[u32; 256]; 4] {
 static STANDARD_DECODE_SHIFTED: [[u32; 256]; 4] = decode_shift_tables(STANDARD_ALPHABET);
 static URL_SAFE_DECODE_SHIFTED: [[u32; 256]; 4] = decode_shift_tables(URL_SAFE_ALPHABET);
 
+/// The Standard tables with the URL-Safe `-` and `_` added, for `LENIENT`.
+const fn lenient_shift_tables() -> [[u32; 256]; 4] {
+    let mut tables = decode_shift_tables(STANDARD_ALPHABET);
+    let url_safe = decode_shift_tables(URL_SAFE_ALPHABET);
+    let mut i = 0;
+    while i < 4 {
+        tables[i][b'-' as usize] = url_safe[i][b'-' as usize];
+        tables[i][b'_' as usize] = url_safe[i][b'_' as usize];
+        i += 1;
+    }
+    tables
+}
+
+static LENIENT_DECODE_SHIFTED: [[u32; 256]; 4] = lenient_shift_tables();
+
 /// Largest value a valid 4-character group can OR to (24 significant bits).
 const GROUP_MAX: u32 = 0x00FF_FFFF;
 
@@ -205,7 +220,9 @@ pub(crate) fn decode_slice(config: &Config, input: &[u8], dst: &mut [u8]) -> Res
 
     // The table maps valid characters to 0..=63 and invalid characters to 0xFF.
     // It is only needed by the tail; the fast loop uses the pre-shifted tables.
-    let table = if config.url_safe {
+    let table = if config.lenient {
+        &LENIENT_DECODE_TABLE
+    } else if config.url_safe {
         &URL_SAFE_DECODE_TABLE
     } else {
         &STANDARD_DECODE_TABLE
@@ -217,7 +234,9 @@ pub(crate) fn decode_slice(config: &Config, input: &[u8], dst: &mut [u8]) -> Res
     // `len_fast <= len - 4`, so this is always within `estimate_decoded_len`.
     let out_fast = len_fast / 8 * 6;
 
-    let shifted: &[[u32; 256]; 4] = if config.url_safe {
+    let shifted: &[[u32; 256]; 4] = if config.lenient {
+        &LENIENT_DECODE_SHIFTED
+    } else if config.url_safe {
         &URL_SAFE_DECODE_SHIFTED
     } else {
         &STANDARD_DECODE_SHIFTED
@@ -0,0 +1,135 @@
+//! AVX2 compaction for ignore-garbage decoding, on the decoder's own nibble
+//! lookup.
+//!
+//! Each 32 bytes go through [`decode_vec_avx2`], whose error vector is zero
+//! in exactly the lanes the decoder accepts; `'='` is added back by a compare
+//! and `vpmovmskb` gives one bit per lane. A vector with nothing to drop is
+//! stored whole. Otherwise each mask byte indexes the shared left-pack table
+//! for its eight lanes, and one `vpshufb` packs every 8-byte group's kept
+//! lanes to the group's front; the four groups are then stored 8 bytes at a
+//! time, each where the last one's kept bytes end. The sub-vector remainder
+//! goes to the scalar kernel.
+
+use super::{DecodeConstantsAvx2, decode_constants_avx2, decode_vec_avx2};
+use crate::Config;
+use crate::garbage::scalar;
+use crate::simd::compact::{COMPACT_LANES, NEXT_GROUP};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m256i, _mm_storel_epi64, _mm_unpackhi_epi64, _mm256_castsi256_si128, _mm256_cmpeq_epi8,
+    _mm256_extracti128_si256, _mm256_loadu_si256, _mm256_movemask_epi8, _mm256_or_si256,
+    _mm256_set1_epi8, _mm256_setr_epi64x, _mm256_setzero_si256, _mm256_shuffle_epi8,
+    _mm256_storeu_si256,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m256i, _mm_storel_epi64, _mm_unpackhi_epi64, _mm256_castsi256_si128, _mm256_cmpeq_epi8,
+    _mm256_extracti128_si256, _mm256_loadu_si256, _mm256_movemask_epi8, _mm256_or_si256,
+    _mm256_set1_epi8, _mm256_setr_epi64x, _mm256_setzero_si256, _mm256_shuffle_epi8,
+    _mm256_storeu_si256,
+};
+
+// Stride constants, imported by the Kani proofs in `verify`.
+
+/// Bytes a step compacts: one vector.
+pub(super) const COMPACT_STEP: usize = 32;
+/// Lanes a group store writes, of which only the kept ones count.
+pub(super) const COMPACT_GROUP: usize = 8;
+
+/// The `vpshufb` control, one `u64` per 8-lane group, that packs the lanes
+/// `keep` selects to the front of each group, in order. `vpshufb` indexes
+/// within a 128-bit lane, so the second group of each is offset by 8.
+#[inline]
+#[allow(clippy::cast_possible_truncation)] // a mask byte, and a group index < 4
+pub(super) fn compact_control(keep: u32) -> [u64; 4] {
+    let mut control = [0u64; 4];
+    for (group, lanes) in control.iter_mut().enumerate() {
+        let byte = (keep >> (8 * group)) as u8;
+        *lanes = COMPACT_LANES[usize::from(byte)] + NEXT_GROUP * (group as u64 % 2);
+    }
+    control
+}
+
+/// The lanes of `chars` to keep: those the decoder accepts, and `'='`.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn keep_mask(chars: __m256i, k: &DecodeConstantsAvx2, pad: __m256i) -> u32 {
+    let (_, err) = decode_vec_avx2(chars, k);
+    let keep = _mm256_or_si256(
+        _mm256_cmpeq_epi8(err, _mm256_setzero_si256()),
+        _mm256_cmpeq_epi8(chars, pad),
+    );
+    _mm256_movemask_epi8(keep).cast_unsigned()
+}
+
+/// Stores the lanes of `chars` that `keep` selects to `dst`, packed, and
+/// returns how many there were.
+///
+/// Each group's store starts where the kept lanes before it end, which is
+/// never past the group's own first lane, so all of them stay inside the
+/// vector's 32 bytes.
+///
+/// # Safety
+/// The CPU must support AVX2, and `dst` must be valid for a 32-byte write.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn store_kept(chars: __m256i, keep: u32, dst: *mut u8) -> usize {
+    if keep == u32::MAX {
+        unsafe { _mm256_storeu_si256(dst.cast::<__m256i>(), chars) };
+        return COMPACT_STEP;
+    }
+    let [c0, c1, c2, c3] = compact_control(keep);
+    let control = _mm256_setr_epi64x(
+        c0.cast_signed(),
+        c1.cast_signed(),
+        c2.cast_signed(),
+        c3.cast_signed(),
+    );
+    let packed = _mm256_shuffle_epi8(chars, control);
+    let lo = _mm256_castsi256_si128(packed);
+    let hi = _mm256_extracti128_si256::<1>(packed);
+
+    let mut kept = 0;
+    for (group, lanes) in [
+        lo,
+        _mm_unpackhi_epi64(lo, lo),
+        hi,
+        _mm_unpackhi_epi64(hi, hi),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        unsafe { _mm_storel_epi64(dst.add(kept).cast(), lanes) };
+        kept += (keep >> (COMPACT_GROUP * group) & 0xFF).count_ones() as usize;
+    }
+    kept
+}
+
+/// Copies the bytes of `src` that `config` decodes, plus `'='`, to the front
+/// of `dst`, and returns how many there were, as
+/// [`scalar::compact`](crate::garbage::scalar::compact) does.
+///
+/// # Safety
+/// The CPU must support AVX2, and `dst` must be at least as long as `src`.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn compact_avx2(config: &Config, src: &[u8], dst: &mut [u8]) -> usize {
+    debug_assert!(dst.len() >= src.len());
+    let k = unsafe { decode_constants_avx2(config) };
+    let pad = _mm256_set1_epi8(b'='.cast_signed());
+    let len = src.len();
+    let src_ptr = src.as_ptr();
+    let dst_ptr = dst.as_mut_ptr();
+
+    // `kept` never passes `done`, so each step's stores stay inside
+    // `dst[..done + 32]`.
+    let mut done = 0;
+    let mut kept = 0;
+    while done + COMPACT_STEP <= len {
+        let chars = unsafe { _mm256_loadu_si256(src_ptr.add(done).cast::<__m256i>()) };
+        kept += unsafe { store_kept(chars, keep_mask(chars, &k, pad), dst_ptr.add(kept)) };
+        done += COMPACT_STEP;
+    }
+
+    kept + scalar::compact(config, &src[done..], &mut dst[kept..])
+}
@@ -0,0 +1,163 @@
+//! AVX2 hex. Encode splits each byte into nibbles and maps them with one
+//! `vpshufb` against the 16 digits; decode classifies digits and letters with
+//! two unsigned range checks and packs digit pairs with one `vpmaddubsw`.
+//! Both hand their sub-vector remainder to the scalar hex kernel.
+
+use crate::Error;
+use crate::hex::scalar;
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m128i, __m256i, _mm_loadu_si128, _mm256_add_epi8, _mm256_and_si256,
+    _mm256_broadcastsi128_si256, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_maddubs_epi16,
+    _mm256_min_epu8, _mm256_movemask_epi8, _mm256_or_si256, _mm256_packus_epi16,
+    _mm256_permute2x128_si256, _mm256_permute4x64_epi64, _mm256_set1_epi8, _mm256_set1_epi16,
+    _mm256_shuffle_epi8, _mm256_srli_epi16, _mm256_storeu_si256, _mm256_sub_epi8,
+    _mm256_unpackhi_epi8, _mm256_unpacklo_epi8,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m128i, __m256i, _mm_loadu_si128, _mm256_add_epi8, _mm256_and_si256,
+    _mm256_broadcastsi128_si256, _mm256_cmpeq_epi8, _mm256_loadu_si256, _mm256_maddubs_epi16,
+    _mm256_min_epu8, _mm256_movemask_epi8, _mm256_or_si256, _mm256_packus_epi16,
+    _mm256_permute2x128_si256, _mm256_permute4x64_epi64, _mm256_set1_epi8, _mm256_set1_epi16,
+    _mm256_shuffle_epi8, _mm256_srli_epi16, _mm256_storeu_si256, _mm256_sub_epi8,
+    _mm256_unpackhi_epi8, _mm256_unpacklo_epi8,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Bytes an encode step consumes: one vector.
+pub(super) const HEX_ENC_IN: usize = 32;
+/// Digits an encode step writes: two vectors.
+pub(super) const HEX_ENC_OUT: usize = 2 * HEX_ENC_IN;
+/// Digits a decode step consumes: two vectors.
+pub(super) const HEX_DEC_IN: usize = 64;
+/// Bytes a decode step writes: one vector.
+pub(super) const HEX_DEC_OUT: usize = HEX_DEC_IN / 2;
+
+/// 32 bytes -> 64 digits, as the two vectors of digits for bytes 0..16 and
+/// 16..32. The unpacks interleave within each 128-bit lane, so the lane
+/// permutes put the halves back in order.
+#[target_feature(enable = "avx2")]
+fn encode_vec_hex_avx2(raw: __m256i, digits: __m256i) -> (__m256i, __m256i) {
+    let nibble = _mm256_set1_epi8(0x0F);
+    let hi = _mm256_shuffle_epi8(digits, _mm256_and_si256(_mm256_srli_epi16(raw, 4), nibble));
+    let lo = _mm256_shuffle_epi8(digits, _mm256_and_si256(raw, nibble));
+    let first = _mm256_unpacklo_epi8(hi, lo);
+    let second = _mm256_unpackhi_epi8(hi, lo);
+    (
+        _mm256_permute2x128_si256::<0x20>(first, second),
+        _mm256_permute2x128_si256::<0x31>(first, second),
+    )
+}
+
+/// Maps 32 digits to their nibbles. Returns the nibbles and a vector that is
+/// `0xFF` in every lane holding a valid digit of either case.
+///
+/// `c - '0'` is at most 9 exactly for a decimal digit, and `(c | 0x20) - 'a'`
+/// at most 5 exactly for a letter digit: folding in `0x20` lowercases `A`-`F`
+/// and sends nothing else into `a`-`f`, and both subtractions wrap everything
+/// below their base far above the bound.
+#[target_feature(enable = "avx2")]
+pub(super) fn decode_vec_hex_avx2(chars: __m256i) -> (__m256i, __m256i) {
+    let decimal = _mm256_sub_epi8(chars, _mm256_set1_epi8(0x30));
+    let is_decimal = _mm256_cmpeq_epi8(_mm256_min_epu8(decimal, _mm256_set1_epi8(9)), decimal);
+    let letter = _mm256_sub_epi8(
+        _mm256_or_si256(chars, _mm256_set1_epi8(0x20)),
+        _mm256_set1_epi8(0x61),
+    );
+    let is_letter = _mm256_cmpeq_epi8(_mm256_min_epu8(letter, _mm256_set1_epi8(5)), letter);
+    let nibbles = _mm256_or_si256(
+        _mm256_and_si256(is_decimal, decimal),
+        _mm256_and_si256(is_letter, _mm256_add_epi8(letter, _mm256_set1_epi8(10))),
+    );
+    (nibbles, _mm256_or_si256(is_decimal, is_letter))
+}
+
+/// Encodes `input` as hex, writing `2 * input.len()` digits into `dst`.
+///
+/// # Safety
+/// The CPU must support AVX2, and `dst` must hold at least
+/// `2 * input.len()` bytes.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn encode_hex_avx2(upper: bool, input: &[u8], dst: &mut [u8]) {
+    debug_assert!(dst.len() >= 2 * input.len());
+    let alphabet = crate::hex::alphabet(upper);
+    let digits = _mm256_broadcastsi128_si256(unsafe {
+        _mm_loadu_si128(alphabet.as_ptr().cast::<__m128i>())
+    });
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_ENC_IN <= len {
+        let raw = unsafe { _mm256_loadu_si256(src.add(done).cast::<__m256i>()) };
+        let (first, second) = encode_vec_hex_avx2(raw, digits);
+        let chars = unsafe { out.add(written) };
+        unsafe {
+            _mm256_storeu_si256(chars.cast::<__m256i>(), first);
+            _mm256_storeu_si256(chars.add(HEX_ENC_OUT / 2).cast::<__m256i>(), second);
+        }
+        done += HEX_ENC_IN;
+        written += HEX_ENC_OUT;
+    }
+
+    scalar::encode_slice(upper, &input[done..], &mut dst[written..]);
+}
+
+/// Decodes hex `input`, in either case, into the first `input.len() / 2`
+/// bytes of `dst`.
+///
+/// # Safety
+/// The CPU must support AVX2, and `dst` must hold at least
+/// `input.len() / 2` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `input` holds anything but hex
+/// digits, and [`Error::InvalidLength`] if it is otherwise valid but of odd
+/// length.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn decode_hex_avx2(input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    debug_assert!(dst.len() >= input.len() / 2);
+    // `vpmaddubsw` weights: high nibble x16 plus low nibble x1, per digit pair.
+    let weights = _mm256_set1_epi16(0x0110);
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut valid = _mm256_set1_epi8(-1);
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_DEC_IN <= len {
+        let chars = unsafe { src.add(done) };
+        let (first, first_ok) =
+            decode_vec_hex_avx2(unsafe { _mm256_loadu_si256(chars.cast::<__m256i>()) });
+        let (second, second_ok) = decode_vec_hex_avx2(unsafe {
+            _mm256_loadu_si256(chars.add(HEX_DEC_IN / 2).cast::<__m256i>())
+        });
+        valid = _mm256_and_si256(valid, _mm256_and_si256(first_ok, second_ok));
+
+        // Each pair packs to at most 0xFF, so the saturating pack is exact; it
+        // interleaves the two vectors by lane, which the qword permute undoes.
+        let bytes = _mm256_packus_epi16(
+            _mm256_maddubs_epi16(first, weights),
+            _mm256_maddubs_epi16(second, weights),
+        );
+        unsafe {
+            _mm256_storeu_si256(
+                out.add(written).cast::<__m256i>(),
+                _mm256_permute4x64_epi64::<0b11_01_10_00>(bytes),
+            );
+        }
+        done += HEX_DEC_IN;
+        written += HEX_DEC_OUT;
+    }
+
+    if _mm256_movemask_epi8(valid) != -1 {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::decode_slice(&input[done..], &mut dst[written..])
+}
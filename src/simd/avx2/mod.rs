@@ -3,36 +3,28 @@ use crate::{Config, Error};
 use core::hint::black_box;
 
 #[cfg(target_arch = "x86")]
-use std::arch::x86::{
-    __m128i, __m256i, _mm_sfence, _mm_storeu_si128, _mm_stream_si128, _mm256_add_epi8,
-    _mm256_and_si256, _mm256_castsi256_si128, _mm256_cmpeq_epi8, _mm256_cmpgt_epi8,
+use core::arch::x86::{
+    __m128i, __m256i, _mm_loadu_si128, _mm_or_si128, _mm_sfence, _mm_slli_si128, _mm_srli_si128,
+    _mm_storeu_si128, _mm_stream_si128, _mm256_add_epi8, _mm256_and_si256,
+    _mm256_broadcastsi128_si256, _mm256_castsi256_si128, _mm256_cmpgt_epi8,
     _mm256_extracti128_si256, _mm256_loadu_si256, _mm256_madd_epi16, _mm256_maddubs_epi16,
-    _mm256_mullo_epi16, _mm256_or_si256, _mm256_permutevar8x32_epi32, _mm256_set_epi8,
-    _mm256_set1_epi8, _mm256_set1_epi32, _mm256_setr_epi8, _mm256_setr_epi32, _mm256_setzero_si256,
-    _mm256_shuffle_epi8, _mm256_srli_epi16, _mm256_storeu_si256, _mm256_sub_epi8, _mm256_subs_epu8,
-    _mm256_testz_si256,
+    _mm256_mullo_epi16, _mm256_or_si256, _mm256_permute2x128_si256, _mm256_permutevar8x32_epi32,
+    _mm256_set_epi8, _mm256_set1_epi8, _mm256_set1_epi32, _mm256_setr_epi8, _mm256_setr_epi32,
+    _mm256_setzero_si256, _mm256_shuffle_epi8, _mm256_srli_epi16, _mm256_storeu_si256,
+    _mm256_sub_epi8, _mm256_subs_epu8, _mm256_testz_si256,
 };
 #[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::{
-    __m128i, __m256i, _mm_sfence, _mm_storeu_si128, _mm_stream_si128, _mm256_add_epi8,
-    _mm256_and_si256, _mm256_castsi256_si128, _mm256_cmpeq_epi8, _mm256_cmpgt_epi8,
+use core::arch::x86_64::{
+    __m128i, __m256i, _mm_loadu_si128, _mm_or_si128, _mm_sfence, _mm_slli_si128, _mm_srli_si128,
+    _mm_storeu_si128, _mm_stream_si128, _mm256_add_epi8, _mm256_and_si256,
+    _mm256_broadcastsi128_si256, _mm256_castsi256_si128, _mm256_cmpgt_epi8,
     _mm256_extracti128_si256, _mm256_loadu_si256, _mm256_madd_epi16, _mm256_maddubs_epi16,
-    _mm256_mullo_epi16, _mm256_or_si256, _mm256_permutevar8x32_epi32, _mm256_set_epi8,
-    _mm256_set1_epi8, _mm256_set1_epi32, _mm256_setr_epi8, _mm256_setr_epi32, _mm256_setzero_si256,
-    _mm256_shuffle_epi8, _mm256_srli_epi16, _mm256_storeu_si256, _mm256_sub_epi8, _mm256_subs_epu8,
-    _mm256_testz_si256,
+    _mm256_mullo_epi16, _mm256_or_si256, _mm256_permute2x128_si256, _mm256_permutevar8x32_epi32,
+    _mm256_set_epi8, _mm256_set1_epi8, _mm256_set1_epi32, _mm256_setr_epi8, _mm256_setr_epi32,
+    _mm256_setzero_si256, _mm256_shuffle_epi8, _mm256_srli_epi16, _mm256_storeu_si256,
+    _mm256_sub_epi8, _mm256_subs_epu8, _mm256_testz_si256,
 };
 
-/// Input length from which the encoder switches to non-temporal stores.
-///
-/// Above it the input plus its 4/3-sized output no longer fit in a typical
-/// last-level cache, so the ordinary stores spend a third of the memory
-/// bandwidth on read-for-ownership traffic for lines that are then overwritten
-/// whole. Below it the output usually *is* reused from cache and bypassing it
-/// costs more than the RFO traffic saves; measured on a 9 MiB-L3 Coffee Lake,
-/// the crossover sits between 2 and 4 MiB.
-const NT_STORE_MIN_LEN: usize = 4 << 20;
-
 /// Rounds per iteration of the encoder's wide tier.
 const ENC_UNROLL: usize = 8;
 /// Vectors per iteration of the decoder's wide tier.
@@ -64,7 +56,7 @@ const DEC_BLOCK_OUT: usize = 24;
 /// Read-ahead margin: every pass reads a full vector per [`DEC_BLOCK_IN`]
 /// characters consumed, so no pass may start within this many bytes of the end.
 const DEC_LEAD: usize = 4;
-/// Offset of `pack_and_store!`'s second 16-byte lane, which is what makes its
+/// Offset of [`store_packed_avx2`]'s second 16-byte lane, which is what makes its
 /// written span wider than the 24 bytes it advances.
 const DEC_PACK_LANE_OFF: usize = 12;
 
@@ -131,8 +123,15 @@ fn encode_vec_avx2(input: __m256i, k: &EncodeConstantsAvx2) -> __m256i {
     let shuffled = _mm256_shuffle_epi8(input, k.reshuffle);
     let aligned = _mm256_srli_epi16(_mm256_mullo_epi16(shuffled, k.align_mul), 10);
     let fields = _mm256_mullo_epi16(_mm256_and_si256(shuffled, k.field_mask), k.field_mul);
-    let indices = _mm256_or_si256(aligned, fields);
+    translate_avx2(_mm256_or_si256(aligned, fields), k)
+}
 
+/// Maps 32 6-bit values to their Base64 characters: the saturating subtract
+/// and compare pick each value's range, and one `vpshufb` of `translate` adds
+/// that range's offset.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn translate_avx2(indices: __m256i, k: &EncodeConstantsAvx2) -> __m256i {
     let lut_idx = _mm256_sub_epi8(
         _mm256_subs_epu8(indices, k.c51),
         _mm256_cmpgt_epi8(indices, k.c25),
@@ -140,39 +139,129 @@ fn encode_vec_avx2(input: __m256i, k: &EncodeConstantsAvx2) -> __m256i {
     _mm256_add_epi8(indices, _mm256_shuffle_epi8(k.translate, lut_idx))
 }
 
-/// # Safety
-/// `dst` must be valid for a 32-byte write, and 16-byte aligned when `NT`.
-#[target_feature(enable = "avx2")]
-unsafe fn store_chars_avx2<const NT: bool>(dst: *mut u8, chars: __m256i) {
-    if NT {
-        let half = dst.cast::<__m128i>();
+/// `vpshufb` controls for [`StreamLag`], as one window: the 16 bytes at
+/// `phase` take a half's bytes from `phase` up, and the 16 at `16 + phase` take
+/// the next half's bytes below `phase`. `0x80` zeroes a lane.
+#[allow(clippy::cast_possible_truncation)] // every entry is below 16
+const STITCH_WINDOW: [u8; 48] = {
+    let mut w = [0x80u8; 48];
+    let mut i = 0;
+    while i < 16 {
+        w[i] = i as u8;
+        w[32 + i] = i as u8;
+        i += 1;
+    }
+    w
+};
+
+/// The non-temporal path's store state.
+///
+/// A round's 32 characters start wherever the destination puts them, which
+/// for a sub-slice need not even be a whole group from a 16-byte boundary, so
+/// they cannot be streamed as they are. Instead every round streams the 32
+/// characters that start `16 - phase` bytes before it, where `phase` is the
+/// distance from a round's start to the next 16-byte boundary: the previous
+/// round's last half, stitched to this one's first, lands exactly on one. The
+/// first round's cached store is the peeled head that covers everything before
+/// the first streamed byte, and [`StreamLag::finish`] covers everything after
+/// the last.
+struct StreamLag {
+    take_prev: __m256i,
+    take_cur: __m256i,
+    prev: __m256i,
+    lag: usize,
+}
+
+impl StreamLag {
+    /// `head` is the first round's characters, already stored cached.
+    ///
+    /// # Safety
+    /// `phase` must be below 16.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn new(head: __m256i, phase: usize) -> Self {
+        let window = STITCH_WINDOW.as_ptr();
+        let take_prev = unsafe { _mm_loadu_si128(window.add(phase).cast()) };
+        let take_cur = unsafe { _mm_loadu_si128(window.add(16 + phase).cast()) };
+        Self {
+            take_prev: _mm256_broadcastsi128_si256(take_prev),
+            take_cur: _mm256_broadcastsi128_si256(take_cur),
+            prev: head,
+            lag: 16 - phase,
+        }
+    }
+
+    /// Streams the 32 characters that start `lag` bytes before the round at
+    /// `dst`, whose characters are `chars`.
+    ///
+    /// # Safety
+    /// `dst.sub(self.lag)` must be valid for a 32-byte write.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn stream(&mut self, dst: *mut u8, chars: __m256i) {
+        // Lane 0 is the previous round's high half then this round's low one,
+        // lane 1 this round's two halves.
+        let straddle = _mm256_permute2x128_si256::<0x21>(self.prev, chars);
+        let out = _mm256_or_si256(
+            _mm256_shuffle_epi8(straddle, self.take_prev),
+            _mm256_shuffle_epi8(chars, self.take_cur),
+        );
+        self.prev = chars;
+
+        let half = unsafe { dst.sub(self.lag) }.cast::<__m128i>();
         unsafe {
-            _mm_stream_si128(half, _mm256_castsi256_si128(chars));
-            _mm_stream_si128(half.add(1), _mm256_extracti128_si256(chars, 1));
+            _mm_stream_si128(half, _mm256_castsi256_si128(out));
+            _mm_stream_si128(half.add(1), _mm256_extracti128_si256(out, 1));
         }
-    } else {
-        unsafe { _mm256_storeu_si256(dst.cast::<__m256i>(), chars) };
+    }
+
+    /// Stores the characters left after the last streamed byte, cached, given
+    /// `end`, where the last round ends, then fences the streamed ones.
+    ///
+    /// # Safety
+    /// `end.sub(16)` must be valid for a 16-byte write.
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn finish(&self, end: *mut u8) {
+        // The last `lag` characters; the high half covers them whatever the
+        // phase.
+        let high = _mm256_extracti128_si256(self.prev, 1);
+        unsafe { _mm_storeu_si128(end.sub(16).cast::<__m128i>(), high) };
+        // Non-temporal stores are not ordered against the caller's later loads.
+        _mm_sfence();
     }
 }
 
 /// Runs `rounds` steady-state encode rounds: each reads the 32 bytes at `src`,
-/// consumes the middle 24 (`src[4..28]`), and writes 32 characters.
+/// consumes the middle 24 (`src[4..28]`), and writes 32 characters. Streamed
+/// through `lag` when it is given, stored cached otherwise.
 ///
 /// # Safety
 /// For every `i < rounds`, `src.add(24 * i)` must be valid for a 32-byte read
-/// and `dst.add(32 * i)` for a 32-byte write; when `NT`, `dst` must also be
-/// 16-byte aligned.
+/// and `dst.add(32 * i)` for a 32-byte write. With a `lag`, its `phase` must be
+/// `dst`'s distance to a 16-byte boundary, and the `lag` bytes before `dst`
+/// must be writable too.
 #[target_feature(enable = "avx2")]
-unsafe fn encode_rounds_avx2<const NT: bool>(
+unsafe fn encode_rounds_avx2(
     src: *const u8,
     dst: *mut u8,
     rounds: usize,
     k: &EncodeConstantsAvx2,
+    mut lag: Option<StreamLag>,
 ) {
     let mut src = src;
     let mut dst = dst;
     let mut remaining = rounds;
 
+    macro_rules! store {
+        ($dst:expr, $chars:expr) => {{
+            match lag.as_mut() {
+                Some(lag) => unsafe { lag.stream($dst, $chars) },
+                None => unsafe { _mm256_storeu_si256($dst.cast::<__m256i>(), $chars) },
+            }
+        }};
+    }
+
     while remaining >= ENC_UNROLL {
         // Loads first, stores second: the eight independent chains keep the
         // multiply latency covered without the scheduler having to reorder
@@ -183,7 +272,8 @@ unsafe fn encode_rounds_avx2<const NT: bool>(
         }
         for (i, raw) in chunk.into_iter().enumerate() {
             let chars = encode_vec_avx2(raw, k);
-            unsafe { store_chars_avx2::<NT>(dst.add(ENC_ROUND_OUT * i), chars) };
+            let out = unsafe { dst.add(ENC_ROUND_OUT * i) };
+            store!(out, chars);
         }
 
         src = unsafe { src.add(ENC_ROUND_IN * ENC_UNROLL) };
@@ -194,16 +284,15 @@ unsafe fn encode_rounds_avx2<const NT: bool>(
     while remaining > 0 {
         let raw = unsafe { _mm256_loadu_si256(src.cast::<__m256i>()) };
         let chars = encode_vec_avx2(raw, k);
-        unsafe { store_chars_avx2::<NT>(dst, chars) };
+        store!(dst, chars);
 
         src = unsafe { src.add(ENC_ROUND_IN) };
         dst = unsafe { dst.add(ENC_ROUND_OUT) };
         remaining -= 1;
     }
 
-    if NT {
-        // Non-temporal stores are not ordered against the caller's later loads.
-        _mm_sfence();
+    if let Some(lag) = lag {
+        unsafe { lag.finish(dst) };
     }
 }
 
@@ -232,13 +321,16 @@ pub(crate) unsafe fn encode_slice_avx2(config: &Config, input: &[u8], dst_slice:
 
         let remaining = rounds - 1;
 
-        // Every store sits at `dst_start + 32 * n`, so one alignment test up
-        // front covers the whole loop.
-        if len >= NT_STORE_MIN_LEN && dst_start.align_offset(16) == 0 {
-            unsafe { encode_rounds_avx2::<true>(src, dst, remaining, &k) };
+        // Every round starts at `dst_start + 32 * n`, so they all share one
+        // phase against a 16-byte boundary, and the first round doubles as the
+        // cached head that peels the destination up to it.
+        let phase = dst.align_offset(16);
+        let lag = if len >= super::nt_store_min_len() && phase < 16 {
+            Some(unsafe { StreamLag::new(out0, phase) })
         } else {
-            unsafe { encode_rounds_avx2::<false>(src, dst, remaining, &k) };
-        }
+            None
+        };
+        unsafe { encode_rounds_avx2(src, dst, remaining, &k, lag) };
 
         // Undo the first round's 20-vs-24 pointer-advancement deficit.
         src = unsafe { src.add(ENC_ROUND_IN * remaining + ENC_LEAD) };
@@ -252,17 +344,19 @@ pub(crate) unsafe fn encode_slice_avx2(config: &Config, input: &[u8], dst_slice:
 /// Precomputed AVX2 decode constants, factored out of [`decode_slice_avx2`]
 /// only to keep its body under clippy's line-count threshold.
 ///
-/// The nibble-lookup validation/decode is Wojciech Muła's (with `@aqrit`'s
-/// `/`-vs-`+` trick), as in `aklomp/base64` and `lemire/fastbase64` (BSD); see
-/// the README. That algorithm covers only the standard alphabet; the URL-safe
-/// `lut_lo`/`lut_hi`/`lut_roll` were re-derived here and verified against all
-/// 256 byte values (see `avx2_lut_url_safe_matches_scalar`).
+/// The nibble-lookup validation/decode is Wojciech Muła's, as in
+/// `aklomp/base64` and `lemire/fastbase64` (BSD); see the README. `@aqrit`'s
+/// `/`-vs-`+` compare, which moves one character to its own `lut_roll` slot,
+/// is generalised here to `lut_shift`, a slot offset per low nibble, so every
+/// character that shares a row with a differently-decoding one gets its own
+/// slot: that lets the lenient tables give `+`, `-` and `/` three. All three
+/// configs' tables were derived here and verified against all 256 byte values
+/// (see `avx2_decode_lut_exhaustive`).
 struct DecodeConstantsAvx2 {
     lut_lo: __m256i,
     lut_hi: __m256i,
+    lut_shift: __m256i,
     lut_roll: __m256i,
-    eq_char: __m256i,
-    eq_shift: __m256i,
     pack_l1: __m256i,
     pack_l2: __m256i,
     pack_shuffle: __m256i,
@@ -276,7 +370,37 @@ unsafe fn decode_constants_avx2(config: &Config) -> DecodeConstantsAvx2 {
     // every `lut_lo`, paired with `lut_hi = 0x10` on rows with no valid chars
     // (0, 1, 8..=15). Rows 2..=7 each get a guard bit that `lut_lo` clears only
     // for that row's valid low nibbles.
-    let (lut_lo, lut_hi, lut_roll, eq_char, eq_shift) = if config.url_safe {
+    //
+    // Delta from ASCII to 6-bit value: `lut_roll[(byte >> 4) + lut_shift[byte &
+    // 0xF]]`. Slots 2..=7 hold each row's usual delta; a low nibble with a
+    // character that needs another moves its whole column up into the free
+    // slots 8..=15. No valid character's slot passes 15.
+    let (lut_lo, lut_hi, lut_shift, lut_roll) = if config.lenient {
+        // Guard bits per high nibble: 2=`+`/`-`/`/`(0x01), 3=digits(0x02),
+        // 4/6=`A`-`O`/`a`-`o`(0x04), 5=`P`-`Z`+`_`(0x08), 7=`p`-`z`(0x20).
+        let lut_lo = _mm256_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3A, 0x3B, 0x3A,
+            0x3B, 0x32, 0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3A,
+            0x3B, 0x3A, 0x3B, 0x32,
+        );
+        let lut_hi = _mm256_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10, 0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10, 0x10, 0x10,
+        );
+        // Row 2 needs three deltas (`+` +19, `-` +17, `/` +16) and row 5 two
+        // (`_` -32), so column D (`-`, `M`, `m`) moves to slots 8, 10, 12 and
+        // column F (`/`, `O`, `_`, `o`) to 11, 13, 14, 15.
+        let lut_shift = _mm256_setr_epi8(
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 9, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            6, 0, 9,
+        );
+        let lut_roll = _mm256_setr_epi8(
+            0, 0, 19, 4, -65, -65, -71, -71, 17, 0, -65, 16, -71, -65, -32, -71, 0, 0, 19, 4, -65,
+            -65, -71, -71, 17, 0, -65, 16, -71, -65, -32, -71,
+        );
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    } else if config.url_safe {
         // Guard bits per high nibble: 2=`-`(0x01), 3=digits(0x02),
         // 4/6=`A`-`O`/`a`-`o`(0x04), 5=`P`-`Z`+`_`(0x08), 7=`p`-`z`(0x20).
         // Row 5 breaks symmetry with row 7 (the `_`), so both need own bits.
@@ -290,13 +414,17 @@ unsafe fn decode_constants_avx2(config: &Config) -> DecodeConstantsAvx2 {
             0x10, 0x10, 0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10,
             0x10, 0x10, 0x10, 0x10,
         );
-        // Delta from ASCII to 6-bit value. Row 5 is ambiguous (`P`..`Z` need
-        // -65, `_` needs -32), so `_` is pushed to slot 5+8=13 for its delta.
+        // Row 5 is ambiguous (`P`..`Z` need -65, `_` needs -32), so column F
+        // (`O`, `_`, `o`) moves to slots 8, 9, 10.
+        let lut_shift = _mm256_setr_epi8(
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 4,
+        );
         let lut_roll = _mm256_setr_epi8(
-            0, 0, 17, 4, -65, -65, -71, -71, 0, 0, 0, 0, 0, -32, 0, 0, 0, 0, 17, 4, -65, -65, -71,
-            -71, 0, 0, 0, 0, 0, -32, 0, 0,
+            0, 0, 17, 4, -65, -65, -71, -71, -65, -32, -71, 0, 0, 0, 0, 0, 0, 0, 17, 4, -65, -65,
+            -71, -71, -65, -32, -71, 0, 0, 0, 0, 0,
         );
-        (lut_lo, lut_hi, lut_roll, b'_', 8i8)
+        (lut_lo, lut_hi, lut_shift, lut_roll)
     } else {
         // Guard bits per high nibble: 2=`+`/`/`(0x01), 3=digits(0x02),
         // 4/6=`A`-`O`/`a`-`o`(0x04), 5/7=`P`-`Z`/`p`-`z`(0x08).
@@ -310,18 +438,19 @@ unsafe fn decode_constants_avx2(config: &Config) -> DecodeConstantsAvx2 {
             0x10, 0x10, 0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x08, 0x10, 0x10, 0x10, 0x10,
             0x10, 0x10, 0x10, 0x10,
         );
-        // Row 2 is ambiguous (`+` needs +19, `/` needs +16), so `/` is pulled
-        // to slot 1 for its delta.
+        // Row 2 is ambiguous (`+` needs +19, `/` needs +16), so column F
+        // (`/`, `O`, `o`) moves to slots 8, 10, 12.
+        let lut_shift = _mm256_setr_epi8(
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 6,
+        );
         let lut_roll = _mm256_setr_epi8(
-            0, 16, 19, 4, -65, -65, -71, -71, 0, 0, 0, 0, 0, 0, 0, 0, 0, 16, 19, 4, -65, -65, -71,
-            -71, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 19, 4, -65, -65, -71, -71, 16, 0, -65, 0, -71, 0, 0, 0, 0, 0, 19, 4, -65, -65,
+            -71, -71, 16, 0, -65, 0, -71, 0, 0, 0,
         );
-        (lut_lo, lut_hi, lut_roll, b'/', -1i8)
+        (lut_lo, lut_hi, lut_shift, lut_roll)
     };
 
-    let eq_char = _mm256_set1_epi8(eq_char.cast_signed());
-    let eq_shift = _mm256_set1_epi8(eq_shift);
-
     // Packing Constants
     let pack_l1 = unsafe { _mm256_loadu_si256(PACK_L1.as_ptr().cast::<__m256i>()) };
     let pack_l2 = unsafe { _mm256_loadu_si256(PACK_L2.as_ptr().cast::<__m256i>()) };
@@ -333,9 +462,8 @@ unsafe fn decode_constants_avx2(config: &Config) -> DecodeConstantsAvx2 {
     DecodeConstantsAvx2 {
         lut_lo,
         lut_hi,
+        lut_shift,
         lut_roll,
-        eq_char,
-        eq_shift,
         pack_l1,
         pack_l2,
         pack_shuffle,
@@ -343,6 +471,127 @@ unsafe fn decode_constants_avx2(config: &Config) -> DecodeConstantsAvx2 {
     }
 }
 
+/// Validates and decodes one vector of 32 characters (nibble lookup,
+/// roll-based; see [`DecodeConstantsAvx2`] for credit). Returns the 6-bit
+/// indices and a vector that is nonzero iff some character was invalid.
+#[target_feature(enable = "avx2")]
+fn decode_vec_avx2(input: __m256i, k: &DecodeConstantsAvx2) -> (__m256i, __m256i) {
+    let hi_nibbles = _mm256_and_si256(_mm256_srli_epi16(input, 4), k.mask_nibble);
+    let lo_nibbles = _mm256_and_si256(input, k.mask_nibble);
+
+    let lo = _mm256_shuffle_epi8(k.lut_lo, lo_nibbles);
+    let hi = _mm256_shuffle_epi8(k.lut_hi, hi_nibbles);
+    let err = _mm256_and_si256(lo, hi);
+
+    let shift = _mm256_shuffle_epi8(k.lut_shift, lo_nibbles);
+    let roll_idx = _mm256_add_epi8(hi_nibbles, shift);
+    let roll = _mm256_shuffle_epi8(k.lut_roll, roll_idx);
+    let indices = _mm256_add_epi8(input, roll);
+
+    (indices, err)
+}
+
+/// Packs 32 indices into 24 bytes: 12 at the bottom of each 128-bit lane, with
+/// the top 4 bytes of each lane zeroed.
+#[target_feature(enable = "avx2")]
+fn pack_avx2(indices: __m256i, k: &DecodeConstantsAvx2) -> __m256i {
+    let m = _mm256_maddubs_epi16(indices, k.pack_l1);
+    let p = _mm256_madd_epi16(m, k.pack_l2);
+    _mm256_shuffle_epi8(p, k.pack_shuffle)
+}
+
+/// Stores the 24 bytes of a [`pack_avx2`] result at `dst`, as two 16-byte
+/// stores that overhang by 4; the next store in the same pass, or the scalar
+/// tail, rewrites the overhang.
+///
+/// # Safety
+/// `dst` must be valid for a 28-byte write.
+#[target_feature(enable = "avx2")]
+unsafe fn store_packed_avx2(dst: *mut u8, packed: __m256i) {
+    unsafe { _mm_storeu_si128(dst.cast::<__m128i>(), _mm256_castsi256_si128(packed)) };
+    let lane_1 = _mm256_extracti128_si256(packed, 1);
+    unsafe { _mm_storeu_si128(dst.add(DEC_PACK_LANE_OFF).cast::<__m128i>(), lane_1) };
+}
+
+/// Streams the 48 bytes of two [`pack_avx2`] results to `dst` as three
+/// non-temporal 16-byte stores.
+///
+/// A streaming store cannot overhang the way [`store_packed_avx2`] does (the
+/// overlapping write would land at an unaligned address), so the four 12-byte
+/// groups are stitched into three whole vectors first. Each group's zeroed top
+/// 4 bytes are what let a plain OR join it to its byte-shifted neighbour.
+///
+/// # Safety
+/// `dst` must be valid for a 48-byte write and 16-byte aligned.
+#[target_feature(enable = "avx2")]
+unsafe fn stream_packed_pair_avx2(dst: *mut u8, a: __m256i, b: __m256i) {
+    let g0 = _mm256_castsi256_si128(a);
+    let g1 = _mm256_extracti128_si256(a, 1);
+    let g2 = _mm256_castsi256_si128(b);
+    let g3 = _mm256_extracti128_si256(b, 1);
+
+    let out = dst.cast::<__m128i>();
+    unsafe {
+        _mm_stream_si128(out, _mm_or_si128(g0, _mm_slli_si128(g1, 12)));
+        _mm_stream_si128(
+            out.add(1),
+            _mm_or_si128(_mm_srli_si128(g1, 4), _mm_slli_si128(g2, 8)),
+        );
+        _mm_stream_si128(
+            out.add(2),
+            _mm_or_si128(_mm_srli_si128(g2, 8), _mm_slli_si128(g3, 4)),
+        );
+    }
+}
+
+/// Runs `iters` wide-tier decode passes: each reads 256 characters at `src`
+/// and writes 192 bytes at `dst`. Returns the OR of every pass's error vector.
+///
+/// # Safety
+/// For every `i < iters`, `src.add(256 * i)` must be valid for a 256-byte read
+/// and `dst.add(192 * i)` for a 196-byte write (192 when `NT`, in which case
+/// `dst` must also be 16-byte aligned).
+#[target_feature(enable = "avx2")]
+unsafe fn decode_wide_avx2<const NT: bool>(
+    src: *const u8,
+    dst: *mut u8,
+    iters: usize,
+    k: &DecodeConstantsAvx2,
+) -> __m256i {
+    let mut src = src;
+    let mut dst = dst;
+    let mut err_acc = _mm256_setzero_si256();
+
+    for _ in 0..iters {
+        let mut packed = [_mm256_setzero_si256(); DEC_UNROLL];
+        for (i, slot) in packed.iter_mut().enumerate() {
+            let raw = unsafe { _mm256_loadu_si256(src.add(DEC_BLOCK_IN * i).cast::<__m256i>()) };
+            let (indices, err) = decode_vec_avx2(raw, k);
+            *slot = pack_avx2(indices, k);
+            err_acc = _mm256_or_si256(err_acc, err);
+        }
+        if NT {
+            for (i, pair) in packed.chunks_exact(2).enumerate() {
+                let out = unsafe { dst.add(2 * DEC_BLOCK_OUT * i) };
+                unsafe { stream_packed_pair_avx2(out, pair[0], pair[1]) };
+            }
+        } else {
+            for (i, p) in packed.into_iter().enumerate() {
+                unsafe { store_packed_avx2(dst.add(DEC_BLOCK_OUT * i), p) };
+            }
+        }
+
+        src = unsafe { src.add(DEC_BLOCK_IN * DEC_UNROLL) };
+        dst = unsafe { dst.add(DEC_BLOCK_OUT * DEC_UNROLL) };
+    }
+
+    if NT {
+        // Non-temporal stores are not ordered against the caller's later loads.
+        _mm_sfence();
+    }
+    err_acc
+}
+
 #[target_feature(enable = "avx2")]
 pub(crate) unsafe fn decode_slice_avx2(
     config: &Config,
@@ -354,59 +603,15 @@ pub(crate) unsafe fn decode_slice_avx2(
     let dst_start = dst_slice.as_mut_ptr();
     let mut dst = dst_start;
 
-    let DecodeConstantsAvx2 {
-        lut_lo,
-        lut_hi,
-        lut_roll,
-        eq_char,
-        eq_shift,
-        pack_l1,
-        pack_l2,
-        pack_shuffle,
-        mask_nibble,
-    } = unsafe { decode_constants_avx2(config) };
-
-    // Validate + decode one vector (nibble lookup, roll-based; see the struct
-    // doc above for credit).
-    macro_rules! decode_vec {
-        ($input:expr) => {{
-            let hi_nibbles = _mm256_and_si256(_mm256_srli_epi16($input, 4), mask_nibble);
-            let lo_nibbles = _mm256_and_si256($input, mask_nibble);
-
-            let lo = _mm256_shuffle_epi8(lut_lo, lo_nibbles);
-            let hi = _mm256_shuffle_epi8(lut_hi, hi_nibbles);
-            let err = _mm256_and_si256(lo, hi);
-
-            let eq = _mm256_cmpeq_epi8($input, eq_char);
-            let roll_idx = _mm256_add_epi8(hi_nibbles, _mm256_and_si256(eq, eq_shift));
-            let roll = _mm256_shuffle_epi8(lut_roll, roll_idx);
-            let indices = _mm256_add_epi8($input, roll);
-
-            (indices, err)
-        }};
-    }
-
-    macro_rules! pack_and_store {
-        ($indices:expr, $dst_ptr:expr) => {{
-            let m = _mm256_maddubs_epi16($indices, pack_l1);
-            let p = _mm256_madd_epi16(m, pack_l2);
-            let out = _mm256_shuffle_epi8(p, pack_shuffle);
-
-            let lane_0 = _mm256_castsi256_si128(out);
-            unsafe { _mm_storeu_si128($dst_ptr.cast::<__m128i>(), lane_0) };
-            let lane_1 = _mm256_extracti128_si256(out, 1);
-            unsafe { _mm_storeu_si128($dst_ptr.add(DEC_PACK_LANE_OFF).cast::<__m128i>(), lane_1) };
-        }};
-    }
+    let k = unsafe { decode_constants_avx2(config) };
 
     // Every load reads a full 32-byte vector per 32 bytes consumed, so no pass
     // may start within 4 bytes of the end; each tier rounds `safe_len` down to
     // its own block size.
     let safe_len = len.saturating_sub(DEC_LEAD);
     let block_wide = DEC_BLOCK_IN * DEC_UNROLL;
-    let aligned_len_wide = safe_len - (safe_len % block_wide);
+    let wide_iters = safe_len / block_wide;
     let aligned_len_32 = safe_len - (safe_len % DEC_BLOCK_IN);
-    let src_end_wide = unsafe { src.add(aligned_len_wide) };
     let src_end_32 = unsafe { src.add(aligned_len_32) };
 
     // Invalid characters are folded into one accumulator and reported after the
@@ -414,33 +619,25 @@ pub(crate) unsafe fn decode_slice_avx2(
     // vector's inputs to stay live across a branch, which costs more registers
     // than this machine has; the caller sees the same `Err` either way, and the
     // bytes written before it are already unspecified on the error path.
-    let mut err_acc = _mm256_setzero_si256();
-
-    // Wide tier: 256 input bytes -> 192 output.
-    while src < src_end_wide {
-        let mut decoded = [_mm256_setzero_si256(); DEC_UNROLL];
-        for (i, slot) in decoded.iter_mut().enumerate() {
-            let raw = unsafe { _mm256_loadu_si256(src.add(DEC_BLOCK_IN * i).cast::<__m256i>()) };
-            let (indices, err) = decode_vec!(raw);
-            *slot = indices;
-            err_acc = _mm256_or_si256(err_acc, err);
-        }
-        for (i, indices) in decoded.into_iter().enumerate() {
-            let out = unsafe { dst.add(DEC_BLOCK_OUT * i) };
-            pack_and_store!(indices, out);
-        }
-
-        src = unsafe { src.add(DEC_BLOCK_IN * DEC_UNROLL) };
-        dst = unsafe { dst.add(DEC_BLOCK_OUT * DEC_UNROLL) };
-    }
+    //
+    // Wide tier: 256 input bytes -> 192 output. Every pass starts at
+    // `dst_start + 192 * n`, so as in the encoder one alignment test up front
+    // covers the whole loop.
+    let mut err_acc = if len >= super::nt_store_min_len() && dst_start.align_offset(16) == 0 {
+        unsafe { decode_wide_avx2::<true>(src, dst, wide_iters, &k) }
+    } else {
+        unsafe { decode_wide_avx2::<false>(src, dst, wide_iters, &k) }
+    };
+    src = unsafe { src.add(block_wide * wide_iters) };
+    dst = unsafe { dst.add(DEC_BLOCK_OUT * DEC_UNROLL * wide_iters) };
 
     // Single tier: 32 input bytes -> 24 output.
     while src < src_end_32 {
         let raw = unsafe { _mm256_loadu_si256(src.cast::<__m256i>()) };
-        let (indices, err) = decode_vec!(raw);
+        let (indices, err) = decode_vec_avx2(raw, &k);
         err_acc = _mm256_or_si256(err_acc, err);
 
-        pack_and_store!(indices, dst);
+        unsafe { store_packed_avx2(dst, pack_avx2(indices, &k)) };
 
         src = unsafe { src.add(DEC_BLOCK_IN) };
         dst = unsafe { dst.add(DEC_BLOCK_OUT) };
@@ -454,6 +651,96 @@ pub(crate) unsafe fn decode_slice_avx2(
     unsafe { super::tail::decode(config, input, src, dst_slice, dst_off) }
 }
 
+// --- Short inputs ---
+
+/// Longest input [`encode_short_avx2`] takes: two steady-state rounds.
+pub(crate) const ENC_SHORT_MAX: usize = 2 * ENC_ROUND_IN;
+/// Longest input [`decode_short_avx2`] takes: two decode blocks.
+pub(crate) const DEC_SHORT_MAX: usize = 2 * DEC_BLOCK_IN;
+
+/// Encodes an input of at most [`ENC_SHORT_MAX`] bytes, padding included.
+///
+/// Both sides are staged through the stack, which is what lets a 32-byte load
+/// and store serve an input of any length: the input is copied in behind a
+/// zeroed [`ENC_LEAD`] so every round takes the steady-state layout, and the
+/// characters are copied out once the `=` are in place.
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn encode_short_avx2(config: &Config, input: &[u8], dst: &mut [u8]) {
+    debug_assert!(input.len() <= ENC_SHORT_MAX);
+    let k = encode_constants_avx2(*config);
+
+    let mut staged = [0u8; ENC_ROUND_IN + ENC_VEC];
+    staged[ENC_LEAD..][..input.len()].copy_from_slice(input);
+    let mut chars = [0u8; 2 * ENC_ROUND_OUT];
+
+    for round in 0..input.len().div_ceil(ENC_ROUND_IN) {
+        let src = staged[ENC_ROUND_IN * round..][..ENC_VEC].as_ptr();
+        let out = chars[ENC_ROUND_OUT * round..][..ENC_ROUND_OUT].as_mut_ptr();
+        let raw = unsafe { _mm256_loadu_si256(src.cast::<__m256i>()) };
+        unsafe { _mm256_storeu_si256(out.cast::<__m256i>(), encode_vec_avx2(raw, &k)) };
+    }
+
+    let (data, total) = super::short::encoded_lens(*config, input.len());
+    chars[data..total].fill(b'=');
+    dst[..total].copy_from_slice(&chars[..total]);
+}
+
+/// Decodes an input of at most [`DEC_SHORT_MAX`] characters, padding included,
+/// staged through the stack as in [`encode_short_avx2`].
+///
+/// # Safety
+/// The CPU must support AVX2.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn decode_short_avx2(
+    config: &Config,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    debug_assert!(input.len() <= DEC_SHORT_MAX);
+    let k = unsafe { decode_constants_avx2(config) };
+    let plan = super::short::plan_decode(*config, input);
+
+    let mut staged = [b'A'; DEC_SHORT_MAX];
+    staged[..plan.chars].copy_from_slice(&input[..plan.chars]);
+    // The second block's packed store spans 28 bytes from 24.
+    let mut bytes = [0u8; DEC_BLOCK_OUT + DEC_PACK_LANE_OFF + 16];
+
+    let mut err_acc = _mm256_setzero_si256();
+    for block in 0..plan.chars.div_ceil(DEC_BLOCK_IN) {
+        let src = staged[DEC_BLOCK_IN * block..][..DEC_BLOCK_IN].as_ptr();
+        let raw = unsafe { _mm256_loadu_si256(src.cast::<__m256i>()) };
+        let (indices, err) = decode_vec_avx2(raw, &k);
+        err_acc = _mm256_or_si256(err_acc, err);
+        let out = bytes[DEC_BLOCK_OUT * block..][..DEC_PACK_LANE_OFF + 16].as_mut_ptr();
+        unsafe { store_packed_avx2(out, pack_avx2(indices, &k)) };
+    }
+
+    if _mm256_testz_si256(err_acc, err_acc) != 1 {
+        return Err(Error::InvalidCharacter);
+    }
+    plan.trailing?;
+    dst[..plan.out].copy_from_slice(&bytes[..plan.out]);
+    Ok(plan.out)
+}
+
+// Hex on the same instruction set.
+mod hex;
+pub(crate) use hex::{decode_hex_avx2, encode_hex_avx2};
+
+// Ignore-garbage compaction on the decoder's lookup.
+mod garbage;
+pub(crate) use garbage::compact_avx2;
+
+// Transcoding: Base64 to Base64 on the decoder's lookup and the encoder's
+// translate step, and Base64 to and from hex on both formats' steps.
+mod transcode;
+pub(crate) use transcode::{
+    base64_to_hex_avx2, hex_to_base64_avx2, remap_avx2, remap_in_place_avx2,
+};
+
 // Verification: Kani proofs, intrinsic models, model/hardware equivalence,
 // and the Miri + hardware coverage suites.
 #[cfg(any(kani, test))]
@@ -0,0 +1,289 @@
+//! AVX2 transcoding, on the Base64 and hex kernels' own steps:
+//!
+//! * Base64 -> Base64: the decoder's nibble lookup turns 32 characters into
+//!   their 6-bit values, validating as it goes, and the encoder's translate
+//!   step turns those straight into the target alphabet's characters.
+//! * Base64 -> hex: the decoder's lookup and pack step turn 32 characters
+//!   into 24 bytes, 12 at the bottom of each 128-bit lane, which the hex
+//!   encoder's nibble `vpshufb`s and in-lane unpacks expand to 48 digits,
+//!   stored a lane at a time.
+//! * hex -> Base64: the hex decoder's range checks and `vpmaddubsw` turn 48
+//!   digits into 24 bytes, and one `vpermd` both undoes the pack's lane
+//!   interleave and lays them out as the Base64 encoder's round expects.
+//!
+//! No bytes ever reach memory between the two formats. The remap loads each
+//! vector before its store, at the same offset, so one core serves both the
+//! copying and the in-place form. The sub-vector remainder of each goes to the
+//! scalar kernel, as do lenient sources.
+
+use super::hex::decode_vec_hex_avx2;
+use super::{
+    decode_constants_avx2, decode_vec_avx2, encode_constants_avx2, encode_vec_avx2, pack_avx2,
+    translate_avx2,
+};
+use crate::transcode::{remap_table_for, scalar};
+use crate::{Config, Error};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m128i, __m256i, _mm_loadu_si128, _mm_storel_epi64, _mm_storeu_si128, _mm256_and_si256,
+    _mm256_broadcastsi128_si256, _mm256_castsi256_si128, _mm256_extracti128_si256,
+    _mm256_inserti128_si256, _mm256_loadu_si256, _mm256_maddubs_epi16, _mm256_movemask_epi8,
+    _mm256_or_si256, _mm256_packus_epi16, _mm256_permutevar8x32_epi32, _mm256_set1_epi8,
+    _mm256_set1_epi16, _mm256_setr_epi32, _mm256_setzero_si256, _mm256_shuffle_epi8,
+    _mm256_srli_epi16, _mm256_storeu_si256, _mm256_testz_si256, _mm256_unpackhi_epi8,
+    _mm256_unpacklo_epi8,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m128i, __m256i, _mm_loadu_si128, _mm_storel_epi64, _mm_storeu_si128, _mm256_and_si256,
+    _mm256_broadcastsi128_si256, _mm256_castsi256_si128, _mm256_extracti128_si256,
+    _mm256_inserti128_si256, _mm256_loadu_si256, _mm256_maddubs_epi16, _mm256_movemask_epi8,
+    _mm256_or_si256, _mm256_packus_epi16, _mm256_permutevar8x32_epi32, _mm256_set1_epi8,
+    _mm256_set1_epi16, _mm256_setr_epi32, _mm256_setzero_si256, _mm256_shuffle_epi8,
+    _mm256_srli_epi16, _mm256_storeu_si256, _mm256_testz_si256, _mm256_unpackhi_epi8,
+    _mm256_unpacklo_epi8,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Characters a step remaps: one vector.
+pub(super) const REMAP_STEP: usize = 32;
+
+/// Remaps the whole vectors of the `len` characters at `src` to `dst`, which
+/// may be the same pointer, and returns how many characters that was.
+///
+/// # Safety
+/// The CPU must support AVX2; `src` must be valid for a `len`-byte read and
+/// `dst` for a `len`-byte write, and if they overlap they must be equal.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if any of them is outside `from`'s
+/// alphabet.
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn remap_raw(
+    from: &Config,
+    to: &Config,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> Result<usize, Error> {
+    let dk = unsafe { decode_constants_avx2(from) };
+    let ek = encode_constants_avx2(*to);
+
+    let mut bad = _mm256_setzero_si256();
+    let mut done = 0;
+    while done + REMAP_STEP <= len {
+        let chars = unsafe { _mm256_loadu_si256(src.add(done).cast::<__m256i>()) };
+        let (indices, err) = decode_vec_avx2(chars, &dk);
+        bad = _mm256_or_si256(bad, err);
+        let mapped = translate_avx2(indices, &ek);
+        unsafe { _mm256_storeu_si256(dst.add(done).cast::<__m256i>(), mapped) };
+        done += REMAP_STEP;
+    }
+
+    if _mm256_testz_si256(bad, bad) == 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    Ok(done)
+}
+
+/// Remaps `src`, in `from`'s alphabet, into `dst` in `to`'s, as
+/// [`scalar::remap`](crate::transcode::scalar::remap) does.
+///
+/// # Safety
+/// The CPU must support AVX2, and `dst` must be at least as long as `src`.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the source
+/// alphabet.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn remap_avx2(
+    from: &Config,
+    to: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(dst.len() >= src.len());
+    let table = remap_table_for(from, to);
+    if from.lenient {
+        return scalar::remap(table, src, dst);
+    }
+    let done = unsafe { remap_raw(from, to, src.as_ptr(), dst.as_mut_ptr(), src.len()) }?;
+    scalar::remap(table, &src[done..], &mut dst[done..])
+}
+
+/// Remaps `buf` where it lies, from `from`'s alphabet to `to`'s, as
+/// [`scalar::remap_in_place`](crate::transcode::scalar::remap_in_place) does.
+///
+/// # Safety
+/// The CPU must support AVX2.
+///
+/// # Errors
+/// As [`remap_avx2`].
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn remap_in_place_avx2(
+    from: &Config,
+    to: &Config,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    let table = remap_table_for(from, to);
+    if from.lenient {
+        return scalar::remap_in_place(table, buf);
+    }
+    let ptr = buf.as_mut_ptr();
+    let done = unsafe { remap_raw(from, to, ptr, ptr, buf.len()) }?;
+    scalar::remap_in_place(table, &mut buf[done..])
+}
+
+// --- Base64 -> hex ---
+
+/// Characters a Base64-to-hex step consumes: one vector.
+pub(super) const B64_HEX_IN: usize = 32;
+/// Digits a Base64-to-hex step writes: two per decoded byte.
+pub(super) const B64_HEX_OUT: usize = B64_HEX_IN / 4 * 6;
+/// Digits one 128-bit lane of a step decodes to, the first 16 of them in one
+/// full store and the rest in a half one.
+pub(super) const B64_HEX_LANE: usize = B64_HEX_OUT / 2;
+
+/// Decodes `src`, whole Base64 groups without padding, straight to hex in
+/// `dst`: `src.len() / 4 * 6` digits, in `upper` or lower case.
+///
+/// # Safety
+/// The CPU must support AVX2; `src.len()` must be a multiple of 4, and `dst`
+/// must hold at least `src.len() / 4 * 6` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside `config`'s
+/// alphabet, `'='` included. `dst` is then partly written.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn base64_to_hex_avx2(
+    config: &Config,
+    upper: bool,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(src.len().is_multiple_of(4) && dst.len() >= src.len() / 4 * 6);
+    if config.lenient {
+        return scalar::base64_to_hex(config, upper, src, dst);
+    }
+    let k = unsafe { decode_constants_avx2(config) };
+    let digits = _mm256_broadcastsi128_si256(unsafe {
+        _mm_loadu_si128(crate::hex::alphabet(upper).as_ptr().cast::<__m128i>())
+    });
+    let nibble = _mm256_set1_epi8(0x0F);
+
+    let len = src.len();
+    let chars = src.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = _mm256_setzero_si256();
+    let mut done = 0;
+    let mut written = 0;
+    while done + B64_HEX_IN <= len {
+        let v = unsafe { _mm256_loadu_si256(chars.add(done).cast::<__m256i>()) };
+        let (indices, err) = decode_vec_avx2(v, &k);
+        bad = _mm256_or_si256(bad, err);
+        let bytes = pack_avx2(indices, &k);
+
+        // Each lane's 12 bytes: the low unpack holds the digits of its first 8,
+        // the high one those of the last 4 in its bottom 8.
+        let hi = _mm256_shuffle_epi8(
+            digits,
+            _mm256_and_si256(_mm256_srli_epi16(bytes, 4), nibble),
+        );
+        let lo = _mm256_shuffle_epi8(digits, _mm256_and_si256(bytes, nibble));
+        let first = _mm256_unpacklo_epi8(hi, lo);
+        let rest = _mm256_unpackhi_epi8(hi, lo);
+        unsafe {
+            let lane = out.add(written);
+            _mm_storeu_si128(lane.cast::<__m128i>(), _mm256_castsi256_si128(first));
+            _mm_storel_epi64(lane.add(16).cast::<__m128i>(), _mm256_castsi256_si128(rest));
+            let lane = lane.add(B64_HEX_LANE);
+            _mm_storeu_si128(lane.cast::<__m128i>(), _mm256_extracti128_si256::<1>(first));
+            _mm_storel_epi64(
+                lane.add(16).cast::<__m128i>(),
+                _mm256_extracti128_si256::<1>(rest),
+            );
+        }
+        done += B64_HEX_IN;
+        written += B64_HEX_OUT;
+    }
+
+    if _mm256_testz_si256(bad, bad) == 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::base64_to_hex(config, upper, &src[done..], &mut dst[written..])
+}
+
+// --- hex -> Base64 ---
+
+/// Digits a hex-to-Base64 step consumes: 24 bytes' worth, a vector and a half.
+pub(super) const HEX_B64_IN: usize = 48;
+/// Characters a hex-to-Base64 step writes: one vector.
+pub(super) const HEX_B64_OUT: usize = 32;
+/// Digits the first load of a step holds, the rest going in a 16-byte one.
+pub(super) const HEX_VEC: usize = 32;
+
+/// Encodes hex `src`, a whole number of 6-digit groups in either case,
+/// straight to Base64 in `dst`: `src.len() / 6 * 4` characters of `config`'s
+/// alphabet.
+///
+/// # Safety
+/// The CPU must support AVX2; `src.len()` must be a multiple of 6, and `dst`
+/// must hold at least `src.len() / 6 * 4` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `src` holds anything but hex digits.
+/// `dst` is then partly written.
+#[target_feature(enable = "avx2")]
+pub(crate) unsafe fn hex_to_base64_avx2(
+    config: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(src.len().is_multiple_of(6) && dst.len() >= src.len() / 6 * 4);
+    let k = encode_constants_avx2(*config);
+    // `vpmaddubsw` weights: high nibble x16 plus low nibble x1, per digit pair.
+    let weights = _mm256_set1_epi16(0x0110);
+    // The second load's upper lane: digits that decode to zero, and pass.
+    let zeros = _mm256_set1_epi8(b'0'.cast_signed());
+    // After the pack, dwords 0, 1, 4, 5, 2, 3 hold bytes 0..24 in order; the
+    // encoder's round reads them from byte 4, behind a dword it ignores.
+    let layout = _mm256_setr_epi32(0, 0, 1, 4, 5, 2, 3, 3);
+
+    let len = src.len();
+    let digits = src.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut valid = _mm256_set1_epi8(-1);
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_B64_IN <= len {
+        let first = unsafe { _mm256_loadu_si256(digits.add(done).cast::<__m256i>()) };
+        let second = _mm256_inserti128_si256::<0>(zeros, unsafe {
+            _mm_loadu_si128(digits.add(done + HEX_VEC).cast::<__m128i>())
+        });
+        let (first, first_ok) = decode_vec_hex_avx2(first);
+        let (second, second_ok) = decode_vec_hex_avx2(second);
+        valid = _mm256_and_si256(valid, _mm256_and_si256(first_ok, second_ok));
+
+        let bytes = _mm256_packus_epi16(
+            _mm256_maddubs_epi16(first, weights),
+            _mm256_maddubs_epi16(second, weights),
+        );
+        let round = _mm256_permutevar8x32_epi32(bytes, layout);
+        unsafe {
+            _mm256_storeu_si256(
+                out.add(written).cast::<__m256i>(),
+                encode_vec_avx2(round, &k),
+            );
+        }
+        done += HEX_B64_IN;
+        written += HEX_B64_OUT;
+    }
+
+    if _mm256_movemask_epi8(valid) != -1 {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::hex_to_base64(config, &src[done..], &mut dst[written..])
+}
@@ -181,13 +181,121 @@ mod kani_verification_avx2 {
         );
     }
 
+    // Non-temporal encode model, mirroring `StreamLag`: every round after the
+    // first streams the 32 characters that start `lag = 16 - phase` bytes before
+    // it, the first round's cached store is the peeled head, and `finish`
+    // stores the last 16 cached.
+    use super::super::STITCH_WINDOW;
+
+    /// Width of one `_mm_stream_si128`, and the boundary it needs.
+    const STREAM_ALIGN: usize = 16;
+
+    /// A symbolic destination address and its `(phase, lag)`. Round starts
+    /// differ by 32, so one phase serves them all.
+    fn any_nt_dst(cap: usize) -> (usize, usize, usize) {
+        let dst_start: usize = kani::any();
+        kani::assume(dst_start <= usize::MAX - cap);
+        let phase = (STREAM_ALIGN - (dst_start + ENC_ROUND_OUT) % STREAM_ALIGN) % STREAM_ALIGN;
+        (dst_start, phase, STREAM_ALIGN - phase)
+    }
+
+    /// Base case: the head reaches the first streamed byte, with no gap for
+    /// any destination alignment.
+    #[kani::proof]
+    fn check_enc_nt_head() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+        let cap = enc_cap(len, padding);
+        let (_, phase, lag) = any_nt_dst(cap);
+
+        // The gate's `phase < 16`, which `align_offset` leaves to the caller.
+        assert!(phase < STREAM_ALIGN);
+        let (_, dst_off) = enc_state(1);
+        assert!(lag <= dst_off, "first stream store starts before dst");
+        assert!(dst_off - lag <= ENC_ROUND_OUT, "gap after the peeled head");
+        assert!(ENC_ROUND_OUT <= cap, "head store leaves output");
+    }
+
+    /// Inductive step: the stream store of an arbitrary round is aligned, in
+    /// bounds, and ends exactly where the next round's begins.
+    #[kani::proof]
+    fn check_enc_nt_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+
+        let rounds = any_enc_rounds(len);
+        let cap = enc_cap(len, padding);
+        let (dst_start, _, lag) = any_nt_dst(cap);
+
+        let done: usize = kani::any();
+        kani::assume(done >= 1 && done < rounds); // a steady-state round
+        let (_, dst_off) = enc_state(done);
+
+        let at = dst_off - lag;
+        let half: usize = kani::any();
+        kani::assume(half < 2);
+        assert_eq!(
+            (dst_start + at + STREAM_ALIGN * half) % STREAM_ALIGN,
+            0,
+            "stream store is misaligned"
+        );
+        assert!(at + ENC_ROUND_OUT <= cap, "stream store leaves output");
+
+        let (_, next_off) = enc_state(done + 1);
+        assert_eq!(at + ENC_ROUND_OUT, next_off - lag);
+    }
+
+    /// Exit case: `finish`'s cached store covers what the last stream store
+    /// left, and stays inside the rounds' own output.
+    #[kani::proof]
+    fn check_enc_nt_finish() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+
+        let rounds = any_enc_rounds(len);
+        let cap = enc_cap(len, padding);
+        let (_, _, lag) = any_nt_dst(cap);
+
+        // `end` is where the rounds stop and the scalar tail takes over.
+        let (_, end) = enc_state(rounds);
+        assert!(end <= cap);
+        assert!(STREAM_ALIGN <= end, "finish store starts before dst");
+        assert!(lag <= STREAM_ALIGN, "finish store misses the last bytes");
+    }
+
+    /// The `vpshufb` controls `StreamLag::new` loads from `STITCH_WINDOW`: for
+    /// every phase, each output byte comes from exactly one of the two
+    /// shuffles, and it is the character `lag` bytes back.
+    #[kani::proof]
+    fn check_enc_nt_stitch_controls() {
+        let phase: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(phase < STREAM_ALIGN && i < STREAM_ALIGN);
+
+        let take_prev = STITCH_WINDOW[phase + i];
+        let take_cur = STITCH_WINDOW[STREAM_ALIGN + phase + i];
+
+        // Byte `i` of a lane sits at `i - lag` from its half's start, which is
+        // byte `i + phase` of the half before while that is below 16.
+        if i + phase < STREAM_ALIGN {
+            assert_eq!(usize::from(take_prev), i + phase);
+            assert_eq!(take_cur, 0x80, "both shuffles write the byte");
+        } else {
+            assert_eq!(take_prev, 0x80, "both shuffles write the byte");
+            assert_eq!(usize::from(take_cur), i + phase - STREAM_ALIGN);
+        }
+    }
+
     // Decoder model, mirroring `decode_slice_avx2`.
     use super::super::{
         DEC_BLOCK_IN, DEC_BLOCK_IN as DEC_LOAD, DEC_BLOCK_OUT, DEC_LEAD, DEC_PACK_LANE_OFF,
         DEC_UNROLL,
     };
 
-    /// Bytes `pack_and_store!` touches (16 at `dst` + 16 at `dst.add(12)`),
+    /// Bytes `store_packed_avx2` touches (16 at `dst` + 16 at `dst.add(12)`),
     /// 4 wider than the 24 it advances.
     const DEC_STORE_SPAN: usize = DEC_PACK_LANE_OFF + 16;
     const DEC_WIDE_IN: usize = DEC_BLOCK_IN * DEC_UNROLL; // input bytes per wide-tier iteration
@@ -197,8 +305,9 @@ mod kani_verification_avx2 {
         TURBO_STANDARD.estimate_decoded_len(len)
     }
 
-    /// The `aligned_len_128` / `aligned_len_32` loop windows (from the
-    /// `saturating_sub(4)` margin that keeps a 32-byte load in bounds).
+    /// The input the wide tier's `wide_iters` passes cover, and the
+    /// `aligned_len_32` window (both from the `saturating_sub(4)` margin that
+    /// keeps a 32-byte load in bounds).
     fn dec_windows(len: usize) -> (usize, usize) {
         let safe = len.saturating_sub(DEC_LEAD);
         (safe - safe % DEC_WIDE_IN, safe - safe % DEC_BLOCK_IN)
@@ -216,9 +325,9 @@ mod kani_verification_avx2 {
         let i: usize = kani::any();
         kani::assume(i <= MAX_LEN / DEC_WIDE_IN);
         let (src_off, dst_off) = (DEC_WIDE_IN * i, DEC_WIDE_OUT * i);
-        kani::assume(src_off < aligned_wide); // guard `src < src_end_wide`
+        kani::assume(src_off < aligned_wide); // guard `i < wide_iters`
 
-        // Widest: the `i = DEC_UNROLL - 1` load and `pack_and_store!`.
+        // Widest: the `i = DEC_UNROLL - 1` load and `store_packed_avx2`.
         let last_src = DEC_BLOCK_IN * (DEC_UNROLL - 1);
         let last_dst = DEC_BLOCK_OUT * (DEC_UNROLL - 1);
         assert!(
@@ -237,6 +346,42 @@ mod kani_verification_avx2 {
         );
     }
 
+    /// The wide tier's non-temporal variant: over an arbitrary iteration, every
+    /// `_mm_stream_si128` lands on a 16-byte boundary of a 16-aligned `dst`,
+    /// and the three of each pair write exactly the pair's 24 + 24 bytes, so
+    /// the pass no longer overhangs at all.
+    #[kani::proof]
+    fn check_dec_wide_step_nt() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (aligned_wide, _) = dec_windows(len);
+        let cap = dec_cap(len);
+
+        // Any address the alignment gate lets through, with room for `cap`.
+        let dst_start: usize = kani::any();
+        kani::assume(dst_start % 16 == 0);
+        kani::assume(dst_start <= usize::MAX - cap);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / DEC_WIDE_IN);
+        let dst_off = DEC_WIDE_OUT * i;
+        kani::assume(DEC_WIDE_IN * i < aligned_wide); // guard `i < wide_iters`
+
+        // `stream_packed_pair_avx2` at `dst + 2 * 12 * pair`, 16 bytes per store.
+        let pair: usize = kani::any();
+        kani::assume(pair < DEC_UNROLL / 2);
+        let store: usize = kani::any();
+        kani::assume(store < 3);
+        let at = dst_off + 2 * DEC_BLOCK_OUT * pair + 16 * store;
+        assert_eq!((dst_start + at) % 16, 0, "stream store is misaligned");
+        assert!(at + 16 <= cap, "stream store leaves output");
+
+        // Three 16-byte stores per pair cover the pair's output exactly.
+        assert_eq!(3 * 16, 2 * DEC_BLOCK_OUT);
+        assert!(dst_off + DEC_WIDE_OUT <= cap, "wide pass leaves output");
+    }
+
     /// Inductive step for the decoder's single-vector tier, entered from
     /// wherever the wide tier stopped.
     #[kani::proof]
@@ -336,6 +481,7 @@ mod kani_verification_avx2 {
         let config = Config {
             url_safe,
             padding: true,
+            lenient: false,
         };
         let input: [u8; ENC_KERNEL_LEN] = kani::any();
 
@@ -397,6 +543,7 @@ mod kani_verification_avx2 {
         let config = Config {
             url_safe: kani::any(),
             padding: true,
+            lenient: false,
         };
         let input: [u8; DEC_KERNEL_LEN] = kani::any();
 
@@ -421,6 +568,296 @@ mod kani_verification_avx2 {
     }
 }
 
+#[cfg(kani)]
+mod kani_verification_hex_avx2 {
+    // Index proofs for the hex kernels in `super::hex`, in the same
+    // step/exit shape as the Base64 ones above. Hex strides are fixed
+    // multiples of two with no read-ahead, so the model is just the loop
+    // counters: after `i` steps the encoder has consumed `IN * i` bytes and
+    // written twice that, and the decoder the reverse.
+
+    use super::super::hex::{HEX_DEC_IN, HEX_DEC_OUT, HEX_ENC_IN, HEX_ENC_OUT};
+
+    /// Largest `len` considered: above `usize::MAX / 2` the `encoded_len`'s
+    /// `len * 2` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 2;
+
+    const _: () = assert!(
+        HEX_ENC_OUT == 2 * HEX_ENC_IN && HEX_DEC_IN == 2 * HEX_DEC_OUT,
+        "a hex step must keep the 2:1 ratio"
+    );
+
+    /// Inductive step for the encoder: the load and the stores of step `i`
+    /// stay inside `input` and the `2 * len` bytes of `dst`.
+    #[kani::proof]
+    fn check_avx2_hex_enc_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_ENC_IN);
+        let (done, written) = (HEX_ENC_IN * i, HEX_ENC_OUT * i);
+        kani::assume(done + HEX_ENC_IN <= len); // guard `done + IN <= len`
+
+        assert!(done + HEX_ENC_IN <= len, "encode load leaves input");
+        assert!(
+            written + HEX_ENC_OUT <= 2 * len,
+            "encode store leaves output"
+        );
+        assert_eq!(written, 2 * done);
+    }
+
+    /// Exit case: the scalar tail's `2 * (len - done)` digits end exactly at
+    /// the end of `dst`.
+    #[kani::proof]
+    fn check_avx2_hex_enc_tail_handoff() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_ENC_IN);
+        let (done, written) = (HEX_ENC_IN * i, HEX_ENC_OUT * i);
+        kani::assume(done <= len && len < done + HEX_ENC_IN); // loop exit
+
+        assert!(written <= 2 * len, "tail slice starts past dst");
+        assert_eq!(written + 2 * (len - done), 2 * len);
+    }
+
+    /// Inductive step for the decoder: the loads and the store of step `i`
+    /// stay inside `input` and the `len / 2` bytes of `dst`, odd `len`
+    /// included.
+    #[kani::proof]
+    fn check_avx2_hex_dec_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_DEC_IN);
+        let (done, written) = (HEX_DEC_IN * i, HEX_DEC_OUT * i);
+        kani::assume(done + HEX_DEC_IN <= len); // guard `done + IN <= len`
+
+        assert!(
+            written + HEX_DEC_OUT <= len / 2,
+            "decode store leaves output"
+        );
+        assert_eq!(2 * written, done);
+    }
+
+    /// Exit case: the scalar tail's `dst[written..]` is in range and holds
+    /// the `(len - done) / 2` bytes it writes.
+    #[kani::proof]
+    fn check_avx2_hex_dec_tail_handoff() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_DEC_IN);
+        let (done, written) = (HEX_DEC_IN * i, HEX_DEC_OUT * i);
+        kani::assume(done <= len && len < done + HEX_DEC_IN); // loop exit
+
+        assert!(written <= len / 2, "tail slice starts past dst");
+        assert_eq!(written + (len - done) / 2, len / 2);
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_garbage_avx2 {
+    // Proofs for the compaction kernel in `super::garbage`. Its stores start at
+    // `kept`, which never passes `done`, so they are bounded by the loads; the
+    // `vpshufb` control is built in safe code, and proven to gather exactly the
+    // kept lanes, in order.
+
+    use super::super::garbage::{COMPACT_GROUP, COMPACT_STEP, compact_control};
+
+    /// Lanes in a 128-bit half, the most a `vpshufb` control byte may index.
+    const XMM_LANES: usize = 16;
+
+    /// Inductive step: a full load, and group stores of 8 bytes that each start
+    /// at most at their group's first lane, so end inside the step, so inside
+    /// `dst`.
+    #[kani::proof]
+    fn check_avx2_compact_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        let kept: usize = kani::any();
+        kani::assume(i <= usize::MAX / COMPACT_STEP);
+        let done = COMPACT_STEP * i;
+        kani::assume(done <= usize::MAX - COMPACT_STEP && done + COMPACT_STEP <= len);
+        kani::assume(kept <= done); // one byte kept per byte read, at most
+
+        let group: usize = kani::any();
+        kani::assume(group < COMPACT_STEP / COMPACT_GROUP);
+        let before: usize = kani::any(); // kept by the step's earlier groups
+        kani::assume(before <= COMPACT_GROUP * group);
+
+        assert!(done + COMPACT_STEP <= len, "load leaves the input");
+        assert!(
+            kept + before + COMPACT_GROUP <= done + COMPACT_STEP,
+            "group store leaves the step"
+        );
+        assert!(
+            kept + before + COMPACT_GROUP <= len,
+            "store leaves the output"
+        );
+    }
+
+    /// Exit case: the scalar tail's `dst[kept..]` is in range and at least as
+    /// long as the `len - done` bytes it compacts.
+    #[kani::proof]
+    fn check_avx2_compact_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        let kept: usize = kani::any();
+        kani::assume(i <= usize::MAX / COMPACT_STEP);
+        let done = COMPACT_STEP * i;
+        kani::assume(done <= len && len - done < COMPACT_STEP); // loop exit
+        kani::assume(kept <= done);
+
+        assert!(kept <= len, "tail slice starts past dst");
+        assert!(len - kept >= len - done, "tail slice too short");
+    }
+
+    /// For every mask, each group's control lanes are its set bits' positions
+    /// within the 128-bit half, in order: the group stores gather exactly the
+    /// kept bytes. Building it indexes in bounds, which Kani checks along the
+    /// way.
+    #[kani::proof]
+    #[kani::unwind(33)]
+    fn check_avx2_compact_control() {
+        let keep: u32 = kani::any();
+        let control = compact_control(keep);
+        for (group, lanes) in control.iter().enumerate() {
+            let lanes = lanes.to_le_bytes();
+            let half = XMM_LANES * (group / 2);
+            let mut j = 0;
+            for lane in COMPACT_GROUP * group..COMPACT_GROUP * (group + 1) {
+                if (keep >> lane) & 1 == 1 {
+                    assert!(usize::from(lanes[j]) < XMM_LANES, "control lane {j}");
+                    assert_eq!(half + usize::from(lanes[j]), lane, "control lane {j}");
+                    j += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_transcode_avx2 {
+    // Index proofs for the kernels in `super::transcode`. The remap reads and
+    // writes the same offsets, so one bound covers both; the hex ones stride
+    // two lengths at once.
+
+    use super::super::transcode::{
+        B64_HEX_IN, B64_HEX_LANE, B64_HEX_OUT, HEX_B64_IN, HEX_B64_OUT, HEX_VEC, REMAP_STEP,
+    };
+
+    /// Bytes in a 128-bit lane, and so in a full lane store.
+    const XMM_LANES: usize = 16;
+
+    /// Inductive step: a full 32-byte load and store, both in bounds.
+    #[kani::proof]
+    fn check_avx2_remap_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(i <= usize::MAX / REMAP_STEP);
+        let done = REMAP_STEP * i;
+        kani::assume(done <= usize::MAX - REMAP_STEP && done + REMAP_STEP <= len);
+
+        assert!(done + REMAP_STEP <= len, "remap step leaves the buffer");
+    }
+
+    /// Exit case: the scalar tail's `src[done..]` and `dst[done..]` are in
+    /// range and cover the rest, less than a step.
+    #[kani::proof]
+    fn check_avx2_remap_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(i <= usize::MAX / REMAP_STEP);
+        let done = REMAP_STEP * i;
+        kani::assume(done <= len && len - done < REMAP_STEP); // loop exit
+
+        assert!(done <= len, "tail slice starts past the buffer");
+        assert!(len - done < REMAP_STEP);
+    }
+
+    /// Base64 -> hex step: a full load, and per lane a full and a half store
+    /// that end exactly at the step's 48 digits.
+    #[kani::proof]
+    fn check_avx2_b64_hex_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 4 == 0 && len <= usize::MAX / 6);
+        kani::assume(i <= len / B64_HEX_IN);
+        let done = B64_HEX_IN * i;
+        kani::assume(done + B64_HEX_IN <= len);
+        let written = B64_HEX_OUT * i;
+
+        assert!(done + B64_HEX_IN <= len, "load leaves the input");
+        let lane: usize = kani::any();
+        kani::assume(lane < 2);
+        let start = written + B64_HEX_LANE * lane;
+        assert!(
+            start + XMM_LANES + XMM_LANES / 2 <= written + B64_HEX_OUT,
+            "lane store leaves the step"
+        );
+        assert!(
+            written + B64_HEX_OUT <= len / 4 * 6,
+            "store leaves the output"
+        );
+    }
+
+    /// Base64 -> hex exit: the scalar tail's `dst[written..]` starts in range
+    /// and holds exactly the digits of the groups left.
+    #[kani::proof]
+    fn check_avx2_b64_hex_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 4 == 0 && len <= usize::MAX / 6);
+        kani::assume(i <= len / B64_HEX_IN);
+        let done = B64_HEX_IN * i;
+        kani::assume(done <= len && len - done < B64_HEX_IN); // loop exit
+        let written = B64_HEX_OUT * i;
+
+        assert!(written <= len / 4 * 6, "tail slice starts past dst");
+        assert_eq!(written + (len - done) / 4 * 6, len / 4 * 6);
+    }
+
+    /// hex -> Base64 step: a full load plus a 16-byte one, both in bounds, and
+    /// a full store.
+    #[kani::proof]
+    fn check_avx2_hex_b64_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 6 == 0);
+        kani::assume(i <= len / HEX_B64_IN);
+        let done = HEX_B64_IN * i;
+        kani::assume(done + HEX_B64_IN <= len);
+        let written = HEX_B64_OUT * i;
+
+        assert!(done + HEX_VEC + XMM_LANES <= len, "load leaves the input");
+        assert!(
+            written + HEX_B64_OUT <= len / 6 * 4,
+            "store leaves the output"
+        );
+    }
+
+    /// hex -> Base64 exit: the scalar tail's `dst[written..]` starts in range
+    /// and holds exactly the characters of the groups left.
+    #[kani::proof]
+    fn check_avx2_hex_b64_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 6 == 0);
+        kani::assume(i <= len / HEX_B64_IN);
+        let done = HEX_B64_IN * i;
+        kani::assume(done <= len && len - done < HEX_B64_IN); // loop exit
+        let written = HEX_B64_OUT * i;
+
+        assert!(written <= len / 6 * 4, "tail slice starts past dst");
+        assert_eq!(written + (len - done) / 6 * 4, len / 6 * 4);
+    }
+}
+
 /// Rust models of every AVX2 intrinsic the kernels use, for the Kani proofs.
 ///
 /// Each is a transcription of the `<operation>` pseudocode published in the Intel
@@ -774,7 +1211,7 @@ mod avx2_stub_equivalence {
 #[cfg(all(test, miri))]
 mod miri_avx2_coverage {
     use super::*;
-    use crate::simd::testutil::{check_decode, check_encode};
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode};
     use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE};
 
     /// Encode against the oracle and decode back, buffers sized as the public
@@ -806,6 +1243,7 @@ mod miri_avx2_coverage {
         let config = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         for &(len, tier) in TIER_LENGTHS {
             println!("standard: len {len} ({tier})");
@@ -818,6 +1256,7 @@ mod miri_avx2_coverage {
         let config = Config {
             url_safe: true,
             padding: true,
+            lenient: false,
         };
         for &(len, tier) in TIER_LENGTHS {
             println!("url-safe: len {len} ({tier})");
@@ -830,6 +1269,7 @@ mod miri_avx2_coverage {
         let config = Config {
             url_safe: false,
             padding: false,
+            lenient: false,
         };
         for &(len, tier) in TIER_LENGTHS {
             println!("no-pad: len {len} ({tier})");
@@ -837,6 +1277,23 @@ mod miri_avx2_coverage {
         }
     }
 
+    /// The short-input kernels at one and two rounds/blocks, into buffers of
+    /// the exact size.
+    #[test]
+    fn miri_avx2_short_inputs() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        for len in [1, 2, 3, 18, 19, 31, 48] {
+            check_encode(&config, &STANDARD, encode_short_avx2, len);
+        }
+        for len in [1, 2, 3, 22, 23, 24, 48] {
+            check_decode_exact(&config, &STANDARD, decode_short_avx2, len);
+        }
+    }
+
     /// Invalid bytes must be caught in every tier, wherever they sit in a
     /// pass. The kernel folds all validation into one accumulator checked after
     /// the loops, so a byte in the very last lane must still fail the call.
@@ -845,6 +1302,7 @@ mod miri_avx2_coverage {
         let config = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         let mut dst = [0u8; 512];
 
@@ -863,8 +1321,8 @@ mod miri_avx2_coverage {
     }
 }
 
-/// Exhaustive regression test for the nibble-lookup `lut_lo`/`lut_hi`/`lut_roll`
-/// tables in [`decode_constants_avx2`], guarding against transcription typos.
+/// Exhaustive regression test for the nibble-lookup `lut_lo`/`lut_hi`/
+/// `lut_shift`/`lut_roll` tables in [`decode_constants_avx2`], guarding against transcription typos.
 /// Runs on real AVX2 hardware under plain `cargo test` (no Kani toolchain);
 /// the tables were hand-derived (see [`DecodeConstantsAvx2`]).
 #[cfg(test)]
@@ -924,6 +1382,7 @@ mod avx2_decode_lut_exhaustive {
         let config = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         check_all_byte_values(&config);
     }
@@ -933,6 +1392,17 @@ mod avx2_decode_lut_exhaustive {
         let config = Config {
             url_safe: true,
             padding: true,
+            lenient: false,
+        };
+        check_all_byte_values(&config);
+    }
+
+    #[test]
+    fn avx2_lut_lenient_matches_scalar() {
+        let config = Config {
+            url_safe: false,
+            padding: false,
+            lenient: true,
         };
         check_all_byte_values(&config);
     }
@@ -940,13 +1410,14 @@ mod avx2_decode_lut_exhaustive {
 
 /// Covers the encoder's non-temporal store path, which needs an input at least
 /// [`NT_STORE_MIN_LEN`] long and so is out of reach for Miri (and for the length
-/// sweep below). The hazard it guards is `_mm_stream_si128`'s 16-byte alignment
-/// requirement, so the destinations here straddle the alignment gate: only the
-/// 0- and 16-shifted ones can take the path, and all must agree with the oracle.
+/// sweep below). Every destination takes the path, whatever its alignment, so
+/// the shifts here walk the stitching through a whole group, half a vector and
+/// both sides of a 16-byte boundary; all must agree with the oracle.
 #[cfg(test)]
 #[cfg(not(miri))]
 mod avx2_encode_non_temporal {
     use super::*;
+    use crate::simd::NT_STORE_MIN_LEN;
     use base64::Engine as _;
     use base64::engine::general_purpose::{STANDARD as REF_STANDARD, URL_SAFE as REF_URL_SAFE};
 
@@ -963,6 +1434,7 @@ mod avx2_encode_non_temporal {
                     Config {
                         url_safe: false,
                         padding: true,
+                        lenient: false,
                     },
                     &REF_STANDARD,
                 ),
@@ -970,12 +1442,13 @@ mod avx2_encode_non_temporal {
                     Config {
                         url_safe: true,
                         padding: true,
+                        lenient: false,
                     },
                     &REF_URL_SAFE,
                 ),
             ] {
                 let expected = oracle.encode(&input);
-                for shift in [0usize, 1, 8, 16] {
+                for shift in [0usize, 1, 2, 3, 4, 8, 15, 16] {
                     let mut dst = vec![0u8; expected.len() + shift];
                     unsafe { encode_slice_avx2(&config, &input, &mut dst[shift..]) };
                     assert_eq!(
@@ -990,6 +1463,132 @@ mod avx2_encode_non_temporal {
     }
 }
 
+/// The decoder's half of [`avx2_encode_non_temporal`]. The threshold is on the
+/// input, so the lengths here are decoded sizes whose encodings land at and
+/// past it; the destinations again straddle the 16-byte alignment gate.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod avx2_decode_non_temporal {
+    use super::*;
+    use crate::simd::NT_STORE_MIN_LEN;
+    use base64::Engine as _;
+    use base64::engine::general_purpose::{STANDARD as REF_STANDARD, URL_SAFE as REF_URL_SAFE};
+
+    #[test]
+    fn avx2_decode_above_non_temporal_threshold() {
+        for len in [NT_STORE_MIN_LEN / 4 * 3, NT_STORE_MIN_LEN / 4 * 3 + 4099] {
+            let input = crate::simd::testutil::bytes(len);
+
+            for (config, oracle) in [
+                (
+                    Config {
+                        url_safe: false,
+                        padding: true,
+                        lenient: false,
+                    },
+                    &REF_STANDARD,
+                ),
+                (
+                    Config {
+                        url_safe: true,
+                        padding: true,
+                        lenient: false,
+                    },
+                    &REF_URL_SAFE,
+                ),
+            ] {
+                let encoded = oracle.encode(&input);
+                for shift in [0usize, 1, 8, 16] {
+                    let mut dst = vec![0u8; len + shift];
+                    let n = unsafe {
+                        decode_slice_avx2(&config, encoded.as_bytes(), &mut dst[shift..])
+                    };
+                    assert_eq!(n, Ok(len), "len {len}, dst shift {shift}");
+                    assert!(
+                        dst[shift..] == input[..],
+                        "len {len}, url_safe {}, dst shift {shift}",
+                        config.url_safe
+                    );
+                }
+            }
+        }
+    }
+
+    /// A bad character deep inside the streamed region is still reported.
+    #[test]
+    fn avx2_decode_non_temporal_rejects_invalid() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        let mut encoded = REF_STANDARD
+            .encode(crate::simd::testutil::bytes(NT_STORE_MIN_LEN))
+            .into_bytes();
+        encoded[NT_STORE_MIN_LEN / 2 + 7] = b'*';
+        let mut dst = vec![0u8; NT_STORE_MIN_LEN];
+        let result = unsafe { decode_slice_avx2(&config, &encoded, &mut dst) };
+        assert_eq!(result, Err(Error::InvalidCharacter));
+    }
+}
+
+/// The short-input kernels against the oracle at every length they take, and
+/// against scalar on every malformed input of those lengths.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod avx2_short_inputs {
+    use super::*;
+    use crate::simd::testutil::{check_decode_short, check_encode_short};
+
+    #[test]
+    fn avx2_encode_short_all_lengths() {
+        check_encode_short(encode_short_avx2, ENC_SHORT_MAX);
+    }
+
+    #[test]
+    fn avx2_decode_short_all_lengths() {
+        check_decode_short(decode_short_avx2, DEC_SHORT_MAX);
+    }
+}
+
+/// The lenient tables: both alphabets mixed, padded and not, at every length
+/// across the tiers, and against scalar's verdict on malformed input.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod avx2_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient;
+
+    #[test]
+    fn avx2_decode_lenient_all_lengths() {
+        for len in (0..=300).chain([1_000, 4_097]) {
+            check_decode_lenient(decode_slice_avx2, len);
+        }
+    }
+
+    #[test]
+    fn avx2_decode_short_lenient() {
+        for len in (0..).take_while(|len: &usize| len.div_ceil(3) * 4 <= DEC_SHORT_MAX) {
+            check_decode_lenient(decode_short_avx2, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx2_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient;
+
+    /// The scalar tail alone, a single block, and a wide pass, each with a
+    /// scalar tail.
+    #[test]
+    fn miri_avx2_decode_lenient_tier_boundaries() {
+        for len in [0, 1, 2, 3, 27, 29, 195] {
+            check_decode_lenient(decode_slice_avx2, len);
+        }
+    }
+}
+
 /// Exhaustive length-boundary regression for the offset-load `encode_slice_avx2`
 /// rewrite: compares against the `base64` oracle at every length 0..=400,
 /// densely covering the `rounds = (len - 4) / 24` arithmetic and the 4-round
@@ -1006,6 +1605,7 @@ mod avx2_encode_length_sweep {
         let config = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         for len in 0..=400 {
             check_encode(&config, &REF_STANDARD, encode_slice_avx2, len);
@@ -1017,6 +1617,7 @@ mod avx2_encode_length_sweep {
         let config = Config {
             url_safe: true,
             padding: true,
+            lenient: false,
         };
         for len in 0..=400 {
             check_encode(&config, &REF_URL_SAFE, encode_slice_avx2, len);
@@ -1028,9 +1629,144 @@ mod avx2_encode_length_sweep {
         let config = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         for len in [1_000, 10_000, 100_000, 1_000_003] {
             check_encode(&config, &REF_STANDARD, encode_slice_avx2, len);
         }
     }
 }
+
+/// The hex kernels against the `hex` crate at every length across the first
+/// few steps and some larger ones, and against scalar's verdict on a bad
+/// digit in every position: full steps only, scalar tail only, and both.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod avx2_hex {
+    use super::*;
+    use crate::simd::testutil::{check_hex, check_hex_rejects};
+
+    #[test]
+    fn avx2_hex_all_lengths_0_to_300() {
+        for len in (0..=300).chain([1_000, 4_097, 100_003]) {
+            check_hex(encode_hex_avx2, decode_hex_avx2, len);
+        }
+    }
+
+    #[test]
+    fn avx2_hex_decode_rejects_invalid() {
+        for len in [2, 62, 64, 66, 128, 190] {
+            check_hex_rejects(decode_hex_avx2, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx2_hex {
+    use super::*;
+    use crate::simd::testutil::{check_hex, check_hex_rejects};
+
+    /// Lengths around each step boundary, plus several steps with a tail.
+    #[test]
+    fn miri_avx2_hex_tier_boundaries() {
+        for len in [0, 1, 31, 32, 33, 64, 100] {
+            check_hex(encode_hex_avx2, decode_hex_avx2, len);
+        }
+    }
+
+    #[test]
+    fn miri_avx2_hex_decode_rejects_invalid() {
+        check_hex_rejects(decode_hex_avx2, 66);
+    }
+}
+
+/// The compaction kernel against scalar at every length across the first few
+/// steps and some larger ones, at every density of garbage.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod avx2_garbage {
+    use super::*;
+    use crate::simd::testutil::check_compact;
+
+    #[test]
+    fn avx2_compact_all_lengths_0_to_300() {
+        for len in (0..=300).chain([1_000, 4_096, 4_097]) {
+            check_compact(compact_avx2, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx2_garbage {
+    use super::*;
+    use crate::simd::testutil::check_compact;
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_avx2_compact_tier_boundaries() {
+        for len in [0, 1, 31, 32, 33, 100] {
+            check_compact(compact_avx2, len);
+        }
+    }
+}
+
+/// The transcoding kernels against scalar at every length across the first
+/// few steps and some larger ones: the remap between every pair of alphabets,
+/// copying and in place, and Base64 to and from hex under every config.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod avx2_transcode {
+    use super::*;
+    use crate::simd::testutil::{check_base64_to_hex, check_hex_to_base64, check_remap};
+
+    #[test]
+    fn avx2_remap_all_lengths_0_to_100() {
+        for len in (0..=100).chain([144, 1_000]) {
+            check_remap(remap_avx2, remap_in_place_avx2, len);
+        }
+    }
+
+    #[test]
+    fn avx2_base64_to_hex_all_groups_0_to_40() {
+        for groups in (0..=40).chain([64, 250]) {
+            check_base64_to_hex(base64_to_hex_avx2, groups);
+        }
+    }
+
+    #[test]
+    fn avx2_hex_to_base64_all_groups_0_to_40() {
+        for groups in (0..=40).chain([64, 250]) {
+            check_hex_to_base64(hex_to_base64_avx2, groups);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx2_transcode {
+    use super::*;
+    use crate::simd::testutil::{check_base64_to_hex, check_hex_to_base64, check_remap};
+
+    /// The scalar tail alone, a full step, and a step plus a tail.
+    #[test]
+    fn miri_avx2_remap_tier_boundaries() {
+        for len in [0, 1, 23, 24, 25, 30] {
+            check_remap(remap_avx2, remap_in_place_avx2, len);
+        }
+    }
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_avx2_base64_to_hex_tier_boundaries() {
+        for groups in [0, 1, 7, 8, 9, 17] {
+            check_base64_to_hex(base64_to_hex_avx2, groups);
+        }
+    }
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_avx2_hex_to_base64_tier_boundaries() {
+        for groups in [0, 1, 7, 8, 9, 17] {
+            check_hex_to_base64(hex_to_base64_avx2, groups);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! AVX512-VBMI Base32, on the Base64 kernel's three VBMI instructions:
+//!
+//! * encode: a `vpermb` gives each qword one 5-byte group, byte-reversed so the
+//!   qword holds the group as a 40-bit integer, `vpmultishiftqb` pulls the
+//!   eight 5-bit fields out of it, and an alphabet `vpermb` maps all 64 at
+//!   once. `vpermb` only reads an index's low 6 bits, so the alphabet is
+//!   repeated twice and the multishift garbage above the field needs no
+//!   masking — the same trick as the Base64 and hex encoders.
+//! * decode: one `vpermi2b` over a 128-byte table maps and validates every
+//!   character; `vpmaddubsw` and `vpmaddwd` pack each group into two 20-bit
+//!   halves, a pair of qword shifts joins them, and a `vpermb` gathers the
+//!   bytes back into order.
+//!
+//! Both run their whole groups through one masked pass at the end, and hand the
+//! final, possibly partial or padded, group to the scalar kernel.
+
+use super::{lanes, zmm_multishift_epi64_epi8, zmm_permutex2var_epi8, zmm_permutexvar_epi8};
+use crate::Error;
+use crate::base32::{Alphabet, Config, decode_table, scalar};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m512i, _mm512_loadu_si512, _mm512_madd_epi16, _mm512_maddubs_epi16, _mm512_mask_loadu_epi8,
+    _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_or_si512,
+    _mm512_set1_epi8, _mm512_set1_epi16, _mm512_set1_epi32, _mm512_set1_epi64,
+    _mm512_setzero_si512, _mm512_slli_epi64, _mm512_srli_epi64, _mm512_storeu_si512,
+    _mm512_ternarylogic_epi32,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m512i, _mm512_loadu_si512, _mm512_madd_epi16, _mm512_maddubs_epi16, _mm512_mask_loadu_epi8,
+    _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_or_si512,
+    _mm512_set1_epi8, _mm512_set1_epi16, _mm512_set1_epi32, _mm512_set1_epi64,
+    _mm512_setzero_si512, _mm512_slli_epi64, _mm512_srli_epi64, _mm512_storeu_si512,
+    _mm512_ternarylogic_epi32,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Bytes in a Base32 group.
+pub(super) const B32_GROUP_IN: usize = 5;
+/// Characters in a Base32 group.
+pub(super) const B32_GROUP_OUT: usize = 8;
+/// Bytes an encode step consumes: eight groups, one per qword.
+pub(super) const B32_ENC_IN: usize = 8 * B32_GROUP_IN;
+/// Characters an encode step writes: a full ZMM.
+pub(super) const B32_ENC_OUT: usize = 8 * B32_GROUP_OUT;
+/// Characters a decode step consumes: a full ZMM.
+pub(super) const B32_DEC_IN: usize = B32_ENC_OUT;
+/// Bytes a decode step writes.
+pub(super) const B32_DEC_OUT: usize = B32_ENC_IN;
+
+/// `vpermb` control giving qword `q` the bytes of group `q` in reverse order,
+/// so it reads as the group's 40-bit big-endian value. The top three lanes of
+/// each qword are never looked at.
+#[allow(clippy::cast_possible_truncation)] // every index is < 40
+const VBMI_B32_SPREAD: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        let (q, k) = (i / 8, i % 8);
+        if k < B32_GROUP_IN {
+            t[i] = (B32_GROUP_IN * q + B32_GROUP_IN - 1 - k) as u8;
+        }
+        i += 1;
+    }
+    t
+};
+
+/// `vpmultishiftqb` controls: character `j` of a group is bits
+/// `35 - 5j .. 40 - 5j` of its 40-bit value.
+const VBMI_B32_MULTISHIFT: i64 = 0x0005_0A0F_1419_1E23;
+
+/// `vpermb` control undoing [`VBMI_B32_SPREAD`] on decode: byte `k` of group
+/// `q` sits in lane `8q + 4 - k`.
+#[allow(clippy::cast_possible_truncation)] // every index is < 64
+const VBMI_B32_GATHER: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < B32_DEC_OUT {
+        let (q, k) = (i / B32_GROUP_IN, i % B32_GROUP_IN);
+        t[i] = (B32_GROUP_OUT * q + B32_GROUP_IN - 1 - k) as u8;
+        i += 1;
+    }
+    t
+};
+
+/// Each alphabet twice over, so any 6-bit index finds its low 5 bits'.
+const fn spread_alphabet(alphabet: Alphabet) -> [u8; 64] {
+    let symbols = alphabet.symbols();
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        t[i] = symbols[i % 32];
+        i += 1;
+    }
+    t
+}
+
+/// The spread alphabets, indexed by `Alphabet as usize`.
+const VBMI_B32_ALPHABETS: [[u8; 64]; 4] = [
+    spread_alphabet(Alphabet::ALL[0]),
+    spread_alphabet(Alphabet::ALL[1]),
+    spread_alphabet(Alphabet::ALL[2]),
+    spread_alphabet(Alphabet::ALL[3]),
+];
+
+/// The ASCII half of an alphabet's reverse lookup, for `vpermi2b`. Invalid
+/// characters keep the scalar table's `0xFF`, whose top bit is all the
+/// validation reads.
+const fn vbmi_decode_table(alphabet: Alphabet) -> [u8; 128] {
+    let full = decode_table(alphabet);
+    let mut t = [0u8; 128];
+    let mut i = 0;
+    while i < 128 {
+        t[i] = full[i];
+        i += 1;
+    }
+    t
+}
+
+/// The reverse lookups, indexed by `Alphabet as usize`.
+const VBMI_B32_DECODE: [[u8; 128]; 4] = [
+    vbmi_decode_table(Alphabet::ALL[0]),
+    vbmi_decode_table(Alphabet::ALL[1]),
+    vbmi_decode_table(Alphabet::ALL[2]),
+    vbmi_decode_table(Alphabet::ALL[3]),
+];
+
+/// Eight groups, in the low 40 bytes of `raw` -> 64 characters.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn encode_vec_base32_vbmi(
+    raw: __m512i,
+    spread: __m512i,
+    shifts: __m512i,
+    symbols: __m512i,
+) -> __m512i {
+    let groups = unsafe { zmm_permutexvar_epi8(spread, raw) };
+    let fields = unsafe { zmm_multishift_epi64_epi8(shifts, groups) };
+    unsafe { zmm_permutexvar_epi8(fields, symbols) }
+}
+
+/// Encodes `input` into Base32 under `config`, writing
+/// [`encoded_len`](crate::base32::Engine::encoded_len) characters into `dst`.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must hold
+/// at least the encoded length of `input`.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn encode_base32_avx512_vbmi(config: &Config, input: &[u8], dst: &mut [u8]) {
+    let alphabet = &VBMI_B32_ALPHABETS[config.alphabet as usize];
+    let symbols = unsafe { _mm512_loadu_si512(alphabet.as_ptr().cast()) };
+    let spread = unsafe { _mm512_loadu_si512(VBMI_B32_SPREAD.as_ptr().cast()) };
+    let shifts = _mm512_set1_epi64(VBMI_B32_MULTISHIFT);
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut done = 0;
+    let mut written = 0;
+    while done + B32_ENC_IN <= len {
+        let raw = unsafe { _mm512_maskz_loadu_epi8(lanes(B32_ENC_IN), src.add(done).cast()) };
+        let chars = encode_vec_base32_vbmi(raw, spread, shifts, symbols);
+        unsafe { _mm512_storeu_si512(out.add(written).cast(), chars) };
+        done += B32_ENC_IN;
+        written += B32_ENC_OUT;
+    }
+
+    // Masked pass: the whole groups among the last `len - done < 40` bytes.
+    let take = (len - done) / B32_GROUP_IN * B32_GROUP_IN;
+    if take > 0 {
+        let out_len = take / B32_GROUP_IN * B32_GROUP_OUT;
+        let raw = unsafe { _mm512_maskz_loadu_epi8(lanes(take), src.add(done).cast()) };
+        let chars = encode_vec_base32_vbmi(raw, spread, shifts, symbols);
+        unsafe { _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(out_len), chars) };
+        done += take;
+        written += out_len;
+    }
+
+    // The final 0-4 bytes, and their padding.
+    scalar::encode_slice(config, &input[done..], &mut dst[written..]);
+}
+
+/// 64 characters -> eight groups, in the low 40 bytes of the result. ORs the
+/// characters and their values into `bad`: a character outside ASCII, or one
+/// the table rejects, sets its lane's top bit.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn decode_vec_base32_vbmi(
+    chars: __m512i,
+    lut_lo: __m512i,
+    lut_hi: __m512i,
+    gather: __m512i,
+    bad: &mut __m512i,
+) -> __m512i {
+    let values = unsafe { zmm_permutex2var_epi8(lut_lo, chars, lut_hi) };
+    *bad = _mm512_ternarylogic_epi32::<0xFE>(*bad, chars, values);
+
+    // Pairs of 5-bit values into 10-bit words, then pairs of words into a
+    // 20-bit dword per half-group.
+    let words = _mm512_maddubs_epi16(values, _mm512_set1_epi16(0x0120));
+    let halves = _mm512_madd_epi16(words, _mm512_set1_epi32(0x0001_0400));
+    // First half above the second: bits 0..40 of each qword are the group.
+    let groups = _mm512_or_si512(
+        _mm512_slli_epi64::<20>(halves),
+        _mm512_srli_epi64::<32>(halves),
+    );
+    unsafe { zmm_permutexvar_epi8(gather, groups) }
+}
+
+/// Decodes Base32 `input` under `config` into `dst`, returning the number of
+/// bytes written.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must hold
+/// at least [`estimate_decoded_len`](crate::base32::Engine::estimate_decoded_len)
+/// bytes.
+///
+/// # Errors
+/// As the scalar kernel: [`Error::InvalidLength`] or [`Error::InvalidCharacter`]
+/// if `input` is not valid Base32 under `config`.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn decode_base32_avx512_vbmi(
+    config: &Config,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    let len = input.len();
+    if len == 0 {
+        return Ok(0);
+    }
+    // Scalar checks this before it reads a character; so must we, to agree
+    // with it on which error a malformed input reports.
+    if config.padding && !len.is_multiple_of(B32_GROUP_OUT) {
+        return Err(Error::InvalidLength);
+    }
+
+    let table = &VBMI_B32_DECODE[config.alphabet as usize];
+    let lut_lo = unsafe { _mm512_loadu_si512(table.as_ptr().cast()) };
+    let lut_hi = unsafe { _mm512_loadu_si512(table.as_ptr().add(64).cast()) };
+    let gather = unsafe { _mm512_loadu_si512(VBMI_B32_GATHER.as_ptr().cast()) };
+
+    // Every group but the last, which may be partial or padded, as in scalar.
+    let body = (len - 1) / B32_GROUP_OUT * B32_GROUP_OUT;
+
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = _mm512_setzero_si512();
+    let mut done = 0;
+    let mut written = 0;
+    while done + B32_DEC_IN <= body {
+        let chars = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        let bytes = decode_vec_base32_vbmi(chars, lut_lo, lut_hi, gather, &mut bad);
+        unsafe {
+            _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(B32_DEC_OUT), bytes);
+        };
+        done += B32_DEC_IN;
+        written += B32_DEC_OUT;
+    }
+
+    // Masked pass: the rest of the body, with the lanes past it backfilled with
+    // a valid character so they decode cleanly.
+    let take = body - done;
+    if take > 0 {
+        let out_len = take / B32_GROUP_OUT * B32_GROUP_IN;
+        let fill = _mm512_set1_epi8(config.alphabet.symbols()[0].cast_signed());
+        let chars = unsafe { _mm512_mask_loadu_epi8(fill, lanes(take), src.add(done).cast()) };
+        let bytes = decode_vec_base32_vbmi(chars, lut_lo, lut_hi, gather, &mut bad);
+        unsafe { _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(out_len), bytes) };
+        done += take;
+        written += out_len;
+    }
+
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    let tail = scalar::decode_slice(config, &input[done..], &mut dst[written..])?;
+    Ok(written + tail)
+}
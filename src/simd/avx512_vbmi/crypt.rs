@@ -0,0 +1,241 @@
+//! AVX512-VBMI crypt(3) and bcrypt Base64, on the Base64 kernel's own
+//! encode and pack steps with per-scheme constants:
+//!
+//! * bcrypt is RFC 4648 bit order under another alphabet, so it runs the
+//!   Base64 gather, multishift and pack controls unchanged.
+//! * crypt(3) reads each group little-endian. That is the byte order the input
+//!   already has, so its gather keeps each qword's six bytes in place, and the
+//!   multishift takes the fields from the bottom up, at offsets 0, 6, .. 42.
+//!   Decode packs with the pair multipliers swapped (`even + odd * 64`, then
+//!   `lo + hi * 4096`), leaving each group's bytes in order at the bottom of
+//!   its dword, and the final `vpermb` just drops every fourth lane.
+//!
+//! Both run their whole groups through one masked pass at the end, and hand the
+//! final one or two bytes (or two or three characters) to the scalar kernel.
+
+use super::{
+    DecodeConstantsVbmi, EncodeConstantsVbmi, VBMI_ENCODE_GATHER, VBMI_MULTISHIFT, VBMI_PACK_L1,
+    VBMI_PACK_L2, VBMI_PACK_SHUFFLE, build_decode_lut, encode_vec_vbmi, lanes, pack_vec_vbmi,
+    zmm_permutex2var_epi8,
+};
+use crate::Error;
+use crate::crypt::{Scheme, scalar};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m512i, _mm512_loadu_si512, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8,
+    _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_set1_epi16,
+    _mm512_set1_epi32, _mm512_set1_epi64, _mm512_setzero_si512, _mm512_storeu_si512,
+    _mm512_ternarylogic_epi32,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m512i, _mm512_loadu_si512, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8,
+    _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_set1_epi16,
+    _mm512_set1_epi32, _mm512_set1_epi64, _mm512_setzero_si512, _mm512_storeu_si512,
+    _mm512_ternarylogic_epi32,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Bytes in a group.
+pub(super) const CRYPT_GROUP_IN: usize = 3;
+/// Characters in a group.
+pub(super) const CRYPT_GROUP_OUT: usize = 4;
+/// Bytes an encode step consumes: sixteen groups, two per qword.
+pub(super) const CRYPT_ENC_IN: usize = 16 * CRYPT_GROUP_IN;
+/// Characters an encode step writes: a full ZMM.
+pub(super) const CRYPT_ENC_OUT: usize = 16 * CRYPT_GROUP_OUT;
+/// Characters a decode step consumes: a full ZMM.
+pub(super) const CRYPT_DEC_IN: usize = CRYPT_ENC_OUT;
+/// Bytes a decode step writes.
+pub(super) const CRYPT_DEC_OUT: usize = CRYPT_ENC_IN;
+
+/// `vpermb` control giving qword `q` input bytes `6q .. 6q + 6` in their own
+/// order: two little-endian groups, in bits 0..23 and 24..47. The top two
+/// lanes of each qword are never looked at.
+#[allow(clippy::cast_possible_truncation)] // every index is < 48
+const VBMI_CRYPT_GATHER: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        let (q, k) = (i / 8, i % 8);
+        if k < 2 * CRYPT_GROUP_IN {
+            t[i] = (2 * CRYPT_GROUP_IN * q + k) as u8;
+        }
+        i += 1;
+    }
+    t
+};
+
+/// `vpmultishiftqb` controls: character `j` of a qword's two groups is bits
+/// `6j .. 6j + 6`, least significant first.
+const VBMI_CRYPT_MULTISHIFT: i64 = 0x2A24_1E18_120C_0600;
+
+/// `vpmaddubsw` multiplier folding each value pair into 12 bits, the first
+/// value low: `even + odd * 64`.
+const VBMI_CRYPT_PACK_L1: i16 = 0x4001;
+
+/// `vpmaddwd` multiplier folding each 12-bit pair into a 24-bit group, the
+/// first half low: `lo + hi * 4096`.
+const VBMI_CRYPT_PACK_L2: i32 = 0x1000_0001;
+
+/// `vpermb` control taking the low three bytes of each of the 16 packed dwords,
+/// already in output order. The top 16 lanes are unused.
+#[allow(clippy::cast_possible_truncation)] // every index is < 64
+const VBMI_CRYPT_PACK: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < CRYPT_DEC_OUT {
+        t[i] = (CRYPT_GROUP_OUT * (i / CRYPT_GROUP_IN) + i % CRYPT_GROUP_IN) as u8;
+        i += 1;
+    }
+    t
+};
+
+/// The 128-byte reverse lookups for `vpermi2b`, indexed by `Scheme as usize`.
+const VBMI_CRYPT_DECODE: [[u8; 128]; 2] = [
+    build_decode_lut(Scheme::ALL[0].symbols()),
+    build_decode_lut(Scheme::ALL[1].symbols()),
+];
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn encode_constants_crypt(scheme: Scheme) -> EncodeConstantsVbmi {
+    let (gather, shifts) = match scheme {
+        Scheme::Crypt => (&VBMI_CRYPT_GATHER, VBMI_CRYPT_MULTISHIFT),
+        Scheme::Bcrypt => (&VBMI_ENCODE_GATHER, VBMI_MULTISHIFT),
+    };
+    EncodeConstantsVbmi {
+        gather: unsafe { _mm512_loadu_si512(gather.as_ptr().cast()) },
+        shifts: _mm512_set1_epi64(shifts),
+        alphabet: unsafe { _mm512_loadu_si512(scheme.symbols().as_ptr().cast()) },
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn decode_constants_crypt(scheme: Scheme) -> DecodeConstantsVbmi {
+    let lut = &VBMI_CRYPT_DECODE[scheme as usize];
+    let (pack_l1, pack_l2, pack) = match scheme {
+        Scheme::Crypt => (VBMI_CRYPT_PACK_L1, VBMI_CRYPT_PACK_L2, unsafe {
+            _mm512_loadu_si512(VBMI_CRYPT_PACK.as_ptr().cast())
+        }),
+        Scheme::Bcrypt => (VBMI_PACK_L1, VBMI_PACK_L2, unsafe {
+            _mm512_loadu_si512(VBMI_PACK_SHUFFLE.as_ptr().cast())
+        }),
+    };
+    DecodeConstantsVbmi {
+        lut_lo: unsafe { _mm512_loadu_si512(lut.as_ptr().cast()) },
+        lut_hi: unsafe { _mm512_loadu_si512(lut.as_ptr().add(64).cast()) },
+        pack_l1: _mm512_set1_epi16(pack_l1),
+        pack_l2: _mm512_set1_epi32(pack_l2),
+        pack,
+    }
+}
+
+/// Encodes `input` under `scheme`, writing
+/// [`encoded_len`](crate::crypt::Engine::encoded_len) characters into `dst`.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must hold
+/// at least the encoded length of `input`.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn encode_crypt_avx512_vbmi(scheme: Scheme, input: &[u8], dst: &mut [u8]) {
+    let k = encode_constants_crypt(scheme);
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut done = 0;
+    let mut written = 0;
+    while done + CRYPT_ENC_IN <= len {
+        let raw = unsafe { _mm512_maskz_loadu_epi8(lanes(CRYPT_ENC_IN), src.add(done).cast()) };
+        let chars = encode_vec_vbmi(raw, &k);
+        unsafe { _mm512_storeu_si512(out.add(written).cast(), chars) };
+        done += CRYPT_ENC_IN;
+        written += CRYPT_ENC_OUT;
+    }
+
+    // Masked pass: the whole groups among the last `len - done < 48` bytes.
+    let take = (len - done) / CRYPT_GROUP_IN * CRYPT_GROUP_IN;
+    if take > 0 {
+        let out_len = take / CRYPT_GROUP_IN * CRYPT_GROUP_OUT;
+        let raw = unsafe { _mm512_maskz_loadu_epi8(lanes(take), src.add(done).cast()) };
+        let chars = encode_vec_vbmi(raw, &k);
+        unsafe { _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(out_len), chars) };
+        done += take;
+        written += out_len;
+    }
+
+    // The final 0-2 bytes.
+    scalar::encode_slice(scheme, &input[done..], &mut dst[written..]);
+}
+
+/// 64 characters -> sixteen groups, in the low 48 bytes of the result. ORs the
+/// characters and their values into `bad`: a character outside ASCII, or one
+/// the table rejects, sets its lane's top bit.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn decode_vec_crypt_vbmi(chars: __m512i, k: &DecodeConstantsVbmi, bad: &mut __m512i) -> __m512i {
+    let values = unsafe { zmm_permutex2var_epi8(k.lut_lo, chars, k.lut_hi) };
+    *bad = _mm512_ternarylogic_epi32::<0xFE>(*bad, chars, values);
+    pack_vec_vbmi(values, k)
+}
+
+/// Decodes `input` under `scheme` into `dst`, whose length must be exactly
+/// the decoded length of `input`.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, `input.len() % 4`
+/// must not be 1, and `dst` must hold
+/// [`decoded_len`](crate::crypt::Engine::decoded_len) bytes.
+///
+/// # Errors
+/// As the scalar kernel: [`Error::InvalidCharacter`] for a character outside
+/// the alphabet.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn decode_crypt_avx512_vbmi(
+    scheme: Scheme,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    let k = decode_constants_crypt(scheme);
+
+    // Every whole group; a final two or three characters go to scalar.
+    let len = input.len();
+    let body = len / CRYPT_GROUP_OUT * CRYPT_GROUP_OUT;
+
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = _mm512_setzero_si512();
+    let mut done = 0;
+    let mut written = 0;
+    while done + CRYPT_DEC_IN <= body {
+        let chars = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        let bytes = decode_vec_crypt_vbmi(chars, &k, &mut bad);
+        unsafe {
+            _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(CRYPT_DEC_OUT), bytes);
+        };
+        done += CRYPT_DEC_IN;
+        written += CRYPT_DEC_OUT;
+    }
+
+    // Masked pass: the rest of the body, with the lanes past it backfilled with
+    // a valid character so they decode cleanly.
+    let take = body - done;
+    if take > 0 {
+        let out_len = take / CRYPT_GROUP_OUT * CRYPT_GROUP_IN;
+        let fill = _mm512_set1_epi8(scheme.symbols()[0].cast_signed());
+        let chars = unsafe { _mm512_mask_loadu_epi8(fill, lanes(take), src.add(done).cast()) };
+        let bytes = decode_vec_crypt_vbmi(chars, &k, &mut bad);
+        unsafe { _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(out_len), bytes) };
+        done += take;
+        written += out_len;
+    }
+
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::decode_slice(scheme, &input[done..], &mut dst[written..])
+}
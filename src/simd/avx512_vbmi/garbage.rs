@@ -0,0 +1,127 @@
+//! AVX512-VBMI compaction for ignore-garbage decoding, on the decoder's own
+//! lookup.
+//!
+//! Each 64 bytes go through the decoder's `vpermi2b` and the sign bits of
+//! `input | index` give the same validity mask the decoder tests, one bit per
+//! lane; `'='` is added back by a compare. A block with nothing to drop is
+//! stored whole. Otherwise the mask's bytes index the shared table of the set
+//! bits' positions, eight lanes each, which line up into a `vpermb` control that
+//! packs the kept lanes to the front for one masked store.
+
+use super::{
+    DecodeConstantsVbmi, decode_constants_vbmi, lanes, zmm_permutex2var_epi8, zmm_permutexvar_epi8,
+};
+use crate::Config;
+use crate::simd::compact::{COMPACT_LANES, NEXT_GROUP};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m512i, _mm512_cmpeq_epi8_mask, _mm512_loadu_si512, _mm512_mask_storeu_epi8,
+    _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_storeu_si512,
+    _mm512_ternarylogic_epi32,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m512i, _mm512_cmpeq_epi8_mask, _mm512_loadu_si512, _mm512_mask_storeu_epi8,
+    _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_storeu_si512,
+    _mm512_ternarylogic_epi32,
+};
+
+/// Bytes a step compacts: a full ZMM. Imported by the Kani index proofs in
+/// `verify`.
+pub(super) const COMPACT_STEP: usize = 64;
+
+/// The `vpermb` control that packs the lanes `keep` selects to the front, in
+/// order. Each mask byte's lanes from the shared table, offset to its 8-lane
+/// group, are written where the previous ones left off, which is never past
+/// the group's own first lane, so every write fits. The control's lanes past
+/// the kept ones are whatever the last write left there, and the masked store
+/// ignores them.
+#[inline]
+#[allow(clippy::cast_possible_truncation)] // a mask byte, and a group index < 8
+pub(super) fn compact_control(keep: u64) -> [u8; COMPACT_STEP] {
+    let mut control = [0u8; COMPACT_STEP];
+    let mut at = 0;
+    for group in 0..8 {
+        let byte = (keep >> (8 * group)) as u8;
+        let lanes = COMPACT_LANES[usize::from(byte)] + NEXT_GROUP * group;
+        control[at..at + 8].copy_from_slice(&lanes.to_le_bytes());
+        at += byte.count_ones() as usize;
+    }
+    control
+}
+
+/// The lanes of `chars` to keep: a byte is bad iff bit 7 of `input | index`
+/// is set, as in the decoder, and `'='` is kept regardless.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn keep_mask(chars: __m512i, k: &DecodeConstantsVbmi, pad: __m512i) -> u64 {
+    let idx = unsafe { zmm_permutex2var_epi8(k.lut_lo, chars, k.lut_hi) };
+    let bad = _mm512_movepi8_mask(_mm512_ternarylogic_epi32::<0xFE>(chars, idx, idx));
+    !bad | _mm512_cmpeq_epi8_mask(chars, pad)
+}
+
+/// Stores the lanes of `chars` that `keep` selects to `dst`, packed, and
+/// returns how many there were.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must be
+/// valid for a write of `keep.count_ones()` bytes, or 64 if all are kept.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+unsafe fn store_kept(chars: __m512i, keep: u64, dst: *mut u8) -> usize {
+    if keep == u64::MAX {
+        unsafe { _mm512_storeu_si512(dst.cast(), chars) };
+        return COMPACT_STEP;
+    }
+    let n = keep.count_ones() as usize;
+    if n > 0 {
+        let control = compact_control(keep);
+        let control = unsafe { _mm512_loadu_si512(control.as_ptr().cast()) };
+        let packed = unsafe { zmm_permutexvar_epi8(control, chars) };
+        unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), lanes(n), packed) };
+    }
+    n
+}
+
+/// Copies the bytes of `src` that `config` decodes, plus `'='`, to the front
+/// of `dst`, and returns how many there were, as
+/// [`scalar::compact`](crate::garbage::scalar::compact) does.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must be at
+/// least as long as `src`.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn compact_avx512_vbmi(config: &Config, src: &[u8], dst: &mut [u8]) -> usize {
+    debug_assert!(dst.len() >= src.len());
+    let k = decode_constants_vbmi(config);
+    let pad = _mm512_set1_epi8(b'='.cast_signed());
+    let len = src.len();
+    let src = src.as_ptr();
+    let dst = dst.as_mut_ptr();
+
+    // Every store starts at or before the step's own offset and writes at most
+    // its 64 bytes, so it stays inside `dst[..done + 64]`.
+    let mut done = 0;
+    let mut kept = 0;
+    while done + COMPACT_STEP <= len {
+        let chars = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        kept += unsafe { store_kept(chars, keep_mask(chars, &k, pad), dst.add(kept)) };
+        done += COMPACT_STEP;
+    }
+
+    // Masked pass: the last `len - done < 64` bytes, with the lanes past them
+    // zeroed and masked off.
+    let take = len - done;
+    if take > 0 {
+        let chars = unsafe { _mm512_maskz_loadu_epi8(lanes(take), src.add(done).cast()) };
+        kept += unsafe {
+            store_kept(
+                chars,
+                keep_mask(chars, &k, pad) & lanes(take),
+                dst.add(kept),
+            )
+        };
+    }
+    kept
+}
@@ -0,0 +1,235 @@
+//! AVX512-VBMI hex, on the Base64 kernel's three VBMI instructions:
+//!
+//! * encode: a `vpermb` spreads 32 bytes to two copies each,
+//!   `vpmultishiftqb` pulls the high nibble out of the first copy and the low
+//!   nibble out of the second, and an alphabet `vpermb` maps all 64 at once —
+//!   3 ops per 64 digits. As in the Base64 encoder, `vpermb` only reads an
+//!   index's low 6 bits, so the alphabet is repeated four times and the
+//!   multishift garbage above the nibble needs no masking.
+//! * decode: one `vpermi2b` over a 128-byte table maps and validates every
+//!   digit, and `vpmaddubsw` + `vpmovwb` pack the pairs.
+//!
+//! Both run their remainder through one masked pass, so scalar only ever sees
+//! an odd final digit.
+
+use super::{lanes, zmm_multishift_epi64_epi8, zmm_permutex2var_epi8, zmm_permutexvar_epi8};
+use crate::Error;
+use crate::hex::scalar;
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m256i, __m512i, _mm256_storeu_si256, _mm512_cvtepi16_epi8, _mm512_loadu_si512,
+    _mm512_maddubs_epi16, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8,
+    _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_set1_epi16, _mm512_set1_epi64,
+    _mm512_setzero_si512, _mm512_storeu_si512, _mm512_ternarylogic_epi32, _mm512_zextsi256_si512,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m256i, __m512i, _mm256_storeu_si256, _mm512_cvtepi16_epi8, _mm512_loadu_si512,
+    _mm512_maddubs_epi16, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8,
+    _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_set1_epi16, _mm512_set1_epi64,
+    _mm512_setzero_si512, _mm512_storeu_si512, _mm512_ternarylogic_epi32, _mm512_zextsi256_si512,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Bytes an encode step consumes: the low half of a ZMM.
+pub(super) const HEX_ENC_IN: usize = 32;
+/// Digits an encode step writes: a full ZMM.
+pub(super) const HEX_ENC_OUT: usize = 2 * HEX_ENC_IN;
+/// Digits a decode step consumes: a full ZMM.
+pub(super) const HEX_DEC_IN: usize = 64;
+/// Bytes a decode step writes: a YMM.
+pub(super) const HEX_DEC_OUT: usize = HEX_DEC_IN / 2;
+
+/// `vpermb` control placing byte `i / 2` in lane `i`: two copies of each of
+/// the low 32 bytes.
+#[allow(clippy::cast_possible_truncation)] // `i / 2` is always < 32
+pub(super) const VBMI_HEX_SPREAD: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        t[i] = (i / 2) as u8;
+        i += 1;
+    }
+    t
+};
+
+/// `vpmultishiftqb` controls for a qword of spread bytes `[a,a,b,b,c,c,d,d]`:
+/// each even lane starts 4 bits into its own byte (the high nibble), each odd
+/// lane at its own byte (the low nibble).
+pub(super) const VBMI_HEX_MULTISHIFT: i64 = 0x3834_2824_1814_0804;
+
+/// The 16 digits four times over, so any 6-bit index finds its low nibble's.
+const fn spread_alphabet(alphabet: &[u8; 16]) -> [u8; 64] {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        t[i] = alphabet[i % 16];
+        i += 1;
+    }
+    t
+}
+
+pub(super) const VBMI_HEX_LOWER: [u8; 64] = spread_alphabet(crate::hex::alphabet(false));
+pub(super) const VBMI_HEX_UPPER: [u8; 64] = spread_alphabet(crate::hex::alphabet(true));
+
+/// 128-byte reverse lookup for `vpermi2b`: ASCII 0-127 -> nibble, `0x80` for
+/// anything but a digit of either case.
+#[allow(clippy::cast_possible_truncation)] // `i` is always < 16, fits in u8
+pub(super) const VBMI_HEX_DECODE: [u8; 128] = {
+    let lower = crate::hex::alphabet(false);
+    let upper = crate::hex::alphabet(true);
+    let mut t = [0x80u8; 128];
+    let mut i = 0;
+    while i < 16 {
+        t[lower[i] as usize] = i as u8;
+        t[upper[i] as usize] = i as u8;
+        i += 1;
+    }
+    t
+};
+
+/// The low 32 bytes of `raw` -> 64 digits.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(super) fn encode_vec_hex_vbmi(
+    raw: __m512i,
+    spread: __m512i,
+    shifts: __m512i,
+    digits: __m512i,
+) -> __m512i {
+    let pairs = unsafe { zmm_permutexvar_epi8(spread, raw) };
+    let nibbles = unsafe { zmm_multishift_epi64_epi8(shifts, pairs) };
+    unsafe { zmm_permutexvar_epi8(nibbles, digits) }
+}
+
+/// Encodes `input` as hex, writing `2 * input.len()` digits into `dst`.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must hold
+/// at least `2 * input.len()` bytes.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn encode_hex_avx512_vbmi(upper: bool, input: &[u8], dst: &mut [u8]) {
+    debug_assert!(dst.len() >= 2 * input.len());
+    let digits = if upper {
+        &VBMI_HEX_UPPER
+    } else {
+        &VBMI_HEX_LOWER
+    };
+    let digits = unsafe { _mm512_loadu_si512(digits.as_ptr().cast()) };
+    let spread = unsafe { _mm512_loadu_si512(VBMI_HEX_SPREAD.as_ptr().cast()) };
+    let shifts = _mm512_set1_epi64(VBMI_HEX_MULTISHIFT);
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_ENC_IN <= len {
+        let raw = unsafe { _mm512_maskz_loadu_epi8(lanes(HEX_ENC_IN), src.add(done).cast()) };
+        let chars = encode_vec_hex_vbmi(raw, spread, shifts, digits);
+        unsafe { _mm512_storeu_si512(out.add(written).cast(), chars) };
+        done += HEX_ENC_IN;
+        written += HEX_ENC_OUT;
+    }
+
+    // Masked pass: the last `len - done < 32` bytes, if any.
+    let take = len - done;
+    if take > 0 {
+        let raw = unsafe { _mm512_maskz_loadu_epi8(lanes(take), src.add(done).cast()) };
+        let chars = encode_vec_hex_vbmi(raw, spread, shifts, digits);
+        unsafe { _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(2 * take), chars) };
+    }
+}
+
+/// 64 digits -> 32 bytes, one in the low byte of each word, OR-ing the digits
+/// and their nibbles into `bad`: a digit outside ASCII, or one the table
+/// rejects, sets its lane's top bit.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(super) fn decode_pairs_hex_vbmi(
+    chars: __m512i,
+    lut_lo: __m512i,
+    lut_hi: __m512i,
+    bad: &mut __m512i,
+) -> __m512i {
+    let nibbles = unsafe { zmm_permutex2var_epi8(lut_lo, chars, lut_hi) };
+    *bad = _mm512_ternarylogic_epi32::<0xFE>(*bad, chars, nibbles);
+    // High nibble x16 plus low nibble x1.
+    _mm512_maddubs_epi16(nibbles, _mm512_set1_epi16(0x0110))
+}
+
+/// 64 digits -> 32 bytes, validating as [`decode_pairs_hex_vbmi`] does.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn decode_vec_hex_vbmi(
+    chars: __m512i,
+    lut_lo: __m512i,
+    lut_hi: __m512i,
+    bad: &mut __m512i,
+) -> __m256i {
+    // One byte per word.
+    _mm512_cvtepi16_epi8(decode_pairs_hex_vbmi(chars, lut_lo, lut_hi, bad))
+}
+
+/// Decodes hex `input`, in either case, into the first `input.len() / 2`
+/// bytes of `dst`.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must hold
+/// at least `input.len() / 2` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `input` holds anything but hex
+/// digits, and [`Error::InvalidLength`] if it is otherwise valid but of odd
+/// length.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn decode_hex_avx512_vbmi(input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    debug_assert!(dst.len() >= input.len() / 2);
+    let lut_lo = unsafe { _mm512_loadu_si512(VBMI_HEX_DECODE.as_ptr().cast()) };
+    let lut_hi = unsafe { _mm512_loadu_si512(VBMI_HEX_DECODE.as_ptr().add(64).cast()) };
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = _mm512_setzero_si512();
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_DEC_IN <= len {
+        let chars = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        let bytes = decode_vec_hex_vbmi(chars, lut_lo, lut_hi, &mut bad);
+        unsafe { _mm256_storeu_si256(out.add(written).cast::<__m256i>(), bytes) };
+        done += HEX_DEC_IN;
+        written += HEX_DEC_OUT;
+    }
+
+    // Masked pass: the remaining whole pairs, with the lanes past them
+    // backfilled with '0' so they decode cleanly.
+    let take = (len - done) & !1;
+    if take > 0 {
+        let chars = unsafe {
+            _mm512_mask_loadu_epi8(
+                _mm512_set1_epi8(b'0'.cast_signed()),
+                lanes(take),
+                src.add(done).cast(),
+            )
+        };
+        let bytes = decode_vec_hex_vbmi(chars, lut_lo, lut_hi, &mut bad);
+        unsafe {
+            _mm512_mask_storeu_epi8(
+                out.add(written).cast::<i8>(),
+                lanes(take / 2),
+                _mm512_zextsi256_si512(bytes),
+            );
+        }
+        done += take;
+        written += take / 2;
+    }
+
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    // At most one unpaired digit is left, which scalar rejects.
+    scalar::decode_slice(&input[done..], &mut dst[written..])
+}
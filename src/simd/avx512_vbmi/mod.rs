@@ -18,26 +18,26 @@
 use crate::{Config, Error};
 
 #[cfg(target_arch = "x86")]
-use std::arch::x86::{
-    __m512i, _mm512_loadu_si512, _mm512_madd_epi16, _mm512_maddubs_epi16, _mm512_mask_loadu_epi8,
-    _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_set1_epi8,
-    _mm512_set1_epi16, _mm512_set1_epi32, _mm512_set1_epi64, _mm512_setzero_si512,
-    _mm512_storeu_si512, _mm512_ternarylogic_epi32,
+use core::arch::x86::{
+    __m512i, _mm_sfence, _mm512_loadu_si512, _mm512_madd_epi16, _mm512_maddubs_epi16,
+    _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8, _mm512_movepi8_mask,
+    _mm512_set1_epi8, _mm512_set1_epi16, _mm512_set1_epi32, _mm512_set1_epi64,
+    _mm512_setzero_si512, _mm512_storeu_si512, _mm512_stream_si512, _mm512_ternarylogic_epi32,
 };
 #[cfg(target_arch = "x86_64")]
-use std::arch::x86_64::{
-    __m512i, _mm512_loadu_si512, _mm512_madd_epi16, _mm512_maddubs_epi16, _mm512_mask_loadu_epi8,
-    _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8, _mm512_movepi8_mask, _mm512_set1_epi8,
-    _mm512_set1_epi16, _mm512_set1_epi32, _mm512_set1_epi64, _mm512_setzero_si512,
-    _mm512_storeu_si512, _mm512_ternarylogic_epi32,
+use core::arch::x86_64::{
+    __m512i, _mm_sfence, _mm512_loadu_si512, _mm512_madd_epi16, _mm512_maddubs_epi16,
+    _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8, _mm512_maskz_loadu_epi8, _mm512_movepi8_mask,
+    _mm512_set1_epi8, _mm512_set1_epi16, _mm512_set1_epi32, _mm512_set1_epi64,
+    _mm512_setzero_si512, _mm512_storeu_si512, _mm512_stream_si512, _mm512_ternarylogic_epi32,
 };
 
 #[cfg(all(not(miri), target_arch = "x86"))]
-use std::arch::x86::{
+use core::arch::x86::{
     _mm512_multishift_epi64_epi8, _mm512_permutex2var_epi8, _mm512_permutexvar_epi8,
 };
 #[cfg(all(not(miri), target_arch = "x86_64"))]
-use std::arch::x86_64::{
+use core::arch::x86_64::{
     _mm512_multishift_epi64_epi8, _mm512_permutex2var_epi8, _mm512_permutexvar_epi8,
 };
 
@@ -53,6 +53,13 @@ const VBMI_ENCODE_URL_SAFE: [u8; 64] =
 /// index, `0xFF` for invalid.
 const VBMI_DECODE_STANDARD: [u8; 128] = build_decode_lut(&VBMI_ENCODE_STANDARD);
 const VBMI_DECODE_URL_SAFE: [u8; 128] = build_decode_lut(&VBMI_ENCODE_URL_SAFE);
+/// Both alphabets merged, for `LENIENT`: the Standard LUT plus `-` and `_`.
+const VBMI_DECODE_LENIENT: [u8; 128] = {
+    let mut lut = VBMI_DECODE_STANDARD;
+    lut[b'-' as usize] = 62;
+    lut[b'_' as usize] = 63;
+    lut
+};
 
 #[allow(clippy::cast_possible_truncation)] // `i` is always < 64, fits in u8
 const fn build_decode_lut(alphabet: &[u8; 64]) -> [u8; 128] {
@@ -126,6 +133,48 @@ const VBMI_PACK_SHUFFLE: [i32; 16] = [
     0,
 ];
 
+/// `vpermt2b` controls for the decoder's non-temporal quad tier, which turn
+/// four 48-byte results `p0..p3` (each in the low lanes of its vector) into
+/// three contiguous 64-byte vectors: `p0[..48] ++ p1[..16]`, then
+/// `p1[16..] ++ p2[..32]`, then `p2[32..] ++ p3`. Control `j` indexes the pair
+/// `(p_j, p_j+1)`, where bit 6 selects the second.
+#[allow(clippy::cast_possible_truncation)] // every entry is below 128
+const fn build_stitch() -> [[u8; 64]; 3] {
+    let mut t = [[0u8; 64]; 3];
+    let mut j = 0;
+    while j < 3 {
+        // Output vector `j` starts at byte `64 * j` of the 192-byte run, which
+        // is `16 * (j + 1)` bytes into `p_j`'s 48 (or past them, into `p_j+1`).
+        let skip = 16 * j;
+        let mut b = 0;
+        while b < 64 {
+            t[j][b] = if skip + b < 48 {
+                (skip + b) as u8
+            } else {
+                (64 + skip + b - 48) as u8
+            };
+            b += 1;
+        }
+        j += 1;
+    }
+    t
+}
+const VBMI_STITCH: [[u8; 64]; 3] = build_stitch();
+
+/// `vpermt2b` controls for [`StreamLagVbmi`], as one window: the 64 bytes at
+/// `phase` take a result's bytes from `phase` up, then the next result's below
+/// `phase`.
+#[allow(clippy::cast_possible_truncation)] // every entry is below 128
+const STREAM_WINDOW: [u8; 128] = {
+    let mut w = [0u8; 128];
+    let mut i = 0;
+    while i < 128 {
+        w[i] = i as u8;
+        i += 1;
+    }
+    w
+};
+
 // --- Stride constants ---
 //
 // The Kani index proofs in `verify` reason over this same arithmetic
@@ -231,16 +280,16 @@ unsafe fn zmm_multishift_epi64_epi8(a: __m512i, b: __m512i) -> __m512i {
 
 // --- VBMI encoder ---
 
-#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
-pub(crate) unsafe fn encode_slice_avx512_vbmi(config: &Config, input: &[u8], dst_slice: &mut [u8]) {
-    let mut src = input.as_ptr();
-    let dst_start = dst_slice.as_mut_ptr();
-    let mut dst = dst_start;
-    let mut rem = input.len();
-
-    let gather = unsafe { _mm512_loadu_si512(VBMI_ENCODE_GATHER.as_ptr().cast()) };
-    let shifts = _mm512_set1_epi64(VBMI_MULTISHIFT);
+/// Encoder constants, loaded once per call.
+struct EncodeConstantsVbmi {
+    gather: __m512i,
+    shifts: __m512i,
+    alphabet: __m512i,
+}
 
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn encode_constants_vbmi(config: &Config) -> EncodeConstantsVbmi {
     // Full 64-byte alphabet in one ZMM; vpermb selects by each index's low 6
     // bits, so the garbage in each index's top 2 bits needs no masking.
     let alphabet = if config.url_safe {
@@ -248,16 +297,116 @@ pub(crate) unsafe fn encode_slice_avx512_vbmi(config: &Config, input: &[u8], dst
     } else {
         unsafe { _mm512_loadu_si512(VBMI_ENCODE_STANDARD.as_ptr().cast()) }
     };
+    EncodeConstantsVbmi {
+        gather: unsafe { _mm512_loadu_si512(VBMI_ENCODE_GATHER.as_ptr().cast()) },
+        shifts: _mm512_set1_epi64(VBMI_MULTISHIFT),
+        alphabet,
+    }
+}
+
+/// 48 input bytes in a ZMM -> 64 output characters, in three port-5 ops.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn encode_vec_vbmi(raw: __m512i, k: &EncodeConstantsVbmi) -> __m512i {
+    let g = unsafe { zmm_permutexvar_epi8(k.gather, raw) };
+    let indices = unsafe { zmm_multishift_epi64_epi8(k.shifts, g) };
+    unsafe { zmm_permutexvar_epi8(indices, k.alphabet) }
+}
+
+/// The non-temporal tiers' store state, the AVX2 encoder's `StreamLag` a
+/// whole vector wide.
+///
+/// Results land at `64 * n` from wherever the destination starts, which an
+/// allocator rarely puts on a 64-byte boundary, so they cannot be streamed as
+/// they are. Instead each streams the 64 bytes that start `64 - phase` bytes
+/// before it, where `phase` is the distance from a result's start to the next
+/// boundary: the previous result's last bytes, stitched to this one's first by
+/// one `vpermt2b`, land exactly on one. The caller's cached head covers
+/// everything before the first streamed byte, and [`StreamLagVbmi::finish`]
+/// covers everything after the last.
+struct StreamLagVbmi {
+    window: __m512i,
+    prev: __m512i,
+    lag: usize,
+}
+
+impl StreamLagVbmi {
+    /// `head` is the 64 bytes just before the first result to stream, already
+    /// stored cached.
+    ///
+    /// # Safety
+    /// `phase` must be below 64.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+    unsafe fn new(head: __m512i, phase: usize) -> Self {
+        Self {
+            window: unsafe { _mm512_loadu_si512(STREAM_WINDOW.as_ptr().add(phase).cast()) },
+            prev: head,
+            lag: 64 - phase,
+        }
+    }
+
+    /// Streams the 64 bytes that start `lag` bytes before the result at
+    /// `dst`, whose bytes are `v`.
+    ///
+    /// # Safety
+    /// `dst.sub(self.lag)` must be valid for a 64-byte write.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+    unsafe fn stream(&mut self, dst: *mut u8, v: __m512i) {
+        let out = unsafe { zmm_permutex2var_epi8(self.prev, self.window, v) };
+        self.prev = v;
+        unsafe { _mm512_stream_si512(dst.sub(self.lag).cast(), out) };
+    }
+
+    /// Stores the bytes left after the last streamed one, cached, given `end`,
+    /// where the last result ends, then fences the streamed ones.
+    ///
+    /// # Safety
+    /// `end.sub(64)` must be valid for a 64-byte write.
+    #[inline]
+    #[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+    unsafe fn finish(&self, end: *mut u8) {
+        // The last `lag` lanes of the last result; the masked store touches
+        // nothing below them.
+        let tail = u64::MAX << (64 - self.lag);
+        unsafe { _mm512_mask_storeu_epi8(end.sub(64).cast::<i8>(), tail, self.prev) };
+        // Non-temporal stores are not ordered against the caller's later loads.
+        _mm_sfence();
+    }
+}
 
-    /// 48 input bytes in a ZMM -> 64 output characters, in three port-5 ops.
-    macro_rules! encode_vec {
-        ($v:expr) => {{
-            let raw = $v;
-            let g = unsafe { zmm_permutexvar_epi8(gather, raw) };
-            let indices = unsafe { zmm_multishift_epi64_epi8(shifts, g) };
-            unsafe { zmm_permutexvar_epi8(indices, alphabet) }
+/// Runs the encoder's quad and single tiers over the `rem` bytes at `src`,
+/// and returns how many 48-byte vectors they encoded. Streamed through `lag`
+/// when it is given, stored cached otherwise.
+///
+/// # Safety
+/// `src` must be valid for a `rem`-byte read and `dst` for a write of the
+/// encoded size of `rem` bytes. With a `lag`, its `phase` must be `dst`'s
+/// distance to a 64-byte boundary, and the 64 bytes before `dst` must be
+/// writable too.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+unsafe fn encode_full_vbmi(
+    src: *const u8,
+    dst: *mut u8,
+    rem: usize,
+    k: &EncodeConstantsVbmi,
+    mut lag: Option<StreamLagVbmi>,
+) -> usize {
+    let mut src = src;
+    let mut dst = dst;
+    let mut rem = rem;
+    let mut vecs = 0;
+
+    macro_rules! store {
+        ($dst:expr, $v:expr) => {{
+            match lag.as_mut() {
+                Some(lag) => unsafe { lag.stream($dst, $v) },
+                None => unsafe { _mm512_storeu_si512($dst.cast(), $v) },
+            }
         }};
     }
+
     macro_rules! load_48 {
         ($off:expr) => {{ unsafe { _mm512_loadu_si512(src.add($off).cast()) } }};
     }
@@ -265,36 +414,70 @@ pub(crate) unsafe fn encode_slice_avx512_vbmi(config: &Config, input: &[u8], dst
     // Quad tier: 192 input bytes -> 256 output. The last load starts 144 bytes
     // in and reads 64, so 208 <= 256 bytes are always in bounds.
     while rem >= ENC_QUAD_MIN {
-        let r0 = encode_vec!(load_48!(0));
-        let r1 = encode_vec!(load_48!(ENC_VEC_IN));
-        let r2 = encode_vec!(load_48!(2 * ENC_VEC_IN));
-        let r3 = encode_vec!(load_48!(3 * ENC_VEC_IN));
-        unsafe { _mm512_storeu_si512(dst.cast(), r0) };
-        unsafe { _mm512_storeu_si512(dst.add(ENC_VEC_OUT).cast(), r1) };
-        unsafe { _mm512_storeu_si512(dst.add(2 * ENC_VEC_OUT).cast(), r2) };
-        unsafe { _mm512_storeu_si512(dst.add(3 * ENC_VEC_OUT).cast(), r3) };
+        let r0 = encode_vec_vbmi(load_48!(0), k);
+        let r1 = encode_vec_vbmi(load_48!(ENC_VEC_IN), k);
+        let r2 = encode_vec_vbmi(load_48!(2 * ENC_VEC_IN), k);
+        let r3 = encode_vec_vbmi(load_48!(3 * ENC_VEC_IN), k);
+        store!(dst, r0);
+        store!(dst.add(ENC_VEC_OUT), r1);
+        store!(dst.add(2 * ENC_VEC_OUT), r2);
+        store!(dst.add(3 * ENC_VEC_OUT), r3);
         src = unsafe { src.add(ENC_QUAD_IN) };
         dst = unsafe { dst.add(ENC_QUAD_OUT) };
         rem -= ENC_QUAD_IN;
+        vecs += ENC_UNROLL;
     }
 
     // Single tier: 48 input bytes -> 64 output. A plain load reads 64 bytes to
     // consume 48, so it needs 64 to exist.
     while rem >= ENC_SINGLE_MIN {
-        let r = encode_vec!(load_48!(0));
-        unsafe { _mm512_storeu_si512(dst.cast(), r) };
+        let r = encode_vec_vbmi(load_48!(0), k);
+        store!(dst, r);
         src = unsafe { src.add(ENC_VEC_IN) };
         dst = unsafe { dst.add(ENC_VEC_OUT) };
         rem -= ENC_VEC_IN;
+        vecs += 1;
     }
 
+    if let Some(lag) = lag {
+        unsafe { lag.finish(dst) };
+    }
+    vecs
+}
+
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn encode_slice_avx512_vbmi(config: &Config, input: &[u8], dst_slice: &mut [u8]) {
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+    let mut rem = input.len();
+
+    let k = encode_constants_vbmi(config);
+
+    // Every full-width store sits at `dst_start + 64 * n`, so they all share
+    // one phase against a 64-byte boundary, and the first vector, stored
+    // cached, is the head that peels the destination up to it.
+    let vecs = if rem >= super::nt_store_min_len() && rem >= ENC_SINGLE_MIN {
+        let head = encode_vec_vbmi(unsafe { _mm512_loadu_si512(src.cast()) }, &k);
+        unsafe { _mm512_storeu_si512(dst.cast(), head) };
+        let next = unsafe { dst.add(ENC_VEC_OUT) };
+        let lag = unsafe { StreamLagVbmi::new(head, next.align_offset(ENC_VEC_OUT)) };
+        let rest = unsafe { src.add(ENC_VEC_IN) };
+        1 + unsafe { encode_full_vbmi(rest, next, rem - ENC_VEC_IN, &k, Some(lag)) }
+    } else {
+        unsafe { encode_full_vbmi(src, dst, rem, &k, None) }
+    };
+    src = unsafe { src.add(ENC_VEC_IN * vecs) };
+    dst = unsafe { dst.add(ENC_VEC_OUT * vecs) };
+    rem -= ENC_VEC_IN * vecs;
+
     // Masked tier: whole triples only, so no padding logic lands here. `rem` is
     // now < 64 and `take` is capped at 48, so this runs at most twice.
     while rem >= ENC_GROUP {
         let take = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
         let out = take / ENC_GROUP * 4;
         let v = unsafe { _mm512_maskz_loadu_epi8(u64::MAX >> (ENC_VEC - take), src.cast()) };
-        let chars = encode_vec!(v);
+        let chars = encode_vec_vbmi(v, &k);
         unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), u64::MAX >> (ENC_VEC - out), chars) };
         src = unsafe { src.add(take) };
         dst = unsafe { dst.add(out) };
@@ -309,92 +492,178 @@ pub(crate) unsafe fn encode_slice_avx512_vbmi(config: &Config, input: &[u8], dst
 
 // --- VBMI decoder ---
 
-#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
-pub(crate) unsafe fn decode_slice_avx512_vbmi(
-    config: &Config,
-    input: &[u8],
-    dst_slice: &mut [u8],
-) -> Result<usize, Error> {
-    let mut src = input.as_ptr();
-    let dst_start = dst_slice.as_mut_ptr();
-    let mut dst = dst_start;
-    let mut rem = input.len();
+/// Decoder constants, loaded once per call.
+struct DecodeConstantsVbmi {
+    lut_lo: __m512i,
+    lut_hi: __m512i,
+    pack_l1: __m512i,
+    pack_l2: __m512i,
+    pack: __m512i,
+}
 
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn decode_constants_vbmi(config: &Config) -> DecodeConstantsVbmi {
     // 128-byte reverse LUT across two ZMMs; vpermi2b picks the register by bit
     // 6 and the byte by the low 6 bits, covering ASCII 0-127 in one lookup.
-    let lut = if config.url_safe {
+    let lut = if config.lenient {
+        &VBMI_DECODE_LENIENT
+    } else if config.url_safe {
         &VBMI_DECODE_URL_SAFE
     } else {
         &VBMI_DECODE_STANDARD
     };
-    let lut_lo = unsafe { _mm512_loadu_si512(lut.as_ptr().cast()) };
-    let lut_hi = unsafe { _mm512_loadu_si512(lut.as_ptr().add(64).cast()) };
-
-    let pack_l1 = _mm512_set1_epi16(VBMI_PACK_L1);
-    let pack_l2 = _mm512_set1_epi32(VBMI_PACK_L2);
-    let pack = unsafe { _mm512_loadu_si512(VBMI_PACK_SHUFFLE.as_ptr().cast()) };
+    DecodeConstantsVbmi {
+        lut_lo: unsafe { _mm512_loadu_si512(lut.as_ptr().cast()) },
+        lut_hi: unsafe { _mm512_loadu_si512(lut.as_ptr().add(64).cast()) },
+        pack_l1: _mm512_set1_epi16(VBMI_PACK_L1),
+        pack_l2: _mm512_set1_epi32(VBMI_PACK_L2),
+        pack: unsafe { _mm512_loadu_si512(VBMI_PACK_SHUFFLE.as_ptr().cast()) },
+    }
+}
 
-    // A character is bad iff its input byte had bit 7 set (>= 0x80, which
-    // vpermi2b silently aliases into the 128-entry table) or the LUT answered
-    // with the 0xFF sentinel. Either way bit 7 of `input | index` is set, so
-    // OR-ing every input and every index into one accumulator and testing its
-    // sign bits once validates the whole buffer. That is one `vpternlogd` per
-    // vector in place of a compare, a movemask and a mask-OR.
-    let mut bad = _mm512_setzero_si512();
+/// Folds 64 indices into 48 bytes, in the low 48 lanes of the result.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn pack_vec_vbmi(idx: __m512i, k: &DecodeConstantsVbmi) -> __m512i {
+    let m = _mm512_maddubs_epi16(idx, k.pack_l1);
+    let p = _mm512_madd_epi16(m, k.pack_l2);
+    unsafe { zmm_permutexvar_epi8(k.pack, p) }
+}
 
-    macro_rules! pack_vec {
-        ($idx:expr) => {{
-            let m = _mm512_maddubs_epi16($idx, pack_l1);
-            let p = _mm512_madd_epi16(m, pack_l2);
-            unsafe { zmm_permutexvar_epi8(pack, p) }
-        }};
-    }
+/// Runs the decoder's quad tier over the `rem` characters at `src`, OR-ing its
+/// inputs and indices into `bad`, and returns how many iterations it ran, at
+/// most `max`. Streamed through `lag` when it is given, stored cached
+/// otherwise.
+///
+/// # Safety
+/// `src` must be valid for a `rem`-byte read and `dst` for a write of
+/// `rem / 4 * 3` bytes. With a `lag`, its `phase` must be `dst`'s distance to
+/// a 64-byte boundary, and the 64 bytes before `dst` must be writable too.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+unsafe fn decode_quad_vbmi(
+    src: *const u8,
+    dst: *mut u8,
+    rem: usize,
+    max: usize,
+    k: &DecodeConstantsVbmi,
+    bad: &mut __m512i,
+    mut lag: Option<StreamLagVbmi>,
+) -> usize {
+    let mut src = src;
+    let mut dst = dst;
+    let mut rem = rem;
+    let mut quads = 0;
+
+    let stitch = if lag.is_some() {
+        VBMI_STITCH.map(|s| unsafe { _mm512_loadu_si512(s.as_ptr().cast()) })
+    } else {
+        [_mm512_setzero_si512(); 3]
+    };
 
-    // Quad tier: 256 input characters -> 192 output bytes. Every tier stops at
-    // least 4 characters short of the end so the final group -- the only one
-    // that may legally carry '=' -- is always decided by the scalar tail, which
-    // owns the padding and length rules.
-    while rem >= DEC_QUAD_MIN {
+    // Every tier stops at least 4 characters short of the end so the final
+    // group -- the only one that may legally carry '=' -- is always decided by
+    // the scalar tail, which owns the padding and length rules.
+    while rem >= DEC_QUAD_MIN && quads < max {
         let v0 = unsafe { _mm512_loadu_si512(src.cast::<__m512i>()) };
         let v1 = unsafe { _mm512_loadu_si512(src.add(DEC_VEC_IN).cast::<__m512i>()) };
         let v2 = unsafe { _mm512_loadu_si512(src.add(2 * DEC_VEC_IN).cast::<__m512i>()) };
         let v3 = unsafe { _mm512_loadu_si512(src.add(3 * DEC_VEC_IN).cast::<__m512i>()) };
 
-        let i0 = unsafe { zmm_permutex2var_epi8(lut_lo, v0, lut_hi) };
-        let i1 = unsafe { zmm_permutex2var_epi8(lut_lo, v1, lut_hi) };
-        let i2 = unsafe { zmm_permutex2var_epi8(lut_lo, v2, lut_hi) };
-        let i3 = unsafe { zmm_permutex2var_epi8(lut_lo, v3, lut_hi) };
+        let i0 = unsafe { zmm_permutex2var_epi8(k.lut_lo, v0, k.lut_hi) };
+        let i1 = unsafe { zmm_permutex2var_epi8(k.lut_lo, v1, k.lut_hi) };
+        let i2 = unsafe { zmm_permutex2var_epi8(k.lut_lo, v2, k.lut_hi) };
+        let i3 = unsafe { zmm_permutex2var_epi8(k.lut_lo, v3, k.lut_hi) };
 
-        let p0 = pack_vec!(i0);
-        let p1 = pack_vec!(i1);
-        let p2 = pack_vec!(i2);
-        let p3 = pack_vec!(i3);
+        let p0 = pack_vec_vbmi(i0, k);
+        let p1 = pack_vec_vbmi(i1, k);
+        let p2 = pack_vec_vbmi(i2, k);
+        let p3 = pack_vec_vbmi(i3, k);
 
         // 0xFE is the 3-input OR; four of them fold all eight vectors in.
         let t0 = _mm512_ternarylogic_epi32::<0xFE>(v0, i0, v1);
         let t1 = _mm512_ternarylogic_epi32::<0xFE>(i1, v2, i2);
         let t2 = _mm512_ternarylogic_epi32::<0xFE>(v3, i3, t0);
-        bad = _mm512_ternarylogic_epi32::<0xFE>(bad, t1, t2);
-
-        // Only the last store needs masking: each of the first three overhangs
-        // its 48 bytes by 16, and the very next store in this same iteration
-        // rewrites exactly that overhang.
-        unsafe { _mm512_storeu_si512(dst.cast(), p0) };
-        unsafe { _mm512_storeu_si512(dst.add(DEC_VEC_OUT).cast(), p1) };
-        unsafe { _mm512_storeu_si512(dst.add(2 * DEC_VEC_OUT).cast(), p2) };
-        unsafe { _mm512_mask_storeu_epi8(dst.add(3 * DEC_VEC_OUT).cast::<i8>(), LOW_48, p3) };
+        *bad = _mm512_ternarylogic_epi32::<0xFE>(*bad, t1, t2);
+
+        if let Some(lag) = lag.as_mut() {
+            // A streaming store cannot overhang and be rewritten the way the
+            // cached ones below are, so the four 48-byte results are first
+            // stitched into three whole vectors, one `vpermt2b` each.
+            let s0 = unsafe { zmm_permutex2var_epi8(p0, stitch[0], p1) };
+            let s1 = unsafe { zmm_permutex2var_epi8(p1, stitch[1], p2) };
+            let s2 = unsafe { zmm_permutex2var_epi8(p2, stitch[2], p3) };
+            unsafe { lag.stream(dst, s0) };
+            unsafe { lag.stream(dst.add(DEC_VEC_IN), s1) };
+            unsafe { lag.stream(dst.add(2 * DEC_VEC_IN), s2) };
+        } else {
+            // Only the last store needs masking: each of the first three
+            // overhangs its 48 bytes by 16, and the very next store in this
+            // same iteration rewrites exactly that overhang.
+            unsafe { _mm512_storeu_si512(dst.cast(), p0) };
+            unsafe { _mm512_storeu_si512(dst.add(DEC_VEC_OUT).cast(), p1) };
+            unsafe { _mm512_storeu_si512(dst.add(2 * DEC_VEC_OUT).cast(), p2) };
+            unsafe { _mm512_mask_storeu_epi8(dst.add(3 * DEC_VEC_OUT).cast::<i8>(), LOW_48, p3) };
+        }
 
         src = unsafe { src.add(DEC_QUAD_IN) };
         dst = unsafe { dst.add(DEC_QUAD_OUT) };
         rem -= DEC_QUAD_IN;
+        quads += 1;
+    }
+
+    if let Some(lag) = lag {
+        unsafe { lag.finish(dst) };
     }
+    quads
+}
+
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn decode_slice_avx512_vbmi(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) -> Result<usize, Error> {
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+    let mut rem = input.len();
+
+    let k = decode_constants_vbmi(config);
+
+    // A character is bad iff its input byte had bit 7 set (>= 0x80, which
+    // vpermi2b silently aliases into the 128-entry table) or the LUT answered
+    // with the 0xFF sentinel. Either way bit 7 of `input | index` is set, so
+    // OR-ing every input and every index into one accumulator and testing its
+    // sign bits once validates the whole buffer. That is one `vpternlogd` per
+    // vector in place of a compare, a movemask and a mask-OR.
+    let mut bad = _mm512_setzero_si512();
+
+    // Quad tier: 256 input characters -> 192 output bytes. Every pass starts at
+    // `dst_start + 192 * n`, a whole number of vectors, so they all share one
+    // phase against a 64-byte boundary; one cached pass peels the destination
+    // up to it, and its last 64 bytes, read back, are the head the streamed
+    // passes lag behind.
+    let quads = if rem >= super::nt_store_min_len() && rem >= DEC_QUAD_MIN {
+        unsafe { decode_quad_vbmi(src, dst, rem, 1, &k, &mut bad, None) };
+        let next = unsafe { dst.add(DEC_QUAD_OUT) };
+        let head = unsafe { _mm512_loadu_si512(next.sub(DEC_VEC_IN).cast::<__m512i>()) };
+        let lag = unsafe { StreamLagVbmi::new(head, next.align_offset(DEC_VEC_IN)) };
+        let (rest, rem) = (unsafe { src.add(DEC_QUAD_IN) }, rem - DEC_QUAD_IN);
+        1 + unsafe { decode_quad_vbmi(rest, next, rem, usize::MAX, &k, &mut bad, Some(lag)) }
+    } else {
+        unsafe { decode_quad_vbmi(src, dst, rem, usize::MAX, &k, &mut bad, None) }
+    };
+    src = unsafe { src.add(DEC_QUAD_IN * quads) };
+    dst = unsafe { dst.add(DEC_QUAD_OUT * quads) };
+    rem -= DEC_QUAD_IN * quads;
 
     // Single tier: 64 input characters -> 48 output bytes.
     while rem >= DEC_SINGLE_MIN {
         let v = unsafe { _mm512_loadu_si512(src.cast::<__m512i>()) };
-        let idx = unsafe { zmm_permutex2var_epi8(lut_lo, v, lut_hi) };
+        let idx = unsafe { zmm_permutex2var_epi8(k.lut_lo, v, k.lut_hi) };
         bad = _mm512_ternarylogic_epi32::<0xFE>(bad, v, idx);
-        let p = pack_vec!(idx);
+        let p = pack_vec_vbmi(idx, &k);
         unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), LOW_48, p) };
         src = unsafe { src.add(DEC_VEC_IN) };
         dst = unsafe { dst.add(DEC_VEC_OUT) };
@@ -413,9 +682,9 @@ pub(crate) unsafe fn decode_slice_avx512_vbmi(
                 src.cast(),
             )
         };
-        let idx = unsafe { zmm_permutex2var_epi8(lut_lo, v, lut_hi) };
+        let idx = unsafe { zmm_permutex2var_epi8(k.lut_lo, v, k.lut_hi) };
         bad = _mm512_ternarylogic_epi32::<0xFE>(bad, v, idx);
-        let p = pack_vec!(idx);
+        let p = pack_vec_vbmi(idx, &k);
         unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), u64::MAX >> (DEC_VEC_IN - out), p) };
         src = unsafe { src.add(take) };
         dst = unsafe { dst.add(out) };
@@ -429,6 +698,98 @@ pub(crate) unsafe fn decode_slice_avx512_vbmi(
     unsafe { super::tail::decode(config, input, src, dst_slice, dst_off) }
 }
 
+// --- Short inputs ---
+
+/// Longest input [`encode_short_avx512_vbmi`] takes: one masked vector.
+pub(crate) const ENC_SHORT_MAX: usize = ENC_VEC_IN;
+/// Longest input [`decode_short_avx512_vbmi`] takes: one masked vector.
+pub(crate) const DEC_SHORT_MAX: usize = DEC_VEC_IN;
+
+/// Mask selecting the low `n` lanes of a ZMM, for any `n` up to 64.
+const fn lanes(n: usize) -> u64 {
+    if n == 0 { 0 } else { u64::MAX >> (64 - n) }
+}
+
+/// Encodes an input of at most [`ENC_SHORT_MAX`] bytes, padding included, in
+/// one masked load and one masked store.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn encode_short_avx512_vbmi(config: &Config, input: &[u8], dst: &mut [u8]) {
+    debug_assert!(input.len() <= ENC_SHORT_MAX);
+    let k = encode_constants_vbmi(config);
+    let (chars, total) = super::short::encoded_lens(*config, input.len());
+
+    let v = unsafe { _mm512_maskz_loadu_epi8(lanes(input.len()), input.as_ptr().cast()) };
+    let out = encode_vec_vbmi(v, &k);
+    unsafe { _mm512_mask_storeu_epi8(dst.as_mut_ptr().cast::<i8>(), lanes(chars), out) };
+    dst[chars..total].fill(b'=');
+}
+
+/// Decodes an input of at most [`DEC_SHORT_MAX`] characters, padding included,
+/// in one masked load and one masked store.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn decode_short_avx512_vbmi(
+    config: &Config,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    debug_assert!(input.len() <= DEC_SHORT_MAX);
+    let k = decode_constants_vbmi(config);
+    let plan = super::short::plan_decode(*config, input);
+
+    // Lanes past `chars` are backfilled with 'A', as in the masked tier.
+    let v = unsafe {
+        _mm512_mask_loadu_epi8(
+            _mm512_set1_epi8(b'A'.cast_signed()),
+            lanes(plan.chars),
+            input.as_ptr().cast(),
+        )
+    };
+    let idx = unsafe { zmm_permutex2var_epi8(k.lut_lo, v, k.lut_hi) };
+    let bad = _mm512_ternarylogic_epi32::<0xFE>(v, idx, idx);
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    plan.trailing?;
+
+    let p = pack_vec_vbmi(idx, &k);
+    unsafe { _mm512_mask_storeu_epi8(dst.as_mut_ptr().cast::<i8>(), lanes(plan.out), p) };
+    Ok(plan.out)
+}
+
+// The same kernels at 256 bits, for hosts that would rather not pay for ZMM.
+mod ymm;
+pub(crate) use ymm::{decode_slice_avx512_vbmi_256, encode_slice_avx512_vbmi_256};
+
+// Hex on the same instruction set.
+mod hex;
+pub(crate) use hex::{decode_hex_avx512_vbmi, encode_hex_avx512_vbmi};
+
+// Base32 on the same instruction set.
+mod base32;
+pub(crate) use base32::{decode_base32_avx512_vbmi, encode_base32_avx512_vbmi};
+
+// crypt(3) and bcrypt Base64, on the Base64 encode and pack steps.
+mod crypt;
+pub(crate) use crypt::{decode_crypt_avx512_vbmi, encode_crypt_avx512_vbmi};
+
+// Transcoding: Base64 to Base64 on the decoder's lookup, remapping instead,
+// and Base64 to and from hex on both formats' steps.
+mod transcode;
+pub(crate) use transcode::{
+    base64_to_hex_avx512_vbmi, hex_to_base64_avx512_vbmi, remap_avx512_vbmi,
+    remap_in_place_avx512_vbmi,
+};
+
+// Ignore-garbage decoding: compaction on the decoder's lookup.
+mod garbage;
+pub(crate) use garbage::compact_avx512_vbmi;
+
 // Verification: Kani proofs, Intel-pseudocode intrinsic models, and the Miri +
 // hardware coverage suites.
 #[cfg(any(kani, test, miri))]
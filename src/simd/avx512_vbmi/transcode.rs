@@ -0,0 +1,369 @@
+//! AVX512-VBMI transcoding, on the Base64 and hex kernels' own steps:
+//!
+//! * Base64 -> Base64: one `vpermi2b` per 64 characters over the ASCII half of
+//!   the remap table, which maps each character straight to its counterpart in
+//!   the target alphabet, with the decoder's `vpternlogd` OR tree for
+//!   validation.
+//! * Base64 -> hex: the decoder's lookup and pack step turn 64 characters into
+//!   48 bytes in a register, which the hex encoder's spread, multishift and
+//!   alphabet `vpermb`s expand to 96 digits, in two halves.
+//! * hex -> Base64: the hex decoder's lookup and `vpmaddubsw` leave 48 bytes
+//!   in the even lanes of two registers, and one `vpermi2b` gathers them
+//!   straight into the Base64 encoder's layout for its multishift and
+//!   alphabet `vpermb`.
+//!
+//! No bytes ever reach memory between the two formats. Each runs its last
+//! partial block through one masked pass, so nothing is left for scalar but
+//! the final group, which the caller owns. The remap loads each block before
+//! its store, at the same offset, so one core serves both the copying and the
+//! in-place form.
+
+use super::hex::{
+    VBMI_HEX_DECODE, VBMI_HEX_LOWER, VBMI_HEX_MULTISHIFT, VBMI_HEX_SPREAD, VBMI_HEX_UPPER,
+    decode_pairs_hex_vbmi, encode_vec_hex_vbmi,
+};
+use super::{
+    VBMI_ENCODE_GATHER, decode_constants_vbmi, encode_constants_vbmi, lanes, pack_vec_vbmi,
+    zmm_multishift_epi64_epi8, zmm_permutex2var_epi8, zmm_permutexvar_epi8,
+};
+use crate::transcode::remap_table_for;
+use crate::{Config, Error};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m512i, _mm512_loadu_si512, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8,
+    _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_set1_epi64, _mm512_setzero_si512,
+    _mm512_storeu_si512, _mm512_ternarylogic_epi32,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m512i, _mm512_loadu_si512, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8,
+    _mm512_movepi8_mask, _mm512_set1_epi8, _mm512_set1_epi64, _mm512_setzero_si512,
+    _mm512_storeu_si512, _mm512_ternarylogic_epi32,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Characters a step remaps: a full ZMM. Imported by the Kani index proofs in
+/// `verify`.
+pub(super) const REMAP_STEP: usize = 64;
+
+/// 64 characters -> their counterparts. ORs the characters and the result
+/// into `bad`: a character outside ASCII, or one the table rejects, sets its
+/// lane's top bit.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn remap_vec_vbmi(chars: __m512i, lut_lo: __m512i, lut_hi: __m512i, bad: &mut __m512i) -> __m512i {
+    let mapped = unsafe { zmm_permutex2var_epi8(lut_lo, chars, lut_hi) };
+    *bad = _mm512_ternarylogic_epi32::<0xFE>(*bad, chars, mapped);
+    mapped
+}
+
+/// Remaps `len` characters from `src` to `dst`, which may be the same pointer.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI; `src` must be valid
+/// for a `len`-byte read and `dst` for a `len`-byte write, and if they overlap
+/// they must be equal.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+unsafe fn remap_raw(
+    table: &[u8; 256],
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> Result<(), Error> {
+    let lut_lo = unsafe { _mm512_loadu_si512(table.as_ptr().cast()) };
+    let lut_hi = unsafe { _mm512_loadu_si512(table.as_ptr().add(64).cast()) };
+
+    let mut bad = _mm512_setzero_si512();
+    let mut done = 0;
+    while done + REMAP_STEP <= len {
+        let chars = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        let mapped = remap_vec_vbmi(chars, lut_lo, lut_hi, &mut bad);
+        unsafe { _mm512_storeu_si512(dst.add(done).cast(), mapped) };
+        done += REMAP_STEP;
+    }
+
+    // Masked pass: the last `len - done < 64` characters, with the lanes past
+    // them backfilled with 'A', which every Base64 alphabet accepts.
+    let take = len - done;
+    if take > 0 {
+        let fill = _mm512_set1_epi8(b'A'.cast_signed());
+        let chars = unsafe { _mm512_mask_loadu_epi8(fill, lanes(take), src.add(done).cast()) };
+        let mapped = remap_vec_vbmi(chars, lut_lo, lut_hi, &mut bad);
+        unsafe { _mm512_mask_storeu_epi8(dst.add(done).cast::<i8>(), lanes(take), mapped) };
+    }
+
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    Ok(())
+}
+
+/// Remaps `src`, in `from`'s alphabet, into `dst` in `to`'s, as
+/// [`scalar::remap`](crate::transcode::scalar::remap) does.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI, and `dst` must be at
+/// least as long as `src`.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the source
+/// alphabet.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn remap_avx512_vbmi(
+    from: &Config,
+    to: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(dst.len() >= src.len());
+    let table = remap_table_for(from, to);
+    unsafe { remap_raw(table, src.as_ptr(), dst.as_mut_ptr(), src.len()) }
+}
+
+/// Remaps `buf` where it lies, from `from`'s alphabet to `to`'s, as
+/// [`scalar::remap_in_place`](crate::transcode::scalar::remap_in_place) does.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI.
+///
+/// # Errors
+/// As [`remap_avx512_vbmi`].
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn remap_in_place_avx512_vbmi(
+    from: &Config,
+    to: &Config,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    let ptr = buf.as_mut_ptr();
+    unsafe { remap_raw(remap_table_for(from, to), ptr, ptr, buf.len()) }
+}
+
+// --- Base64 -> hex ---
+
+/// Characters a Base64-to-hex step consumes: a full ZMM.
+pub(super) const B64_HEX_IN: usize = 64;
+/// Digits a Base64-to-hex step writes: two per decoded byte.
+pub(super) const B64_HEX_OUT: usize = B64_IN_BYTES * 2;
+/// Bytes one Base64 vector decodes to.
+const B64_IN_BYTES: usize = B64_HEX_IN / 4 * 3;
+/// Digits the first hex vector of a step holds, the rest going in the second.
+const HEX_VEC: usize = 64;
+
+/// `vpermb` control placing byte `32 + i / 2` in lane `i`: the hex encoder's
+/// spread, over the upper 16 of a decoded vector's 48 bytes.
+#[allow(clippy::cast_possible_truncation)] // `32 + i / 2` is always < 64
+const VBMI_HEX_SPREAD_HI: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        t[i] = (32 + i / 2) as u8;
+        i += 1;
+    }
+    t
+};
+
+/// Decodes `src`, whole Base64 groups without padding, straight to hex in
+/// `dst`: `src.len() / 4 * 6` digits, in `upper` or lower case.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI; `src.len()` must be
+/// a multiple of 4, and `dst` must hold at least `src.len() / 4 * 6` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside `config`'s
+/// alphabet, `'='` included. `dst` is then partly written.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn base64_to_hex_avx512_vbmi(
+    config: &Config,
+    upper: bool,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(src.len().is_multiple_of(4) && dst.len() >= src.len() / 4 * 6);
+    let k = decode_constants_vbmi(config);
+    let digits = if upper {
+        &VBMI_HEX_UPPER
+    } else {
+        &VBMI_HEX_LOWER
+    };
+    let digits = unsafe { _mm512_loadu_si512(digits.as_ptr().cast()) };
+    let spread_lo = unsafe { _mm512_loadu_si512(VBMI_HEX_SPREAD.as_ptr().cast()) };
+    let spread_hi = unsafe { _mm512_loadu_si512(VBMI_HEX_SPREAD_HI.as_ptr().cast()) };
+    let shifts = _mm512_set1_epi64(VBMI_HEX_MULTISHIFT);
+
+    let len = src.len();
+    let src = src.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = _mm512_setzero_si512();
+    let mut done = 0;
+    let mut written = 0;
+    while done + B64_HEX_IN <= len {
+        let v = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        let idx = unsafe { zmm_permutex2var_epi8(k.lut_lo, v, k.lut_hi) };
+        bad = _mm512_ternarylogic_epi32::<0xFE>(bad, v, idx);
+        let bytes = pack_vec_vbmi(idx, &k);
+        let lo = encode_vec_hex_vbmi(bytes, spread_lo, shifts, digits);
+        let hi = encode_vec_hex_vbmi(bytes, spread_hi, shifts, digits);
+        unsafe {
+            _mm512_storeu_si512(out.add(written).cast(), lo);
+            _mm512_mask_storeu_epi8(
+                out.add(written + HEX_VEC).cast::<i8>(),
+                lanes(B64_HEX_OUT - HEX_VEC),
+                hi,
+            );
+        }
+        done += B64_HEX_IN;
+        written += B64_HEX_OUT;
+    }
+
+    // Masked pass: the last `len - done < 64` characters, whole groups, with
+    // the lanes past them backfilled with 'A', as in the decoder's masked tier.
+    let take = len - done;
+    if take > 0 {
+        let v = unsafe {
+            _mm512_mask_loadu_epi8(
+                _mm512_set1_epi8(b'A'.cast_signed()),
+                lanes(take),
+                src.add(done).cast(),
+            )
+        };
+        let idx = unsafe { zmm_permutex2var_epi8(k.lut_lo, v, k.lut_hi) };
+        bad = _mm512_ternarylogic_epi32::<0xFE>(bad, v, idx);
+        let bytes = pack_vec_vbmi(idx, &k);
+        let n = take / 4 * 6;
+        let lo = encode_vec_hex_vbmi(bytes, spread_lo, shifts, digits);
+        unsafe {
+            _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(n.min(HEX_VEC)), lo);
+        };
+        if n > HEX_VEC {
+            let hi = encode_vec_hex_vbmi(bytes, spread_hi, shifts, digits);
+            unsafe {
+                _mm512_mask_storeu_epi8(
+                    out.add(written + HEX_VEC).cast::<i8>(),
+                    lanes(n - HEX_VEC),
+                    hi,
+                );
+            }
+        }
+    }
+
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    Ok(())
+}
+
+// --- hex -> Base64 ---
+
+/// Digits a hex-to-Base64 step consumes: 48 bytes' worth, a ZMM and a half.
+pub(super) const HEX_B64_IN: usize = 96;
+/// Characters a hex-to-Base64 step writes: a full ZMM.
+pub(super) const HEX_B64_OUT: usize = 64;
+
+/// `vpermi2b` control that gathers the decoded pairs, byte `b` in the even
+/// lane `2 * b` of the first register for `b < 32` and of the second (bit 6)
+/// above, into the Base64 encoder's `[b2,b1,b0, b5,b4,b3, x,x]` layout.
+#[allow(clippy::cast_possible_truncation)] // every entry is < 128
+const VBMI_HEX_GATHER: [u8; 64] = {
+    let mut t = [0u8; 64];
+    let mut i = 0;
+    while i < 64 {
+        let b = VBMI_ENCODE_GATHER[i] as usize;
+        t[i] = if b < 32 { 2 * b } else { 64 + 2 * (b - 32) } as u8;
+        i += 1;
+    }
+    t
+};
+
+/// Encodes hex `src`, a whole number of 6-digit groups in either case,
+/// straight to Base64 in `dst`: `src.len() / 6 * 4` characters of `config`'s
+/// alphabet.
+///
+/// # Safety
+/// The CPU must support AVX512F, AVX512BW and AVX512-VBMI; `src.len()` must be
+/// a multiple of 6, and `dst` must hold at least `src.len() / 6 * 4` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `src` holds anything but hex digits.
+/// `dst` is then partly written.
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+pub(crate) unsafe fn hex_to_base64_avx512_vbmi(
+    config: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(src.len().is_multiple_of(6) && dst.len() >= src.len() / 6 * 4);
+    let k = encode_constants_vbmi(config);
+    let gather = unsafe { _mm512_loadu_si512(VBMI_HEX_GATHER.as_ptr().cast()) };
+    let lut_lo = unsafe { _mm512_loadu_si512(VBMI_HEX_DECODE.as_ptr().cast()) };
+    let lut_hi = unsafe { _mm512_loadu_si512(VBMI_HEX_DECODE.as_ptr().add(64).cast()) };
+    let zeros = _mm512_set1_epi8(b'0'.cast_signed());
+
+    let len = src.len();
+    let src = src.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = _mm512_setzero_si512();
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_B64_IN <= len {
+        let lo = unsafe { _mm512_loadu_si512(src.add(done).cast()) };
+        let hi = unsafe {
+            _mm512_mask_loadu_epi8(
+                zeros,
+                lanes(HEX_B64_IN - HEX_VEC),
+                src.add(done + HEX_VEC).cast(),
+            )
+        };
+        let chars = hex_to_base64_vec_vbmi(lo, hi, lut_lo, lut_hi, gather, &k, &mut bad);
+        unsafe { _mm512_storeu_si512(out.add(written).cast(), chars) };
+        done += HEX_B64_IN;
+        written += HEX_B64_OUT;
+    }
+
+    // Masked pass: the last `len - done < 96` digits, whole groups, with the
+    // lanes past them backfilled with '0', as in the hex decoder's.
+    let take = len - done;
+    if take > 0 {
+        let lo = unsafe {
+            _mm512_mask_loadu_epi8(zeros, lanes(take.min(HEX_VEC)), src.add(done).cast())
+        };
+        let hi = if take > HEX_VEC {
+            unsafe {
+                _mm512_mask_loadu_epi8(zeros, lanes(take - HEX_VEC), src.add(done + HEX_VEC).cast())
+            }
+        } else {
+            zeros
+        };
+        let chars = hex_to_base64_vec_vbmi(lo, hi, lut_lo, lut_hi, gather, &k, &mut bad);
+        unsafe {
+            _mm512_mask_storeu_epi8(out.add(written).cast::<i8>(), lanes(take / 6 * 4), chars);
+        };
+    }
+
+    if _mm512_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    Ok(())
+}
+
+/// 96 digits, the first 64 in `lo` and the rest in `hi`, -> 64 characters.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vbmi")]
+fn hex_to_base64_vec_vbmi(
+    lo: __m512i,
+    hi: __m512i,
+    lut_lo: __m512i,
+    lut_hi: __m512i,
+    gather: __m512i,
+    k: &super::EncodeConstantsVbmi,
+    bad: &mut __m512i,
+) -> __m512i {
+    let pairs_lo = decode_pairs_hex_vbmi(lo, lut_lo, lut_hi, bad);
+    let pairs_hi = decode_pairs_hex_vbmi(hi, lut_lo, lut_hi, bad);
+    let g = unsafe { zmm_permutex2var_epi8(pairs_lo, gather, pairs_hi) };
+    let indices = unsafe { zmm_multishift_epi64_epi8(k.shifts, g) };
+    unsafe { zmm_permutexvar_epi8(indices, k.alphabet) }
+}
@@ -31,7 +31,7 @@ mod kani_verification_avx512_vbmi {
     use super::super::{
         DEC_GROUP, DEC_LEAD, DEC_MASKED_MIN, DEC_QUAD_IN, DEC_QUAD_MIN, DEC_QUAD_OUT,
         DEC_SINGLE_MIN, DEC_VEC_IN, DEC_VEC_OUT, ENC_GROUP, ENC_QUAD_IN, ENC_QUAD_MIN,
-        ENC_QUAD_OUT, ENC_SINGLE_MIN, ENC_VEC, ENC_VEC_IN, ENC_VEC_OUT,
+        ENC_QUAD_OUT, ENC_SINGLE_MIN, ENC_UNROLL, ENC_VEC, ENC_VEC_IN, ENC_VEC_OUT, VBMI_STITCH,
     };
 
     /// Largest `len` considered: above `usize::MAX / 4` the unpadded
@@ -156,6 +156,54 @@ mod kani_verification_avx512_vbmi {
         assert_eq!(rem - ENC_VEC_IN, len - (done + ENC_VEC_IN));
     }
 
+    /// The non-temporal variant of the quad and single tiers. They are the
+    /// same loops with the same loads, so the step proofs above already bound
+    /// them; what streaming changes is the stores. After the cached head,
+    /// vector `n` streams the 64 bytes `lag = 64 - phase` before its slot,
+    /// where `phase` is the distance every slot shares to the next 64-byte
+    /// boundary: the store lands on one, reaches back no further than the
+    /// previous slot and forward no further than its own, and the next store,
+    /// or `finish`'s masked one, picks up exactly where it stops.
+    #[kani::proof]
+    fn check_vbmi_enc_nt_alignment() {
+        let dst_start: usize = kani::any();
+
+        // Any vector after the head, by whichever tier.
+        let n: usize = kani::any();
+        kani::assume(n >= 1 && n <= MAX_LEN / ENC_VEC_IN);
+        let slot = ENC_VEC_OUT * n;
+        kani::assume(dst_start <= usize::MAX - slot - ENC_VEC_OUT);
+
+        // `align_offset` of the first streamed slot, as the caller takes it.
+        let phase = (ENC_VEC_OUT - (dst_start + ENC_VEC_OUT) % ENC_VEC_OUT) % ENC_VEC_OUT;
+        let lag = ENC_VEC_OUT - phase;
+        assert!(
+            (1..=ENC_VEC_OUT).contains(&lag),
+            "finish mask shift out of range"
+        );
+
+        let at = slot - lag;
+        assert_eq!(
+            (dst_start + at) % ENC_VEC_OUT,
+            0,
+            "stream store is misaligned"
+        );
+        assert!(
+            at >= slot - ENC_VEC_OUT,
+            "stream store reaches past the previous slot"
+        );
+        assert!(
+            at + ENC_VEC_OUT < slot + ENC_VEC_OUT,
+            "stream store leaves its slot"
+        );
+        // `finish` covers `[slot + 64 - lag, slot + 64)`, from `end - 64`.
+        assert_eq!(at + ENC_VEC_OUT, slot + ENC_VEC_OUT - lag);
+        assert!(slot + ENC_VEC_OUT >= ENC_VEC_OUT);
+
+        // Both strides keep the slots whole vectors apart.
+        assert_eq!(ENC_QUAD_OUT, ENC_UNROLL * ENC_VEC_OUT);
+    }
+
     /// Inductive step for the encoder's masked tier. The masked load and store
     /// touch only their masked lanes, so the obligation is `take` bytes in and
     /// `out` bytes out — not a full vector of either.
@@ -270,6 +318,72 @@ mod kani_verification_avx512_vbmi {
         assert_eq!(rem - DEC_QUAD_IN, len - (done + DEC_QUAD_IN));
     }
 
+    /// The decoder's non-temporal quad tier: the four 48-byte results are
+    /// stitched into three whole vectors and streamed through the lag, so
+    /// unlike the cached variant nothing overhangs. One cached pass comes
+    /// first, so every streamed vector sits a whole pass or more in, and each
+    /// store lands on a 64-byte boundary, reaching back at most one vector and
+    /// never past its pass.
+    #[kani::proof]
+    fn check_vbmi_dec_quad_step_nt() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        kani::assume(rem >= DEC_QUAD_MIN); // guard `while rem >= 260`
+        // The streamed passes start after the cached one and advance by whole
+        // passes.
+        kani::assume(dst_off >= DEC_QUAD_OUT && dst_off % DEC_QUAD_OUT == 0);
+        let cap = dec_cap(len);
+
+        let dst_start: usize = kani::any();
+        kani::assume(dst_start <= usize::MAX - cap);
+        let phase = (DEC_STORE_WIDE - (dst_start + DEC_QUAD_OUT) % DEC_STORE_WIDE) % DEC_STORE_WIDE;
+        let lag = DEC_STORE_WIDE - phase;
+
+        let j: usize = kani::any();
+        kani::assume(j < 3);
+        let slot = dst_off + DEC_STORE_WIDE * j;
+        let at = slot - lag;
+        assert_eq!(
+            (dst_start + at) % DEC_STORE_WIDE,
+            0,
+            "stream store is misaligned"
+        );
+        assert!(
+            at >= slot - DEC_STORE_WIDE,
+            "stream store reaches too far back"
+        );
+        assert!(slot + DEC_STORE_WIDE <= cap, "stream store leaves output");
+
+        assert_eq!(3 * DEC_STORE_WIDE, DEC_QUAD_OUT);
+        assert!(done + DEC_QUAD_IN <= len);
+    }
+
+    /// The `VBMI_STITCH` controls put every byte of the pass where the cached
+    /// stores would have: output byte `64 * j + b` is byte `(64 * j + b) % 48`
+    /// of result `(64 * j + b) / 48`, and `vpermt2b` with control `j` can only
+    /// reach results `j` and `j + 1`.
+    #[kani::proof]
+    fn check_vbmi_dec_nt_stitch() {
+        let j: usize = kani::any();
+        let b: usize = kani::any();
+        kani::assume(j < 3 && b < DEC_STORE_WIDE);
+
+        let idx = usize::from(VBMI_STITCH[j][b]);
+        assert!(idx < 2 * DEC_STORE_WIDE, "control selects past the pair");
+        let (result, byte) = (j + idx / DEC_STORE_WIDE, idx % DEC_STORE_WIDE);
+        // Only the low 48 lanes of each result hold decoded bytes.
+        assert!(byte < DEC_VEC_OUT, "control selects a zeroed lane");
+
+        let pos = DEC_STORE_WIDE * j + b;
+        assert_eq!(
+            (result, byte),
+            (pos / DEC_VEC_OUT, pos % DEC_VEC_OUT),
+            "byte lands out of order"
+        );
+    }
+
     /// Inductive step for the decoder's single tier.
     #[kani::proof]
     fn check_vbmi_dec_single_step() {
@@ -419,6 +533,7 @@ mod kani_verification_avx512_vbmi {
         let config = Config {
             url_safe,
             padding: true,
+            lenient: false,
         };
         let input: [u8; ENC_KERNEL_LEN] = kani::any();
 
@@ -479,6 +594,7 @@ mod kani_verification_avx512_vbmi {
         let config = Config {
             url_safe: kani::any(),
             padding: true,
+            lenient: false,
         };
         let input: [u8; N] = kani::any();
 
@@ -548,6 +664,7 @@ mod kani_verification_avx512_vbmi {
         let config = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         let input: [u8; ROUNDTRIP_LEN] = kani::any();
 
@@ -564,6 +681,355 @@ mod kani_verification_avx512_vbmi {
     }
 }
 
+#[cfg(kani)]
+mod kani_verification_hex_avx512_vbmi {
+    // Index proofs for the hex kernels in `super::hex`. The full steps are
+    // plain counters, as in the AVX2 hex proofs; the masked pass is the part
+    // worth proving, since its masks come from `lanes`, whose shift is only
+    // defined for up to 64 lanes.
+
+    use super::super::hex::{HEX_DEC_IN, HEX_DEC_OUT, HEX_ENC_IN, HEX_ENC_OUT};
+
+    /// Largest `len` considered: above `usize::MAX / 2` the `encoded_len`'s
+    /// `len * 2` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 2;
+
+    /// Lanes in a ZMM, the most any mask may select.
+    const ZMM_LANES: usize = 64;
+
+    const _: () = assert!(
+        HEX_ENC_OUT == 2 * HEX_ENC_IN && HEX_DEC_IN == 2 * HEX_DEC_OUT,
+        "a hex step must keep the 2:1 ratio"
+    );
+
+    /// Inductive step for the encoder's full steps: a 32-lane masked load and
+    /// a full 64-byte store, both in bounds.
+    #[kani::proof]
+    fn check_vbmi_hex_enc_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_ENC_IN);
+        let (done, written) = (HEX_ENC_IN * i, HEX_ENC_OUT * i);
+        kani::assume(done + HEX_ENC_IN <= len); // guard `done + IN <= len`
+
+        assert!(HEX_ENC_IN <= ZMM_LANES, "load mask shift out of range");
+        assert!(
+            written + HEX_ENC_OUT <= 2 * len,
+            "encode store leaves output"
+        );
+        assert_eq!(written, 2 * done);
+    }
+
+    /// The encoder's masked pass: both masks are in range, and the store
+    /// ends exactly at the end of `dst`, so nothing is left for scalar.
+    #[kani::proof]
+    fn check_vbmi_hex_enc_masked_pass() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_ENC_IN);
+        let (done, written) = (HEX_ENC_IN * i, HEX_ENC_OUT * i);
+        kani::assume(done <= len && len < done + HEX_ENC_IN); // loop exit
+
+        let take = len - done;
+        assert!(take < HEX_ENC_IN, "masked pass is a full step");
+        assert!(2 * take <= ZMM_LANES, "store mask shift out of range");
+        assert_eq!(written + 2 * take, 2 * len);
+    }
+
+    /// Inductive step for the decoder's full steps: a 64-byte load and a
+    /// 32-byte store, both in bounds, odd `len` included.
+    #[kani::proof]
+    fn check_vbmi_hex_dec_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_DEC_IN);
+        let (done, written) = (HEX_DEC_IN * i, HEX_DEC_OUT * i);
+        kani::assume(done + HEX_DEC_IN <= len); // guard `done + IN <= len`
+
+        assert!(
+            written + HEX_DEC_OUT <= len / 2,
+            "decode store leaves output"
+        );
+        assert_eq!(2 * written, done);
+    }
+
+    /// The decoder's masked pass and scalar hand-off: the masks are in range,
+    /// the store stays inside the `len / 2` bytes of `dst`, and scalar is left
+    /// at most the one unpaired digit.
+    #[kani::proof]
+    fn check_vbmi_hex_dec_masked_pass() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_DEC_IN);
+        let (done, written) = (HEX_DEC_IN * i, HEX_DEC_OUT * i);
+        kani::assume(done <= len && len < done + HEX_DEC_IN); // loop exit
+
+        let take = (len - done) & !1;
+        assert!(take <= ZMM_LANES, "load mask shift out of range");
+        assert_eq!(take % 2, 0);
+        assert!(written + take / 2 <= len / 2, "masked store leaves output");
+
+        let (done, written) = (done + take, written + take / 2);
+        assert!(len - done <= 1, "scalar tail holds a whole pair");
+        assert_eq!(written, len / 2);
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_base32_avx512_vbmi {
+    // Index proofs for the Base32 kernels in `super::base32`. Every vector pass
+    // consumes whole groups, so the model tracks groups, as the Base64 VBMI
+    // proofs do: after `g` of them the encoder has read `5g` bytes and written
+    // `8g` characters, and the decoder the reverse.
+
+    use super::super::base32::{
+        B32_DEC_IN, B32_DEC_OUT, B32_ENC_IN, B32_ENC_OUT, B32_GROUP_IN, B32_GROUP_OUT,
+    };
+
+    /// Largest `len` considered: above `usize::MAX / 8` the unpadded
+    /// `encoded_len`'s `len * 8` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 8;
+
+    /// Lanes in a ZMM, the most any mask may select.
+    const ZMM_LANES: usize = 64;
+
+    const _: () = assert!(
+        B32_ENC_IN % B32_GROUP_IN == 0 && B32_DEC_IN % B32_GROUP_OUT == 0,
+        "every Base32 step must consume whole groups"
+    );
+
+    /// The unpadded encoded length, the smaller of the two, so these proofs
+    /// cover the padded engines too.
+    fn enc_cap(len: usize) -> usize {
+        (len * 8).div_ceil(5)
+    }
+
+    /// The decode buffer the engine hands the kernel.
+    fn dec_cap(len: usize) -> usize {
+        len / 8 * 5 + len % 8 * 5 / 8
+    }
+
+    /// Inductive step for the encoder's full steps: a 40-lane masked load and
+    /// a full 64-byte store, both in bounds.
+    #[kani::proof]
+    fn check_vbmi_b32_enc_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / B32_ENC_IN);
+        let (done, written) = (B32_ENC_IN * i, B32_ENC_OUT * i);
+        kani::assume(done + B32_ENC_IN <= len); // guard `done + IN <= len`
+
+        assert!(B32_ENC_IN <= ZMM_LANES, "load mask shift out of range");
+        assert!(
+            written + B32_ENC_OUT <= enc_cap(len),
+            "encode store leaves output"
+        );
+    }
+
+    /// The encoder's masked pass and scalar hand-off: both masks are in range,
+    /// the store stays in `dst`, and scalar sees at most a partial group whose
+    /// characters end inside `dst`.
+    #[kani::proof]
+    fn check_vbmi_b32_enc_masked_pass() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / B32_ENC_IN);
+        let (done, written) = (B32_ENC_IN * i, B32_ENC_OUT * i);
+        kani::assume(done <= len && len < done + B32_ENC_IN); // loop exit
+
+        let take = (len - done) / B32_GROUP_IN * B32_GROUP_IN;
+        let out = take / B32_GROUP_IN * B32_GROUP_OUT;
+        assert!(take < B32_ENC_IN, "masked pass is a full step");
+        assert!(out <= ZMM_LANES, "store mask shift out of range");
+        assert!(written + out <= enc_cap(len), "masked store leaves output");
+
+        let (done, written) = (done + take, written + out);
+        assert!(len - done < B32_GROUP_IN, "scalar tail holds a whole group");
+        assert_eq!(written + enc_cap(len - done), enc_cap(len));
+    }
+
+    /// Inductive step for the decoder's full steps: a 64-byte load and a
+    /// 40-lane masked store, both in bounds.
+    #[kani::proof]
+    fn check_vbmi_b32_dec_step() {
+        let len: usize = kani::any();
+        kani::assume(len >= 1 && len <= MAX_LEN);
+        let body = (len - 1) / B32_GROUP_OUT * B32_GROUP_OUT;
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / B32_DEC_IN);
+        let (done, written) = (B32_DEC_IN * i, B32_DEC_OUT * i);
+        kani::assume(done + B32_DEC_IN <= body); // guard `done + IN <= body`
+
+        assert!(B32_DEC_OUT <= ZMM_LANES, "store mask shift out of range");
+        assert!(
+            written + B32_DEC_OUT <= dec_cap(len),
+            "decode store leaves output"
+        );
+    }
+
+    /// The decoder's masked pass and scalar hand-off: the masks are in range,
+    /// the store stays in `dst`, and scalar's final group fits what is left.
+    #[kani::proof]
+    fn check_vbmi_b32_dec_masked_pass() {
+        let len: usize = kani::any();
+        kani::assume(len >= 1 && len <= MAX_LEN);
+        let body = (len - 1) / B32_GROUP_OUT * B32_GROUP_OUT;
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / B32_DEC_IN);
+        let (done, written) = (B32_DEC_IN * i, B32_DEC_OUT * i);
+        kani::assume(done <= body && body < done + B32_DEC_IN); // loop exit
+
+        let take = body - done;
+        let out = take / B32_GROUP_OUT * B32_GROUP_IN;
+        assert!(take <= ZMM_LANES, "load mask shift out of range");
+        assert_eq!(take % B32_GROUP_OUT, 0);
+        assert!(written + out <= dec_cap(len), "masked store leaves output");
+
+        let (done, written) = (done + take, written + out);
+        assert!((1..=B32_GROUP_OUT).contains(&(len - done)));
+        assert!(written + dec_cap(len - done) <= dec_cap(len));
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_crypt_avx512_vbmi {
+    // Index proofs for the crypt(3)/bcrypt kernels in `super::crypt`, on the
+    // Base32 proofs' group model: after `g` groups the encoder has read `3g`
+    // bytes and written `4g` characters, and the decoder the reverse. Neither
+    // scheme pads, so one pair of length formulas covers both.
+
+    use super::super::crypt::{
+        CRYPT_DEC_IN, CRYPT_DEC_OUT, CRYPT_ENC_IN, CRYPT_ENC_OUT, CRYPT_GROUP_IN, CRYPT_GROUP_OUT,
+    };
+
+    /// Largest `len` considered: comfortably below the `usize::MAX / 4 * 3`
+    /// or so at which `encoded_len` overflows.
+    const MAX_LEN: usize = usize::MAX / 4;
+
+    /// Lanes in a ZMM, the most any mask may select.
+    const ZMM_LANES: usize = 64;
+
+    const _: () = assert!(
+        CRYPT_ENC_IN % CRYPT_GROUP_IN == 0 && CRYPT_DEC_IN % CRYPT_GROUP_OUT == 0,
+        "every crypt step must consume whole groups"
+    );
+
+    /// The encode buffer the engine hands the kernel.
+    fn enc_cap(len: usize) -> usize {
+        len / 3 * 4 + (len % 3 * 4).div_ceil(3)
+    }
+
+    /// The decode buffer the engine hands the kernel.
+    fn dec_cap(len: usize) -> usize {
+        len / 4 * 3 + len % 4 * 3 / 4
+    }
+
+    /// Inductive step for the encoder's full steps: a 48-lane masked load and
+    /// a full 64-byte store, both in bounds.
+    #[kani::proof]
+    fn check_vbmi_crypt_enc_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / CRYPT_ENC_IN);
+        let (done, written) = (CRYPT_ENC_IN * i, CRYPT_ENC_OUT * i);
+        kani::assume(done + CRYPT_ENC_IN <= len); // guard `done + IN <= len`
+
+        assert!(CRYPT_ENC_IN <= ZMM_LANES, "load mask shift out of range");
+        assert!(
+            written + CRYPT_ENC_OUT <= enc_cap(len),
+            "encode store leaves output"
+        );
+    }
+
+    /// The encoder's masked pass and scalar hand-off: both masks are in range,
+    /// the store stays in `dst`, and scalar sees at most a partial group whose
+    /// characters end exactly at the end of `dst`.
+    #[kani::proof]
+    fn check_vbmi_crypt_enc_masked_pass() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / CRYPT_ENC_IN);
+        let (done, written) = (CRYPT_ENC_IN * i, CRYPT_ENC_OUT * i);
+        kani::assume(done <= len && len < done + CRYPT_ENC_IN); // loop exit
+
+        let take = (len - done) / CRYPT_GROUP_IN * CRYPT_GROUP_IN;
+        let out = take / CRYPT_GROUP_IN * CRYPT_GROUP_OUT;
+        assert!(take < CRYPT_ENC_IN, "masked pass is a full step");
+        assert!(out <= ZMM_LANES, "store mask shift out of range");
+        assert!(written + out <= enc_cap(len), "masked store leaves output");
+
+        let (done, written) = (done + take, written + out);
+        assert!(
+            len - done < CRYPT_GROUP_IN,
+            "scalar tail holds a whole group"
+        );
+        assert_eq!(written + enc_cap(len - done), enc_cap(len));
+    }
+
+    /// Inductive step for the decoder's full steps: a 64-byte load and a
+    /// 48-lane masked store, both in bounds.
+    #[kani::proof]
+    fn check_vbmi_crypt_dec_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN && len % CRYPT_GROUP_OUT != 1);
+        let body = len / CRYPT_GROUP_OUT * CRYPT_GROUP_OUT;
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / CRYPT_DEC_IN);
+        let (done, written) = (CRYPT_DEC_IN * i, CRYPT_DEC_OUT * i);
+        kani::assume(done + CRYPT_DEC_IN <= body); // guard `done + IN <= body`
+
+        assert!(CRYPT_DEC_OUT <= ZMM_LANES, "store mask shift out of range");
+        assert!(
+            written + CRYPT_DEC_OUT <= dec_cap(len),
+            "decode store leaves output"
+        );
+    }
+
+    /// The decoder's masked pass and scalar hand-off: the masks are in range,
+    /// the store stays in `dst`, and scalar's final two or three characters
+    /// fill exactly what is left.
+    #[kani::proof]
+    fn check_vbmi_crypt_dec_masked_pass() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN && len % CRYPT_GROUP_OUT != 1);
+        let body = len / CRYPT_GROUP_OUT * CRYPT_GROUP_OUT;
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / CRYPT_DEC_IN);
+        let (done, written) = (CRYPT_DEC_IN * i, CRYPT_DEC_OUT * i);
+        kani::assume(done <= body && body < done + CRYPT_DEC_IN); // loop exit
+
+        let take = body - done;
+        let out = take / CRYPT_GROUP_OUT * CRYPT_GROUP_IN;
+        assert!(take < ZMM_LANES, "masked pass is a full step");
+        assert_eq!(take % CRYPT_GROUP_OUT, 0);
+        assert!(written + out <= dec_cap(len), "masked store leaves output");
+
+        let (done, written) = (done + take, written + out);
+        assert!(len - done < CRYPT_GROUP_OUT && len - done != 1);
+        assert_eq!(written + dec_cap(len - done), dec_cap(len));
+    }
+}
+
 /// Rust models of the AVX-512 instructions the VBMI kernels cannot execute
 /// symbolically.
 ///
@@ -994,6 +1460,7 @@ mod ternarylogic_reconstruction {
 mod avx512_vbmi_stub_equivalence {
     use super::intrinsic_models as model;
     use super::*;
+    use crate::simd::testutil::has_avx512_vbmi;
 
     /// Saturation and sign boundaries, the bit-6/bit-7 selectors the VBMI
     /// permutes key off, index-shaped bytes, and deterministic noise.
@@ -1113,11 +1580,7 @@ mod avx512_vbmi_stub_equivalence {
 
     #[test]
     fn avx512_vbmi_models_match_hardware() {
-        if !(std::is_x86_feature_detected!("avx512f")
-            && std::is_x86_feature_detected!("avx512bw")
-            && std::is_x86_feature_detected!("avx512vbmi"))
-        {
-            eprintln!("skipping: host CPU lacks AVX-512-VBMI");
+        if !has_avx512_vbmi() {
             return;
         }
         unsafe { compare_all() };
@@ -1143,18 +1606,22 @@ mod miri_avx512_vbmi_coverage {
     const STD: Config = Config {
         url_safe: false,
         padding: true,
+        lenient: false,
     };
     const URL: Config = Config {
         url_safe: true,
         padding: true,
+        lenient: false,
     };
     const NO_PAD: Config = Config {
         url_safe: false,
         padding: false,
+        lenient: false,
     };
     const NO_PAD_URL: Config = Config {
         url_safe: true,
         padding: false,
+        lenient: false,
     };
 
     /// Tier boundaries, encode. The vector path now runs down to 3 bytes, so
@@ -1263,6 +1730,19 @@ mod miri_avx512_vbmi_coverage {
         }
     }
 
+    /// The short-input kernels' masked loads and stores, at every empty, partial
+    /// and full mask, into buffers of the exact size.
+    #[test]
+    fn miri_avx512_vbmi_short_inputs() {
+        for &len in &[0, 1, 2, 3, 23, 24, 47, 48] {
+            check_encode(&STD, &STANDARD, encode_short_avx512_vbmi, len);
+            check_encode(&NO_PAD, &STANDARD_NO_PAD, encode_short_avx512_vbmi, len);
+            exact(&URL, &URL_SAFE, len);
+            check_decode_exact(&STD, &STANDARD, decode_short_avx512_vbmi, len);
+            check_decode_exact(&NO_PAD, &STANDARD_NO_PAD, decode_short_avx512_vbmi, len);
+        }
+    }
+
     /// Masked-store regression: every chunk-boundary length must decode into an
     /// exactly-sized buffer without overrunning, for both alphabets and
     /// padded/unpadded input.
@@ -1279,32 +1759,31 @@ mod miri_avx512_vbmi_coverage {
 #[cfg(all(test, not(miri)))]
 mod avx512_vbmi_hardware_coverage {
     use super::*;
-    use crate::simd::testutil::check_decode_exact;
+    use crate::simd::testutil::{check_decode_exact, has_avx512_vbmi};
     use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE};
 
     /// The same exact-buffer masked-store regression the Miri suite runs, but on
     /// real AVX-512-VBMI silicon (skipped when the host CPU lacks it).
     #[test]
     fn hw_avx512_vbmi_decode_exact_buffer_boundaries() {
-        if !(std::is_x86_feature_detected!("avx512f")
-            && std::is_x86_feature_detected!("avx512bw")
-            && std::is_x86_feature_detected!("avx512vbmi"))
-        {
-            eprintln!("skipping: host CPU lacks AVX-512-VBMI");
+        if !has_avx512_vbmi() {
             return;
         }
 
         let standard = Config {
             url_safe: false,
             padding: true,
+            lenient: false,
         };
         let url_safe = Config {
             url_safe: true,
             padding: true,
+            lenient: false,
         };
         let no_pad = Config {
             url_safe: false,
             padding: false,
+            lenient: false,
         };
 
         for &len in &[3, 6, 45, 48, 51, 96, 192, 193, 195, 240, 384, 1000, 1001] {
@@ -1314,3 +1793,632 @@ mod avx512_vbmi_hardware_coverage {
         }
     }
 }
+
+/// The short-input kernels against the oracle at every length they take, and
+/// against scalar on every malformed input of those lengths (skipped when the
+/// host CPU lacks AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_short_inputs {
+    use super::*;
+    use crate::simd::testutil::{check_decode_short, check_encode_short, has_avx512_vbmi};
+
+    #[test]
+    fn hw_avx512_vbmi_encode_short_all_lengths() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        check_encode_short(encode_short_avx512_vbmi, ENC_SHORT_MAX);
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_decode_short_all_lengths() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        check_decode_short(decode_short_avx512_vbmi, DEC_SHORT_MAX);
+    }
+}
+
+/// The non-temporal tiers need an input at least [`NT_STORE_MIN_LEN`] long, so
+/// they are out of reach for Miri and the sweeps above. The hazards are the
+/// lag that brings `_mm512_stream_si512` onto a 64-byte boundary and the
+/// decoder's stitching, so the destinations sit at several phases against a
+/// boundary, every one of them streaming, and all must agree with the oracle.
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_non_temporal {
+    use super::*;
+    use crate::simd::NT_STORE_MIN_LEN;
+    use crate::simd::testutil::has_avx512_vbmi;
+    use base64::Engine as _;
+    use base64::engine::general_purpose::{STANDARD as REF_STANDARD, URL_SAFE as REF_URL_SAFE};
+
+    const CONFIGS: [(Config, &base64::engine::GeneralPurpose); 2] = [
+        (
+            Config {
+                url_safe: false,
+                padding: true,
+                lenient: false,
+            },
+            &REF_STANDARD,
+        ),
+        (
+            Config {
+                url_safe: true,
+                padding: true,
+                lenient: false,
+            },
+            &REF_URL_SAFE,
+        ),
+    ];
+
+    /// A buffer of `len` bytes at each offset from a 64-byte boundary tested.
+    fn shifted(len: usize) -> impl Iterator<Item = (Vec<u8>, usize)> {
+        [0usize, 1, 16, 32, 63].into_iter().map(move |shift| {
+            let buf = vec![0u8; len + 64 + shift];
+            let start = buf.as_ptr().align_offset(64) + shift;
+            (buf, start)
+        })
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_encode_above_non_temporal_threshold() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+
+        // One length exactly at the threshold and one past it whose remainder
+        // reaches the single and masked tiers and the scalar tail.
+        for len in [NT_STORE_MIN_LEN, NT_STORE_MIN_LEN + 4099] {
+            let input = crate::simd::testutil::bytes(len);
+            for (config, oracle) in &CONFIGS {
+                let expected = oracle.encode(&input);
+                for (mut buf, start) in shifted(expected.len()) {
+                    let dst = &mut buf[start..start + expected.len()];
+                    unsafe { encode_slice_avx512_vbmi(config, &input, dst) };
+                    assert!(
+                        dst == expected.as_bytes(),
+                        "len {len}, url_safe {}, dst offset {}",
+                        config.url_safe,
+                        start % 64
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_decode_above_non_temporal_threshold() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+
+        // Decoded sizes whose encodings land at and past the threshold; the
+        // exact-size buffer catches a stitched store that runs long.
+        for len in [NT_STORE_MIN_LEN / 4 * 3, NT_STORE_MIN_LEN / 4 * 3 + 4099] {
+            let input = crate::simd::testutil::bytes(len);
+            for (config, oracle) in &CONFIGS {
+                let encoded = oracle.encode(&input);
+                for (mut buf, start) in shifted(len) {
+                    let dst = &mut buf[start..start + len];
+                    let n = unsafe { decode_slice_avx512_vbmi(config, encoded.as_bytes(), dst) };
+                    assert_eq!(n, Ok(len));
+                    assert!(
+                        dst == &input[..],
+                        "len {len}, url_safe {}, dst offset {}",
+                        config.url_safe,
+                        start % 64
+                    );
+                }
+            }
+        }
+    }
+
+    /// A bad character deep inside the streamed region is still reported.
+    #[test]
+    fn hw_avx512_vbmi_decode_non_temporal_rejects_invalid() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+
+        let (config, oracle) = &CONFIGS[0];
+        let mut encoded = oracle
+            .encode(crate::simd::testutil::bytes(NT_STORE_MIN_LEN))
+            .into_bytes();
+        encoded[NT_STORE_MIN_LEN / 2 + 7] = b'*';
+        let (mut buf, start) = shifted(NT_STORE_MIN_LEN).next().unwrap();
+        let result = unsafe { decode_slice_avx512_vbmi(config, &encoded, &mut buf[start..]) };
+        assert_eq!(result, Err(Error::InvalidCharacter));
+    }
+}
+
+/// The hex kernels against the `hex` crate at every length across the first
+/// few steps and some larger ones, and against scalar's verdict on a bad
+/// digit in every position: full steps, the masked pass, and the odd digit
+/// scalar sees (skipped when the host CPU lacks AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_hex {
+    use super::*;
+    use crate::simd::testutil::{check_hex, check_hex_rejects, has_avx512_vbmi};
+
+    #[test]
+    fn hw_avx512_vbmi_hex_all_lengths_0_to_300() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..=300).chain([1_000, 4_097, 100_003]) {
+            check_hex(encode_hex_avx512_vbmi, decode_hex_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_hex_decode_rejects_invalid() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in [2, 62, 64, 66, 128, 190] {
+            check_hex_rejects(decode_hex_avx512_vbmi, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_hex {
+    use super::*;
+    use crate::simd::testutil::{check_hex, check_hex_rejects};
+
+    /// The masked pass alone, a full step, a step plus masked pass, and each
+    /// with the odd digit the decoder leaves to scalar.
+    #[test]
+    fn miri_avx512_vbmi_hex_tier_boundaries() {
+        for len in [0, 1, 31, 32, 33, 64, 100] {
+            check_hex(encode_hex_avx512_vbmi, decode_hex_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn miri_avx512_vbmi_hex_decode_rejects_invalid() {
+        check_hex_rejects(decode_hex_avx512_vbmi, 66);
+    }
+}
+
+/// The Base32 kernels against the `base32` crate under every engine, at every
+/// length across the first few steps and some larger ones, and against
+/// scalar's verdict on malformed input (skipped when the host CPU lacks
+/// AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_base32 {
+    use super::*;
+    use crate::simd::testutil::{check_base32, check_base32_rejects, has_avx512_vbmi};
+
+    #[test]
+    fn hw_avx512_vbmi_base32_all_lengths_0_to_300() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..=300).chain([1_000, 4_097, 100_003]) {
+            check_base32(encode_base32_avx512_vbmi, decode_base32_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_base32_decode_rejects_invalid() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in [1, 5, 39, 40, 44, 80, 121] {
+            check_base32_rejects(decode_base32_avx512_vbmi, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_base32 {
+    use super::*;
+    use crate::simd::testutil::{check_base32, check_base32_rejects};
+
+    /// The masked passes alone, a full step, a step plus masked pass, and each
+    /// with a partial final group for scalar.
+    #[test]
+    fn miri_avx512_vbmi_base32_tier_boundaries() {
+        for len in [0, 1, 4, 5, 39, 40, 41, 44, 80, 83] {
+            check_base32(encode_base32_avx512_vbmi, decode_base32_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn miri_avx512_vbmi_base32_decode_rejects_invalid() {
+        check_base32_rejects(decode_base32_avx512_vbmi, 44);
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_transcode_avx512_vbmi {
+    // Index proofs for the kernels in `super::transcode`. The remap reads and
+    // writes the same offsets, so one bound covers both; the hex ones stride
+    // two lengths at once.
+
+    use super::super::transcode::{B64_HEX_IN, B64_HEX_OUT, HEX_B64_IN, HEX_B64_OUT, REMAP_STEP};
+
+    /// Lanes in a ZMM, the most any mask may select.
+    const ZMM_LANES: usize = 64;
+
+    /// Inductive step: a full 64-byte load and store, both in bounds.
+    #[kani::proof]
+    fn check_vbmi_remap_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(i <= usize::MAX / REMAP_STEP);
+        let done = REMAP_STEP * i;
+        kani::assume(done <= usize::MAX - REMAP_STEP && done + REMAP_STEP <= len);
+
+        assert!(done + REMAP_STEP <= len, "remap step leaves the buffer");
+    }
+
+    /// The masked pass: its mask is in range and ends exactly at `len`, so
+    /// nothing is left over.
+    #[kani::proof]
+    fn check_vbmi_remap_masked_pass() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(i <= usize::MAX / REMAP_STEP);
+        let done = REMAP_STEP * i;
+        kani::assume(done <= len && len - done < REMAP_STEP); // loop exit
+
+        let take = len - done;
+        assert!(take < ZMM_LANES, "mask shift out of range");
+        assert_eq!(done + take, len);
+    }
+
+    /// Base64 -> hex step: a full load, and a full store plus a 32-lane one
+    /// that end exactly at the step's 96 digits.
+    #[kani::proof]
+    fn check_vbmi_b64_hex_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 4 == 0 && len <= usize::MAX / 6);
+        kani::assume(i <= len / B64_HEX_IN);
+        let done = B64_HEX_IN * i;
+        kani::assume(done + B64_HEX_IN <= len);
+        let written = B64_HEX_OUT * i;
+
+        assert!(done + B64_HEX_IN <= len, "load leaves the input");
+        assert!(written + ZMM_LANES + (B64_HEX_OUT - ZMM_LANES) <= len / 4 * 6);
+        assert!(
+            B64_HEX_OUT - ZMM_LANES <= ZMM_LANES,
+            "mask shift out of range"
+        );
+    }
+
+    /// Base64 -> hex masked pass: both store masks are in range and end at the
+    /// output's end.
+    #[kani::proof]
+    fn check_vbmi_b64_hex_masked_pass() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 4 == 0 && len <= usize::MAX / 6);
+        kani::assume(i <= len / B64_HEX_IN);
+        let done = B64_HEX_IN * i;
+        kani::assume(done <= len && len - done < B64_HEX_IN); // loop exit
+        let written = B64_HEX_OUT * i;
+
+        let take = len - done;
+        assert!(take < ZMM_LANES, "load mask out of range");
+        let n = take / 4 * 6;
+        let lo = n.min(ZMM_LANES);
+        assert!(written + lo <= len / 4 * 6);
+        if n > ZMM_LANES {
+            assert!(n - ZMM_LANES <= ZMM_LANES, "mask shift out of range");
+        }
+        assert_eq!(written + n, len / 4 * 6);
+    }
+
+    /// hex -> Base64 step: a full load plus a 32-lane one, both in bounds, and
+    /// a full store.
+    #[kani::proof]
+    fn check_vbmi_hex_b64_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 6 == 0);
+        kani::assume(i <= len / HEX_B64_IN);
+        let done = HEX_B64_IN * i;
+        kani::assume(done + HEX_B64_IN <= len);
+        let written = HEX_B64_OUT * i;
+
+        assert!(
+            done + ZMM_LANES + (HEX_B64_IN - ZMM_LANES) <= len,
+            "load leaves the input"
+        );
+        assert!(
+            written + HEX_B64_OUT <= len / 6 * 4,
+            "store leaves the output"
+        );
+    }
+
+    /// hex -> Base64 masked pass: the second load only runs when it has lanes
+    /// to read, and the store mask ends at the output's end.
+    #[kani::proof]
+    fn check_vbmi_hex_b64_masked_pass() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 6 == 0);
+        kani::assume(i <= len / HEX_B64_IN);
+        let done = HEX_B64_IN * i;
+        kani::assume(done <= len && len - done < HEX_B64_IN); // loop exit
+        let written = HEX_B64_OUT * i;
+
+        let take = len - done;
+        if take > ZMM_LANES {
+            assert!(take - ZMM_LANES < ZMM_LANES, "mask shift out of range");
+            assert!(done + ZMM_LANES < len, "second load starts past the input");
+        }
+        assert!(take / 6 * 4 < ZMM_LANES, "mask shift out of range");
+        assert_eq!(written + take / 6 * 4, len / 6 * 4);
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_garbage_avx512_vbmi {
+    // Proofs for the compaction kernel in `super::garbage`. Its stores write at
+    // `kept`, which never passes `done`, so they are bounded by the loads; the
+    // `vpermb` control is built in safe code, and proven to gather exactly the
+    // kept lanes, in order.
+
+    use super::super::garbage::{COMPACT_STEP, compact_control};
+
+    /// Lanes in a ZMM, the most any mask may select.
+    const ZMM_LANES: usize = 64;
+
+    /// Inductive step: a full load, and a store of at most 64 bytes from `kept`
+    /// that ends inside the step, so inside `dst`.
+    #[kani::proof]
+    fn check_vbmi_compact_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        let kept: usize = kani::any();
+        kani::assume(i <= usize::MAX / COMPACT_STEP);
+        let done = COMPACT_STEP * i;
+        kani::assume(done <= usize::MAX - COMPACT_STEP && done + COMPACT_STEP <= len);
+        kani::assume(kept <= done); // one byte kept per byte read, at most
+        let n: usize = kani::any();
+        kani::assume(n <= COMPACT_STEP);
+
+        assert!(done + COMPACT_STEP <= len, "load leaves the input");
+        assert!(kept + n <= len, "store leaves the output");
+        assert!(kept + n <= done + COMPACT_STEP, "kept passes done");
+    }
+
+    /// The masked pass: its load mask is in range, and its store ends inside
+    /// `dst`.
+    #[kani::proof]
+    fn check_vbmi_compact_masked_pass() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        let kept: usize = kani::any();
+        kani::assume(i <= usize::MAX / COMPACT_STEP);
+        let done = COMPACT_STEP * i;
+        kani::assume(done <= len && len - done < COMPACT_STEP); // loop exit
+        kani::assume(kept <= done);
+
+        let take = len - done;
+        let n: usize = kani::any();
+        kani::assume(n <= take); // the mask is cut to `take` lanes
+        assert!(take < ZMM_LANES, "mask shift out of range");
+        assert!(kept + n <= len, "store leaves the output");
+    }
+
+    /// For every mask, the control's first lanes are the set bits' positions,
+    /// in order: the store gathers exactly the kept bytes. Building it indexes
+    /// in bounds, which Kani checks along the way.
+    #[kani::proof]
+    #[kani::unwind(65)]
+    fn check_vbmi_compact_control() {
+        let keep: u64 = kani::any();
+        let control = compact_control(keep);
+        let mut j = 0;
+        for lane in 0..ZMM_LANES {
+            if (keep >> lane) & 1 == 1 {
+                assert_eq!(usize::from(control[j]), lane, "control lane {j}");
+                j += 1;
+            }
+        }
+        assert_eq!(j, keep.count_ones() as usize);
+    }
+}
+
+/// The crypt(3) and bcrypt kernels against the `base64` crate under both
+/// schemes, at every length across the first few steps and some larger ones,
+/// and against scalar's verdict on malformed input (skipped when the host CPU
+/// lacks AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_crypt {
+    use super::*;
+    use crate::simd::testutil::{check_crypt, check_crypt_rejects, has_avx512_vbmi};
+
+    #[test]
+    fn hw_avx512_vbmi_crypt_all_lengths_0_to_300() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..=300).chain([1_000, 4_097, 100_003]) {
+            check_crypt(encode_crypt_avx512_vbmi, decode_crypt_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_crypt_decode_rejects_invalid() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in [1, 2, 3, 16, 47, 48, 49, 64, 96, 101] {
+            check_crypt_rejects(decode_crypt_avx512_vbmi, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_crypt {
+    use super::*;
+    use crate::simd::testutil::{check_crypt, check_crypt_rejects};
+
+    /// The masked passes alone, a full step, a step plus masked pass, and each
+    /// with a partial final group for scalar.
+    #[test]
+    fn miri_avx512_vbmi_crypt_tier_boundaries() {
+        for len in [0, 1, 2, 3, 47, 48, 49, 50, 96, 100] {
+            check_crypt(encode_crypt_avx512_vbmi, decode_crypt_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn miri_avx512_vbmi_crypt_decode_rejects_invalid() {
+        check_crypt_rejects(decode_crypt_avx512_vbmi, 50);
+    }
+}
+
+/// The transcoding kernels against scalar: the remap between every pair of
+/// alphabets, copying and in place, and Base64 to and from hex under every
+/// config (skipped when the host CPU lacks AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_transcode {
+    use super::*;
+    use crate::simd::testutil::{
+        check_base64_to_hex, check_hex_to_base64, check_remap, has_avx512_vbmi,
+    };
+
+    #[test]
+    fn hw_avx512_vbmi_remap_lengths() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..=100).chain([144, 1_000]) {
+            check_remap(remap_avx512_vbmi, remap_in_place_avx512_vbmi, len);
+        }
+    }
+    #[test]
+    fn hw_avx512_vbmi_base64_to_hex_lengths() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for groups in (0..=40).chain([64, 250]) {
+            check_base64_to_hex(base64_to_hex_avx512_vbmi, groups);
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_hex_to_base64_lengths() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for groups in (0..=40).chain([64, 250]) {
+            check_hex_to_base64(hex_to_base64_avx512_vbmi, groups);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_transcode {
+    use super::*;
+    use crate::simd::testutil::{check_base64_to_hex, check_hex_to_base64, check_remap};
+
+    /// The masked pass alone, a full step, and a step plus masked pass.
+    #[test]
+    fn miri_avx512_vbmi_remap_tier_boundaries() {
+        for len in [0, 1, 47, 48, 49, 50] {
+            check_remap(remap_avx512_vbmi, remap_in_place_avx512_vbmi, len);
+        }
+    }
+    /// The masked pass with one and two stores, a full step, and a step plus
+    /// masked pass.
+    #[test]
+    fn miri_avx512_vbmi_base64_to_hex_tier_boundaries() {
+        for groups in [0, 1, 10, 11, 16, 17] {
+            check_base64_to_hex(base64_to_hex_avx512_vbmi, groups);
+        }
+    }
+
+    /// The masked pass with one and two loads, a full step, and a step plus
+    /// masked pass.
+    #[test]
+    fn miri_avx512_vbmi_hex_to_base64_tier_boundaries() {
+        for groups in [0, 1, 10, 11, 16, 17] {
+            check_hex_to_base64(hex_to_base64_avx512_vbmi, groups);
+        }
+    }
+}
+
+/// The lenient decode LUT: both alphabets mixed, padded and not, at every
+/// length across the tiers, and against scalar's verdict on malformed input
+/// (skipped when the host CPU lacks AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_lenient {
+    use super::*;
+    use crate::simd::testutil::{check_decode_lenient_exact, has_avx512_vbmi};
+
+    #[test]
+    fn hw_avx512_vbmi_decode_lenient_all_lengths() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..=300).chain([1_000, 4_097]) {
+            check_decode_lenient_exact(decode_slice_avx512_vbmi, len);
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_decode_short_lenient() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..).take_while(|len: &usize| len.div_ceil(3) * 4 <= DEC_SHORT_MAX) {
+            check_decode_lenient_exact(decode_short_avx512_vbmi, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient_exact;
+
+    /// The masked tier alone, a single step, and a quad step, each with a
+    /// scalar tail.
+    #[test]
+    fn miri_avx512_vbmi_decode_lenient_tier_boundaries() {
+        for len in [0, 1, 2, 3, 6, 49, 51, 195] {
+            check_decode_lenient_exact(decode_slice_avx512_vbmi, len);
+        }
+    }
+}
+
+/// The compaction kernel against scalar at every length across the first few
+/// steps and some larger ones, at every density of garbage (skipped when the
+/// host CPU lacks AVX-512-VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_garbage {
+    use super::*;
+    use crate::simd::testutil::{check_compact, has_avx512_vbmi};
+
+    #[test]
+    fn hw_avx512_vbmi_compact_all_lengths_0_to_300() {
+        if !has_avx512_vbmi() {
+            return;
+        }
+        for len in (0..=300).chain([1_000, 4_096, 4_097]) {
+            check_compact(compact_avx512_vbmi, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_garbage {
+    use super::*;
+    use crate::simd::testutil::check_compact;
+
+    /// The masked pass alone, a full step, and a step plus masked pass.
+    #[test]
+    fn miri_avx512_vbmi_compact_tier_boundaries() {
+        for len in [0, 1, 63, 64, 65, 130] {
+            check_compact(compact_avx512_vbmi, len);
+        }
+    }
+}
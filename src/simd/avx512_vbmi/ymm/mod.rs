@@ -0,0 +1,353 @@
+//! The VBMI kernel at 256 bits (AVX-512VL), for hosts where ZMM code costs more
+//! than it wins: on several Intel generations any 512-bit instruction drops
+//! the core into a lower frequency licence, which slows everything else sharing
+//! it, latency-sensitive neighbours included. YMM-width VBMI keeps the same
+//! instructions — `vpermb` gather, `vpmultishiftqb` field split, `vpermi2b`
+//! lookups, `k`-masked tails — without asking for that licence.
+//!
+//! Halving the register halves what each lookup can see, so two things change
+//! from the ZMM kernel. The 64-character alphabet no longer fits one register
+//! and is looked up with `vpermi2b` across two, and the 128-byte decode table
+//! takes two `vpermi2b` lookups, one per half of ASCII, blended on bit 6 of the
+//! character. Everything else, tables included, is the parent module's.
+
+use super::{
+    VBMI_DECODE_LENIENT, VBMI_DECODE_STANDARD, VBMI_DECODE_URL_SAFE, VBMI_ENCODE_GATHER,
+    VBMI_ENCODE_STANDARD, VBMI_ENCODE_URL_SAFE, VBMI_MULTISHIFT, VBMI_PACK_L1, VBMI_PACK_L2,
+    VBMI_PACK_SHUFFLE,
+};
+use crate::{Config, Error};
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m256i, _mm256_loadu_si256, _mm256_madd_epi16, _mm256_maddubs_epi16, _mm256_mask_blend_epi8,
+    _mm256_mask_loadu_epi8, _mm256_mask_storeu_epi8, _mm256_maskz_loadu_epi8, _mm256_movepi8_mask,
+    _mm256_set1_epi8, _mm256_set1_epi16, _mm256_set1_epi32, _mm256_set1_epi64x,
+    _mm256_setzero_si256, _mm256_storeu_si256, _mm256_ternarylogic_epi32, _mm256_test_epi8_mask,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m256i, _mm256_loadu_si256, _mm256_madd_epi16, _mm256_maddubs_epi16, _mm256_mask_blend_epi8,
+    _mm256_mask_loadu_epi8, _mm256_mask_storeu_epi8, _mm256_maskz_loadu_epi8, _mm256_movepi8_mask,
+    _mm256_set1_epi8, _mm256_set1_epi16, _mm256_set1_epi32, _mm256_set1_epi64x,
+    _mm256_setzero_si256, _mm256_storeu_si256, _mm256_ternarylogic_epi32, _mm256_test_epi8_mask,
+};
+
+#[cfg(all(not(miri), target_arch = "x86"))]
+use core::arch::x86::{
+    _mm256_multishift_epi64_epi8, _mm256_permutex2var_epi8, _mm256_permutexvar_epi8,
+};
+#[cfg(all(not(miri), target_arch = "x86_64"))]
+use core::arch::x86_64::{
+    _mm256_multishift_epi64_epi8, _mm256_permutex2var_epi8, _mm256_permutexvar_epi8,
+};
+
+// --- Stride constants ---
+//
+// The ZMM kernel's, at half the width; see the parent module for why the
+// guards are derived rather than written out.
+
+/// Bytes a full-width load reads or a full-width store writes.
+const ENC_VEC: usize = 32;
+/// Input bytes one encode vector consumes.
+const ENC_VEC_IN: usize = 24;
+/// Characters one encode vector produces.
+const ENC_VEC_OUT: usize = 32;
+/// Vectors per iteration of the encoder's quad tier.
+const ENC_UNROLL: usize = 4;
+/// Input bytes per quad-tier iteration.
+const ENC_QUAD_IN: usize = ENC_VEC_IN * ENC_UNROLL;
+/// Characters per quad-tier iteration.
+const ENC_QUAD_OUT: usize = ENC_VEC_OUT * ENC_UNROLL;
+/// Quad-tier guard. The last load starts 72 bytes in and reads 32, so 104
+/// would do; this is the output-sized round number above it, as for ZMM.
+const ENC_QUAD_MIN: usize = 128;
+/// Single-tier guard: a plain load reads a whole vector to consume 24 of it.
+const ENC_SINGLE_MIN: usize = ENC_VEC;
+/// Input bytes per Base64 group; the masked tier handles whole groups only.
+const ENC_GROUP: usize = 3;
+
+/// Characters one decode vector consumes, which is also its load width.
+const DEC_VEC_IN: usize = 32;
+/// Bytes one decode vector produces.
+const DEC_VEC_OUT: usize = 24;
+/// Vectors per iteration of the decoder's quad tier.
+const DEC_UNROLL: usize = 4;
+/// Characters per quad-tier iteration.
+const DEC_QUAD_IN: usize = DEC_VEC_IN * DEC_UNROLL;
+/// Bytes per quad-tier iteration.
+const DEC_QUAD_OUT: usize = DEC_VEC_OUT * DEC_UNROLL;
+/// Characters per Base64 group.
+const DEC_GROUP: usize = 4;
+/// Characters every decode tier stops short of the end, leaving the final
+/// group to the scalar tail.
+const DEC_LEAD: usize = 4;
+/// Quad-tier guard: what it consumes, plus the margin.
+const DEC_QUAD_MIN: usize = DEC_QUAD_IN + DEC_LEAD;
+/// Single-tier guard: what it consumes, plus the margin.
+const DEC_SINGLE_MIN: usize = DEC_VEC_IN + DEC_LEAD;
+/// Masked-tier guard: one group, plus the margin.
+const DEC_MASKED_MIN: usize = DEC_GROUP + DEC_LEAD;
+
+/// Store mask selecting the low 24 bytes of a decoded vector.
+const LOW_24: u32 = (1u32 << DEC_VEC_OUT) - 1;
+
+// ======================================================================
+// Miri-compatible VBMI shims
+// ======================================================================
+
+#[cfg(miri)]
+use self::verify::intrinsic_models as m;
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vl,avx512vbmi")]
+unsafe fn ymm_permutexvar_epi8(idx: __m256i, a: __m256i) -> __m256i {
+    #[cfg(miri)]
+    {
+        unsafe { m::permutexvar_epi8_model(idx, a) }
+    }
+    #[cfg(not(miri))]
+    {
+        _mm256_permutexvar_epi8(idx, a)
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vl,avx512vbmi")]
+unsafe fn ymm_permutex2var_epi8(a: __m256i, idx: __m256i, b: __m256i) -> __m256i {
+    #[cfg(miri)]
+    {
+        unsafe { m::permutex2var_epi8_model(a, idx, b) }
+    }
+    #[cfg(not(miri))]
+    {
+        _mm256_permutex2var_epi8(a, idx, b)
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw,avx512vl,avx512vbmi")]
+unsafe fn ymm_multishift_epi64_epi8(a: __m256i, b: __m256i) -> __m256i {
+    #[cfg(miri)]
+    {
+        unsafe { m::multishift_epi64_epi8_model(a, b) }
+    }
+    #[cfg(not(miri))]
+    {
+        _mm256_multishift_epi64_epi8(a, b)
+    }
+}
+
+// --- VBMI/VL encoder ---
+
+#[target_feature(enable = "avx512f,avx512bw,avx512vl,avx512vbmi")]
+pub(crate) unsafe fn encode_slice_avx512_vbmi_256(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) {
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+    let mut rem = input.len();
+
+    // The ZMM gather's first four qwords: they only reach input bytes 0..23.
+    let gather = unsafe { _mm256_loadu_si256(VBMI_ENCODE_GATHER.as_ptr().cast()) };
+    let shifts = _mm256_set1_epi64x(VBMI_MULTISHIFT);
+
+    // The alphabet across two YMMs; vpermi2b picks the register by each index's
+    // bit 5 and ignores the garbage in its top 2 bits, as vpermb does for ZMM.
+    let alphabet = if config.url_safe {
+        &VBMI_ENCODE_URL_SAFE
+    } else {
+        &VBMI_ENCODE_STANDARD
+    };
+    let alpha_lo = unsafe { _mm256_loadu_si256(alphabet.as_ptr().cast()) };
+    let alpha_hi = unsafe { _mm256_loadu_si256(alphabet.as_ptr().add(32).cast()) };
+
+    /// 24 input bytes in a YMM -> 32 output characters, in three port-5 ops.
+    macro_rules! encode_vec {
+        ($v:expr) => {{
+            let raw = $v;
+            let g = unsafe { ymm_permutexvar_epi8(gather, raw) };
+            let indices = unsafe { ymm_multishift_epi64_epi8(shifts, g) };
+            unsafe { ymm_permutex2var_epi8(alpha_lo, indices, alpha_hi) }
+        }};
+    }
+    macro_rules! load_24 {
+        ($off:expr) => {{ unsafe { _mm256_loadu_si256(src.add($off).cast()) } }};
+    }
+
+    // Quad tier: 96 input bytes -> 128 output.
+    while rem >= ENC_QUAD_MIN {
+        let r0 = encode_vec!(load_24!(0));
+        let r1 = encode_vec!(load_24!(ENC_VEC_IN));
+        let r2 = encode_vec!(load_24!(2 * ENC_VEC_IN));
+        let r3 = encode_vec!(load_24!(3 * ENC_VEC_IN));
+        unsafe { _mm256_storeu_si256(dst.cast(), r0) };
+        unsafe { _mm256_storeu_si256(dst.add(ENC_VEC_OUT).cast(), r1) };
+        unsafe { _mm256_storeu_si256(dst.add(2 * ENC_VEC_OUT).cast(), r2) };
+        unsafe { _mm256_storeu_si256(dst.add(3 * ENC_VEC_OUT).cast(), r3) };
+        src = unsafe { src.add(ENC_QUAD_IN) };
+        dst = unsafe { dst.add(ENC_QUAD_OUT) };
+        rem -= ENC_QUAD_IN;
+    }
+
+    // Single tier: 24 input bytes -> 32 output, reading 32 to consume 24.
+    while rem >= ENC_SINGLE_MIN {
+        let r = encode_vec!(load_24!(0));
+        unsafe { _mm256_storeu_si256(dst.cast(), r) };
+        src = unsafe { src.add(ENC_VEC_IN) };
+        dst = unsafe { dst.add(ENC_VEC_OUT) };
+        rem -= ENC_VEC_IN;
+    }
+
+    // Masked tier: whole triples only. `rem` is now < 32 and `take` is capped
+    // at 24, so this runs at most twice.
+    while rem >= ENC_GROUP {
+        let take = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
+        let out = take / ENC_GROUP * 4;
+        let v = unsafe { _mm256_maskz_loadu_epi8(u32::MAX >> (ENC_VEC - take), src.cast()) };
+        let chars = encode_vec!(v);
+        unsafe { _mm256_mask_storeu_epi8(dst.cast::<i8>(), u32::MAX >> (ENC_VEC - out), chars) };
+        src = unsafe { src.add(take) };
+        dst = unsafe { dst.add(out) };
+        rem -= take;
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::super::tail::encode(config, input, src, dst_slice, dst_off) };
+}
+
+// --- VBMI/VL decoder ---
+
+#[target_feature(enable = "avx512f,avx512bw,avx512vl,avx512vbmi")]
+pub(crate) unsafe fn decode_slice_avx512_vbmi_256(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) -> Result<usize, Error> {
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+    let mut rem = input.len();
+
+    // The 128-byte reverse LUT across four YMMs: one vpermi2b per 64-byte half,
+    // and bit 6 of the character picks which half answers.
+    let lut = if config.lenient {
+        &VBMI_DECODE_LENIENT
+    } else if config.url_safe {
+        &VBMI_DECODE_URL_SAFE
+    } else {
+        &VBMI_DECODE_STANDARD
+    };
+    let lut0 = unsafe { _mm256_loadu_si256(lut.as_ptr().cast()) };
+    let lut1 = unsafe { _mm256_loadu_si256(lut.as_ptr().add(32).cast()) };
+    let lut2 = unsafe { _mm256_loadu_si256(lut.as_ptr().add(64).cast()) };
+    let lut3 = unsafe { _mm256_loadu_si256(lut.as_ptr().add(96).cast()) };
+    let bit6 = _mm256_set1_epi8(0x40);
+
+    let pack_l1 = _mm256_set1_epi16(VBMI_PACK_L1);
+    let pack_l2 = _mm256_set1_epi32(VBMI_PACK_L2);
+    // The ZMM compress control's first eight dwords. Six of them gather the
+    // 24 output bytes; vpermb reads only 5 index bits here, so the other two
+    // fill bytes 24..31 with junk that every store masks or overwrites.
+    let pack = unsafe { _mm256_loadu_si256(VBMI_PACK_SHUFFLE.as_ptr().cast()) };
+
+    // Validity as for ZMM: bit 7 of `input | index` is set for any bad
+    // character, so one OR-accumulator tested once covers the buffer.
+    let mut bad = _mm256_setzero_si256();
+
+    macro_rules! lookup_vec {
+        ($v:expr) => {{
+            let v = $v;
+            let lo = unsafe { ymm_permutex2var_epi8(lut0, v, lut1) };
+            let hi = unsafe { ymm_permutex2var_epi8(lut2, v, lut3) };
+            _mm256_mask_blend_epi8(_mm256_test_epi8_mask(v, bit6), lo, hi)
+        }};
+    }
+    macro_rules! pack_vec {
+        ($idx:expr) => {{
+            let m = _mm256_maddubs_epi16($idx, pack_l1);
+            let p = _mm256_madd_epi16(m, pack_l2);
+            unsafe { ymm_permutexvar_epi8(pack, p) }
+        }};
+    }
+
+    // Quad tier: 128 input characters -> 96 output bytes.
+    while rem >= DEC_QUAD_MIN {
+        let v0 = unsafe { _mm256_loadu_si256(src.cast::<__m256i>()) };
+        let v1 = unsafe { _mm256_loadu_si256(src.add(DEC_VEC_IN).cast::<__m256i>()) };
+        let v2 = unsafe { _mm256_loadu_si256(src.add(2 * DEC_VEC_IN).cast::<__m256i>()) };
+        let v3 = unsafe { _mm256_loadu_si256(src.add(3 * DEC_VEC_IN).cast::<__m256i>()) };
+
+        let i0 = lookup_vec!(v0);
+        let i1 = lookup_vec!(v1);
+        let i2 = lookup_vec!(v2);
+        let i3 = lookup_vec!(v3);
+
+        let p0 = pack_vec!(i0);
+        let p1 = pack_vec!(i1);
+        let p2 = pack_vec!(i2);
+        let p3 = pack_vec!(i3);
+
+        let t0 = _mm256_ternarylogic_epi32::<0xFE>(v0, i0, v1);
+        let t1 = _mm256_ternarylogic_epi32::<0xFE>(i1, v2, i2);
+        let t2 = _mm256_ternarylogic_epi32::<0xFE>(v3, i3, t0);
+        bad = _mm256_ternarylogic_epi32::<0xFE>(bad, t1, t2);
+
+        // As for ZMM, only the last store is masked: each of the first three
+        // overhangs its 24 bytes by 8, which the next store rewrites.
+        unsafe { _mm256_storeu_si256(dst.cast(), p0) };
+        unsafe { _mm256_storeu_si256(dst.add(DEC_VEC_OUT).cast(), p1) };
+        unsafe { _mm256_storeu_si256(dst.add(2 * DEC_VEC_OUT).cast(), p2) };
+        unsafe { _mm256_mask_storeu_epi8(dst.add(3 * DEC_VEC_OUT).cast::<i8>(), LOW_24, p3) };
+
+        src = unsafe { src.add(DEC_QUAD_IN) };
+        dst = unsafe { dst.add(DEC_QUAD_OUT) };
+        rem -= DEC_QUAD_IN;
+    }
+
+    // Single tier: 32 input characters -> 24 output bytes.
+    while rem >= DEC_SINGLE_MIN {
+        let v = unsafe { _mm256_loadu_si256(src.cast::<__m256i>()) };
+        let idx = lookup_vec!(v);
+        bad = _mm256_ternarylogic_epi32::<0xFE>(bad, v, idx);
+        let p = pack_vec!(idx);
+        unsafe { _mm256_mask_storeu_epi8(dst.cast::<i8>(), LOW_24, p) };
+        src = unsafe { src.add(DEC_VEC_IN) };
+        dst = unsafe { dst.add(DEC_VEC_OUT) };
+        rem -= DEC_VEC_IN;
+    }
+
+    // Masked tier, with the lanes past the end backfilled with 'A'.
+    if rem >= DEC_MASKED_MIN {
+        let take = (rem - DEC_LEAD) & !(DEC_GROUP - 1);
+        let out = take / DEC_GROUP * 3;
+        let v = unsafe {
+            _mm256_mask_loadu_epi8(
+                _mm256_set1_epi8(b'A'.cast_signed()),
+                u32::MAX >> (DEC_VEC_IN - take),
+                src.cast(),
+            )
+        };
+        let idx = lookup_vec!(v);
+        bad = _mm256_ternarylogic_epi32::<0xFE>(bad, v, idx);
+        let p = pack_vec!(idx);
+        unsafe { _mm256_mask_storeu_epi8(dst.cast::<i8>(), u32::MAX >> (DEC_VEC_IN - out), p) };
+        src = unsafe { src.add(take) };
+        dst = unsafe { dst.add(out) };
+    }
+
+    if _mm256_movepi8_mask(bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::super::tail::decode(config, input, src, dst_slice, dst_off) }
+}
+
+// Verification: Kani proofs, Intel-pseudocode intrinsic models, and the Miri +
+// hardware coverage suites, laid out as the parent module's.
+#[cfg(any(kani, test, miri))]
+mod verify;
@@ -0,0 +1,1219 @@
+//! AVX-512VL+VBMI (YMM) verification: Kani proofs, Intel-pseudocode intrinsic
+//! models, and the Miri + hardware coverage suites. Laid out like the parent
+//! module's `verify.rs`, whose tier structure this kernel shares at half width.
+
+use super::*;
+
+#[cfg(kani)]
+mod kani_verification_avx512_vbmi_256 {
+    use super::*;
+    use crate::{Config, STANDARD as TURBO_STANDARD, STANDARD_NO_PAD as TURBO_STANDARD_NO_PAD};
+
+    // Only used inside `#[kani::stub(...)]` paths, which don't count as a use.
+    #[allow(unused_imports)]
+    use super::intrinsic_models as m;
+
+    // Layer 1 — index proofs, over a symbolic `len` and an arbitrary iteration,
+    // with every stride imported from the kernel module. The group model is the
+    // ZMM one: after `g` groups the encoder has written `4 * g` characters and
+    // the decoder `3 * g` bytes.
+
+    use super::super::{
+        DEC_GROUP, DEC_LEAD, DEC_MASKED_MIN, DEC_QUAD_IN, DEC_QUAD_MIN, DEC_QUAD_OUT,
+        DEC_SINGLE_MIN, DEC_VEC_IN, DEC_VEC_OUT, ENC_GROUP, ENC_QUAD_IN, ENC_QUAD_MIN,
+        ENC_QUAD_OUT, ENC_SINGLE_MIN, ENC_VEC, ENC_VEC_IN, ENC_VEC_OUT,
+    };
+
+    /// Largest `len` considered: above `usize::MAX / 4` the unpadded
+    /// `encoded_len`'s `len * 4` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 4;
+
+    /// A full-width store writes as many bytes as a full-width load reads; the
+    /// decoder's quad tier overhangs by the difference.
+    const DEC_STORE_WIDE: usize = DEC_VEC_IN;
+
+    const ENC_QUAD_GROUPS: usize = ENC_QUAD_IN / ENC_GROUP;
+    const ENC_SINGLE_GROUPS: usize = ENC_VEC_IN / ENC_GROUP;
+    const DEC_QUAD_GROUPS: usize = DEC_QUAD_IN / DEC_GROUP;
+    const DEC_SINGLE_GROUPS: usize = DEC_VEC_IN / DEC_GROUP;
+
+    const _: () = assert!(
+        ENC_QUAD_IN % ENC_GROUP == 0 && ENC_VEC_IN % ENC_GROUP == 0,
+        "every encode tier must consume whole 3-byte groups"
+    );
+    const _: () = assert!(
+        ENC_QUAD_GROUPS * 4 == ENC_QUAD_OUT && ENC_SINGLE_GROUPS * 4 == ENC_VEC_OUT,
+        "every encode tier must emit 4 characters per group"
+    );
+    const _: () = assert!(
+        DEC_QUAD_IN % DEC_GROUP == 0 && DEC_VEC_IN % DEC_GROUP == 0,
+        "every decode tier must consume whole 4-character groups"
+    );
+    const _: () = assert!(
+        DEC_QUAD_GROUPS * 3 == DEC_QUAD_OUT && DEC_SINGLE_GROUPS * 3 == DEC_VEC_OUT,
+        "every decode tier must emit 3 bytes per group"
+    );
+
+    fn enc_cap(len: usize, padding: bool) -> usize {
+        if padding {
+            TURBO_STANDARD.encoded_len(len)
+        } else {
+            TURBO_STANDARD_NO_PAD.encoded_len(len)
+        }
+    }
+
+    fn dec_cap(len: usize) -> usize {
+        TURBO_STANDARD.estimate_decoded_len(len)
+    }
+
+    /// An arbitrary reachable encoder state. Returns `(done, dst_off, rem)`.
+    fn any_enc_state(len: usize) -> (usize, usize, usize) {
+        let g: usize = kani::any();
+        kani::assume(g <= MAX_LEN / ENC_GROUP);
+        let done = ENC_GROUP * g;
+        kani::assume(done <= len);
+        (done, 4 * g, len - done)
+    }
+
+    /// As [`any_enc_state`], for the decoder.
+    fn any_dec_state(len: usize) -> (usize, usize, usize) {
+        let g: usize = kani::any();
+        kani::assume(g <= MAX_LEN / DEC_GROUP);
+        let done = DEC_GROUP * g;
+        kani::assume(done <= len);
+        (done, 3 * g, len - done)
+    }
+
+    // --- Encoder ---
+
+    #[kani::proof]
+    fn check_vbmi256_enc_quad_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem >= ENC_QUAD_MIN); // guard `while rem >= 128`
+        let cap = enc_cap(len, padding);
+
+        assert!(
+            done + 3 * ENC_VEC_IN + ENC_VEC <= len,
+            "quad load leaves input"
+        );
+        assert!(
+            dst_off + 3 * ENC_VEC_OUT + ENC_VEC_OUT <= cap,
+            "quad store leaves output"
+        );
+
+        assert_eq!(
+            dst_off + ENC_QUAD_OUT,
+            4 * (done / ENC_GROUP + ENC_QUAD_GROUPS)
+        );
+        assert!(done + ENC_QUAD_IN <= len);
+        assert_eq!(rem - ENC_QUAD_IN, len - (done + ENC_QUAD_IN));
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_enc_single_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem >= ENC_SINGLE_MIN); // guard `while rem >= 32`
+        let cap = enc_cap(len, padding);
+
+        assert!(done + ENC_VEC <= len, "single load leaves input");
+        assert!(dst_off + ENC_VEC_OUT <= cap, "single store leaves output");
+
+        assert_eq!(
+            dst_off + ENC_VEC_OUT,
+            4 * (done / ENC_GROUP + ENC_SINGLE_GROUPS)
+        );
+        assert!(done + ENC_VEC_IN <= len);
+        assert_eq!(rem - ENC_VEC_IN, len - (done + ENC_VEC_IN));
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_enc_masked_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem >= ENC_GROUP && rem < ENC_SINGLE_MIN);
+        let cap = enc_cap(len, padding);
+
+        let take = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
+        let out = take / ENC_GROUP * 4;
+
+        assert!(
+            (ENC_GROUP..=ENC_VEC_IN).contains(&take),
+            "load mask shift out of range"
+        );
+        assert!(
+            (4..=ENC_VEC_OUT).contains(&out),
+            "store mask shift out of range"
+        );
+
+        assert!(done + take <= len, "masked load leaves input");
+        assert!(dst_off + out <= cap, "masked store leaves output");
+
+        assert_eq!(take % ENC_GROUP, 0);
+        assert_eq!(dst_off + out, 4 * ((done + take) / ENC_GROUP));
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_enc_masked_terminates() {
+        let rem: usize = kani::any();
+        kani::assume(rem >= ENC_GROUP && rem < ENC_SINGLE_MIN);
+
+        let take1 = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
+        assert!(take1 >= ENC_GROUP, "first pass makes no progress");
+        let rem1 = rem - take1;
+
+        if rem1 >= ENC_GROUP {
+            let take2 = (rem1 - rem1 % ENC_GROUP).min(ENC_VEC_IN);
+            assert!(take2 >= ENC_GROUP, "second pass makes no progress");
+            assert!(rem1 - take2 < ENC_GROUP, "a third pass would be needed");
+        }
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_enc_tail_handoff() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem < ENC_GROUP);
+
+        assert_eq!(
+            dst_off + enc_cap(rem, padding),
+            enc_cap(len, padding),
+            "prefix + tail must equal encoded length"
+        );
+        assert_eq!(done + rem, len);
+    }
+
+    // --- Decoder ---
+
+    #[kani::proof]
+    fn check_vbmi256_dec_quad_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        kani::assume(rem >= DEC_QUAD_MIN); // guard `while rem >= 132`
+        let cap = dec_cap(len);
+
+        assert!(
+            done + 3 * DEC_VEC_IN + DEC_VEC_IN <= len,
+            "quad load leaves input"
+        );
+        assert!(
+            dst_off + 2 * DEC_VEC_OUT + DEC_STORE_WIDE <= cap,
+            "quad unmasked store overhang leaves output"
+        );
+        assert!(
+            dst_off + DEC_QUAD_OUT <= cap,
+            "quad masked store leaves output"
+        );
+
+        assert_eq!(
+            dst_off + DEC_QUAD_OUT,
+            3 * (done / DEC_GROUP + DEC_QUAD_GROUPS)
+        );
+        assert!(done + DEC_QUAD_IN <= len);
+        assert_eq!(rem - DEC_QUAD_IN, len - (done + DEC_QUAD_IN));
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_dec_single_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        kani::assume(rem >= DEC_SINGLE_MIN); // guard `while rem >= 36`
+        let cap = dec_cap(len);
+
+        assert!(done + DEC_VEC_IN <= len, "single load leaves input");
+        assert!(dst_off + DEC_VEC_OUT <= cap, "single store leaves output");
+
+        assert_eq!(
+            dst_off + DEC_VEC_OUT,
+            3 * (done / DEC_GROUP + DEC_SINGLE_GROUPS)
+        );
+        assert!(done + DEC_VEC_IN <= len);
+        assert_eq!(rem - DEC_VEC_IN, len - (done + DEC_VEC_IN));
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_dec_masked_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        kani::assume(rem >= DEC_MASKED_MIN && rem < DEC_SINGLE_MIN);
+        let cap = dec_cap(len);
+
+        let take = (rem - DEC_LEAD) & !(DEC_GROUP - 1);
+        let out = take / DEC_GROUP * 3;
+
+        assert!(
+            (DEC_GROUP..=DEC_VEC_IN).contains(&take),
+            "load mask shift out of range"
+        );
+        assert!(
+            (3..=DEC_VEC_OUT).contains(&out),
+            "store mask shift out of range"
+        );
+
+        assert!(done + take <= len, "masked load leaves input");
+        assert!(dst_off + out <= cap, "masked store leaves output");
+
+        assert_eq!(take % DEC_GROUP, 0);
+        assert_eq!(dst_off + out, 3 * ((done + take) / DEC_GROUP));
+    }
+
+    #[kani::proof]
+    fn check_vbmi256_dec_tail_slack() {
+        let rem: usize = kani::any();
+        kani::assume(rem <= MAX_LEN);
+
+        if rem >= DEC_QUAD_MIN {
+            assert!(
+                rem - DEC_QUAD_IN >= DEC_LEAD,
+                "quad tier ate the last group"
+            );
+        }
+        if rem >= DEC_SINGLE_MIN {
+            assert!(
+                rem - DEC_VEC_IN >= DEC_LEAD,
+                "single tier ate the last group"
+            );
+        }
+        if rem >= DEC_MASKED_MIN {
+            let take = (rem - DEC_LEAD) & !(DEC_GROUP - 1);
+            let left = rem - take;
+            assert!(take >= DEC_GROUP, "masked tier makes no progress");
+            assert!(left >= DEC_LEAD, "masked tier ate the last group");
+            assert!(left < DEC_MASKED_MIN, "masked tier would run again");
+        }
+    }
+
+    // Layer 2 — kernel proofs over symbolic bytes. Half the width means half
+    // the symbolic state per vector, but the quad tiers are still left to
+    // Layer 1, as for ZMM.
+
+    /// One single-tier vector (24 bytes), one masked vector (6 bytes), and a
+    /// 2-byte scalar tail.
+    const ENC_KERNEL_LEN: usize = 32;
+    /// One single-tier vector (32 characters) and a 4-character scalar tail.
+    const DEC_KERNEL_LEN: usize = 36;
+    /// One masked vector (8 characters) and a 4-character scalar tail.
+    const DEC_MASKED_KERNEL_LEN: usize = 12;
+    /// One full masked encode vector, whose 32 characters then decode through
+    /// one masked decode vector plus a scalar group.
+    const ROUNDTRIP_LEN: usize = 24;
+
+    const _: () = assert!(
+        ENC_KERNEL_LEN >= ENC_SINGLE_MIN
+            && ENC_KERNEL_LEN < ENC_QUAD_MIN
+            && ENC_KERNEL_LEN - ENC_VEC_IN >= ENC_GROUP
+            && ENC_KERNEL_LEN % ENC_GROUP != 0,
+        "ENC_KERNEL_LEN must run a single-tier vector, then a masked vector, \
+         then leave a partial group for scalar"
+    );
+    const _: () = assert!(
+        DEC_KERNEL_LEN >= DEC_SINGLE_MIN
+            && DEC_KERNEL_LEN < DEC_QUAD_MIN
+            && DEC_KERNEL_LEN % DEC_GROUP == 0,
+        "DEC_KERNEL_LEN must run a single-tier vector and still be decodable"
+    );
+    const _: () = assert!(
+        DEC_MASKED_KERNEL_LEN >= DEC_MASKED_MIN
+            && DEC_MASKED_KERNEL_LEN < DEC_SINGLE_MIN
+            && DEC_MASKED_KERNEL_LEN % DEC_GROUP == 0,
+        "DEC_MASKED_KERNEL_LEN must run the masked tier and still be decodable"
+    );
+    const _: () = assert!(
+        ROUNDTRIP_LEN < ENC_SINGLE_MIN && ROUNDTRIP_LEN % ENC_GROUP == 0,
+        "ROUNDTRIP_LEN must be a whole number of groups in the masked tier"
+    );
+
+    const ENC_KERNEL_CAP: usize = TURBO_STANDARD.encoded_len(ENC_KERNEL_LEN);
+    const DEC_KERNEL_CAP: usize = TURBO_STANDARD.estimate_decoded_len(DEC_KERNEL_LEN);
+    const DEC_MASKED_KERNEL_CAP: usize = TURBO_STANDARD.estimate_decoded_len(DEC_MASKED_KERNEL_LEN);
+    const ROUNDTRIP_ENC_CAP: usize = TURBO_STANDARD.encoded_len(ROUNDTRIP_LEN);
+    const ROUNDTRIP_DEC_CAP: usize = TURBO_STANDARD.estimate_decoded_len(ROUNDTRIP_ENC_CAP);
+
+    fn encode_matches_scalar(url_safe: bool) {
+        let config = Config {
+            url_safe,
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; ENC_KERNEL_LEN] = kani::any();
+
+        let mut ymm_out = [0u8; ENC_KERNEL_CAP];
+        let mut scalar_out = [0u8; ENC_KERNEL_CAP];
+
+        unsafe { encode_slice_avx512_vbmi_256(&config, &input, &mut ymm_out) };
+        crate::scalar::encode_slice(&config, &input, &mut scalar_out);
+
+        assert_eq!(ymm_out, scalar_out, "kernel and scalar encoded differently");
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm256_permutexvar_epi8, m::permutexvar_epi8_model)]
+    #[kani::stub(_mm256_permutex2var_epi8, m::permutex2var_epi8_model)]
+    #[kani::stub(_mm256_multishift_epi64_epi8, m::multishift_epi64_epi8_model)]
+    #[kani::stub(_mm256_maskz_loadu_epi8, m::maskz_loadu_epi8_model)]
+    #[kani::stub(_mm256_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_vbmi256_encode_matches_scalar_standard() {
+        encode_matches_scalar(false);
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm256_permutexvar_epi8, m::permutexvar_epi8_model)]
+    #[kani::stub(_mm256_permutex2var_epi8, m::permutex2var_epi8_model)]
+    #[kani::stub(_mm256_multishift_epi64_epi8, m::multishift_epi64_epi8_model)]
+    #[kani::stub(_mm256_maskz_loadu_epi8, m::maskz_loadu_epi8_model)]
+    #[kani::stub(_mm256_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_vbmi256_encode_matches_scalar_url_safe() {
+        encode_matches_scalar(true);
+    }
+
+    /// Value and rejection both pinned against scalar; error kinds are not
+    /// compared, for the reason given on the ZMM harness.
+    fn decode_matches_scalar<const N: usize, const CAP: usize>() {
+        let config = Config {
+            url_safe: kani::any(),
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; N] = kani::any();
+
+        let mut ymm_out = [0u8; CAP];
+        let mut scalar_out = [0u8; CAP];
+
+        let ymm = unsafe { decode_slice_avx512_vbmi_256(&config, &input, &mut ymm_out) };
+        let scalar = crate::scalar::decode_slice(&config, &input, &mut scalar_out);
+
+        match scalar {
+            Ok(n) => {
+                assert_eq!(ymm, Ok(n), "scalar accepted an input the kernel rejected");
+                assert_eq!(
+                    &ymm_out[..n],
+                    &scalar_out[..n],
+                    "kernel and scalar decoded to different bytes"
+                );
+            }
+            Err(_) => assert!(ymm.is_err(), "kernel accepted an input scalar rejected"),
+        }
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm256_permutexvar_epi8, m::permutexvar_epi8_model)]
+    #[kani::stub(_mm256_permutex2var_epi8, m::permutex2var_epi8_model)]
+    #[kani::stub(_mm256_maddubs_epi16, m::maddubs_epi16_model)]
+    #[kani::stub(_mm256_madd_epi16, m::madd_epi16_model)]
+    #[kani::stub(_mm256_ternarylogic_epi32, m::ternarylogic_epi32_model)]
+    #[kani::stub(_mm256_movepi8_mask, m::movepi8_mask_model)]
+    #[kani::stub(_mm256_test_epi8_mask, m::test_epi8_mask_model)]
+    #[kani::stub(_mm256_mask_blend_epi8, m::mask_blend_epi8_model)]
+    #[kani::stub(_mm256_mask_loadu_epi8, m::mask_loadu_epi8_model)]
+    #[kani::stub(_mm256_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_vbmi256_decode_matches_scalar() {
+        decode_matches_scalar::<DEC_KERNEL_LEN, DEC_KERNEL_CAP>();
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm256_permutexvar_epi8, m::permutexvar_epi8_model)]
+    #[kani::stub(_mm256_permutex2var_epi8, m::permutex2var_epi8_model)]
+    #[kani::stub(_mm256_maddubs_epi16, m::maddubs_epi16_model)]
+    #[kani::stub(_mm256_madd_epi16, m::madd_epi16_model)]
+    #[kani::stub(_mm256_ternarylogic_epi32, m::ternarylogic_epi32_model)]
+    #[kani::stub(_mm256_movepi8_mask, m::movepi8_mask_model)]
+    #[kani::stub(_mm256_test_epi8_mask, m::test_epi8_mask_model)]
+    #[kani::stub(_mm256_mask_blend_epi8, m::mask_blend_epi8_model)]
+    #[kani::stub(_mm256_mask_loadu_epi8, m::mask_loadu_epi8_model)]
+    #[kani::stub(_mm256_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_vbmi256_decode_matches_scalar_masked() {
+        decode_matches_scalar::<DEC_MASKED_KERNEL_LEN, DEC_MASKED_KERNEL_CAP>();
+    }
+
+    /// `Decode(Encode(x)) == x` over every input of [`ROUNDTRIP_LEN`] bytes.
+    #[kani::proof]
+    #[kani::stub(_mm256_permutexvar_epi8, m::permutexvar_epi8_model)]
+    #[kani::stub(_mm256_permutex2var_epi8, m::permutex2var_epi8_model)]
+    #[kani::stub(_mm256_multishift_epi64_epi8, m::multishift_epi64_epi8_model)]
+    #[kani::stub(_mm256_maddubs_epi16, m::maddubs_epi16_model)]
+    #[kani::stub(_mm256_madd_epi16, m::madd_epi16_model)]
+    #[kani::stub(_mm256_ternarylogic_epi32, m::ternarylogic_epi32_model)]
+    #[kani::stub(_mm256_movepi8_mask, m::movepi8_mask_model)]
+    #[kani::stub(_mm256_test_epi8_mask, m::test_epi8_mask_model)]
+    #[kani::stub(_mm256_mask_blend_epi8, m::mask_blend_epi8_model)]
+    #[kani::stub(_mm256_mask_loadu_epi8, m::mask_loadu_epi8_model)]
+    #[kani::stub(_mm256_maskz_loadu_epi8, m::maskz_loadu_epi8_model)]
+    #[kani::stub(_mm256_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_vbmi256_roundtrip_standard() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; ROUNDTRIP_LEN] = kani::any();
+
+        let mut enc_buf = [0u8; ROUNDTRIP_ENC_CAP];
+        let mut dec_buf = [0u8; ROUNDTRIP_DEC_CAP];
+
+        unsafe {
+            encode_slice_avx512_vbmi_256(&config, &input, &mut enc_buf);
+            let dec_len = decode_slice_avx512_vbmi_256(&config, &enc_buf, &mut dec_buf)
+                .expect("valid encoding failed to decode");
+            assert_eq!(dec_len, ROUNDTRIP_LEN);
+            assert_eq!(&dec_buf[..dec_len], &input, "roundtrip mismatch");
+        }
+    }
+}
+
+/// Rust models of the 256-bit forms of the instructions this kernel issues,
+/// transcribed from the Intel Intrinsics Guide under the same rules as the ZMM
+/// models (and reusing their `TernaryOP` reconstruction). Miri takes the three
+/// byte permutes through the `cfg(miri)` shims; Kani takes the whole set.
+/// [`avx512_vbmi_256_stub_equivalence`] checks each against the hardware.
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+#[allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::missing_const_for_fn,
+    clippy::missing_transmute_annotations,
+    clippy::needless_late_init,
+    clippy::needless_range_loop
+)]
+pub(super) mod intrinsic_models {
+    use super::*;
+    use crate::simd::avx512_vbmi::verify::intrinsic_models::TernaryOP;
+    use std::mem::transmute;
+
+    // NOTE: scaffolding, not from Intel. Reads bit `n` of a little-endian byte
+    // vector, for the places the pseudocode indexes a single bit.
+    fn bit(v: &[u8; 32], n: usize) -> u8 {
+        (v[n / 8] >> (n % 8)) & 1
+    }
+
+    // NOTE: scaffolding, not from Intel. The `Saturate16` helper the pseudocode
+    // calls by name.
+    fn Saturate16(x: i32) -> i16 {
+        x.clamp(-32768, 32767) as i16
+    }
+
+    // STUB: _mm256_permutexvar_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_permutexvar_epi8
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn permutexvar_epi8_model(
+        idx: __m256i,
+        a: __m256i,
+    ) -> __m256i {
+        let idx: [u8; 32] = unsafe { transmute(idx) };
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let mut dst = [0u8; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	id := idx[i+4:i]*8
+            let id = usize::from(idx[i / 8] & 0x1F) * 8;
+            // 	dst[i+7:i] := a[id+7:id]
+            dst[i / 8] = a[id / 8];
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+        // NOTE: `__m256i` is exactly 256 bits; there is nothing above to zero.
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_permutex2var_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_permutex2var_epi8
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn permutex2var_epi8_model(
+        a: __m256i,
+        idx: __m256i,
+        b: __m256i,
+    ) -> __m256i {
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let idx: [u8; 32] = unsafe { transmute(idx) };
+        let b: [u8; 32] = unsafe { transmute(b) };
+        let mut dst = [0u8; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	off := 8*idx[i+4:i]
+            let off = 8 * usize::from(idx[i / 8] & 0x1F);
+            // 	dst[i+7:i] := idx[i+5] ? b[off+7:off] : a[off+7:off]
+            dst[i / 8] = if bit(&idx, i + 5) == 1 {
+                b[off / 8]
+            } else {
+                a[off / 8]
+            };
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_multishift_epi64_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_multishift_epi64_epi8
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn multishift_epi64_epi8_model(
+        a: __m256i,
+        b: __m256i,
+    ) -> __m256i {
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let b: [u8; 32] = unsafe { transmute(b) };
+        let mut dst = [0u8; 32];
+
+        // FOR i := 0 to 3
+        for i in 0..4 {
+            // 	q := i * 64
+            let q = i * 64;
+            // 	FOR j := 0 to 7
+            for j in 0..8 {
+                // 		tmp8 := 0
+                let mut tmp8: u8 = 0;
+                // 		ctrl := a[q+j*8+7:q+j*8] & 63
+                let ctrl = usize::from(a[(q + j * 8) / 8]) & 63;
+                // 		FOR l := 0 to 7
+                for l in 0..8 {
+                    // 			tmp8[l] := b[q+((ctrl+l) & 63)]
+                    tmp8 |= bit(&b, q + ((ctrl + l) & 63)) << l;
+                }
+                // 		ENDFOR
+                // 		dst[q+j*8+7:q+j*8] := tmp8[7:0]
+                dst[(q + j * 8) / 8] = tmp8;
+            }
+            // 	ENDFOR
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_maddubs_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_maddubs_epi16
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn maddubs_epi16_model(
+        a: __m256i,
+        b: __m256i,
+    ) -> __m256i {
+        // NOTE: `a` holds unsigned bytes, `b` signed ones.
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let b: [i8; 32] = unsafe { transmute(b) };
+        let mut dst = [0i16; 16];
+
+        // FOR j := 0 to 15
+        for j in 0..16 {
+            // 	i := j*16
+            let i = j * 16;
+            // 	dst[i+15:i] := Saturate16( a[i+15:i+8]*b[i+15:i+8] + a[i+7:i]*b[i+7:i] )
+            dst[i / 16] = Saturate16(
+                i32::from(a[(i + 8) / 8]) * i32::from(b[(i + 8) / 8])
+                    + i32::from(a[i / 8]) * i32::from(b[i / 8]),
+            );
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_madd_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_madd_epi16
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn madd_epi16_model(
+        a: __m256i,
+        b: __m256i,
+    ) -> __m256i {
+        let a: [i16; 16] = unsafe { transmute(a) };
+        let b: [i16; 16] = unsafe { transmute(b) };
+        let mut dst = [0i32; 8];
+
+        // FOR j := 0 to 7
+        for j in 0..8 {
+            // 	i := j*32
+            let i = j * 32;
+            // 	dst[i+31:i] := SignExtend32(a[i+31:i+16]*b[i+31:i+16]) + SignExtend32(a[i+15:i]*b[i+15:i])
+            // NOTE: `wrapping`, as in the ZMM model.
+            dst[i / 32] = (i32::from(a[(i + 16) / 16]) * i32::from(b[(i + 16) / 16]))
+                .wrapping_add(i32::from(a[i / 16]) * i32::from(b[i / 16]));
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_ternarylogic_epi32
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_ternarylogic_epi32
+    //
+    // NOTE: `TernaryOP` is the ZMM module's reconstruction; see there.
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn ternarylogic_epi32_model<const IMM8: i32>(
+        a: __m256i,
+        b: __m256i,
+        c: __m256i,
+    ) -> __m256i {
+        let a: [u32; 8] = unsafe { transmute(a) };
+        let b: [u32; 8] = unsafe { transmute(b) };
+        let c: [u32; 8] = unsafe { transmute(c) };
+        let mut dst = [0u32; 8];
+
+        // imm8[7:0] = LogicExp(_MM_TERNLOG_A, _MM_TERNLOG_B, _MM_TERNLOG_C)
+        let imm8 = IMM8;
+
+        // FOR j := 0 to 7
+        for j in 0..8 {
+            // 	i := j*32
+            let i = j * 32;
+            // 	FOR h := 0 to 31
+            for h in 0..32 {
+                // 		dst[i+h] := TernaryOP(imm8[7:0], a[i+h], b[i+h], c[i+h])
+                dst[i / 32] |= TernaryOP(
+                    imm8 & 0xFF,
+                    (a[i / 32] >> h) & 1,
+                    (b[i / 32] >> h) & 1,
+                    (c[i / 32] >> h) & 1,
+                ) << h;
+            }
+            // 	ENDFOR
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_movepi8_mask
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_movepi8_mask
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn movepi8_mask_model(a: __m256i) -> u32 {
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let mut k = 0u32;
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF a[i+7]
+            if bit(&a, i + 7) == 1 {
+                // 		k[j] := 1
+                k |= 1u32 << j;
+            // 	ELSE
+            } else {
+                // 		k[j] := 0
+                k &= !(1u32 << j);
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // k[MAX:32] := 0
+
+        k
+    }
+
+    // STUB: _mm256_test_epi8_mask
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_test_epi8_mask
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn test_epi8_mask_model(
+        a: __m256i,
+        b: __m256i,
+    ) -> u32 {
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let b: [u8; 32] = unsafe { transmute(b) };
+        let mut k = 0u32;
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	k[j] := ((a[i+7:i] AND b[i+7:i]) != 0) ? 1 : 0
+            k |= u32::from((a[i / 8] & b[i / 8]) != 0) << j;
+        }
+        // ENDFOR
+        // k[MAX:32] := 0
+
+        k
+    }
+
+    // STUB: _mm256_mask_blend_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_mask_blend_epi8
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn mask_blend_epi8_model(
+        k: u32,
+        a: __m256i,
+        b: __m256i,
+    ) -> __m256i {
+        let a: [u8; 32] = unsafe { transmute(a) };
+        let b: [u8; 32] = unsafe { transmute(b) };
+        let mut dst = [0u8; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		dst[i+7:i] := b[i+7:i]
+                dst[i / 8] = b[i / 8];
+            // 	ELSE
+            } else {
+                // 		dst[i+7:i] := a[i+7:i]
+                dst[i / 8] = a[i / 8];
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_mask_loadu_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_mask_loadu_epi8
+    //
+    // NOTE: the masked-off lanes are never read.
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn mask_loadu_epi8_model(
+        src: __m256i,
+        k: u32,
+        mem_addr: *const i8,
+    ) -> __m256i {
+        let src: [u8; 32] = unsafe { transmute(src) };
+        let mut dst = [0u8; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		dst[i+7:i] := MEM[mem_addr+i+7:mem_addr+i]
+                dst[i / 8] = unsafe { mem_addr.add(i / 8).read_unaligned() }.cast_unsigned();
+            // 	ELSE
+            } else {
+                // 		dst[i+7:i] := src[i+7:i]
+                dst[i / 8] = src[i / 8];
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_maskz_loadu_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_maskz_loadu_epi8
+    //
+    // NOTE: as `mask_loadu_epi8_model`, the masked-off lanes are never read.
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn maskz_loadu_epi8_model(
+        k: u32,
+        mem_addr: *const i8,
+    ) -> __m256i {
+        let mut dst = [0u8; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		dst[i+7:i] := MEM[mem_addr+i+7:mem_addr+i]
+                dst[i / 8] = unsafe { mem_addr.add(i / 8).read_unaligned() }.cast_unsigned();
+            // 	ELSE
+            } else {
+                // 		dst[i+7:i] := 0
+                dst[i / 8] = 0;
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:256] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm256_mask_storeu_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm256_mask_storeu_epi8
+    //
+    // NOTE: as `mask_loadu_epi8_model`, the masked-off lanes are never written.
+    pub(in crate::simd::avx512_vbmi::ymm) unsafe fn mask_storeu_epi8_model(
+        mem_addr: *mut i8,
+        k: u32,
+        a: __m256i,
+    ) {
+        let a: [u8; 32] = unsafe { transmute(a) };
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		MEM[mem_addr+i+7:mem_addr+i] := a[i+7:i]
+                unsafe { mem_addr.add(i / 8).write_unaligned(a[i / 8].cast_signed()) };
+            }
+            // 	FI
+        }
+        // ENDFOR
+    }
+}
+
+/// Checks every model in [`intrinsic_models`] against the real instruction on
+/// AVX-512VL+VBMI hardware, under plain `cargo test`. Skips, loudly, on a host
+/// without the subsets.
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_256_stub_equivalence {
+    use super::intrinsic_models as model;
+    use super::*;
+    use crate::simd::testutil::has_avx512_vbmi_vl;
+
+    /// Sign boundaries, the bit-5/6/7 selectors the permutes and blends key
+    /// off, index-shaped bytes, and deterministic noise.
+    fn probes() -> Vec<[u8; 32]> {
+        let byte = |i: usize| u8::try_from(i).expect("index below the 32-byte vector width");
+
+        let mut out = vec![[0x00; 32], [0xFF; 32], [0x80; 32], [0x7F; 32], [0x40; 32]];
+        out.push(core::array::from_fn(byte));
+        out.push(core::array::from_fn(|i| byte(i) | 0x20));
+        out.push(core::array::from_fn(|i| byte(i) | 0x60));
+        out.push(core::array::from_fn(|i| byte(i) * 4 + 0x2B));
+        out.push(core::array::from_fn(|i| 0xFF - byte(i)));
+
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..8 {
+            out.push(core::array::from_fn(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                u8::try_from(state >> 56).expect("shifted down to 8 bits")
+            }));
+        }
+        out
+    }
+
+    #[target_feature(enable = "avx512f,avx512bw,avx512vl,avx512vbmi")]
+    unsafe fn compare_all() {
+        use std::mem::transmute;
+
+        let byte = |i: usize| u8::try_from(i).expect("index below the 32-byte vector width");
+        let probes = probes();
+        // SAFETY: `__m256i` has no invalid bit patterns, so it and `[u8; 32]`
+        // are freely transmutable both ways.
+        let bytes = |v: __m256i| -> [u8; 32] { unsafe { transmute::<__m256i, [u8; 32]>(v) } };
+        let ymm = |b: [u8; 32]| -> __m256i { unsafe { transmute::<[u8; 32], __m256i>(b) } };
+
+        macro_rules! same2 {
+            ($real:expr, $model:expr, $wrap:expr) => {
+                for x in &probes {
+                    for y in &probes {
+                        let (a, b) = (ymm(*x), ymm(*y));
+                        assert_eq!(
+                            $wrap($real(a, b)),
+                            $wrap(unsafe { $model(a, b) }),
+                            "{}: a={x:02x?} b={y:02x?}",
+                            stringify!($real)
+                        );
+                    }
+                }
+            };
+        }
+
+        let mask = |k: u32| k;
+        same2!(
+            _mm256_permutexvar_epi8,
+            model::permutexvar_epi8_model,
+            bytes
+        );
+        same2!(
+            _mm256_multishift_epi64_epi8,
+            model::multishift_epi64_epi8_model,
+            bytes
+        );
+        same2!(_mm256_maddubs_epi16, model::maddubs_epi16_model, bytes);
+        same2!(_mm256_madd_epi16, model::madd_epi16_model, bytes);
+        same2!(_mm256_test_epi8_mask, model::test_epi8_mask_model, mask);
+
+        for x in &probes {
+            let a = ymm(*x);
+            assert_eq!(
+                _mm256_movepi8_mask(a),
+                unsafe { model::movepi8_mask_model(a) },
+                "_mm256_movepi8_mask: a={x:02x?}"
+            );
+            for y in &probes {
+                let idx = ymm(*y);
+                let other = ymm(core::array::from_fn(|i| byte(i) ^ 0xA5));
+                assert_eq!(
+                    bytes(_mm256_permutex2var_epi8(a, idx, other)),
+                    bytes(unsafe { model::permutex2var_epi8_model(a, idx, other) }),
+                    "_mm256_permutex2var_epi8: a={x:02x?} idx={y:02x?}"
+                );
+                assert_eq!(
+                    bytes(_mm256_ternarylogic_epi32::<0xFE>(a, idx, other)),
+                    bytes(unsafe { model::ternarylogic_epi32_model::<0xFE>(a, idx, other) }),
+                    "_mm256_ternarylogic_epi32: a={x:02x?} b={y:02x?}"
+                );
+            }
+        }
+
+        for &k in &[0u32, 1, u32::MAX, 0x00FF_FFFF, 0xAAAA_AAAA] {
+            for x in &probes {
+                let (a, b) = (ymm([0x11; 32]), ymm(*x));
+                assert_eq!(
+                    bytes(_mm256_mask_blend_epi8(k, a, b)),
+                    bytes(unsafe { model::mask_blend_epi8_model(k, a, b) }),
+                    "_mm256_mask_blend_epi8: k={k:#010x} b={x:02x?}"
+                );
+            }
+
+            let src_bytes: [u8; 32] = core::array::from_fn(|i| byte(i) ^ 0x5A);
+            let fill = ymm([0x11; 32]);
+
+            assert_eq!(
+                bytes(unsafe { _mm256_mask_loadu_epi8(fill, k, src_bytes.as_ptr().cast()) }),
+                bytes(unsafe { model::mask_loadu_epi8_model(fill, k, src_bytes.as_ptr().cast()) }),
+                "_mm256_mask_loadu_epi8: k={k:#010x}"
+            );
+            assert_eq!(
+                bytes(unsafe { _mm256_maskz_loadu_epi8(k, src_bytes.as_ptr().cast()) }),
+                bytes(unsafe { model::maskz_loadu_epi8_model(k, src_bytes.as_ptr().cast()) }),
+                "_mm256_maskz_loadu_epi8: k={k:#010x}"
+            );
+
+            let value = ymm(core::array::from_fn(|i| byte(i).wrapping_mul(3)));
+            let mut real_dst = [0u8; 32];
+            let mut model_dst = [0u8; 32];
+            unsafe { _mm256_mask_storeu_epi8(real_dst.as_mut_ptr().cast(), k, value) };
+            unsafe { model::mask_storeu_epi8_model(model_dst.as_mut_ptr().cast(), k, value) };
+            assert_eq!(real_dst, model_dst, "_mm256_mask_storeu_epi8: k={k:#010x}");
+        }
+    }
+
+    #[test]
+    fn avx512_vbmi_256_models_match_hardware() {
+        if !has_avx512_vbmi_vl() {
+            return;
+        }
+        unsafe { compare_all() };
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512_vbmi_256_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode};
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+    fn enc(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_encode(config, oracle, encode_slice_avx512_vbmi_256, len);
+    }
+    fn dec(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_decode(config, oracle, decode_slice_avx512_vbmi_256, len);
+    }
+    fn exact(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_decode_exact(config, oracle, decode_slice_avx512_vbmi_256, len);
+    }
+
+    const STD: Config = Config {
+        url_safe: false,
+        padding: true,
+        lenient: false,
+    };
+    const URL: Config = Config {
+        url_safe: true,
+        padding: true,
+        lenient: false,
+    };
+    const NO_PAD: Config = Config {
+        url_safe: false,
+        padding: false,
+        lenient: false,
+    };
+    const NO_PAD_URL: Config = Config {
+        url_safe: true,
+        padding: false,
+        lenient: false,
+    };
+
+    /// Tier boundaries, encode: quad at >= 128, single at >= 32, masked below
+    /// that (twice when the remainder exceeds 24).
+    #[test]
+    fn miri_avx512_vbmi_256_encode_tier_boundaries() {
+        for &len in &[
+            0, 1, 2, 3, 4, 23, 24, 25, 27, 30, 31, 32, 33, 48, 127, 128, 129,
+        ] {
+            enc(&STD, &STANDARD, len);
+        }
+        for &len in &[3, 23, 31, 50, 131] {
+            enc(&NO_PAD_URL, &URL_SAFE_NO_PAD, len);
+        }
+    }
+
+    /// Tier boundaries, decode, in decoded byte lengths. The character
+    /// thresholds are 132 / 36 / 8.
+    #[test]
+    fn miri_avx512_vbmi_256_decode_tier_boundaries() {
+        for &len in &[0, 1, 2, 3, 4, 5, 6, 21, 24, 25, 26, 27, 96, 97, 99] {
+            dec(&STD, &STANDARD, len);
+        }
+        dec(&URL, &URL_SAFE, 50);
+        for &len in &[3, 25, 97] {
+            dec(&NO_PAD, &STANDARD_NO_PAD, len);
+        }
+    }
+
+    /// Invalid bytes must be caught in every tier and the scalar tail, in both
+    /// halves of the split lookup and above ASCII.
+    #[test]
+    fn miri_avx512_vbmi_256_decode_error_detection() {
+        let mut dst = [0u8; 256];
+        for &(len, bad_at, byte, where_) in &[
+            (132, 0, b'$', "quad tier, low half"),
+            (132, 127, b'{', "quad tier, high half"),
+            (36, 31, b'?', "single tier"),
+            (12, 5, b'?', "masked tier"),
+            (36, 0, 0xFF, "high bit, single tier"),
+            (12, 0, 0x80u8, "high bit, masked tier"),
+            (36, 33, b'?', "scalar tail"),
+        ] {
+            let mut input = vec![b'A'; len];
+            input[bad_at] = byte;
+            let res = unsafe { decode_slice_avx512_vbmi_256(&STD, &input, &mut dst) };
+            assert!(res.is_err(), "missed invalid byte in {where_}");
+        }
+    }
+
+    /// Masked-store regression: chunk-boundary lengths decode into an
+    /// exactly-sized buffer without overrunning.
+    #[test]
+    fn miri_avx512_vbmi_256_decode_exact_buffer_boundaries() {
+        for &len in &[3, 6, 21, 24, 27, 48, 96, 97, 99, 120, 192] {
+            exact(&STD, &STANDARD, len);
+            exact(&URL, &URL_SAFE, len);
+            exact(&NO_PAD, &STANDARD_NO_PAD, len);
+        }
+    }
+}
+
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_256_hardware_coverage {
+    use super::*;
+    use crate::simd::testutil::{
+        check_decode, check_decode_exact, check_encode, has_avx512_vbmi_vl,
+    };
+    use base64::engine::general_purpose::{
+        STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_STANDARD_NO_PAD, URL_SAFE as REF_URL_SAFE,
+    };
+
+    /// Every length 0..=400 against the `base64` oracle, crossing each tier
+    /// boundary of both kernels several times.
+    #[test]
+    fn hw_avx512_vbmi_256_all_lengths_0_to_400() {
+        if !has_avx512_vbmi_vl() {
+            return;
+        }
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for len in 0..=400 {
+                check_encode(&config, oracle, encode_slice_avx512_vbmi_256, len);
+                check_decode(&config, oracle, decode_slice_avx512_vbmi_256, len);
+            }
+        }
+    }
+
+    /// Every byte value in every lane of a single-tier vector, so both halves
+    /// of the split lookup and the high-bit rejection are checked against
+    /// scalar.
+    #[test]
+    fn hw_avx512_vbmi_256_decode_lut_exhaustive() {
+        if !has_avx512_vbmi_vl() {
+            return;
+        }
+        for url_safe in [false, true] {
+            let config = Config {
+                url_safe,
+                padding: true,
+                lenient: false,
+            };
+            for candidate in 0u8..=255 {
+                let mut input = [candidate; 36];
+                input[32..].copy_from_slice(b"AAAA");
+
+                let mut ymm_out = [0u8; 32];
+                let ymm = unsafe { decode_slice_avx512_vbmi_256(&config, &input, &mut ymm_out) };
+                let mut scalar_out = [0u8; 32];
+                let scalar = crate::scalar::decode_slice(&config, &input[..32], &mut scalar_out);
+
+                match scalar {
+                    Ok(n) => {
+                        assert_eq!(ymm, Ok(n + 3), "byte {candidate:#04x}: scalar accepted it");
+                        assert_eq!(
+                            &ymm_out[..n],
+                            &scalar_out[..n],
+                            "byte {candidate:#04x}: decoded value mismatch"
+                        );
+                    }
+                    Err(e) => assert_eq!(ymm, Err(e), "byte {candidate:#04x}: validity mismatch"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn hw_avx512_vbmi_256_decode_exact_buffer_boundaries() {
+        if !has_avx512_vbmi_vl() {
+            return;
+        }
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for &len in &[3, 6, 21, 24, 27, 48, 96, 97, 99, 120, 192, 1000, 1001] {
+                check_decode_exact(&config, oracle, decode_slice_avx512_vbmi_256, len);
+            }
+        }
+    }
+}
+
+/// The lenient decode LUT, split across the two `vpermi2b` halves, at every
+/// length across the tiers (skipped when the host CPU lacks AVX-512VL+VBMI).
+#[cfg(all(test, not(miri)))]
+mod avx512_vbmi_256_lenient {
+    use super::*;
+    use crate::simd::testutil::{check_decode_lenient_exact, has_avx512_vbmi_vl};
+
+    #[test]
+    fn hw_avx512_vbmi_256_decode_lenient_all_lengths() {
+        if !has_avx512_vbmi_vl() {
+            return;
+        }
+        for len in (0..=300).chain([1_000]) {
+            check_decode_lenient_exact(decode_slice_avx512_vbmi_256, len);
+        }
+    }
+}
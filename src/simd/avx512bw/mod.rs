@@ -0,0 +1,481 @@
+//! AVX-512BW Base64, for Skylake-SP/Cascade Lake-class CPUs that have the
+//! 512-bit byte instructions but not VBMI's cross-lane byte permutes.
+//!
+//! The arithmetic is the AVX2 kernel's at four 128-bit lanes: the `vpshufb`
+//! reshuffle and multiply-based field split on encode, the nibble-LUT
+//! validation and `vpmaddubsw`/`vpmaddwd` packing on decode. The one thing
+//! `vpshufb` cannot do is move bytes between lanes, so a single dword permute
+//! (`vpermd`, plain AVX-512F) does it on each side: on encode it spreads 48
+//! input bytes into four 12-byte lane groups, on decode it closes the four
+//! 12-byte lane results back up into 48 contiguous bytes.
+//!
+//! The loop structure is the VBMI kernel's: quad, single and masked tiers,
+//! where the masked `vmovdqu8` passes take the remainder down to the final
+//! partial group instead of handing tens of bytes to the scalar kernel.
+
+use super::PACK_SHUFFLE;
+use crate::{Config, Error};
+use core::hint::black_box;
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m128i, __m512i, _mm_loadu_si128, _mm_setr_epi8, _mm512_add_epi8, _mm512_and_si512,
+    _mm512_broadcast_i32x4, _mm512_cmpgt_epi8_mask, _mm512_loadu_si512, _mm512_madd_epi16,
+    _mm512_maddubs_epi16, _mm512_mask_add_epi8, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8,
+    _mm512_maskz_loadu_epi8, _mm512_mullo_epi16, _mm512_or_si512, _mm512_set1_epi8,
+    _mm512_set1_epi16, _mm512_set1_epi32, _mm512_setzero_si512, _mm512_srli_epi16,
+    _mm512_storeu_si512, _mm512_subs_epu8, _mm512_test_epi8_mask,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m128i, __m512i, _mm_loadu_si128, _mm_setr_epi8, _mm512_add_epi8, _mm512_and_si512,
+    _mm512_broadcast_i32x4, _mm512_cmpgt_epi8_mask, _mm512_loadu_si512, _mm512_madd_epi16,
+    _mm512_maddubs_epi16, _mm512_mask_add_epi8, _mm512_mask_loadu_epi8, _mm512_mask_storeu_epi8,
+    _mm512_maskz_loadu_epi8, _mm512_mullo_epi16, _mm512_or_si512, _mm512_set1_epi8,
+    _mm512_set1_epi16, _mm512_set1_epi32, _mm512_setzero_si512, _mm512_srli_epi16,
+    _mm512_storeu_si512, _mm512_subs_epu8, _mm512_test_epi8_mask,
+};
+
+#[cfg(all(not(miri), target_arch = "x86"))]
+use core::arch::x86::{_mm512_permutexvar_epi32, _mm512_shuffle_epi8};
+#[cfg(all(not(miri), target_arch = "x86_64"))]
+use core::arch::x86_64::{_mm512_permutexvar_epi32, _mm512_shuffle_epi8};
+
+// --- Compile-time lookup tables ---
+
+/// `vpermd` control that spreads 48 input bytes over the four lanes, 12 per
+/// lane at the lane's start: lane `k` gets dwords `3k..3k+3`, and a repeat of
+/// the last one as filler the reshuffle never reads.
+const BW_ENCODE_SPREAD: [i32; 16] = [0, 1, 2, 2, 3, 4, 5, 5, 6, 7, 8, 8, 9, 10, 11, 11];
+
+/// `vpermd` control that closes the four lanes' 12 packed bytes up into 48
+/// contiguous output bytes. The top 16 bytes are unused.
+const BW_PACK_COMPRESS: [i32; 16] = [0, 1, 2, 4, 5, 6, 8, 9, 10, 12, 13, 14, 0, 0, 0, 0];
+
+/// `vpmaddubsw` multiplier that folds each index pair into one 12-bit value:
+/// `even * 64 + odd`.
+const BW_PACK_L1: i16 = 0x0140;
+
+/// `vpmaddwd` multiplier that folds each 12-bit pair into one 24-bit value.
+const BW_PACK_L2: i32 = 0x0001_1000;
+
+// --- Stride constants ---
+//
+// The Kani index proofs in `verify` reason over this same arithmetic
+// symbolically, and import these rather than restating them, so a stride that
+// changes here changes the proofs too instead of silently drifting out from
+// under them. They are the VBMI kernel's, since the tiers are.
+
+/// Bytes a full-width load reads or a full-width store writes.
+const ENC_VEC: usize = 64;
+/// Input bytes one encode vector consumes.
+const ENC_VEC_IN: usize = 48;
+/// Characters one encode vector produces.
+const ENC_VEC_OUT: usize = 64;
+/// Vectors per iteration of the encoder's quad tier.
+const ENC_UNROLL: usize = 4;
+/// Input bytes per quad-tier iteration.
+const ENC_QUAD_IN: usize = ENC_VEC_IN * ENC_UNROLL;
+/// Characters per quad-tier iteration.
+const ENC_QUAD_OUT: usize = ENC_VEC_OUT * ENC_UNROLL;
+/// Quad-tier guard. The binding requirement is only that the last load
+/// (starting 144 bytes in, reading 64) stays in bounds, i.e. 208; this is the
+/// output-sized round number above it, and Layer 1 proves it suffices.
+const ENC_QUAD_MIN: usize = 256;
+/// Single-tier guard: a plain load reads a whole vector to consume 48 of it.
+const ENC_SINGLE_MIN: usize = ENC_VEC;
+/// Input bytes per Base64 group; the masked tier handles whole groups only.
+const ENC_GROUP: usize = 3;
+
+/// Characters one decode vector consumes, which is also its load width.
+const DEC_VEC_IN: usize = 64;
+/// Bytes one decode vector produces.
+const DEC_VEC_OUT: usize = 48;
+/// Vectors per iteration of the decoder's quad tier.
+const DEC_UNROLL: usize = 4;
+/// Characters per quad-tier iteration.
+const DEC_QUAD_IN: usize = DEC_VEC_IN * DEC_UNROLL;
+/// Bytes per quad-tier iteration.
+const DEC_QUAD_OUT: usize = DEC_VEC_OUT * DEC_UNROLL;
+/// Characters per Base64 group.
+const DEC_GROUP: usize = 4;
+/// Characters every decode tier stops short of the end, so that the final
+/// group — the only one that may legally carry `'='` — is always decided by the
+/// scalar tail, which owns the padding and length rules.
+const DEC_LEAD: usize = 4;
+/// Quad-tier guard: what it consumes, plus the margin.
+const DEC_QUAD_MIN: usize = DEC_QUAD_IN + DEC_LEAD;
+/// Single-tier guard: what it consumes, plus the margin.
+const DEC_SINGLE_MIN: usize = DEC_VEC_IN + DEC_LEAD;
+/// Masked-tier guard: one group, plus the margin.
+const DEC_MASKED_MIN: usize = DEC_GROUP + DEC_LEAD;
+
+/// Store mask selecting the low 48 bytes of a decoded vector.
+const LOW_48: u64 = (1u64 << DEC_VEC_OUT) - 1;
+
+// ======================================================================
+// Miri-compatible permute shims
+// ======================================================================
+
+#[cfg(miri)]
+use self::verify::intrinsic_models as m;
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn zmm_shuffle_epi8(a: __m512i, b: __m512i) -> __m512i {
+    #[cfg(miri)]
+    {
+        unsafe { m::shuffle_epi8_model(a, b) }
+    }
+    #[cfg(not(miri))]
+    {
+        _mm512_shuffle_epi8(a, b)
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn zmm_permutexvar_epi32(idx: __m512i, a: __m512i) -> __m512i {
+    #[cfg(miri)]
+    {
+        unsafe { m::permutexvar_epi32_model(idx, a) }
+    }
+    #[cfg(not(miri))]
+    {
+        _mm512_permutexvar_epi32(idx, a)
+    }
+}
+
+/// The same 16 bytes in all four lanes, which is what every in-lane `vpshufb`
+/// table here needs.
+#[inline]
+#[target_feature(enable = "avx512f,avx512bw")]
+fn lanes(v: __m128i) -> __m512i {
+    _mm512_broadcast_i32x4(v)
+}
+
+// --- AVX-512BW encoder ---
+
+/// Precomputed encode constants: the SSSE3 kernel's, one copy per lane (see
+/// `EncodeConstantsAvx2` for credit), plus the lane spread.
+struct EncodeConstantsAvx512Bw {
+    spread: __m512i,
+    reshuffle: __m512i,
+    align_mul: __m512i,
+    field_mask: __m512i,
+    field_mul: __m512i,
+    translate: __m512i,
+    c51: __m512i,
+    c25: __m512i,
+}
+
+#[target_feature(enable = "avx512f,avx512bw")]
+fn encode_constants_avx512bw(config: Config) -> EncodeConstantsAvx512Bw {
+    let translate = if config.url_safe {
+        _mm_setr_epi8(
+            65, 71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -17, 32, 0, 0,
+        )
+    } else {
+        _mm_setr_epi8(
+            65, 71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -19, -16, 0, 0,
+        )
+    };
+
+    EncodeConstantsAvx512Bw {
+        spread: unsafe { _mm512_loadu_si512(BW_ENCODE_SPREAD.as_ptr().cast()) },
+        reshuffle: lanes(_mm_setr_epi8(
+            1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10,
+        )),
+        // Kept opaque for the same reason as in the AVX2 kernel: LLVM would
+        // otherwise strength-reduce the power-of-two multiplies into longer
+        // shift-and-blend sequences.
+        align_mul: black_box(_mm512_set1_epi32(0x0010_0001)),
+        field_mask: _mm512_set1_epi32(0x003F_03F0),
+        field_mul: black_box(_mm512_set1_epi32(0x0100_0010)),
+        translate: lanes(translate),
+        c51: _mm512_set1_epi8(51),
+        c25: _mm512_set1_epi8(25),
+    }
+}
+
+/// Encodes the first 48 of 64 raw input bytes into 64 Base64 characters; see
+/// `encode_vec_avx2` for how the two multiplies split the 6-bit fields. The
+/// compare that picks the LUT row lands in a mask register here, so a masked
+/// add stands in for the AVX2 kernel's subtract of an all-ones lane.
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn encode_vec_avx512bw(input: __m512i, k: &EncodeConstantsAvx512Bw) -> __m512i {
+    let spread = unsafe { zmm_permutexvar_epi32(k.spread, input) };
+    let shuffled = unsafe { zmm_shuffle_epi8(spread, k.reshuffle) };
+    let aligned = _mm512_srli_epi16::<10>(_mm512_mullo_epi16(shuffled, k.align_mul));
+    let fields = _mm512_mullo_epi16(_mm512_and_si512(shuffled, k.field_mask), k.field_mul);
+    let indices = _mm512_or_si512(aligned, fields);
+
+    let reduced = _mm512_subs_epu8(indices, k.c51);
+    let above_25 = _mm512_cmpgt_epi8_mask(indices, k.c25);
+    let lut_idx = _mm512_mask_add_epi8(reduced, above_25, reduced, _mm512_set1_epi8(1));
+    _mm512_add_epi8(indices, unsafe { zmm_shuffle_epi8(k.translate, lut_idx) })
+}
+
+#[target_feature(enable = "avx512f,avx512bw")]
+pub(crate) unsafe fn encode_slice_avx512bw(config: &Config, input: &[u8], dst_slice: &mut [u8]) {
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+    let mut rem = input.len();
+
+    let k = encode_constants_avx512bw(*config);
+
+    macro_rules! encode_vec {
+        ($v:expr) => {{
+            let raw = $v;
+            unsafe { encode_vec_avx512bw(raw, &k) }
+        }};
+    }
+    macro_rules! load_48 {
+        ($off:expr) => {{ unsafe { _mm512_loadu_si512(src.add($off).cast()) } }};
+    }
+
+    // Quad tier: 192 input bytes -> 256 output. The last load starts 144 bytes
+    // in and reads 64, so 208 <= 256 bytes are always in bounds.
+    while rem >= ENC_QUAD_MIN {
+        let r0 = encode_vec!(load_48!(0));
+        let r1 = encode_vec!(load_48!(ENC_VEC_IN));
+        let r2 = encode_vec!(load_48!(2 * ENC_VEC_IN));
+        let r3 = encode_vec!(load_48!(3 * ENC_VEC_IN));
+        unsafe { _mm512_storeu_si512(dst.cast(), r0) };
+        unsafe { _mm512_storeu_si512(dst.add(ENC_VEC_OUT).cast(), r1) };
+        unsafe { _mm512_storeu_si512(dst.add(2 * ENC_VEC_OUT).cast(), r2) };
+        unsafe { _mm512_storeu_si512(dst.add(3 * ENC_VEC_OUT).cast(), r3) };
+        src = unsafe { src.add(ENC_QUAD_IN) };
+        dst = unsafe { dst.add(ENC_QUAD_OUT) };
+        rem -= ENC_QUAD_IN;
+    }
+
+    // Single tier: 48 input bytes -> 64 output. A plain load reads 64 bytes to
+    // consume 48, so it needs 64 to exist.
+    while rem >= ENC_SINGLE_MIN {
+        let r = encode_vec!(load_48!(0));
+        unsafe { _mm512_storeu_si512(dst.cast(), r) };
+        src = unsafe { src.add(ENC_VEC_IN) };
+        dst = unsafe { dst.add(ENC_VEC_OUT) };
+        rem -= ENC_VEC_IN;
+    }
+
+    // Masked tier: whole triples only, so no padding logic lands here. `rem` is
+    // now < 64 and `take` is capped at 48, so this runs at most twice.
+    while rem >= ENC_GROUP {
+        let take = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
+        let out = take / ENC_GROUP * 4;
+        let v = unsafe { _mm512_maskz_loadu_epi8(u64::MAX >> (ENC_VEC - take), src.cast()) };
+        let chars = encode_vec!(v);
+        unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), u64::MAX >> (ENC_VEC - out), chars) };
+        src = unsafe { src.add(take) };
+        dst = unsafe { dst.add(out) };
+        rem -= take;
+    }
+
+    // Scalar now sees at most the final 1-2 bytes, plus whatever padding the
+    // config asks for.
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::encode(config, input, src, dst_slice, dst_off) };
+}
+
+// --- AVX-512BW decoder ---
+
+/// Precomputed decode constants: the SSSE3 kernel's nibble LUTs, one copy per
+/// lane (see `DecodeConstantsAvx2` for their derivation and credit), and the
+/// packing constants.
+struct DecodeConstantsAvx512Bw {
+    lut_lo: __m512i,
+    lut_hi: __m512i,
+    lut_shift: __m512i,
+    lut_roll: __m512i,
+    pack_l1: __m512i,
+    pack_l2: __m512i,
+    pack_shuffle: __m512i,
+    pack_compress: __m512i,
+    mask_nibble: __m512i,
+}
+
+#[target_feature(enable = "avx512f,avx512bw")]
+unsafe fn decode_constants_avx512bw(config: &Config) -> DecodeConstantsAvx512Bw {
+    let (lut_lo, lut_hi, lut_shift, lut_roll) = if config.lenient {
+        let lut_lo = _mm_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3A, 0x3B, 0x3A,
+            0x3B, 0x32,
+        );
+        let lut_hi = _mm_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10,
+        );
+        let lut_shift = _mm_setr_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 9);
+        let lut_roll = _mm_setr_epi8(
+            0, 0, 19, 4, -65, -65, -71, -71, 17, 0, -65, 16, -71, -65, -32, -71,
+        );
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    } else if config.url_safe {
+        let lut_lo = _mm_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3B, 0x3B, 0x3A,
+            0x3B, 0x33,
+        );
+        let lut_hi = _mm_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10,
+        );
+        let lut_shift = _mm_setr_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4);
+        let lut_roll = _mm_setr_epi8(
+            0, 0, 17, 4, -65, -65, -71, -71, -65, -32, -71, 0, 0, 0, 0, 0,
+        );
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    } else {
+        let lut_lo = _mm_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x1A, 0x1B, 0x1B,
+            0x1B, 0x1A,
+        );
+        let lut_hi = _mm_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x08, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10,
+        );
+        let lut_shift = _mm_setr_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6);
+        let lut_roll = _mm_setr_epi8(0, 0, 19, 4, -65, -65, -71, -71, 16, 0, -65, 0, -71, 0, 0, 0);
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    };
+
+    // The shared pack shuffle is 32 bytes wide; both halves are the same
+    // 16-byte pattern, so the low one is broadcast.
+    DecodeConstantsAvx512Bw {
+        lut_lo: lanes(lut_lo),
+        lut_hi: lanes(lut_hi),
+        lut_shift: lanes(lut_shift),
+        lut_roll: lanes(lut_roll),
+        pack_l1: _mm512_set1_epi16(BW_PACK_L1),
+        pack_l2: _mm512_set1_epi32(BW_PACK_L2),
+        pack_shuffle: lanes(unsafe { _mm_loadu_si128(PACK_SHUFFLE.as_ptr().cast::<__m128i>()) }),
+        pack_compress: unsafe { _mm512_loadu_si512(BW_PACK_COMPRESS.as_ptr().cast()) },
+        mask_nibble: _mm512_set1_epi8(0x0F),
+    }
+}
+
+#[target_feature(enable = "avx512f,avx512bw")]
+pub(crate) unsafe fn decode_slice_avx512bw(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) -> Result<usize, Error> {
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+    let mut rem = input.len();
+
+    let DecodeConstantsAvx512Bw {
+        lut_lo,
+        lut_hi,
+        lut_shift,
+        lut_roll,
+        pack_l1,
+        pack_l2,
+        pack_shuffle,
+        pack_compress,
+        mask_nibble,
+    } = unsafe { decode_constants_avx512bw(config) };
+
+    // Validate + decode one vector, exactly as `decode_slice_avx2` does, with
+    // each 128-bit lane shuffled through its own copy of the tables. A
+    // character is bad iff its `err` byte is non-zero, so OR-ing every one into
+    // `bad` and testing it once after the loops validates the whole buffer.
+    let mut bad = _mm512_setzero_si512();
+    macro_rules! decode_vec {
+        ($input:expr) => {{
+            let v = $input;
+            let hi_nibbles = _mm512_and_si512(_mm512_srli_epi16::<4>(v), mask_nibble);
+            let lo_nibbles = _mm512_and_si512(v, mask_nibble);
+
+            let lo = unsafe { zmm_shuffle_epi8(lut_lo, lo_nibbles) };
+            let hi = unsafe { zmm_shuffle_epi8(lut_hi, hi_nibbles) };
+            bad = _mm512_or_si512(bad, _mm512_and_si512(lo, hi));
+
+            let shift = unsafe { zmm_shuffle_epi8(lut_shift, lo_nibbles) };
+            let roll_idx = _mm512_add_epi8(hi_nibbles, shift);
+            let roll = unsafe { zmm_shuffle_epi8(lut_roll, roll_idx) };
+            _mm512_add_epi8(v, roll)
+        }};
+    }
+
+    // 12 bytes land at the bottom of each lane; the dword permute closes them
+    // up into the low 48 bytes.
+    macro_rules! pack_vec {
+        ($idx:expr) => {{
+            let m = _mm512_maddubs_epi16($idx, pack_l1);
+            let p = _mm512_madd_epi16(m, pack_l2);
+            let packed = unsafe { zmm_shuffle_epi8(p, pack_shuffle) };
+            unsafe { zmm_permutexvar_epi32(pack_compress, packed) }
+        }};
+    }
+
+    // Quad tier: 256 input characters -> 192 output bytes. Every tier stops at
+    // least 4 characters short of the end so the final group -- the only one
+    // that may legally carry '=' -- is always decided by the scalar tail.
+    while rem >= DEC_QUAD_MIN {
+        let v0 = unsafe { _mm512_loadu_si512(src.cast::<__m512i>()) };
+        let v1 = unsafe { _mm512_loadu_si512(src.add(DEC_VEC_IN).cast::<__m512i>()) };
+        let v2 = unsafe { _mm512_loadu_si512(src.add(2 * DEC_VEC_IN).cast::<__m512i>()) };
+        let v3 = unsafe { _mm512_loadu_si512(src.add(3 * DEC_VEC_IN).cast::<__m512i>()) };
+
+        let p0 = pack_vec!(decode_vec!(v0));
+        let p1 = pack_vec!(decode_vec!(v1));
+        let p2 = pack_vec!(decode_vec!(v2));
+        let p3 = pack_vec!(decode_vec!(v3));
+
+        // As in the VBMI kernel, only the last store needs masking: each of the
+        // first three overhangs its 48 bytes by 16, and the next store in this
+        // same iteration rewrites exactly that overhang.
+        unsafe { _mm512_storeu_si512(dst.cast(), p0) };
+        unsafe { _mm512_storeu_si512(dst.add(DEC_VEC_OUT).cast(), p1) };
+        unsafe { _mm512_storeu_si512(dst.add(2 * DEC_VEC_OUT).cast(), p2) };
+        unsafe { _mm512_mask_storeu_epi8(dst.add(3 * DEC_VEC_OUT).cast::<i8>(), LOW_48, p3) };
+
+        src = unsafe { src.add(DEC_QUAD_IN) };
+        dst = unsafe { dst.add(DEC_QUAD_OUT) };
+        rem -= DEC_QUAD_IN;
+    }
+
+    // Single tier: 64 input characters -> 48 output bytes.
+    while rem >= DEC_SINGLE_MIN {
+        let v = unsafe { _mm512_loadu_si512(src.cast::<__m512i>()) };
+        let p = pack_vec!(decode_vec!(v));
+        unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), LOW_48, p) };
+        src = unsafe { src.add(DEC_VEC_IN) };
+        dst = unsafe { dst.add(DEC_VEC_OUT) };
+        rem -= DEC_VEC_IN;
+    }
+
+    // Masked tier: the lanes past the end are backfilled with 'A', which decodes
+    // to index 0, so they cannot trip validation.
+    if rem >= DEC_MASKED_MIN {
+        let take = (rem - DEC_LEAD) & !(DEC_GROUP - 1);
+        let out = take / DEC_GROUP * 3;
+        let v = unsafe {
+            _mm512_mask_loadu_epi8(
+                _mm512_set1_epi8(b'A'.cast_signed()),
+                u64::MAX >> (DEC_VEC_IN - take),
+                src.cast(),
+            )
+        };
+        let p = pack_vec!(decode_vec!(v));
+        unsafe { _mm512_mask_storeu_epi8(dst.cast::<i8>(), u64::MAX >> (DEC_VEC_IN - out), p) };
+        src = unsafe { src.add(take) };
+        dst = unsafe { dst.add(out) };
+    }
+
+    if _mm512_test_epi8_mask(bad, bad) != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::decode(config, input, src, dst_slice, dst_off) }
+}
+
+// Verification: Kani proofs, Intel-pseudocode intrinsic models, and the Miri +
+// hardware coverage suites.
+#[cfg(any(kani, test, miri))]
+mod verify;
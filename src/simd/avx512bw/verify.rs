@@ -0,0 +1,1268 @@
+//! AVX-512BW verification: Kani proofs, Intel-pseudocode intrinsic models,
+//! and the Miri + hardware coverage suites. Laid out like
+//! `avx512_vbmi/verify.rs`, whose tier structure this kernel shares.
+
+use super::*;
+
+#[cfg(kani)]
+mod kani_verification_avx512bw {
+    use super::*;
+    use crate::{Config, STANDARD as TURBO_STANDARD, STANDARD_NO_PAD as TURBO_STANDARD_NO_PAD};
+
+    // Only used inside `#[kani::stub(...)]` paths, which don't count as a use.
+    #[allow(unused_imports)]
+    use super::intrinsic_models as m;
+
+    // Layer 1 — index proofs: reason over a symbolic `len` and an arbitrary
+    // iteration (no vectors), giving an induction (step/exit) that covers all N
+    // cheaply. Every stride is imported from the kernel module rather than
+    // restated, so a stride that changes there changes these proofs too. See
+    // the README's "Safety & Verification".
+    //
+    // As for VBMI, every tier consumes a whole number of Base64 groups, so both
+    // models track groups consumed: after `g` groups the encoder has written
+    // `4 * g` characters and the decoder `3 * g` bytes.
+
+    use super::super::{
+        DEC_GROUP, DEC_LEAD, DEC_MASKED_MIN, DEC_QUAD_IN, DEC_QUAD_MIN, DEC_QUAD_OUT,
+        DEC_SINGLE_MIN, DEC_VEC_IN, DEC_VEC_OUT, ENC_GROUP, ENC_QUAD_IN, ENC_QUAD_MIN,
+        ENC_QUAD_OUT, ENC_SINGLE_MIN, ENC_VEC, ENC_VEC_IN, ENC_VEC_OUT,
+    };
+
+    /// Largest `len` considered: above `usize::MAX / 4` the unpadded
+    /// `encoded_len`'s `len * 4` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 4;
+
+    /// A full-width store writes exactly as many bytes as a full-width load
+    /// reads; the decoder's quad tier is the only place the distinction shows,
+    /// where three of its four stores are unmasked and so overhang their 48.
+    const DEC_STORE_WIDE: usize = DEC_VEC_IN;
+
+    /// Groups consumed per iteration of each tier.
+    const ENC_QUAD_GROUPS: usize = ENC_QUAD_IN / ENC_GROUP;
+    const ENC_SINGLE_GROUPS: usize = ENC_VEC_IN / ENC_GROUP;
+    const DEC_QUAD_GROUPS: usize = DEC_QUAD_IN / DEC_GROUP;
+    const DEC_SINGLE_GROUPS: usize = DEC_VEC_IN / DEC_GROUP;
+
+    // The group model above is only faithful if every tier really does consume
+    // whole groups at the documented ratio. Fail the build if a stride is
+    // edited into something that doesn't.
+    const _: () = assert!(
+        ENC_QUAD_IN % ENC_GROUP == 0 && ENC_VEC_IN % ENC_GROUP == 0,
+        "every encode tier must consume whole 3-byte groups"
+    );
+    const _: () = assert!(
+        ENC_QUAD_GROUPS * 4 == ENC_QUAD_OUT && ENC_SINGLE_GROUPS * 4 == ENC_VEC_OUT,
+        "every encode tier must emit 4 characters per group"
+    );
+    const _: () = assert!(
+        DEC_QUAD_IN % DEC_GROUP == 0 && DEC_VEC_IN % DEC_GROUP == 0,
+        "every decode tier must consume whole 4-character groups"
+    );
+    const _: () = assert!(
+        DEC_QUAD_GROUPS * 3 == DEC_QUAD_OUT && DEC_SINGLE_GROUPS * 3 == DEC_VEC_OUT,
+        "every decode tier must emit 3 bytes per group"
+    );
+
+    fn enc_cap(len: usize, padding: bool) -> usize {
+        if padding {
+            TURBO_STANDARD.encoded_len(len)
+        } else {
+            TURBO_STANDARD_NO_PAD.encoded_len(len)
+        }
+    }
+
+    fn dec_cap(len: usize) -> usize {
+        TURBO_STANDARD.estimate_decoded_len(len)
+    }
+
+    /// An arbitrary reachable encoder state: `g` whole groups consumed, with
+    /// `rem` input bytes still to go. Returns `(done, dst_off, rem)`.
+    fn any_enc_state(len: usize) -> (usize, usize, usize) {
+        let g: usize = kani::any();
+        kani::assume(g <= MAX_LEN / ENC_GROUP);
+        let done = ENC_GROUP * g;
+        kani::assume(done <= len);
+        (done, 4 * g, len - done)
+    }
+
+    /// As [`any_enc_state`], for the decoder: 4 characters in, 3 bytes out.
+    fn any_dec_state(len: usize) -> (usize, usize, usize) {
+        let g: usize = kani::any();
+        kani::assume(g <= MAX_LEN / DEC_GROUP);
+        let done = DEC_GROUP * g;
+        kani::assume(done <= len);
+        (done, 3 * g, len - done)
+    }
+
+    // --- Encoder ---
+
+    /// Inductive step for the encoder's quad tier.
+    #[kani::proof]
+    fn check_bw_enc_quad_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem >= ENC_QUAD_MIN); // guard `while rem >= 256`
+        let cap = enc_cap(len, padding);
+
+        // Widest accesses: the fourth load starts 3 vectors in and reads a full
+        // 64, and the fourth store writes a full 64 at 3 vectors out.
+        assert!(
+            done + 3 * ENC_VEC_IN + ENC_VEC <= len,
+            "quad load leaves input"
+        );
+        assert!(
+            dst_off + 3 * ENC_VEC_OUT + ENC_VEC_OUT <= cap,
+            "quad store leaves output"
+        );
+
+        assert_eq!(
+            dst_off + ENC_QUAD_OUT,
+            4 * (done / ENC_GROUP + ENC_QUAD_GROUPS)
+        );
+        assert!(done + ENC_QUAD_IN <= len);
+        assert_eq!(rem - ENC_QUAD_IN, len - (done + ENC_QUAD_IN));
+    }
+
+    /// Inductive step for the encoder's single tier.
+    #[kani::proof]
+    fn check_bw_enc_single_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem >= ENC_SINGLE_MIN); // guard `while rem >= 64`
+        let cap = enc_cap(len, padding);
+
+        assert!(done + ENC_VEC <= len, "single load leaves input");
+        assert!(dst_off + ENC_VEC_OUT <= cap, "single store leaves output");
+
+        assert_eq!(
+            dst_off + ENC_VEC_OUT,
+            4 * (done / ENC_GROUP + ENC_SINGLE_GROUPS)
+        );
+        assert!(done + ENC_VEC_IN <= len);
+        assert_eq!(rem - ENC_VEC_IN, len - (done + ENC_VEC_IN));
+    }
+
+    /// Inductive step for the encoder's masked tier. The masked load and store
+    /// touch only their masked lanes, so the obligation is `take` bytes in and
+    /// `out` bytes out — not a full vector of either.
+    #[kani::proof]
+    fn check_bw_enc_masked_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        // Guard `while rem >= 3`, entered only once the single tier has stopped.
+        kani::assume(rem >= ENC_GROUP && rem < ENC_SINGLE_MIN);
+        let cap = enc_cap(len, padding);
+
+        let take = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
+        let out = take / ENC_GROUP * 4;
+
+        assert!(
+            (ENC_GROUP..=ENC_VEC_IN).contains(&take),
+            "load mask shift out of range"
+        );
+        assert!(
+            (4..=ENC_VEC_OUT).contains(&out),
+            "store mask shift out of range"
+        );
+
+        assert!(done + take <= len, "masked load leaves input");
+        assert!(dst_off + out <= cap, "masked store leaves output");
+
+        assert_eq!(take % ENC_GROUP, 0);
+        assert_eq!(dst_off + out, 4 * ((done + take) / ENC_GROUP));
+    }
+
+    /// The masked tier's loop terminates, in at most two iterations.
+    #[kani::proof]
+    fn check_bw_enc_masked_terminates() {
+        let rem: usize = kani::any();
+        kani::assume(rem >= ENC_GROUP && rem < ENC_SINGLE_MIN);
+
+        let take1 = (rem - rem % ENC_GROUP).min(ENC_VEC_IN);
+        assert!(take1 >= ENC_GROUP, "first pass makes no progress");
+        let rem1 = rem - take1;
+
+        if rem1 >= ENC_GROUP {
+            let take2 = (rem1 - rem1 % ENC_GROUP).min(ENC_VEC_IN);
+            assert!(take2 >= ENC_GROUP, "second pass makes no progress");
+            assert!(rem1 - take2 < ENC_GROUP, "a third pass would be needed");
+        }
+    }
+
+    /// Exit case: the scalar encoder sees at most a final partial group, and
+    /// the vector prefix plus that group is exactly the encoded length.
+    #[kani::proof]
+    fn check_bw_enc_tail_handoff() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_enc_state(len);
+        kani::assume(rem < ENC_GROUP); // every loop has exited
+
+        assert_eq!(
+            dst_off + enc_cap(rem, padding),
+            enc_cap(len, padding),
+            "prefix + tail must equal encoded length"
+        );
+        assert_eq!(done + rem, len);
+    }
+
+    // --- Decoder ---
+
+    /// Inductive step for the decoder's quad tier.
+    #[kani::proof]
+    fn check_bw_dec_quad_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        kani::assume(rem >= DEC_QUAD_MIN); // guard `while rem >= 260`
+        let cap = dec_cap(len);
+
+        assert!(
+            done + 3 * DEC_VEC_IN + DEC_VEC_IN <= len,
+            "quad load leaves input"
+        );
+
+        // The first three stores are unmasked, so each overhangs its 48 bytes
+        // by 16; the third reaches furthest of them. Only the fourth is masked,
+        // and it closes the iteration exactly at 192.
+        assert!(
+            dst_off + 2 * DEC_VEC_OUT + DEC_STORE_WIDE <= cap,
+            "quad unmasked store overhang leaves output"
+        );
+        assert!(
+            dst_off + DEC_QUAD_OUT <= cap,
+            "quad masked store leaves output"
+        );
+
+        assert_eq!(
+            dst_off + DEC_QUAD_OUT,
+            3 * (done / DEC_GROUP + DEC_QUAD_GROUPS)
+        );
+        assert!(done + DEC_QUAD_IN <= len);
+        assert_eq!(rem - DEC_QUAD_IN, len - (done + DEC_QUAD_IN));
+    }
+
+    /// Inductive step for the decoder's single tier.
+    #[kani::proof]
+    fn check_bw_dec_single_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        kani::assume(rem >= DEC_SINGLE_MIN); // guard `while rem >= 68`
+        let cap = dec_cap(len);
+
+        assert!(done + DEC_VEC_IN <= len, "single load leaves input");
+        assert!(dst_off + DEC_VEC_OUT <= cap, "single store leaves output");
+
+        assert_eq!(
+            dst_off + DEC_VEC_OUT,
+            3 * (done / DEC_GROUP + DEC_SINGLE_GROUPS)
+        );
+        assert!(done + DEC_VEC_IN <= len);
+        assert_eq!(rem - DEC_VEC_IN, len - (done + DEC_VEC_IN));
+    }
+
+    /// The decoder's masked tier: mask shifts in range, accesses in bounds, and
+    /// the invariant preserved.
+    #[kani::proof]
+    fn check_bw_dec_masked_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (done, dst_off, rem) = any_dec_state(len);
+        // Guard `if rem >= 8`, entered only once the single tier has stopped.
+        kani::assume(rem >= DEC_MASKED_MIN && rem < DEC_SINGLE_MIN);
+        let cap = dec_cap(len);
+
+        let take = (rem - DEC_LEAD) & !(DEC_GROUP - 1);
+        let out = take / DEC_GROUP * 3;
+
+        assert!(
+            (DEC_GROUP..=DEC_VEC_IN).contains(&take),
+            "load mask shift out of range"
+        );
+        assert!(
+            (3..=DEC_VEC_OUT).contains(&out),
+            "store mask shift out of range"
+        );
+
+        assert!(done + take <= len, "masked load leaves input");
+        assert!(dst_off + out <= cap, "masked store leaves output");
+
+        assert_eq!(take % DEC_GROUP, 0);
+        assert_eq!(dst_off + out, 3 * ((done + take) / DEC_GROUP));
+    }
+
+    /// Exit case: every tier that runs leaves at least a full group
+    /// unconsumed, so the final (possibly padded) group is always the scalar
+    /// tail's. See `check_vbmi_dec_tail_slack` for why this matters.
+    #[kani::proof]
+    fn check_bw_dec_tail_slack() {
+        let rem: usize = kani::any();
+        kani::assume(rem <= MAX_LEN);
+
+        if rem >= DEC_QUAD_MIN {
+            assert!(
+                rem - DEC_QUAD_IN >= DEC_LEAD,
+                "quad tier ate the last group"
+            );
+        }
+        if rem >= DEC_SINGLE_MIN {
+            assert!(
+                rem - DEC_VEC_IN >= DEC_LEAD,
+                "single tier ate the last group"
+            );
+        }
+        if rem >= DEC_MASKED_MIN {
+            let take = (rem - DEC_LEAD) & !(DEC_GROUP - 1);
+            let left = rem - take;
+            assert!(take >= DEC_GROUP, "masked tier makes no progress");
+            assert!(left >= DEC_LEAD, "masked tier ate the last group");
+            assert!(left < DEC_MASKED_MIN, "masked tier would run again");
+        }
+    }
+
+    // Layer 2 — kernel proofs: run the real code over symbolic bytes (the lane
+    // spread and field split, the translate LUT, the nibble validation, the
+    // pack and lane compress). As for VBMI, the quad tiers are left to Layer 1:
+    // a quad iteration is four copies of the vector these harnesses cover.
+
+    /// One single-tier vector (48 bytes), one masked vector (15 bytes), and a
+    /// 1-byte scalar tail — every encode tier below the quad, in one harness.
+    const ENC_KERNEL_LEN: usize = 64;
+    /// One single-tier vector (64 characters) and a 4-character scalar tail.
+    const DEC_KERNEL_LEN: usize = 68;
+    /// One masked vector (8 characters) and a 4-character scalar tail.
+    const DEC_MASKED_KERNEL_LEN: usize = 12;
+    /// One full masked encode vector, whose 64 characters then decode through
+    /// one masked decode vector plus a scalar group.
+    const ROUNDTRIP_LEN: usize = 48;
+
+    // Guard: a length below its tier's threshold would prove nothing but the
+    // scalar fallback. Fail the build rather than quietly verify less.
+    const _: () = assert!(
+        ENC_KERNEL_LEN >= ENC_SINGLE_MIN
+            && ENC_KERNEL_LEN < ENC_QUAD_MIN
+            && ENC_KERNEL_LEN - ENC_VEC_IN >= ENC_GROUP
+            && ENC_KERNEL_LEN % ENC_GROUP != 0,
+        "ENC_KERNEL_LEN must run a single-tier vector, then a masked vector, \
+         then leave a partial group for scalar"
+    );
+    const _: () = assert!(
+        DEC_KERNEL_LEN >= DEC_SINGLE_MIN
+            && DEC_KERNEL_LEN < DEC_QUAD_MIN
+            && DEC_KERNEL_LEN % DEC_GROUP == 0,
+        "DEC_KERNEL_LEN must run a single-tier vector and still be decodable"
+    );
+    const _: () = assert!(
+        DEC_MASKED_KERNEL_LEN >= DEC_MASKED_MIN
+            && DEC_MASKED_KERNEL_LEN < DEC_SINGLE_MIN
+            && DEC_MASKED_KERNEL_LEN % DEC_GROUP == 0,
+        "DEC_MASKED_KERNEL_LEN must run the masked tier and still be decodable"
+    );
+    const _: () = assert!(
+        ROUNDTRIP_LEN < ENC_SINGLE_MIN && ROUNDTRIP_LEN % ENC_GROUP == 0,
+        "ROUNDTRIP_LEN must be a whole number of groups in the masked tier"
+    );
+
+    const ENC_KERNEL_CAP: usize = TURBO_STANDARD.encoded_len(ENC_KERNEL_LEN);
+    const DEC_KERNEL_CAP: usize = TURBO_STANDARD.estimate_decoded_len(DEC_KERNEL_LEN);
+    const DEC_MASKED_KERNEL_CAP: usize = TURBO_STANDARD.estimate_decoded_len(DEC_MASKED_KERNEL_LEN);
+    const ROUNDTRIP_ENC_CAP: usize = TURBO_STANDARD.encoded_len(ROUNDTRIP_LEN);
+    const ROUNDTRIP_DEC_CAP: usize = TURBO_STANDARD.estimate_decoded_len(ROUNDTRIP_ENC_CAP);
+
+    /// The vectorized encoder agrees with the scalar one on every input of this
+    /// length.
+    fn encode_matches_scalar(url_safe: bool) {
+        let config = Config {
+            url_safe,
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; ENC_KERNEL_LEN] = kani::any();
+
+        let mut bw_out = [0u8; ENC_KERNEL_CAP];
+        let mut scalar_out = [0u8; ENC_KERNEL_CAP];
+
+        unsafe { encode_slice_avx512bw(&config, &input, &mut bw_out) };
+        crate::scalar::encode_slice(&config, &input, &mut scalar_out);
+
+        assert_eq!(bw_out, scalar_out, "kernel and scalar encoded differently");
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm512_shuffle_epi8, m::shuffle_epi8_model)]
+    #[kani::stub(_mm512_permutexvar_epi32, m::permutexvar_epi32_model)]
+    #[kani::stub(_mm512_mullo_epi16, m::mullo_epi16_model)]
+    #[kani::stub(_mm512_subs_epu8, m::subs_epu8_model)]
+    #[kani::stub(_mm512_cmpgt_epi8_mask, m::cmpgt_epi8_mask_model)]
+    #[kani::stub(_mm512_mask_add_epi8, m::mask_add_epi8_model)]
+    #[kani::stub(_mm512_maskz_loadu_epi8, m::maskz_loadu_epi8_model)]
+    #[kani::stub(_mm512_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_bw_encode_matches_scalar_standard() {
+        encode_matches_scalar(false);
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm512_shuffle_epi8, m::shuffle_epi8_model)]
+    #[kani::stub(_mm512_permutexvar_epi32, m::permutexvar_epi32_model)]
+    #[kani::stub(_mm512_mullo_epi16, m::mullo_epi16_model)]
+    #[kani::stub(_mm512_subs_epu8, m::subs_epu8_model)]
+    #[kani::stub(_mm512_cmpgt_epi8_mask, m::cmpgt_epi8_mask_model)]
+    #[kani::stub(_mm512_mask_add_epi8, m::mask_add_epi8_model)]
+    #[kani::stub(_mm512_maskz_loadu_epi8, m::maskz_loadu_epi8_model)]
+    #[kani::stub(_mm512_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_bw_encode_matches_scalar_url_safe() {
+        encode_matches_scalar(true);
+    }
+
+    /// The vectorized decoder agrees with the scalar one over every input of
+    /// length `N`, which pins rejection as well as value. Error kinds are not
+    /// compared, for the reason given on the VBMI harness.
+    fn decode_matches_scalar<const N: usize, const CAP: usize>() {
+        let config = Config {
+            url_safe: kani::any(),
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; N] = kani::any();
+
+        let mut bw_out = [0u8; CAP];
+        let mut scalar_out = [0u8; CAP];
+
+        let bw = unsafe { decode_slice_avx512bw(&config, &input, &mut bw_out) };
+        let scalar = crate::scalar::decode_slice(&config, &input, &mut scalar_out);
+
+        match scalar {
+            Ok(n) => {
+                assert_eq!(bw, Ok(n), "scalar accepted an input the kernel rejected");
+                assert_eq!(
+                    &bw_out[..n],
+                    &scalar_out[..n],
+                    "kernel and scalar decoded to different bytes"
+                );
+            }
+            Err(_) => assert!(bw.is_err(), "kernel accepted an input scalar rejected"),
+        }
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm512_shuffle_epi8, m::shuffle_epi8_model)]
+    #[kani::stub(_mm512_permutexvar_epi32, m::permutexvar_epi32_model)]
+    #[kani::stub(_mm512_maddubs_epi16, m::maddubs_epi16_model)]
+    #[kani::stub(_mm512_madd_epi16, m::madd_epi16_model)]
+    #[kani::stub(_mm512_test_epi8_mask, m::test_epi8_mask_model)]
+    #[kani::stub(_mm512_mask_loadu_epi8, m::mask_loadu_epi8_model)]
+    #[kani::stub(_mm512_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_bw_decode_matches_scalar() {
+        decode_matches_scalar::<DEC_KERNEL_LEN, DEC_KERNEL_CAP>();
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm512_shuffle_epi8, m::shuffle_epi8_model)]
+    #[kani::stub(_mm512_permutexvar_epi32, m::permutexvar_epi32_model)]
+    #[kani::stub(_mm512_maddubs_epi16, m::maddubs_epi16_model)]
+    #[kani::stub(_mm512_madd_epi16, m::madd_epi16_model)]
+    #[kani::stub(_mm512_test_epi8_mask, m::test_epi8_mask_model)]
+    #[kani::stub(_mm512_mask_loadu_epi8, m::mask_loadu_epi8_model)]
+    #[kani::stub(_mm512_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_bw_decode_matches_scalar_masked() {
+        decode_matches_scalar::<DEC_MASKED_KERNEL_LEN, DEC_MASKED_KERNEL_CAP>();
+    }
+
+    /// `Decode(Encode(x)) == x` over every input of [`ROUNDTRIP_LEN`] bytes,
+    /// through both kernels end to end.
+    #[kani::proof]
+    #[kani::stub(_mm512_shuffle_epi8, m::shuffle_epi8_model)]
+    #[kani::stub(_mm512_permutexvar_epi32, m::permutexvar_epi32_model)]
+    #[kani::stub(_mm512_mullo_epi16, m::mullo_epi16_model)]
+    #[kani::stub(_mm512_subs_epu8, m::subs_epu8_model)]
+    #[kani::stub(_mm512_cmpgt_epi8_mask, m::cmpgt_epi8_mask_model)]
+    #[kani::stub(_mm512_maddubs_epi16, m::maddubs_epi16_model)]
+    #[kani::stub(_mm512_madd_epi16, m::madd_epi16_model)]
+    #[kani::stub(_mm512_mask_add_epi8, m::mask_add_epi8_model)]
+    #[kani::stub(_mm512_test_epi8_mask, m::test_epi8_mask_model)]
+    #[kani::stub(_mm512_mask_loadu_epi8, m::mask_loadu_epi8_model)]
+    #[kani::stub(_mm512_maskz_loadu_epi8, m::maskz_loadu_epi8_model)]
+    #[kani::stub(_mm512_mask_storeu_epi8, m::mask_storeu_epi8_model)]
+    fn check_bw_roundtrip_standard() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; ROUNDTRIP_LEN] = kani::any();
+
+        let mut enc_buf = [0u8; ROUNDTRIP_ENC_CAP];
+        let mut dec_buf = [0u8; ROUNDTRIP_DEC_CAP];
+
+        unsafe {
+            encode_slice_avx512bw(&config, &input, &mut enc_buf);
+            let dec_len = decode_slice_avx512bw(&config, &enc_buf, &mut dec_buf)
+                .expect("valid encoding failed to decode");
+            assert_eq!(dec_len, ROUNDTRIP_LEN);
+            assert_eq!(&dec_buf[..dec_len], &input, "roundtrip mismatch");
+        }
+    }
+}
+
+/// Rust models of the AVX-512 instructions the BW kernels cannot execute
+/// symbolically, transcribed from the Intel Intrinsics Guide pseudocode under
+/// the same rules as the VBMI models: every Intel line is quoted with the Rust
+/// statement it became beneath it, bit offsets are kept and divided by 8 at
+/// the point of access, and lines carrying no Intel text are marked `NOTE:`.
+///
+/// Miri takes only the two permutes, through the `cfg(miri)` shims in the
+/// parent module, and executes everything else for real; Kani takes the whole
+/// set through `#[kani::stub(...)]`. [`avx512bw_stub_equivalence`] checks every
+/// model against the real instruction on any host with the subsets.
+// As for the VBMI models, every consumer is invisible to rustc's dead-code
+// pass, and which models look "used" depends on the harness being compiled.
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+// Literal-transcription lints, disabled rather than let them reshape the
+// pseudocode.
+#[allow(
+    clippy::cast_lossless,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::missing_const_for_fn,
+    clippy::missing_transmute_annotations,
+    clippy::needless_late_init,
+    clippy::needless_range_loop
+)]
+pub(super) mod intrinsic_models {
+    use super::*;
+    use core::mem::transmute;
+
+    // NOTE: scaffolding, not from Intel. Reads bit `n` of a little-endian byte
+    // vector, for the places the pseudocode indexes a single bit.
+    fn bit(v: &[u8; 64], n: usize) -> u8 {
+        (v[n / 8] >> (n % 8)) & 1
+    }
+
+    // NOTE: scaffolding, not from Intel. The `SaturateU8` and `Saturate16`
+    // helpers the pseudocode calls by name.
+    fn SaturateU8(x: i16) -> u8 {
+        x.clamp(0, 255) as u8
+    }
+    fn Saturate16(x: i32) -> i16 {
+        x.clamp(-32768, 32767) as i16
+    }
+
+    // STUB: _mm512_shuffle_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_shuffle_epi8
+    pub(in crate::simd::avx512bw) unsafe fn shuffle_epi8_model(a: __m512i, b: __m512i) -> __m512i {
+        let a: [u8; 64] = unsafe { transmute(a) };
+        let b: [u8; 64] = unsafe { transmute(b) };
+        let mut dst = [0u8; 64];
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF b[i+7] == 1
+            if bit(&b, i + 7) == 1 {
+                // 		dst[i+7:i] := 0
+                dst[i / 8] = 0;
+            // 	ELSE
+            } else {
+                // 		index[5:0] := b[i+3:i] + (j & 0x30)
+                let index = usize::from(b[i / 8] & 0x0F) + (j & 0x30);
+                // 		dst[i+7:i] := a[index*8+7:index*8]
+                dst[i / 8] = a[(index * 8) / 8];
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+        // NOTE: `__m512i` is exactly 512 bits; there is nothing above to zero.
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_permutexvar_epi32
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_permutexvar_epi32
+    pub(in crate::simd::avx512bw) unsafe fn permutexvar_epi32_model(
+        idx: __m512i,
+        a: __m512i,
+    ) -> __m512i {
+        let idx: [u32; 16] = unsafe { transmute(idx) };
+        let a: [u32; 16] = unsafe { transmute(a) };
+        let mut dst = [0u32; 16];
+
+        // FOR j := 0 to 15
+        for j in 0..16 {
+            // 	i := j*32
+            let i = j * 32;
+            // 	id := idx[i+3:i]*32
+            let id = (idx[i / 32] & 0x0F) as usize * 32;
+            // 	dst[i+31:i] := a[id+31:id]
+            dst[i / 32] = a[id / 32];
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_mullo_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_mullo_epi16
+    pub(in crate::simd::avx512bw) unsafe fn mullo_epi16_model(a: __m512i, b: __m512i) -> __m512i {
+        let a: [i16; 32] = unsafe { transmute(a) };
+        let b: [i16; 32] = unsafe { transmute(b) };
+        let mut dst = [0i16; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*16
+            let i = j * 16;
+            // 	tmp[31:0] := SignExtend32(a[i+15:i]) * SignExtend32(b[i+15:i])
+            let tmp: i32 = i32::from(a[i / 16]) * i32::from(b[i / 16]);
+            // 	dst[i+15:i] := tmp[15:0]
+            dst[i / 16] = tmp as i16;
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_subs_epu8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_subs_epu8
+    pub(in crate::simd::avx512bw) unsafe fn subs_epu8_model(a: __m512i, b: __m512i) -> __m512i {
+        let a: [u8; 64] = unsafe { transmute(a) };
+        let b: [u8; 64] = unsafe { transmute(b) };
+        let mut dst = [0u8; 64];
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	dst[i+7:i] := SaturateU8(a[i+7:i] - b[i+7:i])
+            dst[i / 8] = SaturateU8(i16::from(a[i / 8]) - i16::from(b[i / 8]));
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_cmpgt_epi8_mask
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_cmpgt_epi8_mask
+    pub(in crate::simd::avx512bw) unsafe fn cmpgt_epi8_mask_model(a: __m512i, b: __m512i) -> u64 {
+        // NOTE: a signed compare.
+        let a: [i8; 64] = unsafe { transmute(a) };
+        let b: [i8; 64] = unsafe { transmute(b) };
+        let mut k = 0u64;
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	k[j] := ( a[i+7:i] > b[i+7:i] ) ? 1 : 0
+            k |= u64::from(a[i / 8] > b[i / 8]) << j;
+        }
+        // ENDFOR
+        // k[MAX:64] := 0
+
+        k
+    }
+
+    // STUB: _mm512_test_epi8_mask
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_test_epi8_mask
+    pub(in crate::simd::avx512bw) unsafe fn test_epi8_mask_model(a: __m512i, b: __m512i) -> u64 {
+        let a: [u8; 64] = unsafe { transmute(a) };
+        let b: [u8; 64] = unsafe { transmute(b) };
+        let mut k = 0u64;
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	k[j] := ((a[i+7:i] AND b[i+7:i]) != 0) ? 1 : 0
+            k |= u64::from((a[i / 8] & b[i / 8]) != 0) << j;
+        }
+        // ENDFOR
+        // k[MAX:64] := 0
+
+        k
+    }
+
+    // STUB: _mm512_mask_add_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_mask_add_epi8
+    pub(in crate::simd::avx512bw) unsafe fn mask_add_epi8_model(
+        src: __m512i,
+        k: u64,
+        a: __m512i,
+        b: __m512i,
+    ) -> __m512i {
+        let src: [u8; 64] = unsafe { transmute(src) };
+        let a: [u8; 64] = unsafe { transmute(a) };
+        let b: [u8; 64] = unsafe { transmute(b) };
+        let mut dst = [0u8; 64];
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		dst[i+7:i] := a[i+7:i] + b[i+7:i]
+                // NOTE: the sum is truncated to its 8-bit destination.
+                dst[i / 8] = a[i / 8].wrapping_add(b[i / 8]);
+            // 	ELSE
+            } else {
+                // 		dst[i+7:i] := src[i+7:i]
+                dst[i / 8] = src[i / 8];
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_maddubs_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_maddubs_epi16
+    pub(in crate::simd::avx512bw) unsafe fn maddubs_epi16_model(a: __m512i, b: __m512i) -> __m512i {
+        // NOTE: `a` holds unsigned bytes, `b` signed ones.
+        let a: [u8; 64] = unsafe { transmute(a) };
+        let b: [i8; 64] = unsafe { transmute(b) };
+        let mut dst = [0i16; 32];
+
+        // FOR j := 0 to 31
+        for j in 0..32 {
+            // 	i := j*16
+            let i = j * 16;
+            // 	dst[i+15:i] := Saturate16( a[i+15:i+8]*b[i+15:i+8] + a[i+7:i]*b[i+7:i] )
+            dst[i / 16] = Saturate16(
+                i32::from(a[(i + 8) / 8]) * i32::from(b[(i + 8) / 8])
+                    + i32::from(a[i / 8]) * i32::from(b[i / 8]),
+            );
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_madd_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_madd_epi16
+    pub(in crate::simd::avx512bw) unsafe fn madd_epi16_model(a: __m512i, b: __m512i) -> __m512i {
+        let a: [i16; 32] = unsafe { transmute(a) };
+        let b: [i16; 32] = unsafe { transmute(b) };
+        let mut dst = [0i32; 16];
+
+        // FOR j := 0 to 15
+        for j in 0..16 {
+            // 	i := j*32
+            let i = j * 32;
+            // 	dst[i+31:i] := SignExtend32(a[i+31:i+16]*b[i+31:i+16]) + SignExtend32(a[i+15:i]*b[i+15:i])
+            // NOTE: the sum is `wrapping` because it lands in a 32-bit
+            // destination, which the two extreme products can overflow.
+            dst[i / 32] = (i32::from(a[(i + 16) / 16]) * i32::from(b[(i + 16) / 16]))
+                .wrapping_add(i32::from(a[i / 16]) * i32::from(b[i / 16]));
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_mask_loadu_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_mask_loadu_epi8
+    //
+    // NOTE: the masked-off lanes are never read, which is what lets the kernel
+    // point the load at fewer than 64 valid bytes.
+    pub(in crate::simd::avx512bw) unsafe fn mask_loadu_epi8_model(
+        src: __m512i,
+        k: u64,
+        mem_addr: *const i8,
+    ) -> __m512i {
+        let src: [u8; 64] = unsafe { transmute(src) };
+        let mut dst = [0u8; 64];
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		dst[i+7:i] := MEM[mem_addr+i+7:mem_addr+i]
+                dst[i / 8] = unsafe { mem_addr.add(i / 8).read_unaligned() }.cast_unsigned();
+            // 	ELSE
+            } else {
+                // 		dst[i+7:i] := src[i+7:i]
+                dst[i / 8] = src[i / 8];
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_maskz_loadu_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_maskz_loadu_epi8
+    //
+    // NOTE: as `mask_loadu_epi8_model`, the masked-off lanes are never read.
+    pub(in crate::simd::avx512bw) unsafe fn maskz_loadu_epi8_model(
+        k: u64,
+        mem_addr: *const i8,
+    ) -> __m512i {
+        let mut dst = [0u8; 64];
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		dst[i+7:i] := MEM[mem_addr+i+7:mem_addr+i]
+                dst[i / 8] = unsafe { mem_addr.add(i / 8).read_unaligned() }.cast_unsigned();
+            // 	ELSE
+            } else {
+                // 		dst[i+7:i] := 0
+                dst[i / 8] = 0;
+            }
+            // 	FI
+        }
+        // ENDFOR
+        // dst[MAX:512] := 0
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm512_mask_storeu_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm512_mask_storeu_epi8
+    //
+    // NOTE: as `mask_loadu_epi8_model`, the masked-off lanes are never written.
+    pub(in crate::simd::avx512bw) unsafe fn mask_storeu_epi8_model(
+        mem_addr: *mut i8,
+        k: u64,
+        a: __m512i,
+    ) {
+        let a: [u8; 64] = unsafe { transmute(a) };
+
+        // FOR j := 0 to 63
+        for j in 0..64 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF k[j]
+            if (k >> j) & 1 == 1 {
+                // 		MEM[mem_addr+i+7:mem_addr+i] := a[i+7:i]
+                unsafe { mem_addr.add(i / 8).write_unaligned(a[i / 8].cast_signed()) };
+            }
+            // 	FI
+        }
+        // ENDFOR
+    }
+}
+
+/// Checks every model in [`intrinsic_models`] against the real instruction on
+/// AVX-512BW hardware, under plain `cargo test`. Skips, loudly, on a host
+/// without the subsets.
+#[cfg(all(test, not(miri)))]
+mod avx512bw_stub_equivalence {
+    use super::intrinsic_models as model;
+    use super::*;
+    use crate::simd::testutil::has_avx512bw;
+
+    /// Saturation and sign boundaries, the bit-7 zeroing and lane-crossing
+    /// selectors the shuffles key off, index-shaped bytes, and deterministic
+    /// noise.
+    fn probes() -> Vec<[u8; 64]> {
+        let byte = |i: usize| u8::try_from(i).expect("index below the 64-byte vector width");
+
+        let mut out = vec![[0x00; 64], [0xFF; 64], [0x80; 64], [0x7F; 64], [0x19; 64]];
+        out.push(core::array::from_fn(byte));
+        out.push(core::array::from_fn(|i| byte(i) | 0x80));
+        out.push(core::array::from_fn(|i| byte(i % 16)));
+        out.push(core::array::from_fn(|i| byte(i % 26) + 13));
+        out.push(core::array::from_fn(|i| 0xFF - byte(i)));
+
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..8 {
+            out.push(core::array::from_fn(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                u8::try_from(state >> 56).expect("shifted down to 8 bits")
+            }));
+        }
+        out
+    }
+
+    #[target_feature(enable = "avx512f,avx512bw")]
+    unsafe fn compare_all() {
+        use std::mem::transmute;
+
+        let byte = |i: usize| u8::try_from(i).expect("index below the 64-byte vector width");
+        let probes = probes();
+        // SAFETY: `__m512i` has no invalid bit patterns, so it and `[u8; 64]`
+        // are freely transmutable both ways.
+        let bytes = |v: __m512i| -> [u8; 64] { unsafe { transmute::<__m512i, [u8; 64]>(v) } };
+        let zmm = |b: [u8; 64]| -> __m512i { unsafe { transmute::<[u8; 64], __m512i>(b) } };
+
+        // Each arm: `real(a, b)` must equal `model(a, b)` for every probe pair.
+        macro_rules! same2 {
+            ($real:ident, $model:ident, $wrap:expr) => {
+                for x in &probes {
+                    for y in &probes {
+                        let (a, b) = (zmm(*x), zmm(*y));
+                        assert_eq!(
+                            $wrap($real(a, b)),
+                            $wrap(unsafe { model::$model(a, b) }),
+                            "{}: a={x:02x?} b={y:02x?}",
+                            stringify!($real)
+                        );
+                    }
+                }
+            };
+        }
+
+        let mask = |k: u64| k;
+        same2!(_mm512_shuffle_epi8, shuffle_epi8_model, bytes);
+        same2!(_mm512_permutexvar_epi32, permutexvar_epi32_model, bytes);
+        same2!(_mm512_mullo_epi16, mullo_epi16_model, bytes);
+        same2!(_mm512_subs_epu8, subs_epu8_model, bytes);
+        same2!(_mm512_maddubs_epi16, maddubs_epi16_model, bytes);
+        same2!(_mm512_madd_epi16, madd_epi16_model, bytes);
+        same2!(_mm512_cmpgt_epi8_mask, cmpgt_epi8_mask_model, mask);
+        same2!(_mm512_test_epi8_mask, test_epi8_mask_model, mask);
+
+        // Masked ops, over masks that exercise both halves and both ends of the
+        // vector.
+        for &k in &[
+            0u64,
+            1,
+            0xFFFF_FFFF_FFFF_FFFF,
+            0x0000_FFFF_FFFF_FFFF,
+            0xAAAA_AAAA_AAAA_AAAA,
+        ] {
+            for x in &probes {
+                let (src, a) = (zmm([0x11; 64]), zmm(*x));
+                assert_eq!(
+                    bytes(_mm512_mask_add_epi8(src, k, a, a)),
+                    bytes(unsafe { model::mask_add_epi8_model(src, k, a, a) }),
+                    "_mm512_mask_add_epi8: k={k:#018x} a={x:02x?}"
+                );
+            }
+
+            let src_bytes: [u8; 64] = core::array::from_fn(|i| byte(i) ^ 0x5A);
+            let fill = zmm([0x11; 64]);
+
+            assert_eq!(
+                bytes(unsafe { _mm512_mask_loadu_epi8(fill, k, src_bytes.as_ptr().cast()) }),
+                bytes(unsafe { model::mask_loadu_epi8_model(fill, k, src_bytes.as_ptr().cast()) }),
+                "_mm512_mask_loadu_epi8: k={k:#018x}"
+            );
+            assert_eq!(
+                bytes(unsafe { _mm512_maskz_loadu_epi8(k, src_bytes.as_ptr().cast()) }),
+                bytes(unsafe { model::maskz_loadu_epi8_model(k, src_bytes.as_ptr().cast()) }),
+                "_mm512_maskz_loadu_epi8: k={k:#018x}"
+            );
+
+            let value = zmm(core::array::from_fn(|i| byte(i).wrapping_mul(3)));
+            let mut real_dst = [0u8; 64];
+            let mut model_dst = [0u8; 64];
+            unsafe { _mm512_mask_storeu_epi8(real_dst.as_mut_ptr().cast(), k, value) };
+            unsafe { model::mask_storeu_epi8_model(model_dst.as_mut_ptr().cast(), k, value) };
+            assert_eq!(real_dst, model_dst, "_mm512_mask_storeu_epi8: k={k:#018x}");
+        }
+    }
+
+    #[test]
+    fn avx512bw_models_match_hardware() {
+        if !has_avx512bw() {
+            return;
+        }
+        unsafe { compare_all() };
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512bw_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode};
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+    fn enc(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_encode(config, oracle, encode_slice_avx512bw, len);
+    }
+    fn dec(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_decode(config, oracle, decode_slice_avx512bw, len);
+    }
+    fn exact(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_decode_exact(config, oracle, decode_slice_avx512bw, len);
+    }
+
+    const STD: Config = Config {
+        url_safe: false,
+        padding: true,
+        lenient: false,
+    };
+    const URL: Config = Config {
+        url_safe: true,
+        padding: true,
+        lenient: false,
+    };
+    const NO_PAD: Config = Config {
+        url_safe: false,
+        padding: false,
+        lenient: false,
+    };
+    const NO_PAD_URL: Config = Config {
+        url_safe: true,
+        padding: false,
+        lenient: false,
+    };
+
+    /// Tier boundaries, encode: quad at >= 256, single at >= 64, masked below
+    /// that (twice when the remainder exceeds 48).
+    #[test]
+    fn miri_avx512bw_encode_tier_boundaries() {
+        for &len in &[
+            0, 1, 2, 3, 4, 47, 48, 49, 51, 62, 63, 64, 65, 96, 255, 256, 257,
+        ] {
+            enc(&STD, &STANDARD, len);
+        }
+        for &len in &[3, 47, 63, 100, 259] {
+            enc(&NO_PAD_URL, &URL_SAFE_NO_PAD, len);
+        }
+    }
+
+    /// Tier boundaries, decode, in *decoded* byte lengths. The character
+    /// thresholds are 260 / 68 / 8.
+    #[test]
+    fn miri_avx512bw_decode_tier_boundaries() {
+        for &len in &[0, 1, 2, 3, 4, 5, 6, 45, 48, 49, 50, 51, 52, 192, 193, 195] {
+            dec(&STD, &STANDARD, len);
+        }
+        dec(&URL, &URL_SAFE, 100);
+        for &len in &[3, 49, 193] {
+            dec(&NO_PAD, &STANDARD_NO_PAD, len);
+        }
+    }
+
+    /// Invalid bytes must be caught in every tier and the scalar tail,
+    /// including the high-bit bytes the nibble LUTs flag via their catch-all
+    /// row.
+    #[test]
+    fn miri_avx512bw_decode_error_detection() {
+        let mut dst = [0u8; 512];
+        for &(len, bad_at, byte, where_) in &[
+            (260, 0, b'$', "quad tier, first byte"),
+            (260, 255, b'$', "quad tier, last byte"),
+            (68, 63, b'?', "single tier"),
+            (12, 5, b'?', "masked tier"),
+            (68, 0, 0xFF, "high bit, single tier"),
+            (12, 0, 0x80u8, "high bit, masked tier"),
+            (68, 65, b'?', "scalar tail"),
+        ] {
+            let mut input = vec![b'A'; len];
+            input[bad_at] = byte;
+            let res = unsafe { decode_slice_avx512bw(&STD, &input, &mut dst) };
+            assert!(res.is_err(), "missed invalid byte in {where_}");
+        }
+    }
+
+    /// Masked-store regression: chunk-boundary lengths decode into an
+    /// exactly-sized buffer without overrunning.
+    #[test]
+    fn miri_avx512bw_decode_exact_buffer_boundaries() {
+        for &len in &[3, 6, 45, 48, 51, 96, 192, 193, 195, 240, 384] {
+            exact(&STD, &STANDARD, len);
+            exact(&URL, &URL_SAFE, len);
+            exact(&NO_PAD, &STANDARD_NO_PAD, len);
+        }
+    }
+}
+
+/// Exhaustive regression test for the nibble-lookup tables in
+/// [`decode_constants_avx512bw`], guarding against transcription typos when
+/// they were broadcast from the SSSE3 ones. Every byte value is placed in all
+/// 64 lanes at once, so each of the four table copies is exercised.
+#[cfg(all(test, not(miri)))]
+mod avx512bw_decode_lut_exhaustive {
+    use super::*;
+    use crate::simd::testutil::has_avx512bw;
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn check_all_byte_values(config: &Config) {
+        for candidate in 0u8..=255 {
+            let mut input = [candidate; 68];
+            input[64..].copy_from_slice(b"AAAA");
+
+            let mut bw_out = [0u8; 64];
+            let bw_result = unsafe { decode_slice_avx512bw(config, &input, &mut bw_out) };
+
+            // Oracle: the first 64 bytes via the (separately tested) scalar path.
+            let mut scalar_out = [0u8; 64];
+            let scalar_result = crate::scalar::decode_slice(config, &input[..64], &mut scalar_out);
+
+            match scalar_result {
+                Ok(scalar_len) => {
+                    assert_eq!(
+                        bw_result,
+                        Ok(scalar_len + 3),
+                        "byte {candidate:#04x} ({candidate}): scalar accepted it"
+                    );
+                    assert_eq!(
+                        &bw_out[..scalar_len],
+                        &scalar_out[..scalar_len],
+                        "byte {candidate:#04x} ({candidate}): decoded value mismatch"
+                    );
+                }
+                Err(scalar_err) => {
+                    assert_eq!(
+                        bw_result,
+                        Err(scalar_err),
+                        "byte {candidate:#04x} ({candidate}): avx512bw/scalar disagree on validity"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn avx512bw_lut_standard_matches_scalar() {
+        if !has_avx512bw() {
+            return;
+        }
+        check_all_byte_values(&Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        });
+    }
+
+    #[test]
+    fn avx512bw_lut_url_safe_matches_scalar() {
+        if !has_avx512bw() {
+            return;
+        }
+        check_all_byte_values(&Config {
+            url_safe: true,
+            padding: true,
+            lenient: false,
+        });
+    }
+
+    #[test]
+    fn avx512bw_lut_lenient_matches_scalar() {
+        if !has_avx512bw() {
+            return;
+        }
+        check_all_byte_values(&Config {
+            url_safe: false,
+            padding: false,
+            lenient: true,
+        });
+    }
+}
+
+#[cfg(all(test, not(miri)))]
+mod avx512bw_hardware_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode, has_avx512bw};
+    use base64::engine::general_purpose::{
+        STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_STANDARD_NO_PAD, URL_SAFE as REF_URL_SAFE,
+    };
+
+    /// Every length 0..=600 against the `base64` oracle, which crosses each
+    /// tier boundary of both kernels at least twice.
+    #[test]
+    fn hw_avx512bw_all_lengths_0_to_600() {
+        if !has_avx512bw() {
+            return;
+        }
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for len in 0..=600 {
+                check_encode(&config, oracle, encode_slice_avx512bw, len);
+                check_decode(&config, oracle, decode_slice_avx512bw, len);
+            }
+        }
+    }
+
+    /// The exact-buffer masked-store regression the Miri suite runs, on real
+    /// silicon.
+    #[test]
+    fn hw_avx512bw_decode_exact_buffer_boundaries() {
+        if !has_avx512bw() {
+            return;
+        }
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for &len in &[3, 6, 45, 48, 51, 96, 192, 193, 195, 240, 384, 1000, 1001] {
+                check_decode_exact(&config, oracle, decode_slice_avx512bw, len);
+            }
+        }
+    }
+}
+
+/// The lenient tables: both alphabets mixed, padded and not, decoded into an
+/// exactly-sized buffer at every length across the tiers.
+#[cfg(all(test, not(miri)))]
+mod avx512bw_lenient {
+    use super::*;
+    use crate::simd::testutil::{check_decode_lenient_exact, has_avx512bw};
+
+    #[test]
+    fn avx512bw_decode_lenient_all_lengths() {
+        if !has_avx512bw() {
+            return;
+        }
+        for len in (0..=600).chain([1_000, 4_097]) {
+            check_decode_lenient_exact(decode_slice_avx512bw, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_avx512bw_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient_exact;
+
+    /// The masked tier, a single vector, and the quad tier, each with a tail.
+    #[test]
+    fn miri_avx512bw_decode_lenient_tier_boundaries() {
+        for len in [0, 1, 2, 3, 6, 49, 51, 193, 195] {
+            check_decode_lenient_exact(decode_slice_avx512bw, len);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! The left-pack table the ignore-garbage compaction kernels share. Each turns
+//! its decoder's validity mask into a byte shuffle eight lanes at a time: the
+//! mask's bytes index this table, and the entries, offset to their group, are
+//! the shuffle's control.
+
+/// For each 8-bit mask, the positions of its set bits in order, one per byte
+/// from the low one up; the bytes past them are 0.
+const fn build_compact_lanes() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut mask = 0;
+    while mask < 256 {
+        let mut lanes = 0u64;
+        let mut at = 0;
+        let mut bit = 0u64;
+        while bit < 8 {
+            if mask & (1 << bit) != 0 {
+                lanes |= bit << (8 * at);
+                at += 1;
+            }
+            bit += 1;
+        }
+        table[mask] = lanes;
+        mask += 1;
+    }
+    table
+}
+
+/// See [`build_compact_lanes`]. Every byte of an entry is below 8, so adding a
+/// group's offset to all eight at once never carries into the next.
+pub(super) static COMPACT_LANES: [u64; 256] = build_compact_lanes();
+
+/// Adds to a [`COMPACT_LANES`] entry to offset all eight positions by 8.
+pub(super) const NEXT_GROUP: u64 = 0x0808_0808_0808_0808;
@@ -1,20 +1,66 @@
-// `x86_simd` (from build.rs) is already "x86 with some AVX kernel", so each arm
-// only needs to add its own feature.
-#[cfg(all(x86_simd, feature = "avx2"))]
+// `x86_ssse3`/`x86_avx2`/`x86_avx512bw`/`x86_avx512_vbmi` (from build.rs)
+// already fold in the arch and the feature.
+#[cfg(x86_avx2)]
 mod avx2;
-#[cfg(all(x86_simd, feature = "avx512-vbmi"))]
+#[cfg(x86_avx512_vbmi)]
 mod avx512_vbmi;
+#[cfg(x86_avx512bw)]
+mod avx512bw;
+#[cfg(x86_ssse3)]
+mod ssse3;
 
-#[cfg(all(x86_simd, feature = "avx2"))]
-pub(crate) use avx2::{decode_slice_avx2, encode_slice_avx2};
-#[cfg(all(x86_simd, feature = "avx512-vbmi"))]
-pub(crate) use avx512_vbmi::{decode_slice_avx512_vbmi, encode_slice_avx512_vbmi};
+#[cfg(x86_avx2)]
+pub(crate) use avx2::{
+    DEC_SHORT_MAX as DEC_SHORT_MAX_AVX2, ENC_SHORT_MAX as ENC_SHORT_MAX_AVX2, base64_to_hex_avx2,
+    compact_avx2, decode_hex_avx2, decode_short_avx2, decode_slice_avx2, encode_hex_avx2,
+    encode_short_avx2, encode_slice_avx2, hex_to_base64_avx2, remap_avx2, remap_in_place_avx2,
+};
+#[cfg(x86_avx512_vbmi)]
+pub(crate) use avx512_vbmi::{
+    DEC_SHORT_MAX as DEC_SHORT_MAX_AVX512_VBMI, ENC_SHORT_MAX as ENC_SHORT_MAX_AVX512_VBMI,
+    base64_to_hex_avx512_vbmi, compact_avx512_vbmi, decode_base32_avx512_vbmi,
+    decode_crypt_avx512_vbmi, decode_hex_avx512_vbmi, decode_short_avx512_vbmi,
+    decode_slice_avx512_vbmi, decode_slice_avx512_vbmi_256, encode_base32_avx512_vbmi,
+    encode_crypt_avx512_vbmi, encode_hex_avx512_vbmi, encode_short_avx512_vbmi,
+    encode_slice_avx512_vbmi, encode_slice_avx512_vbmi_256, hex_to_base64_avx512_vbmi,
+    remap_avx512_vbmi, remap_in_place_avx512_vbmi,
+};
+#[cfg(x86_avx512bw)]
+pub(crate) use avx512bw::{decode_slice_avx512bw, encode_slice_avx512bw};
+#[cfg(x86_ssse3)]
+pub(crate) use ssse3::{decode_slice_ssse3, encode_slice_ssse3};
 
 #[cfg(all(target_arch = "aarch64", feature = "neon"))]
 mod neon;
 #[cfg(all(target_arch = "aarch64", feature = "neon"))]
-pub(crate) use neon::{decode_slice_neon, encode_slice_neon};
+pub(crate) use neon::{
+    DEC_SHORT_MAX as DEC_SHORT_MAX_NEON, ENC_SHORT_MAX as ENC_SHORT_MAX_NEON, base64_to_hex_neon,
+    compact_neon, decode_hex_neon, decode_short_neon, decode_slice_neon, encode_hex_neon,
+    encode_short_neon, encode_slice_neon, hex_to_base64_neon, remap_in_place_neon, remap_neon,
+};
 
+#[cfg(wasm_simd)]
+mod wasm;
+#[cfg(wasm_simd)]
+pub(crate) use wasm::{decode_slice_wasm, encode_slice_wasm};
+
+#[cfg(portable_simd)]
+mod portable;
+#[cfg(portable_simd)]
+pub(crate) use portable::{decode_slice_portable, encode_slice_portable};
+
+#[cfg(any(
+    x86_avx2,
+    x86_avx512_vbmi,
+    all(target_arch = "aarch64", feature = "neon")
+))]
+mod compact;
+#[cfg(any(
+    x86_avx2,
+    x86_avx512_vbmi,
+    all(target_arch = "aarch64", feature = "neon")
+))]
+mod short;
 #[cfg(test)]
 mod testutil;
 
@@ -22,7 +68,9 @@ mod testutil;
 /// calls these with the pointer/offset state they left off at; `src` points at
 /// the first unconsumed input byte and `dst_off` is how many bytes the loops
 /// already wrote.
-#[cfg(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))]
+///
+/// The portable kernel is safe code and slices its own remainder instead.
+#[cfg(any(x86_simd, all(target_arch = "aarch64", feature = "neon"), wasm_simd))]
 mod tail {
     use crate::{Config, Error, scalar};
 
@@ -61,22 +109,67 @@ mod tail {
     }
 }
 
-#[cfg(all(x86_simd, feature = "avx2"))]
+/// Input length from which the AVX2 and AVX512-VBMI kernels switch to
+/// non-temporal stores.
+///
+/// Above it the input plus its output no longer fit in a typical last-level
+/// cache, so the ordinary stores spend a share of the memory bandwidth on
+/// read-for-ownership traffic for lines that are then overwritten whole. Below
+/// it the output usually *is* reused from cache and bypassing it costs more
+/// than the RFO traffic saves; measured for the AVX2 encoder on a 9 MiB-L3
+/// Coffee Lake, the crossover sits between 2 and 4 MiB. The decoders' output is
+/// smaller than their input rather than larger, but not by enough to move the
+/// crossover out of that range, so they share the threshold.
+///
+/// This is the default; [`crate::calibrate`] replaces it with the crossover it
+/// measures on the host, read back through [`nt_store_min_len`].
+#[cfg(any(x86_avx2, x86_avx512_vbmi))]
+pub(crate) const NT_STORE_MIN_LEN: usize = 4 << 20;
+
+/// Shortest input [`set_nt_store_min_len`] lets stream. The output of anything
+/// shorter fits in L2 on every CPU these kernels run on, so streaming it never
+/// pays, and the floor keeps the streaming tiers on inputs that reach their
+/// steady state.
+#[cfg(any(x86_avx2, x86_avx512_vbmi))]
+const NT_STORE_FLOOR: usize = 64 << 10;
+
+#[cfg(any(x86_avx2, x86_avx512_vbmi))]
+static NT_STORE_MIN: core::sync::atomic::AtomicUsize =
+    core::sync::atomic::AtomicUsize::new(NT_STORE_MIN_LEN);
+
+/// The installed non-temporal threshold, [`NT_STORE_MIN_LEN`] until replaced.
+#[cfg(any(x86_avx2, x86_avx512_vbmi))]
+#[inline]
+pub(crate) fn nt_store_min_len() -> usize {
+    NT_STORE_MIN.load(core::sync::atomic::Ordering::Relaxed)
+}
+
+/// Installs a non-temporal threshold, raised to [`NT_STORE_FLOOR`] if below it.
+#[cfg(any(x86_avx2, x86_avx512_vbmi))]
+pub(crate) fn set_nt_store_min_len(len: usize) {
+    NT_STORE_MIN.store(
+        len.max(NT_STORE_FLOOR),
+        core::sync::atomic::Ordering::Relaxed,
+    );
+}
+
+#[cfg(any(x86_ssse3, x86_avx2))]
 const PACK_L1: [i8; 32] = [
     0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01,
     0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01,
 ];
 
-#[cfg(all(x86_simd, feature = "avx2"))]
+#[cfg(any(x86_ssse3, x86_avx2))]
 const PACK_L2: [i16; 16] = [
     0x1000, 0x0001, 0x1000, 0x0001, 0x1000, 0x0001, 0x1000, 0x0001, 0x1000, 0x0001, 0x1000, 0x0001,
     0x1000, 0x0001, 0x1000, 0x0001,
 ];
 
-// These are used by the AVX2 packer; the VBMI kernel builds its multipliers
-// from immediates and does its own permute, so all three are absent from a
-// VBMI-only build.
-#[cfg(all(x86_simd, feature = "avx2"))]
+// These are used by the AVX2 packer, and their low halves by the SSSE3 one.
+// The AVX-512BW packer broadcasts the low half of the shuffle but builds its
+// multipliers from immediates, as the VBMI kernel does for everything; so a
+// VBMI-only build has none of the three.
+#[cfg(any(x86_ssse3, x86_avx2, x86_avx512bw))]
 const PACK_SHUFFLE: [i8; 32] = [
     2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, -1, -1, -1, -1, 2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12,
     -1, -1, -1, -1,
@@ -0,0 +1,98 @@
+//! NEON compaction for ignore-garbage decoding, on the decoder's own nibble
+//! lookup.
+//!
+//! Each 16 bytes go through [`valid_lanes_neon`], the mask the decoder reduces
+//! to its error flag; `'='` is added back by a compare. A vector with nothing
+//! to drop is stored whole. Otherwise the mask is weighed down to one bit per
+//! lane, a byte per half, and each byte indexes the shared left-pack table for
+//! its eight lanes; one `tbl` packs each half's kept lanes to the half's
+//! front, and the halves are stored 8 bytes at a time, the second where the
+//! first one's kept bytes end. The sub-vector remainder goes to the scalar
+//! kernel.
+
+use super::{decode_constants_neon, valid_lanes_neon};
+use crate::Config;
+use crate::garbage::scalar;
+use crate::simd::compact::{COMPACT_LANES, NEXT_GROUP};
+
+use core::arch::aarch64::{
+    uint8x16_t, vaddv_u8, vandq_u8, vceqq_u8, vcombine_u8, vcreate_u8, vdupq_n_u8, vget_high_u8,
+    vget_low_u8, vld1q_u8, vminvq_u8, vorrq_u8, vqtbl1q_u8, vst1_u8, vst1q_u8,
+};
+
+/// Bytes a step compacts: one vector. Imported by the Kani proofs in
+/// `verify`.
+pub(super) const COMPACT_STEP: usize = 16;
+
+/// Each lane's bit in its half's mask byte.
+const LANE_BITS: [u8; 16] = [1, 2, 4, 8, 16, 32, 64, 128, 1, 2, 4, 8, 16, 32, 64, 128];
+
+/// The `tbl` control, one `u64` per half, that packs the lanes the mask bytes
+/// `lo` and `hi` select to the front of each half, in order.
+#[inline]
+pub(super) fn compact_control(lo: u8, hi: u8) -> [u64; 2] {
+    [
+        COMPACT_LANES[usize::from(lo)],
+        COMPACT_LANES[usize::from(hi)] + NEXT_GROUP,
+    ]
+}
+
+/// Stores the lanes of `chars` that `keep` (`0xFF` or `0` per lane) selects
+/// to `dst`, packed, and returns how many there were.
+///
+/// The second half's store starts where the first half's kept lanes end,
+/// which is never past its own first lane, so both stay inside the vector's
+/// 16 bytes.
+///
+/// # Safety
+/// `dst` must be valid for a 16-byte write.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn store_kept(chars: uint8x16_t, keep: uint8x16_t, bits: uint8x16_t, dst: *mut u8) -> usize {
+    if vminvq_u8(keep) == 0xFF {
+        unsafe { vst1q_u8(dst, chars) };
+        return COMPACT_STEP;
+    }
+    let bits = vandq_u8(keep, bits);
+    let lo = vaddv_u8(vget_low_u8(bits));
+    let hi = vaddv_u8(vget_high_u8(bits));
+    let [c_lo, c_hi] = compact_control(lo, hi);
+    let packed = vqtbl1q_u8(chars, vcombine_u8(vcreate_u8(c_lo), vcreate_u8(c_hi)));
+
+    let first = lo.count_ones() as usize;
+    unsafe {
+        vst1_u8(dst, vget_low_u8(packed));
+        vst1_u8(dst.add(first), vget_high_u8(packed));
+    }
+    first + hi.count_ones() as usize
+}
+
+/// Copies the bytes of `src` that `config` decodes, plus `'='`, to the front
+/// of `dst`, and returns how many there were, as
+/// [`scalar::compact`](crate::garbage::scalar::compact) does.
+///
+/// # Safety
+/// `dst` must be at least as long as `src`.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn compact_neon(config: &Config, src: &[u8], dst: &mut [u8]) -> usize {
+    debug_assert!(dst.len() >= src.len());
+    let k = unsafe { decode_constants_neon(config) };
+    let pad = vdupq_n_u8(b'=');
+    let bits = unsafe { vld1q_u8(LANE_BITS.as_ptr()) };
+    let len = src.len();
+    let src_ptr = src.as_ptr();
+    let dst_ptr = dst.as_mut_ptr();
+
+    // `kept` never passes `done`, so each step's stores stay inside
+    // `dst[..done + 16]`.
+    let mut done = 0;
+    let mut kept = 0;
+    while done + COMPACT_STEP <= len {
+        let chars = unsafe { vld1q_u8(src_ptr.add(done)) };
+        let keep = vorrq_u8(valid_lanes_neon(chars, &k), vceqq_u8(chars, pad));
+        kept += unsafe { store_kept(chars, keep, bits, dst_ptr.add(kept)) };
+        done += COMPACT_STEP;
+    }
+
+    kept + scalar::compact(config, &src[done..], &mut dst[kept..])
+}
@@ -0,0 +1,103 @@
+//! NEON hex. The structured loads and stores do the interleaving for free:
+//! encode maps both nibbles of 16 bytes with `tbl` and writes the digit pairs
+//! with one `st2`, and decode splits 32 digits into high and low with one
+//! `ld2`, validates both, and ORs them together. Both hand their sub-vector
+//! remainder to the scalar hex kernel.
+
+use crate::Error;
+use crate::hex::scalar;
+
+use core::arch::aarch64::{
+    uint8x16_t, uint8x16x2_t, vaddq_u8, vandq_u8, vbslq_u8, vcleq_u8, vdupq_n_u8, vld1q_u8,
+    vld2q_u8, vminvq_u8, vorrq_u8, vqtbl1q_u8, vshlq_n_u8, vshrq_n_u8, vst1q_u8, vst2q_u8,
+    vsubq_u8,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Bytes an encode step consumes: one vector.
+pub(super) const HEX_ENC_IN: usize = 16;
+/// Digits an encode step writes: an interleaved pair of vectors.
+pub(super) const HEX_ENC_OUT: usize = 2 * HEX_ENC_IN;
+/// Digits a decode step consumes: a de-interleaved pair of vectors.
+pub(super) const HEX_DEC_IN: usize = 32;
+/// Bytes a decode step writes: one vector.
+pub(super) const HEX_DEC_OUT: usize = HEX_DEC_IN / 2;
+
+/// Maps 16 digits to their nibbles. Returns the nibbles and a vector that is
+/// `0xFF` in every lane holding a valid digit of either case; see
+/// `avx2::hex::decode_vec_hex_avx2` for why the two range checks suffice.
+#[inline]
+#[target_feature(enable = "neon")]
+pub(super) fn decode_vec_hex_neon(chars: uint8x16_t) -> (uint8x16_t, uint8x16_t) {
+    let decimal = vsubq_u8(chars, vdupq_n_u8(b'0'));
+    let is_decimal = vcleq_u8(decimal, vdupq_n_u8(9));
+    let letter = vsubq_u8(vorrq_u8(chars, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+    let is_letter = vcleq_u8(letter, vdupq_n_u8(5));
+    let nibbles = vbslq_u8(is_decimal, decimal, vaddq_u8(letter, vdupq_n_u8(10)));
+    (nibbles, vorrq_u8(is_decimal, is_letter))
+}
+
+/// Encodes `input` as hex, writing `2 * input.len()` digits into `dst`.
+///
+/// # Safety
+/// `dst` must hold at least `2 * input.len()` bytes.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn encode_hex_neon(upper: bool, input: &[u8], dst: &mut [u8]) {
+    debug_assert!(dst.len() >= 2 * input.len());
+    let digits = unsafe { vld1q_u8(crate::hex::alphabet(upper).as_ptr()) };
+    let nibble = vdupq_n_u8(0x0F);
+
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_ENC_IN <= len {
+        let raw = unsafe { vld1q_u8(src.add(done)) };
+        let pairs = uint8x16x2_t(
+            vqtbl1q_u8(digits, vshrq_n_u8::<4>(raw)),
+            vqtbl1q_u8(digits, vandq_u8(raw, nibble)),
+        );
+        unsafe { vst2q_u8(out.add(written), pairs) };
+        done += HEX_ENC_IN;
+        written += HEX_ENC_OUT;
+    }
+
+    scalar::encode_slice(upper, &input[done..], &mut dst[written..]);
+}
+
+/// Decodes hex `input`, in either case, into the first `input.len() / 2`
+/// bytes of `dst`.
+///
+/// # Safety
+/// `dst` must hold at least `input.len() / 2` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `input` holds anything but hex
+/// digits, and [`Error::InvalidLength`] if it is otherwise valid but of odd
+/// length.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn decode_hex_neon(input: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    debug_assert!(dst.len() >= input.len() / 2);
+    let len = input.len();
+    let src = input.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut valid = vdupq_n_u8(0xFF);
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_DEC_IN <= len {
+        let uint8x16x2_t(hi_chars, lo_chars) = unsafe { vld2q_u8(src.add(done)) };
+        let (hi, hi_ok) = decode_vec_hex_neon(hi_chars);
+        let (lo, lo_ok) = decode_vec_hex_neon(lo_chars);
+        valid = vandq_u8(valid, vandq_u8(hi_ok, lo_ok));
+        unsafe { vst1q_u8(out.add(written), vorrq_u8(vshlq_n_u8::<4>(hi), lo)) };
+        done += HEX_DEC_IN;
+        written += HEX_DEC_OUT;
+    }
+
+    if vminvq_u8(valid) != 0xFF {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::decode_slice(&input[done..], &mut dst[written..])
+}
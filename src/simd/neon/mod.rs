@@ -0,0 +1,546 @@
+use crate::{Config, Error};
+
+use core::arch::aarch64::{
+    int8x16_t, int16x8_t, int32x4_t, uint8x16_t, uint16x8_t, vaddq_s8, vaddq_u8, vandq_s8,
+    vandq_u8, vandq_u16, vceqq_u8, vcgtq_s8, vcombine_u16, vdupq_n_s8, vdupq_n_u8, vdupq_n_u16,
+    vget_low_s8, vget_low_s16, vget_low_u8, vget_low_u16, vld1q_s8, vld1q_s16, vld1q_u8, vld1q_u16,
+    vmaxvq_u8, vmull_high_s8, vmull_high_s16, vmull_high_u16, vmull_s8, vmull_s16, vmull_u16,
+    vmulq_u16, vorrq_u16, vpaddq_s16, vpaddq_s32, vqsubq_u8, vqtbl1q_s8, vqtbl1q_u8,
+    vreinterpret_s8_u8, vreinterpretq_s8_u8, vreinterpretq_u8_s8, vreinterpretq_u8_s32,
+    vreinterpretq_u8_u16, vreinterpretq_u16_u8, vshrn_n_u32, vshrq_n_u8, vst1q_u8,
+};
+
+/// Rounds per iteration of the encoder's quad tier.
+const ENC_UNROLL: usize = 4;
+/// Vectors per iteration of the decoder's quad tier.
+const DEC_UNROLL: usize = 4;
+
+// Stride constants. The Kani index proofs in `verify` reason over this same
+// arithmetic symbolically, and import these rather than restating them, so a
+// stride that changes here changes the proofs too instead of silently drifting
+// out from under them.
+
+/// Input bytes an encode round consumes.
+const ENC_ROUND_IN: usize = 12;
+/// Characters an encode round writes.
+const ENC_ROUND_OUT: usize = 16;
+/// Bytes each encode load reads: a full vector, of which only the first
+/// [`ENC_ROUND_IN`] are consumed.
+const ENC_VEC: usize = 16;
+/// Bytes each encode load reads past what its round consumes, so no round may
+/// start within this many bytes of the end.
+const ENC_READ_AHEAD: usize = ENC_VEC - ENC_ROUND_IN;
+
+/// Input characters a single-vector decode pass consumes, which is also exactly
+/// what each of its loads reads.
+const DEC_BLOCK_IN: usize = 16;
+/// Bytes a single-vector decode pass advances `dst` by.
+const DEC_BLOCK_OUT: usize = 12;
+/// Characters every decode tier stops short of the end, so the final group —
+/// the only one that may carry `'='` — is always left to the scalar tail.
+const DEC_LEAD: usize = 4;
+
+/// Unsigned multiply-high for u16x8. NEON has no `mulhi_u16`, so emulate it
+/// with a widening multiply and narrowing shift.
+#[inline]
+unsafe fn vmulhq_u16(a: uint16x8_t, b: uint16x8_t) -> uint16x8_t {
+    unsafe {
+        let lo = vshrn_n_u32(vmull_u16(vget_low_u16(a), vget_low_u16(b)), 16);
+        let hi = vshrn_n_u32(vmull_high_u16(a, b), 16);
+        vcombine_u16(lo, hi)
+    }
+}
+
+/// Stand-in for `_mm_maddubs_epi16`: widen to s16, then pairwise-add adjacent
+/// lanes to `[a0*b0+a1*b1, ...]`. Unlike the x86 instruction, `a` is widened as
+/// *signed* and the sum wraps rather than saturates; neither matters for the
+/// 6-bit indices it is fed, and the verification model spells out the former.
+#[inline]
+unsafe fn vmaddubs_s16(a: uint8x16_t, b: int8x16_t) -> int16x8_t {
+    unsafe {
+        let prod_lo = vmull_s8(vreinterpret_s8_u8(vget_low_u8(a)), vget_low_s8(b));
+        let prod_hi = vmull_high_s8(vreinterpretq_s8_u8(a), b);
+        vpaddq_s16(prod_lo, prod_hi)
+    }
+}
+
+/// Equivalent of `_mm_madd_epi16`: pairwise s16*s16 multiply-add into s32.
+#[inline]
+unsafe fn vmadd_s32(a: int16x8_t, b: int16x8_t) -> int32x4_t {
+    unsafe {
+        let prod_lo = vmull_s16(vget_low_s16(a), vget_low_s16(b));
+        let prod_hi = vmull_high_s16(a, b);
+        vpaddq_s32(prod_lo, prod_hi)
+    }
+}
+
+// --- NEON encoder ---
+
+/// Precomputed NEON encode constants, shared by [`encode_slice_neon`] and
+/// [`encode_short_neon`].
+struct EncodeConstantsNeon {
+    shuffle: uint8x16_t,
+    mul_right_shift: uint16x8_t,
+    mul_left_shift: uint16x8_t,
+    mask_lo_6bits: uint16x8_t,
+    mask_hi_6bits: uint16x8_t,
+    offset_base: int8x16_t,
+    set_25: int8x16_t,
+    delta_lower: int8x16_t,
+    set_51: uint8x16_t,
+    lut_offsets: int8x16_t,
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn encode_constants_neon(config: &Config) -> EncodeConstantsNeon {
+    // Shuffle: rearrange 12 input bytes into positions for 6-bit extraction
+    let shuffle = unsafe {
+        let s: [u8; 16] = [1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10];
+        vld1q_u8(s.as_ptr())
+    };
+
+    // Multipliers for shift-via-multiply (same constants as AVX2)
+    let mul_right_shift: uint16x8_t = unsafe {
+        let m: [u16; 8] = [
+            0x0040, 0x0400, 0x0040, 0x0400, 0x0040, 0x0400, 0x0040, 0x0400,
+        ];
+        vld1q_u16(m.as_ptr())
+    };
+    let mul_left_shift: uint16x8_t = unsafe {
+        let m: [u16; 8] = [
+            0x0010, 0x0100, 0x0010, 0x0100, 0x0010, 0x0100, 0x0010, 0x0100,
+        ];
+        vld1q_u16(m.as_ptr())
+    };
+
+    let mask_lo_6bits = vdupq_n_u16(0x003F);
+    let mask_hi_6bits = vdupq_n_u16(0x3F00);
+
+    // Character mapping constants
+    let offset_base = vdupq_n_s8(65); // 'A'
+    let set_25 = vdupq_n_s8(25);
+    let delta_lower = vdupq_n_s8(6);
+    let set_51 = vdupq_n_u8(51);
+
+    let (sym_plus, sym_slash): (i8, i8) = if config.url_safe {
+        (-88, -39)
+    } else {
+        (-90, -87)
+    };
+
+    let lut_offsets = unsafe {
+        let l: [i8; 16] = [
+            0, -75, -75, -75, -75, -75, -75, -75, -75, -75, -75, sym_plus, sym_slash, 0, 0, 0,
+        ];
+        vld1q_s8(l.as_ptr())
+    };
+
+    EncodeConstantsNeon {
+        shuffle,
+        mul_right_shift,
+        mul_left_shift,
+        mask_lo_6bits,
+        mask_hi_6bits,
+        offset_base,
+        set_25,
+        delta_lower,
+        set_51,
+        lut_offsets,
+    }
+}
+
+/// Encode one 128-bit vector: 12 input bytes -> 16 output bytes.
+#[inline]
+#[target_feature(enable = "neon")]
+fn encode_vec_neon(in_vec: uint8x16_t, k: &EncodeConstantsNeon) -> uint8x16_t {
+    // Shuffle, then extract the 6-bit indices via multiply-shift.
+    let v = vqtbl1q_u8(in_vec, k.shuffle);
+    let v_u16 = vreinterpretq_u16_u8(v);
+    let lo = vmulq_u16(v_u16, k.mul_left_shift);
+    let hi = unsafe { vmulhq_u16(v_u16, k.mul_right_shift) };
+    let indices = vreinterpretq_u8_u16(vorrq_u16(
+        vandq_u16(lo, k.mask_hi_6bits),
+        vandq_u16(hi, k.mask_lo_6bits),
+    ));
+    translate_neon(indices, k)
+}
+
+/// Maps 16 6-bit values to their Base64 characters branchlessly: letters by
+/// offset and compare, then digits and the two symbols by one `tbl` of
+/// `lut_offsets`.
+#[inline]
+#[target_feature(enable = "neon")]
+fn translate_neon(indices: uint8x16_t, k: &EncodeConstantsNeon) -> uint8x16_t {
+    let signed = vreinterpretq_s8_u8(indices);
+    let mut char_val = vaddq_s8(signed, k.offset_base);
+    let gt25 = vcgtq_s8(signed, k.set_25);
+    char_val = vaddq_s8(char_val, vandq_s8(vreinterpretq_s8_u8(gt25), k.delta_lower));
+
+    let offset_special = vqtbl1q_s8(k.lut_offsets, vqsubq_u8(indices, k.set_51));
+    vreinterpretq_u8_s8(vaddq_s8(char_val, offset_special))
+}
+
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn encode_slice_neon(config: &Config, input: &[u8], dst_slice: &mut [u8]) {
+    let len = input.len();
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+
+    let k = unsafe { encode_constants_neon(config) };
+
+    // Every load reads a full vector, so no round may start within
+    // `ENC_READ_AHEAD` bytes of the end.
+    let safe_len = len.saturating_sub(ENC_READ_AHEAD);
+
+    // Quad tier: 48 input bytes -> 64 output.
+    let aligned_len_quad = safe_len - (safe_len % (ENC_ROUND_IN * ENC_UNROLL));
+    let src_end_quad = unsafe { src.add(aligned_len_quad) };
+
+    while src < src_end_quad {
+        let v0 = encode_vec_neon(unsafe { vld1q_u8(src) }, &k);
+        let v1 = encode_vec_neon(unsafe { vld1q_u8(src.add(ENC_ROUND_IN)) }, &k);
+        let v2 = encode_vec_neon(unsafe { vld1q_u8(src.add(ENC_ROUND_IN * 2)) }, &k);
+        let v3 = encode_vec_neon(unsafe { vld1q_u8(src.add(ENC_ROUND_IN * 3)) }, &k);
+
+        unsafe { vst1q_u8(dst, v0) };
+        unsafe { vst1q_u8(dst.add(ENC_ROUND_OUT), v1) };
+        unsafe { vst1q_u8(dst.add(ENC_ROUND_OUT * 2), v2) };
+        unsafe { vst1q_u8(dst.add(ENC_ROUND_OUT * 3), v3) };
+
+        src = unsafe { src.add(ENC_ROUND_IN * ENC_UNROLL) };
+        dst = unsafe { dst.add(ENC_ROUND_OUT * ENC_UNROLL) };
+    }
+
+    // Single tier: 12 input bytes -> 16 output.
+    let aligned_len_single = safe_len - (safe_len % ENC_ROUND_IN);
+    let src_end_single = unsafe { input.as_ptr().add(aligned_len_single) };
+
+    while src < src_end_single {
+        let v = encode_vec_neon(unsafe { vld1q_u8(src) }, &k);
+        unsafe { vst1q_u8(dst, v) };
+
+        src = unsafe { src.add(ENC_ROUND_IN) };
+        dst = unsafe { dst.add(ENC_ROUND_OUT) };
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::encode(config, input, src, dst_slice, dst_off) };
+}
+
+// --- NEON decoder ---
+
+/// Precomputed NEON decode constants, shared by [`decode_slice_neon`] and
+/// [`decode_short_neon`]: the SSSE3 kernel's nibble LUTs (see
+/// `DecodeConstantsAvx2` for their derivation and credit) and packing
+/// constants. `tbl` zeroes out-of-range indices where `pshufb` wraps them, but
+/// a valid character's roll index never exceeds 15, so the tables carry over
+/// unchanged.
+struct DecodeConstantsNeon {
+    lut_lo: uint8x16_t,
+    lut_hi: uint8x16_t,
+    lut_shift: uint8x16_t,
+    lut_roll: int8x16_t,
+    pack_l1: int8x16_t,
+    pack_l2: int16x8_t,
+    pack_shuffle: uint8x16_t,
+    mask_nibble: uint8x16_t,
+}
+
+#[target_feature(enable = "neon")]
+unsafe fn decode_constants_neon(config: &Config) -> DecodeConstantsNeon {
+    let (lut_lo, lut_hi, lut_shift, lut_roll): ([u8; 16], [u8; 16], [u8; 16], [i8; 16]) =
+        if config.lenient {
+            (
+                [
+                    0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3A, 0x3B,
+                    0x3A, 0x3B, 0x32,
+                ],
+                [
+                    0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10,
+                    0x10, 0x10, 0x10,
+                ],
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 9],
+                [
+                    0, 0, 19, 4, -65, -65, -71, -71, 17, 0, -65, 16, -71, -65, -32, -71,
+                ],
+            )
+        } else if config.url_safe {
+            (
+                [
+                    0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3B, 0x3B,
+                    0x3A, 0x3B, 0x33,
+                ],
+                [
+                    0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10,
+                    0x10, 0x10, 0x10,
+                ],
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4],
+                [
+                    0, 0, 17, 4, -65, -65, -71, -71, -65, -32, -71, 0, 0, 0, 0, 0,
+                ],
+            )
+        } else {
+            (
+                [
+                    0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x1A, 0x1B,
+                    0x1B, 0x1B, 0x1A,
+                ],
+                [
+                    0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x08, 0x10, 0x10, 0x10, 0x10, 0x10,
+                    0x10, 0x10, 0x10,
+                ],
+                [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6],
+                [0, 0, 19, 4, -65, -65, -71, -71, 16, 0, -65, 0, -71, 0, 0, 0],
+            )
+        };
+
+    // Packing constants (same as x86 PACK_L1/L2/SHUFFLE but 128-bit)
+    let pack_l1 = unsafe {
+        let p: [i8; 16] = [
+            0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01, 0x40, 0x01,
+            0x40, 0x01,
+        ];
+        vld1q_s8(p.as_ptr())
+    };
+    let pack_l2 = unsafe {
+        let p: [i16; 8] = [
+            0x1000, 0x0001, 0x1000, 0x0001, 0x1000, 0x0001, 0x1000, 0x0001,
+        ];
+        vld1q_s16(p.as_ptr())
+    };
+    let pack_shuffle = unsafe {
+        let p: [u8; 16] = [
+            2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        vld1q_u8(p.as_ptr())
+    };
+
+    DecodeConstantsNeon {
+        lut_lo: unsafe { vld1q_u8(lut_lo.as_ptr()) },
+        lut_hi: unsafe { vld1q_u8(lut_hi.as_ptr()) },
+        lut_shift: unsafe { vld1q_u8(lut_shift.as_ptr()) },
+        lut_roll: unsafe { vld1q_s8(lut_roll.as_ptr()) },
+        pack_l1,
+        pack_l2,
+        pack_shuffle,
+        mask_nibble: vdupq_n_u8(0x0F),
+    }
+}
+
+/// The nibble-LUT validation bits of `input_vec`, given its high and low
+/// nibbles: zero in exactly the lanes the decoder accepts.
+#[inline]
+#[target_feature(enable = "neon")]
+fn err_lanes_neon(hi: uint8x16_t, lo: uint8x16_t, k: &DecodeConstantsNeon) -> uint8x16_t {
+    vandq_u8(vqtbl1q_u8(k.lut_lo, lo), vqtbl1q_u8(k.lut_hi, hi))
+}
+
+/// Validate + decode one 128-bit vector into its indices, and a nonzero byte
+/// if any character was invalid.
+#[inline]
+#[target_feature(enable = "neon")]
+fn decode_vec_neon(input_vec: uint8x16_t, k: &DecodeConstantsNeon) -> (uint8x16_t, u8) {
+    let hi = vshrq_n_u8(input_vec, 4);
+    let lo = vandq_u8(input_vec, k.mask_nibble);
+
+    // Reduce the per-byte error bits; nonzero means an error.
+    let err_any = vmaxvq_u8(err_lanes_neon(hi, lo, k));
+
+    // The high nibble, nudged by the low one for the two symbols, picks the
+    // offset from character to index.
+    let roll_idx = vaddq_u8(hi, vqtbl1q_u8(k.lut_shift, lo));
+    let roll = vqtbl1q_s8(k.lut_roll, roll_idx);
+    let indices = vaddq_s8(vreinterpretq_s8_u8(input_vec), roll);
+
+    (vreinterpretq_u8_s8(indices), err_any)
+}
+
+/// `0xFF` in every lane of `input_vec` the decoder accepts, `0` elsewhere.
+#[inline]
+#[target_feature(enable = "neon")]
+fn valid_lanes_neon(input_vec: uint8x16_t, k: &DecodeConstantsNeon) -> uint8x16_t {
+    let hi = vshrq_n_u8(input_vec, 4);
+    let lo = vandq_u8(input_vec, k.mask_nibble);
+    vceqq_u8(err_lanes_neon(hi, lo, k), vdupq_n_u8(0))
+}
+
+/// Pack 6-bit indices to bytes: maddubs, madd, then shuffle out 3 bytes per
+/// 4-byte lane. The 12 bytes land at the bottom, with the top 4 zeroed.
+#[inline]
+#[target_feature(enable = "neon")]
+fn pack_neon(indices: uint8x16_t, k: &DecodeConstantsNeon) -> uint8x16_t {
+    let m = unsafe { vmaddubs_s16(indices, k.pack_l1) };
+    let p = unsafe { vmadd_s32(m, k.pack_l2) };
+    vqtbl1q_u8(vreinterpretq_u8_s32(p), k.pack_shuffle)
+}
+
+/// Stores the 12 bytes of a [`pack_neon`] result at `dst`. Writes 16 bytes;
+/// the high 4 are overwritten next iteration.
+///
+/// # Safety
+/// `dst` must be valid for a 16-byte write.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn pack_and_store_neon(indices: uint8x16_t, dst: *mut u8, k: &DecodeConstantsNeon) {
+    unsafe { vst1q_u8(dst, pack_neon(indices, k)) };
+}
+
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn decode_slice_neon(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) -> Result<usize, Error> {
+    let len = input.len();
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+
+    let k = unsafe { decode_constants_neon(config) };
+
+    // Both tiers stop `DEC_LEAD` characters short of the end.
+    let safe_len = len.saturating_sub(DEC_LEAD);
+
+    // Quad tier: 64 input bytes -> 48 output.
+    let aligned_len_quad = safe_len - (safe_len % (DEC_BLOCK_IN * DEC_UNROLL));
+    let src_end_quad = unsafe { src.add(aligned_len_quad) };
+
+    while src < src_end_quad {
+        let v0 = unsafe { vld1q_u8(src) };
+        let v1 = unsafe { vld1q_u8(src.add(DEC_BLOCK_IN)) };
+        let v2 = unsafe { vld1q_u8(src.add(DEC_BLOCK_IN * 2)) };
+        let v3 = unsafe { vld1q_u8(src.add(DEC_BLOCK_IN * 3)) };
+
+        let (i0, e0) = decode_vec_neon(v0, &k);
+        let (i1, e1) = decode_vec_neon(v1, &k);
+        let (i2, e2) = decode_vec_neon(v2, &k);
+        let (i3, e3) = decode_vec_neon(v3, &k);
+
+        if (e0 | e1 | e2 | e3) != 0 {
+            return Err(Error::InvalidCharacter);
+        }
+
+        unsafe { pack_and_store_neon(i0, dst, &k) };
+        unsafe { pack_and_store_neon(i1, dst.add(DEC_BLOCK_OUT), &k) };
+        unsafe { pack_and_store_neon(i2, dst.add(DEC_BLOCK_OUT * 2), &k) };
+        unsafe { pack_and_store_neon(i3, dst.add(DEC_BLOCK_OUT * 3), &k) };
+
+        src = unsafe { src.add(DEC_BLOCK_IN * DEC_UNROLL) };
+        dst = unsafe { dst.add(DEC_BLOCK_OUT * DEC_UNROLL) };
+    }
+
+    // Single tier: 16 input bytes -> 12 output.
+    let aligned_len_single = safe_len - (safe_len % DEC_BLOCK_IN);
+    let src_end_single = unsafe { input.as_ptr().add(aligned_len_single) };
+
+    while src < src_end_single {
+        let v = unsafe { vld1q_u8(src) };
+        let (idx, err) = decode_vec_neon(v, &k);
+
+        if err != 0 {
+            return Err(Error::InvalidCharacter);
+        }
+
+        unsafe { pack_and_store_neon(idx, dst, &k) };
+
+        src = unsafe { src.add(DEC_BLOCK_IN) };
+        dst = unsafe { dst.add(DEC_BLOCK_OUT) };
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::decode(config, input, src, dst_slice, dst_off) }
+}
+
+// --- Short inputs ---
+
+/// Longest input [`encode_short_neon`] takes: two rounds.
+pub(crate) const ENC_SHORT_MAX: usize = 2 * ENC_ROUND_IN;
+/// Longest input [`decode_short_neon`] takes: two single-vector passes.
+pub(crate) const DEC_SHORT_MAX: usize = 2 * DEC_BLOCK_IN;
+
+/// Encodes an input of at most [`ENC_SHORT_MAX`] bytes, padding included.
+///
+/// NEON has no masked loads, so both sides are staged through the stack: the
+/// input is copied into a zeroed buffer wide enough for every round's full
+/// load, and the characters are copied out once the `=` are in place.
+///
+/// # Safety
+/// The CPU must support NEON.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn encode_short_neon(config: &Config, input: &[u8], dst: &mut [u8]) {
+    debug_assert!(input.len() <= ENC_SHORT_MAX);
+    let k = unsafe { encode_constants_neon(config) };
+
+    let mut staged = [0u8; ENC_SHORT_MAX + ENC_READ_AHEAD];
+    staged[..input.len()].copy_from_slice(input);
+    let mut chars = [0u8; 2 * ENC_ROUND_OUT];
+
+    for round in 0..input.len().div_ceil(ENC_ROUND_IN) {
+        let src = staged[ENC_ROUND_IN * round..][..ENC_VEC].as_ptr();
+        let out = chars[ENC_ROUND_OUT * round..][..ENC_ROUND_OUT].as_mut_ptr();
+        let v = encode_vec_neon(unsafe { vld1q_u8(src) }, &k);
+        unsafe { vst1q_u8(out, v) };
+    }
+
+    let (data, total) = super::short::encoded_lens(*config, input.len());
+    chars[data..total].fill(b'=');
+    dst[..total].copy_from_slice(&chars[..total]);
+}
+
+/// Decodes an input of at most [`DEC_SHORT_MAX`] characters, padding included,
+/// staged through the stack as in [`encode_short_neon`].
+///
+/// # Safety
+/// The CPU must support NEON.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn decode_short_neon(
+    config: &Config,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    debug_assert!(input.len() <= DEC_SHORT_MAX);
+    let k = unsafe { decode_constants_neon(config) };
+    let plan = super::short::plan_decode(*config, input);
+
+    let mut staged = [b'A'; DEC_SHORT_MAX];
+    staged[..plan.chars].copy_from_slice(&input[..plan.chars]);
+    // The second pass's store spans 16 bytes from 12.
+    let mut bytes = [0u8; DEC_BLOCK_OUT + DEC_BLOCK_IN];
+
+    let mut err = 0;
+    for block in 0..plan.chars.div_ceil(DEC_BLOCK_IN) {
+        let src = staged[DEC_BLOCK_IN * block..][..DEC_BLOCK_IN].as_ptr();
+        let (idx, e) = decode_vec_neon(unsafe { vld1q_u8(src) }, &k);
+        err |= e;
+        let out = bytes[DEC_BLOCK_OUT * block..][..DEC_BLOCK_IN].as_mut_ptr();
+        unsafe { pack_and_store_neon(idx, out, &k) };
+    }
+
+    if err != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    plan.trailing?;
+    dst[..plan.out].copy_from_slice(&bytes[..plan.out]);
+    Ok(plan.out)
+}
+
+// Hex on the same instruction set.
+mod hex;
+pub(crate) use hex::{decode_hex_neon, encode_hex_neon};
+
+// Ignore-garbage compaction on the decoder's nibble lookup.
+mod garbage;
+pub(crate) use garbage::compact_neon;
+
+// Transcoding: Base64 to Base64 on the decoder's lookup and the encoder's
+// translate step, and Base64 to and from hex on both formats' steps.
+mod transcode;
+pub(crate) use transcode::{
+    base64_to_hex_neon, hex_to_base64_neon, remap_in_place_neon, remap_neon,
+};
+
+// Verification: Kani proofs, intrinsic models, model/hardware equivalence,
+// and the Miri + hardware coverage suites.
+#[cfg(any(kani, test))]
+mod verify;
@@ -0,0 +1,240 @@
+//! NEON transcoding, on the Base64 and hex kernels' own steps:
+//!
+//! * Base64 -> Base64: the decoder's nibble `tbl`s validate 16 characters and
+//!   turn them into their 6-bit values, and the encoder's translate step
+//!   turns those straight into the target alphabet's characters.
+//! * Base64 -> hex: the decoder's lookup and pack step turn 16 characters
+//!   into 12 bytes, which the hex encoder's nibble `tbl`s and a `zip` expand
+//!   to 24 digits.
+//! * hex -> Base64: an `uzp` splits 24 digits into high and low, as the hex
+//!   decoder's `ld2` does, and its range checks and a shift-or leave 12 bytes
+//!   at the bottom of a vector, just where the Base64 encoder's round reads
+//!   them.
+//!
+//! No bytes ever reach memory between the two formats. The remap loads each
+//! vector before its store, at the same offset, so one core serves both the
+//! copying and the in-place form. The sub-vector remainder of each goes to the
+//! scalar kernel, as do lenient sources.
+
+use super::hex::decode_vec_hex_neon;
+use super::{
+    decode_constants_neon, decode_vec_neon, encode_constants_neon, encode_vec_neon, pack_neon,
+    translate_neon,
+};
+use crate::transcode::{remap_table_for, scalar};
+use crate::{Config, Error};
+
+use core::arch::aarch64::{
+    vandq_u8, vcombine_u8, vdup_n_u8, vdupq_n_u8, vget_low_u8, vld1_u8, vld1q_u8, vminvq_u8,
+    vorrq_u8, vqtbl1q_u8, vshlq_n_u8, vshrq_n_u8, vst1_u8, vst1q_u8, vuzp1q_u8, vuzp2q_u8,
+    vzip1q_u8, vzip2q_u8,
+};
+
+// Stride constants, imported by the Kani index proofs in `verify`.
+
+/// Characters a step remaps: one vector.
+pub(super) const REMAP_STEP: usize = 16;
+
+/// Remaps the whole vectors of the `len` characters at `src` to `dst`, which
+/// may be the same pointer, and returns how many characters that was.
+///
+/// # Safety
+/// `src` must be valid for a `len`-byte read and `dst` for a `len`-byte
+/// write, and if they overlap they must be equal.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if any of them is outside `from`'s
+/// alphabet.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn remap_raw(
+    from: &Config,
+    to: &Config,
+    src: *const u8,
+    dst: *mut u8,
+    len: usize,
+) -> Result<usize, Error> {
+    let dk = unsafe { decode_constants_neon(from) };
+    let ek = unsafe { encode_constants_neon(to) };
+
+    let mut bad = 0;
+    let mut done = 0;
+    while done + REMAP_STEP <= len {
+        let chars = unsafe { vld1q_u8(src.add(done)) };
+        let (indices, err) = decode_vec_neon(chars, &dk);
+        bad |= err;
+        unsafe { vst1q_u8(dst.add(done), translate_neon(indices, &ek)) };
+        done += REMAP_STEP;
+    }
+
+    if bad != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    Ok(done)
+}
+
+/// Remaps `src`, in `from`'s alphabet, into `dst` in `to`'s, as
+/// [`scalar::remap`](crate::transcode::scalar::remap) does.
+///
+/// # Safety
+/// `dst` must be at least as long as `src`.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the source
+/// alphabet.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn remap_neon(
+    from: &Config,
+    to: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(dst.len() >= src.len());
+    let table = remap_table_for(from, to);
+    if from.lenient {
+        return scalar::remap(table, src, dst);
+    }
+    let done = unsafe { remap_raw(from, to, src.as_ptr(), dst.as_mut_ptr(), src.len()) }?;
+    scalar::remap(table, &src[done..], &mut dst[done..])
+}
+
+/// Remaps `buf` where it lies, from `from`'s alphabet to `to`'s, as
+/// [`scalar::remap_in_place`](crate::transcode::scalar::remap_in_place) does.
+///
+/// # Safety
+/// The CPU must support NEON, as every aarch64 one does.
+///
+/// # Errors
+/// As [`remap_neon`].
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn remap_in_place_neon(
+    from: &Config,
+    to: &Config,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    let table = remap_table_for(from, to);
+    if from.lenient {
+        return scalar::remap_in_place(table, buf);
+    }
+    let ptr = buf.as_mut_ptr();
+    let done = unsafe { remap_raw(from, to, ptr, ptr, buf.len()) }?;
+    scalar::remap_in_place(table, &mut buf[done..])
+}
+
+// --- Base64 -> hex ---
+
+/// Characters a Base64-to-hex step consumes: one vector.
+pub(super) const B64_HEX_IN: usize = 16;
+/// Digits a Base64-to-hex step writes: two per decoded byte.
+pub(super) const B64_HEX_OUT: usize = B64_HEX_IN / 4 * 6;
+/// Digits the first store of a step holds, the rest going in a half one;
+/// likewise the loads of a hex-to-Base64 step.
+pub(super) const HEX_VEC: usize = 16;
+
+/// Decodes `src`, whole Base64 groups without padding, straight to hex in
+/// `dst`: `src.len() / 4 * 6` digits, in `upper` or lower case.
+///
+/// # Safety
+/// `src.len()` must be a multiple of 4, and `dst` must hold at least
+/// `src.len() / 4 * 6` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside `config`'s
+/// alphabet, `'='` included. `dst` is then partly written.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn base64_to_hex_neon(
+    config: &Config,
+    upper: bool,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(src.len().is_multiple_of(4) && dst.len() >= src.len() / 4 * 6);
+    if config.lenient {
+        return scalar::base64_to_hex(config, upper, src, dst);
+    }
+    let k = unsafe { decode_constants_neon(config) };
+    let digits = unsafe { vld1q_u8(crate::hex::alphabet(upper).as_ptr()) };
+    let nibble = vdupq_n_u8(0x0F);
+
+    let len = src.len();
+    let chars = src.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut bad = 0;
+    let mut done = 0;
+    let mut written = 0;
+    while done + B64_HEX_IN <= len {
+        let (indices, err) = decode_vec_neon(unsafe { vld1q_u8(chars.add(done)) }, &k);
+        bad |= err;
+        let bytes = pack_neon(indices, &k);
+
+        // The low zip holds the digits of the first 8 bytes, the high one
+        // those of the last 4 in its bottom 8.
+        let hi = vqtbl1q_u8(digits, vshrq_n_u8::<4>(bytes));
+        let lo = vqtbl1q_u8(digits, vandq_u8(bytes, nibble));
+        unsafe {
+            vst1q_u8(out.add(written), vzip1q_u8(hi, lo));
+            vst1_u8(out.add(written + HEX_VEC), vget_low_u8(vzip2q_u8(hi, lo)));
+        }
+        done += B64_HEX_IN;
+        written += B64_HEX_OUT;
+    }
+
+    if bad != 0 {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::base64_to_hex(config, upper, &src[done..], &mut dst[written..])
+}
+
+// --- hex -> Base64 ---
+
+/// Digits a hex-to-Base64 step consumes: 12 bytes' worth, a vector and a half.
+pub(super) const HEX_B64_IN: usize = 24;
+/// Characters a hex-to-Base64 step writes: one vector.
+pub(super) const HEX_B64_OUT: usize = 16;
+
+/// Encodes hex `src`, a whole number of 6-digit groups in either case,
+/// straight to Base64 in `dst`: `src.len() / 6 * 4` characters of `config`'s
+/// alphabet.
+///
+/// # Safety
+/// `src.len()` must be a multiple of 6, and `dst` must hold at least
+/// `src.len() / 6 * 4` bytes.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `src` holds anything but hex digits.
+/// `dst` is then partly written.
+#[target_feature(enable = "neon")]
+pub(crate) unsafe fn hex_to_base64_neon(
+    config: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    debug_assert!(src.len().is_multiple_of(6) && dst.len() >= src.len() / 6 * 4);
+    let k = unsafe { encode_constants_neon(config) };
+    // The second load's upper half: digits that decode to zero, and pass.
+    let zeros = vdup_n_u8(b'0');
+
+    let len = src.len();
+    let digits = src.as_ptr();
+    let out = dst.as_mut_ptr();
+    let mut valid = vdupq_n_u8(0xFF);
+    let mut done = 0;
+    let mut written = 0;
+    while done + HEX_B64_IN <= len {
+        let first = unsafe { vld1q_u8(digits.add(done)) };
+        let second = vcombine_u8(unsafe { vld1_u8(digits.add(done + HEX_VEC)) }, zeros);
+        let (hi, hi_ok) = decode_vec_hex_neon(vuzp1q_u8(first, second));
+        let (lo, lo_ok) = decode_vec_hex_neon(vuzp2q_u8(first, second));
+        valid = vandq_u8(valid, vandq_u8(hi_ok, lo_ok));
+
+        let bytes = vorrq_u8(vshlq_n_u8::<4>(hi), lo);
+        unsafe { vst1q_u8(out.add(written), encode_vec_neon(bytes, &k)) };
+        done += HEX_B64_IN;
+        written += HEX_B64_OUT;
+    }
+
+    if vminvq_u8(valid) != 0xFF {
+        return Err(Error::InvalidCharacter);
+    }
+    scalar::hex_to_base64(config, &src[done..], &mut dst[written..])
+}
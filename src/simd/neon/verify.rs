@@ -0,0 +1,1461 @@
+//! NEON verification: Kani proofs, Arm-pseudocode intrinsic model stubs, the
+//! model/hardware equivalence check, and the Miri + hardware coverage suites.
+//! Laid out like `ssse3/verify.rs`, whose kernel this one parallels.
+
+use super::*;
+
+#[cfg(kani)]
+mod kani_verification_neon {
+    use super::*;
+    use crate::{Config, STANDARD as TURBO_STANDARD, STANDARD_NO_PAD as TURBO_STANDARD_NO_PAD};
+
+    // Only used inside `#[kani::stub(...)]` paths, which don't count as a use.
+    #[allow(unused_imports)]
+    use super::intrinsic_models as m;
+
+    // Layer 1 — index proofs: reason over a symbolic `len` and an arbitrary
+    // iteration index (no vectors), giving an induction (base/step/exit) that
+    // covers all N cheaply. Every stride is imported from the kernel module
+    // rather than restated. See the README's "Safety & Verification".
+
+    /// Largest `len` considered: above `usize::MAX / 4` the unpadded
+    /// `encoded_len`'s `len * 4` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 4;
+
+    // Encoder model, mirroring `encode_slice_neon`.
+    use super::super::{
+        ENC_READ_AHEAD, ENC_ROUND_IN, ENC_ROUND_OUT, ENC_UNROLL, ENC_VEC as ENC_LOAD,
+    };
+
+    const ENC_QUAD_IN: usize = ENC_ROUND_IN * ENC_UNROLL; // input bytes per quad-tier iteration
+    const ENC_QUAD_OUT: usize = ENC_ROUND_OUT * ENC_UNROLL; // dst advance per quad-tier iteration
+
+    fn enc_cap(len: usize, padding: bool) -> usize {
+        if padding {
+            TURBO_STANDARD.encoded_len(len)
+        } else {
+            TURBO_STANDARD_NO_PAD.encoded_len(len)
+        }
+    }
+
+    /// Symbolic `n` pinned to `len.saturating_sub(4) / stride` via
+    /// inequalities (cheaper for CBMC than division; `check_enc_windows_model`
+    /// proves they agree).
+    fn any_enc_quotient(len: usize, stride: usize) -> usize {
+        let safe = len.saturating_sub(ENC_READ_AHEAD);
+        let n: usize = kani::any();
+        kani::assume(n <= MAX_LEN / stride);
+        kani::assume(stride * n <= safe);
+        kani::assume(safe < stride * (n + 1));
+        n
+    }
+
+    /// `(quads, rounds)`: iterations of the quad tier, and rounds run by both
+    /// tiers together.
+    fn any_enc_windows(len: usize) -> (usize, usize) {
+        (
+            any_enc_quotient(len, ENC_QUAD_IN),
+            any_enc_quotient(len, ENC_ROUND_IN),
+        )
+    }
+
+    /// `(src_off, dst_off)` after `done` rounds.
+    fn enc_state(done: usize) -> (usize, usize) {
+        (ENC_ROUND_IN * done, ENC_ROUND_OUT * done)
+    }
+
+    /// Isolated so the suite's non-power-of-two divisions own their run. Also
+    /// ties the tiers together: the single tier resumes exactly where the quad
+    /// tier stopped and runs fewer than `ENC_UNROLL` rounds.
+    #[kani::proof]
+    fn check_enc_windows_model() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let safe = len.saturating_sub(ENC_READ_AHEAD);
+        let (quads, rounds) = any_enc_windows(len);
+        assert_eq!(ENC_QUAD_IN * quads, safe - safe % ENC_QUAD_IN);
+        assert_eq!(ENC_ROUND_IN * rounds, safe - safe % ENC_ROUND_IN);
+        assert!(ENC_UNROLL * quads <= rounds);
+        assert!(rounds < ENC_UNROLL * (quads + 1));
+    }
+
+    /// Inductive step for the quad tier, over an arbitrary iteration.
+    #[kani::proof]
+    fn check_enc_quad_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (quads, _) = any_enc_windows(len);
+        let cap = enc_cap(len, padding);
+
+        let i: usize = kani::any();
+        kani::assume(i < quads); // guard `src < src_end_quad`
+        let (src_off, dst_off) = enc_state(ENC_UNROLL * i);
+        assert_eq!((src_off, dst_off), (ENC_QUAD_IN * i, ENC_QUAD_OUT * i));
+
+        // Widest body accesses: the fourth round's load and store.
+        let last_src = ENC_ROUND_IN * (ENC_UNROLL - 1);
+        let last_dst = ENC_ROUND_OUT * (ENC_UNROLL - 1);
+        assert!(
+            src_off + last_src + ENC_LOAD <= len,
+            "quad load leaves input"
+        );
+        assert!(
+            dst_off + last_dst + ENC_ROUND_OUT <= cap,
+            "quad store leaves output"
+        );
+
+        assert_eq!(
+            (src_off + ENC_QUAD_IN, dst_off + ENC_QUAD_OUT),
+            enc_state(ENC_UNROLL * (i + 1))
+        );
+    }
+
+    /// Inductive step for the single-round tier, entered from wherever the
+    /// quad tier stopped.
+    #[kani::proof]
+    fn check_enc_single_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (quads, rounds) = any_enc_windows(len);
+        let cap = enc_cap(len, padding);
+
+        let done: usize = kani::any();
+        kani::assume(ENC_UNROLL * quads <= done);
+        kani::assume(done < rounds); // guard `src < src_end_single`
+
+        let (src_off, dst_off) = enc_state(done);
+        assert!(src_off + ENC_LOAD <= len, "single load leaves input");
+        assert!(dst_off + ENC_ROUND_OUT <= cap, "single store leaves output");
+
+        assert_eq!(
+            (src_off + ENC_ROUND_IN, dst_off + ENC_ROUND_OUT),
+            enc_state(done + 1)
+        );
+    }
+
+    /// The 4-byte read-ahead margin is exact: every round that runs keeps its
+    /// 16-byte load inside `input`, and the round after the last would not, so
+    /// the guard neither overreads nor forfeits a round.
+    #[kani::proof]
+    fn check_enc_read_ahead_margin() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (_, rounds) = any_enc_windows(len);
+        if rounds > 0 {
+            let (last_src, _) = enc_state(rounds - 1);
+            assert!(last_src + ENC_LOAD <= len, "last round overreads");
+        }
+        let (next_src, _) = enc_state(rounds);
+        assert!(next_src + ENC_LOAD > len, "margin forfeits a round");
+    }
+
+    /// Exit case: the scalar handoff accounts for the rest.
+    #[kani::proof]
+    fn check_enc_tail_handoff() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (_, rounds) = any_enc_windows(len);
+        let (src_off, dst_off) = enc_state(rounds);
+
+        // The rounds stop short of the read-ahead margin, so once any ran, the
+        // tail holds at least the 4 bytes they left behind.
+        assert!(src_off <= len);
+        let tail = len - src_off;
+        assert!(tail >= ENC_READ_AHEAD.min(len));
+
+        // Prefix + scalar tail is exactly the encoded length (no over/short write).
+        assert_eq!(
+            dst_off + enc_cap(tail, padding),
+            enc_cap(len, padding),
+            "prefix + tail must equal encoded length"
+        );
+    }
+
+    // Decoder model, mirroring `decode_slice_neon`.
+    use super::super::{
+        DEC_BLOCK_IN, DEC_BLOCK_IN as DEC_LOAD, DEC_BLOCK_OUT, DEC_LEAD, DEC_UNROLL,
+    };
+
+    /// Bytes `pack_and_store_neon` touches: one full vector, 4 wider than the 12
+    /// it advances.
+    const DEC_STORE_SPAN: usize = 16;
+    const DEC_QUAD_IN: usize = DEC_BLOCK_IN * DEC_UNROLL; // input bytes per quad-tier iteration
+    const DEC_QUAD_OUT: usize = DEC_BLOCK_OUT * DEC_UNROLL; // dst advance per quad-tier iteration
+
+    fn dec_cap(len: usize) -> usize {
+        TURBO_STANDARD.estimate_decoded_len(len)
+    }
+
+    /// The `aligned_len_quad` / `aligned_len_single` loop windows.
+    fn dec_windows(len: usize) -> (usize, usize) {
+        let safe = len.saturating_sub(DEC_LEAD);
+        (safe - safe % DEC_QUAD_IN, safe - safe % DEC_BLOCK_IN)
+    }
+
+    /// Inductive step for the decoder's quad tier, over an arbitrary iteration.
+    #[kani::proof]
+    fn check_dec_quad_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (aligned_quad, _) = dec_windows(len);
+        let cap = dec_cap(len);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / DEC_QUAD_IN);
+        let (src_off, dst_off) = (DEC_QUAD_IN * i, DEC_QUAD_OUT * i);
+        kani::assume(src_off < aligned_quad); // guard `src < src_end_quad`
+
+        let last_src = DEC_BLOCK_IN * (DEC_UNROLL - 1);
+        let last_dst = DEC_BLOCK_OUT * (DEC_UNROLL - 1);
+        assert!(
+            src_off + last_src + DEC_LOAD <= len,
+            "quad load leaves input"
+        );
+        assert!(
+            dst_off + last_dst + DEC_STORE_SPAN <= cap,
+            "quad store leaves output"
+        );
+
+        assert_eq!(
+            (src_off + DEC_QUAD_IN, dst_off + DEC_QUAD_OUT),
+            (DEC_QUAD_IN * (i + 1), DEC_QUAD_OUT * (i + 1))
+        );
+    }
+
+    /// Inductive step for the decoder's single-vector tier, entered from
+    /// wherever the quad tier stopped.
+    #[kani::proof]
+    fn check_dec_single_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (aligned_quad, aligned_block) = dec_windows(len);
+        let cap = dec_cap(len);
+        let quads = aligned_quad / DEC_QUAD_IN;
+
+        let j: usize = kani::any();
+        kani::assume(j <= MAX_LEN / DEC_BLOCK_IN);
+        let src_off = aligned_quad + DEC_BLOCK_IN * j;
+        let dst_off = DEC_QUAD_OUT * quads + DEC_BLOCK_OUT * j;
+        kani::assume(src_off < aligned_block); // guard `src < src_end_single`
+
+        assert!(src_off + DEC_LOAD <= len, "single load leaves input");
+        assert!(
+            dst_off + DEC_STORE_SPAN <= cap,
+            "single store leaves output"
+        );
+
+        assert_eq!(
+            (src_off + DEC_BLOCK_IN, dst_off + DEC_BLOCK_OUT),
+            (
+                aligned_quad + DEC_BLOCK_IN * (j + 1),
+                DEC_QUAD_OUT * quads + DEC_BLOCK_OUT * (j + 1)
+            )
+        );
+    }
+
+    /// Exit case: whatever the loops leave fits the space the caller
+    /// guaranteed, so the scalar decoder cannot overrun it, and it always
+    /// includes the final group.
+    #[kani::proof]
+    fn check_dec_tail_handoff() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (_, aligned_block) = dec_windows(len);
+        let cap = dec_cap(len);
+
+        let dst_off = DEC_BLOCK_OUT * (aligned_block / DEC_BLOCK_IN);
+        assert!(aligned_block <= len);
+        let tail = len - aligned_block;
+        assert!(
+            tail >= DEC_LEAD.min(len),
+            "final group reached the vector tiers"
+        );
+        assert!(
+            dst_off + dec_cap(tail) <= cap,
+            "scalar tail can overrun output"
+        );
+    }
+
+    // Layer 2 — kernel proofs: run the real code over symbolic bytes. Layer 1
+    // owns the loop arithmetic, so each reaches its kernel once. The quad tiers
+    // run the same `encode_vec_neon` / `decode_vec_neon` bodies four times over, and
+    // are out of CBMC's reach at 52+ symbolic bytes. Buffers are the exact
+    // public-API capacities, so any real overrun fails.
+
+    /// Two rounds and a 5-byte scalar tail, so a round after the first runs
+    /// with the per-round stride Layer 1 asserts.
+    const ENC_KERNEL_LEN: usize = 29;
+    /// One single-vector pass + a 4-character scalar tail.
+    const DEC_KERNEL_LEN: usize = 20;
+
+    // Guard: a length below its tier's threshold would prove nothing but the
+    // scalar fallback. Fail the build instead.
+    const _: () = assert!(
+        ENC_KERNEL_LEN >= ENC_LOAD
+            && (ENC_KERNEL_LEN - ENC_READ_AHEAD) / ENC_ROUND_IN >= 2
+            && ENC_KERNEL_LEN % ENC_ROUND_IN != 0,
+        "ENC_KERNEL_LEN must run two rounds and leave an unaligned tail"
+    );
+    const _: () = assert!(
+        (DEC_KERNEL_LEN - DEC_LEAD) / DEC_BLOCK_IN == 1,
+        "DEC_KERNEL_LEN must run one single-vector decode pass"
+    );
+    // A length that is not a multiple of 4 can only ever decode to `Err` under a
+    // padded config, which would make the `Ok` half of the proof below vacuous.
+    const _: () = assert!(
+        DEC_KERNEL_LEN % 4 == 0,
+        "DEC_KERNEL_LEN must be able to decode successfully"
+    );
+
+    const ENC_KERNEL_CAP: usize = TURBO_STANDARD.encoded_len(ENC_KERNEL_LEN);
+    const ENC_KERNEL_DEC_CAP: usize = TURBO_STANDARD.estimate_decoded_len(ENC_KERNEL_CAP);
+    const DEC_KERNEL_CAP: usize = TURBO_STANDARD.estimate_decoded_len(DEC_KERNEL_LEN);
+
+    /// `Decode(Encode(x)) == x` over every 29-byte input.
+    fn roundtrip_kernel(url_safe: bool) {
+        let config = Config {
+            url_safe,
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; ENC_KERNEL_LEN] = kani::any();
+
+        let mut enc_buf = [0u8; ENC_KERNEL_CAP];
+        let mut dec_buf = [0u8; ENC_KERNEL_DEC_CAP];
+
+        unsafe {
+            encode_slice_neon(&config, &input, &mut enc_buf);
+            let dec_len = decode_slice_neon(&config, &enc_buf, &mut dec_buf)
+                .expect("valid encoding failed to decode");
+            assert_eq!(dec_len, ENC_KERNEL_LEN);
+            assert_eq!(&dec_buf[..dec_len], &input, "roundtrip mismatch");
+        }
+    }
+
+    #[kani::proof]
+    #[kani::stub(vqtbl1q_u8, m::vqtbl1q_u8_stub)]
+    #[kani::stub(vqtbl1q_s8, m::vqtbl1q_s8_stub)]
+    #[kani::stub(vqsubq_u8, m::vqsubq_u8_stub)]
+    #[kani::stub(vmaxvq_u8, m::vmaxvq_u8_stub)]
+    #[kani::stub(vmulhq_u16, m::vmulhq_u16_stub)]
+    #[kani::stub(vmaddubs_s16, m::vmaddubs_s16_stub)]
+    #[kani::stub(vmadd_s32, m::vmadd_s32_stub)]
+    fn check_neon_roundtrip_standard() {
+        roundtrip_kernel(false);
+    }
+
+    #[kani::proof]
+    #[kani::stub(vqtbl1q_u8, m::vqtbl1q_u8_stub)]
+    #[kani::stub(vqtbl1q_s8, m::vqtbl1q_s8_stub)]
+    #[kani::stub(vqsubq_u8, m::vqsubq_u8_stub)]
+    #[kani::stub(vmaxvq_u8, m::vmaxvq_u8_stub)]
+    #[kani::stub(vmulhq_u16, m::vmulhq_u16_stub)]
+    #[kani::stub(vmaddubs_s16, m::vmaddubs_s16_stub)]
+    #[kani::stub(vmadd_s32, m::vmadd_s32_stub)]
+    fn check_neon_roundtrip_url_safe() {
+        roundtrip_kernel(true);
+    }
+
+    /// The vectorized decoder agrees with the scalar one on every 20-character
+    /// input, acceptance and rejection alike. Error kinds are not compared, for
+    /// the reason given on `check_avx2_decode_matches_scalar`.
+    #[kani::proof]
+    #[kani::stub(vqtbl1q_u8, m::vqtbl1q_u8_stub)]
+    #[kani::stub(vqtbl1q_s8, m::vqtbl1q_s8_stub)]
+    #[kani::stub(vmaxvq_u8, m::vmaxvq_u8_stub)]
+    #[kani::stub(vmaddubs_s16, m::vmaddubs_s16_stub)]
+    #[kani::stub(vmadd_s32, m::vmadd_s32_stub)]
+    fn check_neon_decode_matches_scalar() {
+        let config = Config {
+            url_safe: kani::any(),
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; DEC_KERNEL_LEN] = kani::any();
+
+        let mut simd_out = [0u8; DEC_KERNEL_CAP];
+        let mut scalar_out = [0u8; DEC_KERNEL_CAP];
+
+        let simd = unsafe { decode_slice_neon(&config, &input, &mut simd_out) };
+        let scalar = crate::scalar::decode_slice(&config, &input, &mut scalar_out);
+
+        match scalar {
+            Ok(n) => {
+                assert_eq!(simd, Ok(n), "scalar accepted an input the kernel rejected");
+                assert_eq!(
+                    &simd_out[..n],
+                    &scalar_out[..n],
+                    "kernel and scalar decoded to different bytes"
+                );
+            }
+            Err(_) => assert!(simd.is_err(), "kernel accepted an input scalar rejected"),
+        }
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_hex_neon {
+    // Index proofs for the hex kernels in `super::hex`, in the same
+    // step/exit shape as the Base64 ones above. Hex strides are fixed
+    // multiples of two with no read-ahead, so the model is just the loop
+    // counters: after `i` steps the encoder has consumed `IN * i` bytes and
+    // written twice that, and the decoder the reverse.
+
+    use super::super::hex::{HEX_DEC_IN, HEX_DEC_OUT, HEX_ENC_IN, HEX_ENC_OUT};
+
+    /// Largest `len` considered: above `usize::MAX / 2` the `encoded_len`'s
+    /// `len * 2` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 2;
+
+    const _: () = assert!(
+        HEX_ENC_OUT == 2 * HEX_ENC_IN && HEX_DEC_IN == 2 * HEX_DEC_OUT,
+        "a hex step must keep the 2:1 ratio"
+    );
+
+    /// Inductive step for the encoder: the load and the stores of step `i`
+    /// stay inside `input` and the `2 * len` bytes of `dst`.
+    #[kani::proof]
+    fn check_neon_hex_enc_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_ENC_IN);
+        let (done, written) = (HEX_ENC_IN * i, HEX_ENC_OUT * i);
+        kani::assume(done + HEX_ENC_IN <= len); // guard `done + IN <= len`
+
+        assert!(done + HEX_ENC_IN <= len, "encode load leaves input");
+        assert!(
+            written + HEX_ENC_OUT <= 2 * len,
+            "encode store leaves output"
+        );
+        assert_eq!(written, 2 * done);
+    }
+
+    /// Exit case: the scalar tail's `2 * (len - done)` digits end exactly at
+    /// the end of `dst`.
+    #[kani::proof]
+    fn check_neon_hex_enc_tail_handoff() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_ENC_IN);
+        let (done, written) = (HEX_ENC_IN * i, HEX_ENC_OUT * i);
+        kani::assume(done <= len && len < done + HEX_ENC_IN); // loop exit
+
+        assert!(written <= 2 * len, "tail slice starts past dst");
+        assert_eq!(written + 2 * (len - done), 2 * len);
+    }
+
+    /// Inductive step for the decoder: the loads and the store of step `i`
+    /// stay inside `input` and the `len / 2` bytes of `dst`, odd `len`
+    /// included.
+    #[kani::proof]
+    fn check_neon_hex_dec_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_DEC_IN);
+        let (done, written) = (HEX_DEC_IN * i, HEX_DEC_OUT * i);
+        kani::assume(done + HEX_DEC_IN <= len); // guard `done + IN <= len`
+
+        assert!(
+            written + HEX_DEC_OUT <= len / 2,
+            "decode store leaves output"
+        );
+        assert_eq!(2 * written, done);
+    }
+
+    /// Exit case: the scalar tail's `dst[written..]` is in range and holds
+    /// the `(len - done) / 2` bytes it writes.
+    #[kani::proof]
+    fn check_neon_hex_dec_tail_handoff() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / HEX_DEC_IN);
+        let (done, written) = (HEX_DEC_IN * i, HEX_DEC_OUT * i);
+        kani::assume(done <= len && len < done + HEX_DEC_IN); // loop exit
+
+        assert!(written <= len / 2, "tail slice starts past dst");
+        assert_eq!(written + (len - done) / 2, len / 2);
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_garbage_neon {
+    // Proofs for the compaction kernel in `super::garbage`. Its stores start at
+    // `kept`, which never passes `done`, so they are bounded by the loads; the
+    // `tbl` control is built in safe code, and proven to gather exactly the
+    // kept lanes, in order.
+
+    use super::super::garbage::{COMPACT_STEP, compact_control};
+
+    /// Lanes a half's store writes: a D register.
+    const HALF: usize = 8;
+
+    /// Inductive step: a full load, and half stores of 8 bytes that each start
+    /// at most at their half's first lane, so end inside the step, so inside
+    /// `dst`.
+    #[kani::proof]
+    fn check_neon_compact_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        let kept: usize = kani::any();
+        kani::assume(i <= usize::MAX / COMPACT_STEP);
+        let done = COMPACT_STEP * i;
+        kani::assume(done <= usize::MAX - COMPACT_STEP && done + COMPACT_STEP <= len);
+        kani::assume(kept <= done); // one byte kept per byte read, at most
+        let first: usize = kani::any(); // kept by the first half
+        kani::assume(first <= HALF);
+
+        assert!(done + COMPACT_STEP <= len, "load leaves the input");
+        assert!(kept + COMPACT_STEP <= len, "whole store leaves the output");
+        assert!(
+            kept + first + HALF <= done + COMPACT_STEP,
+            "second store leaves the step"
+        );
+    }
+
+    /// Exit case: the scalar tail's `dst[kept..]` is in range and at least as
+    /// long as the `len - done` bytes it compacts.
+    #[kani::proof]
+    fn check_neon_compact_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        let kept: usize = kani::any();
+        kani::assume(i <= usize::MAX / COMPACT_STEP);
+        let done = COMPACT_STEP * i;
+        kani::assume(done <= len && len - done < COMPACT_STEP); // loop exit
+        kani::assume(kept <= done);
+
+        assert!(kept <= len, "tail slice starts past dst");
+        assert!(len - kept >= len - done, "tail slice too short");
+    }
+
+    /// For every pair of mask bytes, each half's control lanes are its set
+    /// bits' positions in the vector, in order: the half stores gather exactly
+    /// the kept bytes.
+    #[kani::proof]
+    #[kani::unwind(17)]
+    fn check_neon_compact_control() {
+        let lo: u8 = kani::any();
+        let hi: u8 = kani::any();
+        let keep = u16::from_le_bytes([lo, hi]);
+        let control = compact_control(lo, hi);
+        for (half, lanes) in control.iter().enumerate() {
+            let lanes = lanes.to_le_bytes();
+            let mut j = 0;
+            for lane in HALF * half..HALF * (half + 1) {
+                if (keep >> lane) & 1 == 1 {
+                    assert_eq!(usize::from(lanes[j]), lane, "control lane {j}");
+                    j += 1;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_transcode_neon {
+    // Index proofs for the kernels in `super::transcode`. The remap reads and
+    // writes the same offsets, so one bound covers both; the hex ones stride
+    // two lengths at once.
+
+    use super::super::transcode::{
+        B64_HEX_IN, B64_HEX_OUT, HEX_B64_IN, HEX_B64_OUT, HEX_VEC, REMAP_STEP,
+    };
+
+    /// Bytes in a half-vector (`d`) register, and so in a half load or store.
+    const HALF_LANES: usize = 8;
+
+    /// Inductive step: a full 16-byte load and store, both in bounds.
+    #[kani::proof]
+    fn check_neon_remap_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(i <= usize::MAX / REMAP_STEP);
+        let done = REMAP_STEP * i;
+        kani::assume(done <= usize::MAX - REMAP_STEP && done + REMAP_STEP <= len);
+
+        assert!(done + REMAP_STEP <= len, "remap step leaves the buffer");
+    }
+
+    /// Exit case: the scalar tail's `src[done..]` and `dst[done..]` are in
+    /// range and cover the rest, less than a step.
+    #[kani::proof]
+    fn check_neon_remap_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(i <= usize::MAX / REMAP_STEP);
+        let done = REMAP_STEP * i;
+        kani::assume(done <= len && len - done < REMAP_STEP); // loop exit
+
+        assert!(done <= len, "tail slice starts past the buffer");
+        assert!(len - done < REMAP_STEP);
+    }
+
+    /// Base64 -> hex step: a full load, and a full and a half store that end
+    /// exactly at the step's 24 digits.
+    #[kani::proof]
+    fn check_neon_b64_hex_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 4 == 0 && len <= usize::MAX / 6);
+        kani::assume(i <= len / B64_HEX_IN);
+        let done = B64_HEX_IN * i;
+        kani::assume(done + B64_HEX_IN <= len);
+        let written = B64_HEX_OUT * i;
+
+        assert!(done + B64_HEX_IN <= len, "load leaves the input");
+        assert_eq!(
+            HEX_VEC + HALF_LANES,
+            B64_HEX_OUT,
+            "stores miss the step's end"
+        );
+        assert!(
+            written + B64_HEX_OUT <= len / 4 * 6,
+            "store leaves the output"
+        );
+    }
+
+    /// Base64 -> hex exit: the scalar tail's `dst[written..]` starts in range
+    /// and holds exactly the digits of the groups left.
+    #[kani::proof]
+    fn check_neon_b64_hex_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 4 == 0 && len <= usize::MAX / 6);
+        kani::assume(i <= len / B64_HEX_IN);
+        let done = B64_HEX_IN * i;
+        kani::assume(done <= len && len - done < B64_HEX_IN); // loop exit
+        let written = B64_HEX_OUT * i;
+
+        assert!(written <= len / 4 * 6, "tail slice starts past dst");
+        assert_eq!(written + (len - done) / 4 * 6, len / 4 * 6);
+    }
+
+    /// hex -> Base64 step: a full load plus a half one, both in bounds, and a
+    /// full store.
+    #[kani::proof]
+    fn check_neon_hex_b64_step() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 6 == 0);
+        kani::assume(i <= len / HEX_B64_IN);
+        let done = HEX_B64_IN * i;
+        kani::assume(done + HEX_B64_IN <= len);
+        let written = HEX_B64_OUT * i;
+
+        assert!(done + HEX_VEC + HALF_LANES <= len, "load leaves the input");
+        assert!(
+            written + HEX_B64_OUT <= len / 6 * 4,
+            "store leaves the output"
+        );
+    }
+
+    /// hex -> Base64 exit: the scalar tail's `dst[written..]` starts in range
+    /// and holds exactly the characters of the groups left.
+    #[kani::proof]
+    fn check_neon_hex_b64_tail_handoff() {
+        let len: usize = kani::any();
+        let i: usize = kani::any();
+        kani::assume(len % 6 == 0);
+        kani::assume(i <= len / HEX_B64_IN);
+        let done = HEX_B64_IN * i;
+        kani::assume(done <= len && len - done < HEX_B64_IN); // loop exit
+        let written = HEX_B64_OUT * i;
+
+        assert!(written <= len / 6 * 4, "tail slice starts past dst");
+        assert_eq!(written + (len - done) / 6 * 4, len / 6 * 4);
+    }
+}
+
+/// Rust models of the NEON intrinsics the Kani proofs stub out, each a
+/// line-for-line transcription of the instruction's "Operation" pseudocode in
+/// the Arm Architecture Reference Manual (DDI 0487, A64 `TBL`, `UQSUB`,
+/// `UMAXV`, `UMULL`, `SMULL`, `SHRN` and `ADDP`). Arm's pseudocode is written
+/// per element (`Elem[v, e, esize]`) rather than per bit offset, so the element
+/// index is used directly; otherwise the conventions are those spelled out on
+/// `avx2::verify::intrinsic_models`, lines carrying no Arm text marked `NOTE:`.
+///
+/// The three kernel helpers (`vmulhq_u16`, `vmaddubs_s16`, `vmadd_s32`) are
+/// modelled as a whole: each is the concatenation of the pseudocode of the
+/// instructions it is built from, with the intermediate register named as in
+/// the helper.
+#[cfg(any(kani, test))]
+// Consumers reach these through `#[kani::stub(...)]` attribute arguments or
+// the hardware equivalence test only, so which look "used" depends on the
+// harness being compiled.
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+// The transcription is more literal than idiomatic Rust would be, on purpose.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::missing_const_for_fn,
+    clippy::needless_range_loop
+)]
+pub(super) mod intrinsic_models {
+    use super::*;
+    use core::mem::transmute;
+
+    // NOTE: scaffolding, not from Arm. The `SatQ` helper the pseudocode calls
+    // by name, for the unsigned 8-bit case.
+    fn UnsignedSatQ8(x: i16) -> u8 {
+        x.clamp(0, 255) as u8
+    }
+
+    // STUB: vqtbl1q_u8 (TBL, single register)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vqtbl1q_u8
+    pub(super) unsafe fn vqtbl1q_u8_stub(t: uint8x16_t, idx: uint8x16_t) -> uint8x16_t {
+        let table: [u8; 16] = unsafe { transmute(t) };
+        let indices: [u8; 16] = unsafe { transmute(idx) };
+        // NOTE: `regs` is 1, so the table is the one source register.
+        let regs = 1;
+
+        // result = if is_tbl then Zeros() else V[d];
+        let mut result = [0u8; 16];
+        // for i = 0 to elements - 1
+        for i in 0..16 {
+            // index = UInt(Elem[indices, i, 8]);
+            let index = usize::from(indices[i]);
+            // if index < 16 * regs then
+            if index < 16 * regs {
+                // Elem[result, i, 8] = Elem[table, index, 8];
+                result[i] = table[index];
+            }
+        }
+
+        unsafe { transmute(result) }
+    }
+
+    // STUB: vqtbl1q_s8 (TBL, single register)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vqtbl1q_s8
+    pub(super) unsafe fn vqtbl1q_s8_stub(t: int8x16_t, idx: uint8x16_t) -> int8x16_t {
+        // NOTE: `TBL` moves bytes without interpreting them; the signed form
+        // differs from `vqtbl1q_u8` in its Rust types only.
+        let t: uint8x16_t = unsafe { transmute(t) };
+        unsafe { transmute(vqtbl1q_u8_stub(t, idx)) }
+    }
+
+    // STUB: vqsubq_u8 (UQSUB)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vqsubq_u8
+    pub(super) unsafe fn vqsubq_u8_stub(a: uint8x16_t, b: uint8x16_t) -> uint8x16_t {
+        let operand1: [u8; 16] = unsafe { transmute(a) };
+        let operand2: [u8; 16] = unsafe { transmute(b) };
+        let mut result = [0u8; 16];
+
+        // for e = 0 to elements-1
+        for e in 0..16 {
+            // element1 = Int(Elem[operand1, e, esize], unsigned);
+            let element1 = i16::from(operand1[e]);
+            // element2 = Int(Elem[operand2, e, esize], unsigned);
+            let element2 = i16::from(operand2[e]);
+            // diff = element1 - element2;
+            let diff = element1 - element2;
+            // (Elem[result, e, esize], sat) = SatQ(diff, esize, unsigned);
+            result[e] = UnsignedSatQ8(diff);
+        }
+
+        unsafe { transmute(result) }
+    }
+
+    // STUB: vmaxvq_u8 (UMAXV)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vmaxvq_u8
+    pub(super) unsafe fn vmaxvq_u8_stub(a: uint8x16_t) -> u8 {
+        let operand: [u8; 16] = unsafe { transmute(a) };
+
+        // integer maxmin = Int(Elem[operand, 0, esize], unsigned);
+        let mut maxmin = operand[0];
+        // for e = 1 to elements-1
+        for e in 1..16 {
+            // element = Int(Elem[operand, e, esize], unsigned);
+            let element = operand[e];
+            // maxmin = if min then Min(maxmin, element) else Max(maxmin, element);
+            maxmin = maxmin.max(element);
+        }
+        // V[d] = maxmin<esize-1:0>;
+        maxmin
+    }
+
+    // STUB: vmulhq_u16 (kernel helper: UMULL, UMULL2, SHRN #16, SHRN2 #16)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vmull_u16
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vshrn_n_u32
+    pub(super) unsafe fn vmulhq_u16_stub(a: uint16x8_t, b: uint16x8_t) -> uint16x8_t {
+        let operand1: [u16; 8] = unsafe { transmute(a) };
+        let operand2: [u16; 8] = unsafe { transmute(b) };
+        // NOTE: `UMULL` covers lanes 0..4 and `UMULL2` lanes 4..8; together
+        // they widen every lane, so one loop stands for both.
+        let mut product = [0u32; 8];
+        let mut result = [0u16; 8];
+
+        // UMULL{2}: for e = 0 to elements-1
+        for e in 0..8 {
+            // element1 = Int(Elem[operand1, e, esize], unsigned);
+            let element1 = u32::from(operand1[e]);
+            // element2 = Int(Elem[operand2, e, esize], unsigned);
+            let element2 = u32::from(operand2[e]);
+            // Elem[result, e, 2*esize] = (element1 * element2)<2*esize-1:0>;
+            product[e] = element1 * element2;
+        }
+        // SHRN{2} #16: for e = 0 to elements-1
+        for e in 0..8 {
+            // element = (UInt(Elem[operand, e, 2*esize]) + round_const) >> shift;
+            // NOTE: `round_const` is 0 for the non-rounding `SHRN`.
+            let element = product[e] >> 16;
+            // Elem[result, e, esize] = element<esize-1:0>;
+            result[e] = element as u16;
+        }
+
+        unsafe { transmute(result) }
+    }
+
+    // STUB: vmaddubs_s16 (kernel helper: SMULL, SMULL2, ADDP)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vmull_s8
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vpaddq_s16
+    pub(super) unsafe fn vmaddubs_s16_stub(a: uint8x16_t, b: int8x16_t) -> int16x8_t {
+        // NOTE: the helper reinterprets `a` as signed before `SMULL`, so its
+        // bytes are read as `i8` here too.
+        let operand1: [i8; 16] = unsafe { transmute(a) };
+        let operand2: [i8; 16] = unsafe { transmute(b) };
+        let mut product = [0i16; 16];
+        let mut result = [0i16; 8];
+
+        // SMULL{2}: for e = 0 to elements-1
+        for e in 0..16 {
+            // element1 = Int(Elem[operand1, e, esize], unsigned);
+            let element1 = i16::from(operand1[e]);
+            // element2 = Int(Elem[operand2, e, esize], unsigned);
+            let element2 = i16::from(operand2[e]);
+            // Elem[result, e, 2*esize] = (element1 * element2)<2*esize-1:0>;
+            product[e] = element1 * element2;
+        }
+        // ADDP: concat = operand2:operand1;
+        // NOTE: `product` already holds `SMULL`'s result in its low half and
+        // `SMULL2`'s in its high half, which is that concatenation.
+        // for e = 0 to elements-1
+        for e in 0..8 {
+            // element1 = Elem[concat, 2*e, esize];
+            let element1 = product[2 * e];
+            // element2 = Elem[concat, (2*e)+1, esize];
+            let element2 = product[2 * e + 1];
+            // Elem[result, e, esize] = element1 + element2;
+            // NOTE: `wrapping`, as the sum is truncated to `esize` bits.
+            result[e] = element1.wrapping_add(element2);
+        }
+
+        unsafe { transmute(result) }
+    }
+
+    // STUB: vmadd_s32 (kernel helper: SMULL, SMULL2, ADDP)
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vmull_s16
+    // REFERENCE: https://developer.arm.com/architectures/instruction-sets/intrinsics/vpaddq_s32
+    pub(super) unsafe fn vmadd_s32_stub(a: int16x8_t, b: int16x8_t) -> int32x4_t {
+        let operand1: [i16; 8] = unsafe { transmute(a) };
+        let operand2: [i16; 8] = unsafe { transmute(b) };
+        let mut product = [0i32; 8];
+        let mut result = [0i32; 4];
+
+        // SMULL{2}: for e = 0 to elements-1
+        for e in 0..8 {
+            // element1 = Int(Elem[operand1, e, esize], unsigned);
+            let element1 = i32::from(operand1[e]);
+            // element2 = Int(Elem[operand2, e, esize], unsigned);
+            let element2 = i32::from(operand2[e]);
+            // Elem[result, e, 2*esize] = (element1 * element2)<2*esize-1:0>;
+            product[e] = element1 * element2;
+        }
+        // ADDP: concat = operand2:operand1;
+        // for e = 0 to elements-1
+        for e in 0..4 {
+            // element1 = Elem[concat, 2*e, esize];
+            let element1 = product[2 * e];
+            // element2 = Elem[concat, (2*e)+1, esize];
+            let element2 = product[2 * e + 1];
+            // Elem[result, e, esize] = element1 + element2;
+            // NOTE: `wrapping`: two `i16::MIN` squares overflow 32 bits.
+            result[e] = element1.wrapping_add(element2);
+        }
+
+        unsafe { transmute(result) }
+    }
+}
+
+/// Checks every model in [`intrinsic_models`] against the real instruction,
+/// under plain `cargo test`. NEON is part of the aarch64 baseline, so unlike
+/// the x86 equivalence suites this one never skips.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_stub_equivalence {
+    use super::intrinsic_models as model;
+    use super::*;
+    use core::mem::transmute_copy;
+
+    /// Saturation and sign boundaries, out-of-range table indices, index-shaped
+    /// bytes, and deterministic noise.
+    fn probes() -> Vec<[u8; 16]> {
+        let byte = |i: usize| u8::try_from(i).expect("index below the 16-byte vector width");
+
+        let mut out = vec![[0x00; 16], [0xFF; 16], [0x80; 16], [0x7F; 16], [0x01; 16]];
+        out.push(core::array::from_fn(byte));
+        out.push(core::array::from_fn(|i| byte(i) | 0x10));
+        out.push(core::array::from_fn(|i| 0xFF - byte(i)));
+
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..12 {
+            out.push(core::array::from_fn(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                u8::try_from(state >> 56).expect("shifted down to 8 bits")
+            }));
+        }
+        out
+    }
+
+    /// Reinterprets 16 bytes as any of the 128-bit vector types.
+    fn vec<T>(x: &[u8; 16]) -> T {
+        const { assert!(size_of::<T>() == 16) };
+        // SAFETY: the NEON vector types have no invalid bit patterns, and the
+        // assertion above pins the size.
+        unsafe { transmute_copy(x) }
+    }
+
+    fn bytes<T>(v: &T) -> [u8; 16] {
+        const { assert!(size_of::<T>() == 16) };
+        // SAFETY: as for `vec`, in the other direction.
+        unsafe { transmute_copy(v) }
+    }
+
+    #[target_feature(enable = "neon")]
+    #[allow(unused_unsafe)] // the intrinsics are safe to call here, the helpers are not
+    unsafe fn compare_all() {
+        let probes = probes();
+
+        // Each arm: `real(a, b)` must equal `model(a, b)` for every probe pair.
+        macro_rules! same {
+            ($real:ident, $model:ident) => {
+                for x in &probes {
+                    for y in &probes {
+                        assert_eq!(
+                            bytes(&unsafe { $real(vec(x), vec(y)) }),
+                            bytes(&unsafe { model::$model(vec(x), vec(y)) }),
+                            "{}: a={x:02x?} b={y:02x?}",
+                            stringify!($real)
+                        );
+                    }
+                }
+            };
+        }
+
+        same!(vqtbl1q_u8, vqtbl1q_u8_stub);
+        same!(vqtbl1q_s8, vqtbl1q_s8_stub);
+        same!(vqsubq_u8, vqsubq_u8_stub);
+        same!(vmulhq_u16, vmulhq_u16_stub);
+        same!(vmaddubs_s16, vmaddubs_s16_stub);
+        same!(vmadd_s32, vmadd_s32_stub);
+        for x in &probes {
+            assert_eq!(
+                vmaxvq_u8(vec(x)),
+                unsafe { model::vmaxvq_u8_stub(vec(x)) },
+                "vmaxvq_u8: a={x:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn neon_models_match_hardware() {
+        unsafe { compare_all() };
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_neon_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode};
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE};
+
+    fn enc(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_encode(config, oracle, encode_slice_neon, len);
+    }
+    fn dec(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_decode(config, oracle, decode_slice_neon, len);
+    }
+
+    const STD: Config = Config {
+        url_safe: false,
+        padding: true,
+        lenient: false,
+    };
+
+    // Encoder tiers: single-vector is 12 bytes, quad is 48.
+    #[test]
+    fn miri_neon_encode_scalar_fallback() {
+        enc(&STD, &STANDARD, 1); // < 12 -> pure scalar
+        enc(&STD, &STANDARD, 11);
+    }
+
+    #[test]
+    fn miri_neon_encode_single_vector_loop() {
+        enc(&STD, &STANDARD, 12); // 1 loop
+        enc(&STD, &STANDARD, 24); // 2 loops
+        enc(&STD, &STANDARD, 13); // 1 loop + scalar
+    }
+
+    #[test]
+    fn miri_neon_encode_quad_vector_loop() {
+        enc(&STD, &STANDARD, 48); // 1 quad
+        enc(&STD, &STANDARD, 96); // 2 quads
+        enc(&STD, &STANDARD, 49); // 1 quad + scalar
+        enc(&STD, &STANDARD, 60); // 1 quad + 1 single
+    }
+
+    #[test]
+    fn miri_neon_encode_url_safe() {
+        enc(
+            &Config {
+                url_safe: true,
+                padding: true,
+                lenient: false,
+            },
+            &URL_SAFE,
+            50,
+        );
+    }
+
+    // Short-input kernels: one and two rounds/passes, exact buffers.
+    #[test]
+    fn miri_neon_short_inputs() {
+        for len in [1, 2, 11, 12, 13, 24] {
+            enc(&STD, &STANDARD, len);
+            check_encode(&STD, &STANDARD, encode_short_neon, len);
+            check_decode_exact(&STD, &STANDARD, decode_short_neon, len);
+        }
+    }
+
+    // Decoder tiers: single-vector is 16 bytes, quad is 64.
+    #[test]
+    fn miri_neon_decode_scalar_fallback() {
+        dec(&STD, &STANDARD, 3); // 4 chars
+        dec(&STD, &STANDARD, 9); // 12 chars, < 16
+    }
+
+    #[test]
+    fn miri_neon_decode_single_vector_loop() {
+        dec(&STD, &STANDARD, 12); // 1 loop
+        dec(&STD, &STANDARD, 24); // 2 loops
+        dec(&STD, &STANDARD, 13); // 1 loop + scalar
+    }
+
+    #[test]
+    fn miri_neon_decode_quad_vector_loop() {
+        dec(&STD, &STANDARD, 48); // 1 quad
+        dec(&STD, &STANDARD, 96); // 2 quads
+        dec(&STD, &STANDARD, 49); // 1 quad + remainder
+    }
+
+    #[test]
+    fn miri_neon_decode_url_safe() {
+        let config = Config {
+            url_safe: true,
+            padding: false,
+            lenient: false,
+        };
+        let input = b"-_-_-_-_-_-_-_-_"; // 16 bytes
+        let mut dst = [0u8; 16];
+        unsafe {
+            decode_slice_neon(&config, input, &mut dst).unwrap();
+        }
+    }
+
+    /// An invalid byte must be caught in every tier and the scalar tail.
+    #[test]
+    fn miri_neon_decode_error_detection() {
+        let mut dst = [0u8; 128];
+        for &(len, bad_at, where_) in &[
+            (64, 63, "quad tier, last lane"),
+            (16, 15, "single tier"),
+            (64, 0, "quad tier, first byte"),
+            (17, 16, "scalar tail"),
+        ] {
+            let mut input = vec![b'A'; len];
+            input[bad_at] = b'$';
+            let res = unsafe { decode_slice_neon(&STD, &input, &mut dst) };
+            assert!(res.is_err(), "missed invalid byte in {where_}");
+        }
+    }
+
+    #[test]
+    fn miri_neon_roundtrip_standard() {
+        for &len in &[12, 24, 48, 49, 60, 96] {
+            enc(&STD, &STANDARD, len);
+            dec(&STD, &STANDARD, len);
+        }
+    }
+
+    #[test]
+    fn miri_neon_encode_no_padding() {
+        let config = Config {
+            url_safe: false,
+            padding: false,
+            lenient: false,
+        };
+        for &len in &[1, 12, 13, 24, 48, 49] {
+            enc(&config, &STANDARD_NO_PAD, len);
+        }
+    }
+
+    #[test]
+    fn miri_neon_decode_no_padding() {
+        let config = Config {
+            url_safe: false,
+            padding: false,
+            lenient: false,
+        };
+        for &len in &[3, 12, 13, 24, 48, 49] {
+            dec(&config, &STANDARD_NO_PAD, len);
+        }
+    }
+
+    #[test]
+    fn miri_neon_decode_url_safe_padded() {
+        dec(
+            &Config {
+                url_safe: true,
+                padding: true,
+                lenient: false,
+            },
+            &URL_SAFE,
+            50,
+        );
+    }
+}
+
+/// Exhaustive regression test for the decode constants in
+/// [`decode_constants_neon`]: every byte value through the vector pass.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_decode_lut_exhaustive {
+    use super::*;
+
+    /// For every byte value, decode a 20-byte input of that byte (16 through
+    /// the NEON fast path, 4 valid filler bytes into the scalar tail) and check
+    /// `decode_slice_neon` agrees with the scalar decoder on validity and value.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn check_all_byte_values(config: &Config) {
+        for candidate in 0u8..=255 {
+            let mut input = [candidate; 20];
+            input[16..].copy_from_slice(b"AAAA");
+
+            let mut neon_out = [0u8; 32];
+            let neon_result = unsafe { decode_slice_neon(config, &input, &mut neon_out) };
+
+            // Oracle: the first 16 bytes via the (separately tested) scalar path.
+            let mut scalar_out = [0u8; 32];
+            let scalar_result = crate::scalar::decode_slice(config, &input[..16], &mut scalar_out);
+
+            match scalar_result {
+                Ok(scalar_len) => {
+                    assert_eq!(
+                        neon_result,
+                        Ok(scalar_len + 3),
+                        "byte {candidate:#04x} ({candidate}): scalar accepted it"
+                    );
+                    assert_eq!(
+                        &neon_out[..scalar_len],
+                        &scalar_out[..scalar_len],
+                        "byte {candidate:#04x} ({candidate}): decoded value mismatch"
+                    );
+                }
+                Err(scalar_err) => {
+                    assert_eq!(
+                        neon_result,
+                        Err(scalar_err),
+                        "byte {candidate:#04x} ({candidate}): neon/scalar disagree on validity"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neon_lut_standard_matches_scalar() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        check_all_byte_values(&config);
+    }
+
+    #[test]
+    fn neon_lut_url_safe_matches_scalar() {
+        let config = Config {
+            url_safe: true,
+            padding: true,
+            lenient: false,
+        };
+        check_all_byte_values(&config);
+    }
+
+    #[test]
+    fn neon_lut_lenient_matches_scalar() {
+        let config = Config {
+            url_safe: false,
+            padding: false,
+            lenient: true,
+        };
+        check_all_byte_values(&config);
+    }
+}
+
+/// The short-input kernels against the oracle at every length they take, and
+/// against scalar on every malformed input of those lengths.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_short_inputs {
+    use super::*;
+    use crate::simd::testutil::{check_decode_short, check_encode_short};
+
+    #[test]
+    fn neon_encode_short_all_lengths() {
+        check_encode_short(encode_short_neon, ENC_SHORT_MAX);
+    }
+
+    #[test]
+    fn neon_decode_short_all_lengths() {
+        check_decode_short(decode_short_neon, DEC_SHORT_MAX);
+    }
+}
+
+/// Length-boundary regression against the `base64` oracle at every length
+/// 0..=400, covering the read-ahead and lead margins and both tiers of each
+/// loop, plus a few large lengths.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_length_sweep {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_encode};
+    use base64::engine::general_purpose::{
+        STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_STANDARD_NO_PAD, URL_SAFE as REF_URL_SAFE,
+    };
+
+    #[test]
+    fn neon_all_lengths_0_to_400() {
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for len in 0..=400 {
+                check_encode(&config, oracle, encode_slice_neon, len);
+                check_decode(&config, oracle, decode_slice_neon, len);
+            }
+        }
+    }
+
+    #[test]
+    fn neon_large_lengths() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        for len in [1_000, 10_000, 100_003] {
+            check_encode(&config, &REF_STANDARD, encode_slice_neon, len);
+            check_decode(&config, &REF_STANDARD, decode_slice_neon, len);
+        }
+    }
+}
+
+/// The hex kernels against the `hex` crate at every length across the first
+/// few steps and some larger ones, and against scalar's verdict on a bad
+/// digit in every position: full steps only, scalar tail only, and both.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_hex {
+    use super::*;
+    use crate::simd::testutil::{check_hex, check_hex_rejects};
+
+    #[test]
+    fn neon_hex_all_lengths_0_to_200() {
+        for len in (0..=200).chain([1_000, 4_097, 100_003]) {
+            check_hex(encode_hex_neon, decode_hex_neon, len);
+        }
+    }
+
+    #[test]
+    fn neon_hex_decode_rejects_invalid() {
+        for len in [2, 30, 32, 34, 64, 94] {
+            check_hex_rejects(decode_hex_neon, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_neon_hex {
+    use super::*;
+    use crate::simd::testutil::{check_hex, check_hex_rejects};
+
+    /// Lengths around each step boundary, plus several steps with a tail.
+    #[test]
+    fn miri_neon_hex_tier_boundaries() {
+        for len in [0, 1, 15, 16, 17, 32, 50] {
+            check_hex(encode_hex_neon, decode_hex_neon, len);
+        }
+    }
+
+    #[test]
+    fn miri_neon_hex_decode_rejects_invalid() {
+        check_hex_rejects(decode_hex_neon, 34);
+    }
+}
+
+/// The compaction kernel against scalar at every length across the first few
+/// steps and some larger ones, at every density of garbage.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_garbage {
+    use super::*;
+    use crate::simd::testutil::check_compact;
+
+    #[test]
+    fn neon_compact_all_lengths_0_to_200() {
+        for len in (0..=200).chain([1_000, 4_096, 4_097]) {
+            check_compact(compact_neon, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_neon_garbage {
+    use super::*;
+    use crate::simd::testutil::check_compact;
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_neon_compact_tier_boundaries() {
+        for len in [0, 1, 15, 16, 17, 50] {
+            check_compact(compact_neon, len);
+        }
+    }
+}
+
+/// The transcoding kernels against scalar at every length across the first
+/// few steps and some larger ones: the remap between every pair of alphabets,
+/// copying and in place, and Base64 to and from hex under every config.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_transcode {
+    use super::*;
+    use crate::simd::testutil::{check_base64_to_hex, check_hex_to_base64, check_remap};
+
+    #[test]
+    fn neon_remap_all_lengths_0_to_100() {
+        for len in (0..=100).chain([144, 1_000]) {
+            check_remap(remap_neon, remap_in_place_neon, len);
+        }
+    }
+
+    #[test]
+    fn neon_base64_to_hex_all_groups_0_to_40() {
+        for groups in (0..=40).chain([64, 250]) {
+            check_base64_to_hex(base64_to_hex_neon, groups);
+        }
+    }
+
+    #[test]
+    fn neon_hex_to_base64_all_groups_0_to_40() {
+        for groups in (0..=40).chain([64, 250]) {
+            check_hex_to_base64(hex_to_base64_neon, groups);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_neon_transcode {
+    use super::*;
+    use crate::simd::testutil::{check_base64_to_hex, check_hex_to_base64, check_remap};
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_neon_remap_tier_boundaries() {
+        for len in [0, 1, 11, 12, 13, 30] {
+            check_remap(remap_neon, remap_in_place_neon, len);
+        }
+    }
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_neon_base64_to_hex_tier_boundaries() {
+        for groups in [0, 1, 3, 4, 5, 9] {
+            check_base64_to_hex(base64_to_hex_neon, groups);
+        }
+    }
+
+    /// The scalar tail alone, a full step, and steps plus a tail.
+    #[test]
+    fn miri_neon_hex_to_base64_tier_boundaries() {
+        for groups in [0, 1, 3, 4, 5, 9] {
+            check_hex_to_base64(hex_to_base64_neon, groups);
+        }
+    }
+}
+
+/// The lenient tables: both alphabets mixed, padded and not, at every length
+/// across the tiers, and against scalar's verdict on malformed input.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod neon_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient;
+
+    #[test]
+    fn neon_decode_lenient_all_lengths() {
+        for len in (0..=300).chain([1_000, 4_097]) {
+            check_decode_lenient(decode_slice_neon, len);
+        }
+    }
+
+    #[test]
+    fn neon_decode_short_lenient() {
+        for len in (0..).take_while(|len: &usize| len.div_ceil(3) * 4 <= DEC_SHORT_MAX) {
+            check_decode_lenient(decode_short_neon, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_neon_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient;
+
+    /// The scalar tail alone, a single block, and the quad tier, each with a
+    /// scalar tail.
+    #[test]
+    fn miri_neon_decode_lenient_tier_boundaries() {
+        for len in [0, 1, 2, 3, 15, 17, 51] {
+            check_decode_lenient(decode_slice_neon, len);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Portable `core::simd` kernel (feature `portable-simd`, nightly only).
+//!
+//! The same 12-in/16-out encode round and 16-in/12-out decode block as the
+//! SSSE3 and NEON kernels, written against `core::simd` rather than one ISA's
+//! intrinsics, so LLVM lowers it to whatever vector unit the target has (RVV,
+//! LSX, VSX, `simd128`), or to plain scalar code where there is none. Byte
+//! rearrangement uses compile-time `simd_swizzle!` indices, and the alphabet
+//! is mapped with compares and selects instead of a table lookup, since a
+//! dynamic byte shuffle is the one operation not every vector ISA has.
+//!
+//! Unlike every other kernel this one is safe code: loads and stores go
+//! through the slices, so nothing here needs the proofs the `unsafe` kernels
+//! carry.
+#![forbid(unsafe_code)]
+// Every kernel takes `&Config`, and the dispatch tables must match them.
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use crate::{Config, Error, scalar};
+use core::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use core::simd::num::SimdUint;
+use core::simd::{Mask, Select, Simd, simd_swizzle};
+
+type U8x16 = Simd<u8, 16>;
+type U16x16 = Simd<u16, 16>;
+
+/// Input bytes an encode round consumes.
+const ENC_ROUND_IN: usize = 12;
+/// Characters an encode round writes.
+const ENC_ROUND_OUT: usize = 16;
+/// Bytes each encode load reads: a full vector, of which only the first
+/// [`ENC_ROUND_IN`] are consumed.
+const ENC_VEC: usize = 16;
+
+/// Input characters a decode block consumes.
+const DEC_BLOCK_IN: usize = 16;
+/// Bytes a decode block writes.
+const DEC_BLOCK_OUT: usize = 12;
+/// Characters the decode loop stops short of the end, so the final group —
+/// the only one that may carry `'='` — is always left to the scalar tail.
+const DEC_LEAD: usize = 4;
+
+/// The two alphabet-specific characters, for index 62 and 63.
+#[derive(Clone, Copy)]
+struct Symbols {
+    c62: u8,
+    c63: u8,
+}
+
+impl Symbols {
+    const fn new(config: &Config) -> Self {
+        if config.url_safe {
+            Self {
+                c62: b'-',
+                c63: b'_',
+            }
+        } else {
+            Self {
+                c62: b'+',
+                c63: b'/',
+            }
+        }
+    }
+}
+
+// --- Encoder ---
+
+/// Encodes the first 12 bytes of `block` into 16 characters.
+#[inline]
+fn encode_round(block: U8x16, sym: Symbols) -> U8x16 {
+    // Each character's six bits straddle at most two input bytes. Pair them
+    // up big-endian in a 16-bit lane, then shift the character down to the
+    // bottom: for bytes `b0 b1 b2` the four characters are `b0b1 >> 10`,
+    // `b0b1 >> 4`, `b1b2 >> 6` and `b1b2 >> 0`, each masked to six bits.
+    let hi = simd_swizzle!(block, [0, 0, 1, 1, 3, 3, 4, 4, 6, 6, 7, 7, 9, 9, 10, 10]);
+    let lo = simd_swizzle!(block, [1, 1, 2, 2, 4, 4, 5, 5, 7, 7, 8, 8, 10, 10, 11, 11]);
+    let pairs = (hi.cast::<u16>() << U16x16::splat(8)) | lo.cast::<u16>();
+    let shifts = U16x16::from_array([10, 4, 6, 0, 10, 4, 6, 0, 10, 4, 6, 0, 10, 4, 6, 0]);
+    let indices = ((pairs >> shifts) & U16x16::splat(0x3F)).cast::<u8>();
+
+    // Index -> character: one offset per alphabet range, picked by compare.
+    let splat = U8x16::splat;
+    let mut offset = splat(b'A');
+    offset = indices.simd_ge(splat(26)).select(splat(b'a' - 26), offset);
+    offset = indices
+        .simd_ge(splat(52))
+        .select(splat(b'0'.wrapping_sub(52)), offset);
+    offset = indices
+        .simd_eq(splat(62))
+        .select(splat(sym.c62.wrapping_sub(62)), offset);
+    offset = indices
+        .simd_eq(splat(63))
+        .select(splat(sym.c63.wrapping_sub(63)), offset);
+    indices + offset
+}
+
+pub(crate) fn encode_slice_portable(config: &Config, input: &[u8], dst: &mut [u8]) {
+    let sym = Symbols::new(config);
+
+    // Every window is a full vector, so a round never reads past `input`;
+    // the 4 bytes of read-ahead are the start of the next round.
+    let mut rounds = 0;
+    for (block, out) in input
+        .windows(ENC_VEC)
+        .step_by(ENC_ROUND_IN)
+        .zip(dst.chunks_exact_mut(ENC_ROUND_OUT))
+    {
+        encode_round(U8x16::from_slice(block), sym).copy_to_slice(out);
+        rounds += 1;
+    }
+
+    scalar::encode_slice(
+        config,
+        &input[ENC_ROUND_IN * rounds..],
+        &mut dst[ENC_ROUND_OUT * rounds..],
+    );
+}
+
+// --- Decoder ---
+
+/// Maps 16 characters to their 6-bit indices, and reports which lanes held a
+/// character of the alphabet.
+#[inline]
+fn decode_block(chars: U8x16, sym: Symbols) -> (U8x16, Mask<i8, 16>) {
+    let splat = U8x16::splat;
+    let upper = chars.simd_ge(splat(b'A')) & chars.simd_le(splat(b'Z'));
+    let lower = chars.simd_ge(splat(b'a')) & chars.simd_le(splat(b'z'));
+    let digit = chars.simd_ge(splat(b'0')) & chars.simd_le(splat(b'9'));
+    let is62 = chars.simd_eq(splat(sym.c62));
+    let is63 = chars.simd_eq(splat(sym.c63));
+
+    let mut delta = splat(0);
+    delta = upper.select(splat(0u8.wrapping_sub(b'A')), delta);
+    delta = lower.select(splat(26u8.wrapping_sub(b'a')), delta);
+    delta = digit.select(splat(52u8.wrapping_sub(b'0')), delta);
+    delta = is62.select(splat(62u8.wrapping_sub(sym.c62)), delta);
+    delta = is63.select(splat(63u8.wrapping_sub(sym.c63)), delta);
+
+    (chars + delta, upper | lower | digit | is62 | is63)
+}
+
+/// Packs 16 indices into 12 bytes, in the low lanes of the result.
+#[inline]
+fn pack_block(indices: U8x16) -> U8x16 {
+    // The mirror image of `encode_round`: each output byte straddles two
+    // indices, so pair them up in a 12-bit value and shift the byte down:
+    // for indices `i0 i1 i2 i3` the bytes are `i0i1 >> 4`, `i1i2 >> 2` and
+    // `i2i3 >> 0`, truncated to eight bits.
+    let hi = simd_swizzle!(
+        indices,
+        [0, 1, 2, 4, 5, 6, 8, 9, 10, 12, 13, 14, 0, 0, 0, 0]
+    );
+    let lo = simd_swizzle!(
+        indices,
+        [1, 2, 3, 5, 6, 7, 9, 10, 11, 13, 14, 15, 0, 0, 0, 0]
+    );
+    let pairs = (hi.cast::<u16>() << U16x16::splat(6)) | lo.cast::<u16>();
+    let shifts = U16x16::from_array([4, 2, 0, 4, 2, 0, 4, 2, 0, 4, 2, 0, 0, 0, 0, 0]);
+    (pairs >> shifts).cast::<u8>()
+}
+
+pub(crate) fn decode_slice_portable(
+    config: &Config,
+    input: &[u8],
+    dst: &mut [u8],
+) -> Result<usize, Error> {
+    let sym = Symbols::new(config);
+    let body = &input[..input.len().saturating_sub(DEC_LEAD)];
+
+    // Invalid characters are folded into one accumulator and reported after
+    // the loop, as in the other kernels.
+    let mut bad = Mask::<i8, 16>::splat(false);
+    let mut blocks = 0;
+    for (block, out) in body
+        .chunks_exact(DEC_BLOCK_IN)
+        .zip(dst.chunks_exact_mut(DEC_BLOCK_OUT))
+    {
+        let (indices, valid) = decode_block(U8x16::from_slice(block), sym);
+        bad |= !valid;
+        out.copy_from_slice(&pack_block(indices).as_array()[..DEC_BLOCK_OUT]);
+        blocks += 1;
+    }
+
+    if bad.any() {
+        return Err(Error::InvalidCharacter);
+    }
+
+    let written = DEC_BLOCK_OUT * blocks;
+    Ok(written
+        + scalar::decode_slice(config, &input[DEC_BLOCK_IN * blocks..], &mut dst[written..])?)
+}
+
+#[cfg(test)]
+#[cfg(not(miri))]
+mod portable_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode};
+    use base64::engine::general_purpose::{
+        STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_STANDARD_NO_PAD, URL_SAFE as REF_URL_SAFE,
+    };
+
+    #[test]
+    fn portable_all_lengths_0_to_400() {
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for len in 0..=400 {
+                check_encode(&config, oracle, encode_slice_portable, len);
+                check_decode(&config, oracle, decode_slice_portable, len);
+                // No block overhangs its 12 bytes, so an exact buffer is enough.
+                check_decode_exact(&config, oracle, decode_slice_portable, len);
+            }
+        }
+    }
+
+    /// Every byte value through the vector block, against the scalar decoder
+    /// on validity and value.
+    #[test]
+    fn portable_decode_matches_scalar_on_every_byte() {
+        for url_safe in [false, true] {
+            let config = Config {
+                url_safe,
+                padding: true,
+                lenient: false,
+            };
+            for candidate in 0u8..=255 {
+                let mut input = [candidate; 20];
+                input[16..].copy_from_slice(b"AAAA");
+
+                let mut out = [0u8; 15];
+                let result = decode_slice_portable(&config, &input, &mut out);
+
+                let mut scalar_out = [0u8; 12];
+                match crate::scalar::decode_slice(&config, &input[..16], &mut scalar_out) {
+                    Ok(n) => {
+                        assert_eq!(result, Ok(n + 3), "byte {candidate:#04x}");
+                        assert_eq!(&out[..n], &scalar_out[..n], "byte {candidate:#04x}");
+                    }
+                    Err(err) => assert_eq!(result, Err(err), "byte {candidate:#04x}"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Length rules shared by the short-input kernels.
+//!
+//! Inputs below a kernel's dispatch threshold used to run scalar end to end,
+//! through the wide tables in `scalar.rs`. The short kernels instead run the
+//! whole input through one or two vectors, padding and partial groups
+//! included, so the only per-input work left in scalar code is the arithmetic
+//! here: how many characters carry data, and how many bytes they decode to.
+
+use crate::{Config, Error};
+
+/// Characters that carry data when encoding `len` bytes, and the encoded
+/// length once `config`'s padding is added. The characters between the two
+/// are all `=`.
+pub(super) const fn encoded_lens(config: Config, len: usize) -> (usize, usize) {
+    let chars = (len * 4).div_ceil(3);
+    let total = if config.padding {
+        len.div_ceil(3) * 4
+    } else {
+        chars
+    };
+    (chars, total)
+}
+
+/// What a short decoder does with its input: validate and decode the first
+/// `chars` characters to `out` bytes, then report `trailing`.
+///
+/// This is the scalar tail's length and padding logic, folded into two
+/// numbers: characters from a padded group on are dropped from `chars` (and
+/// their bytes from `out`), and a partial group the config does not allow is
+/// dropped and reported. Whatever `chars` leaves out of a vector is filled
+/// with `'A'`, which decodes to index 0, so it neither trips validation nor
+/// sets a bit in the bytes that are kept.
+pub(super) struct DecodePlan {
+    pub(super) chars: usize,
+    pub(super) out: usize,
+    /// The verdict once `chars` validated: `Ok` or an [`Error::InvalidLength`]
+    /// that, as in scalar, loses to a bad character in the groups before it.
+    pub(super) trailing: Result<(), Error>,
+}
+
+pub(super) fn plan_decode(config: Config, input: &[u8]) -> DecodePlan {
+    let len = input.len();
+
+    // Scalar's fast loop stops short of the last 4 characters on an 8-character
+    // stride, and only its tail stops at padding: the first whole group there
+    // that ends in '=' ends the input, whatever follows it.
+    let mut group = len.saturating_sub(4) / 8 * 8;
+    while len - group >= 4 {
+        if input[group + 3] == b'=' {
+            // "XX==" and "XXX=" are accepted whether or not the config pads.
+            let pad = if input[group + 2] == b'=' { 2 } else { 1 };
+            return DecodePlan {
+                chars: group + 4 - pad,
+                out: group / 4 * 3 + 3 - pad,
+                trailing: Ok(()),
+            };
+        }
+        group += 4;
+    }
+
+    let partial = len - group;
+    if partial == 0 {
+        return DecodePlan {
+            chars: len,
+            out: group / 4 * 3,
+            trailing: Ok(()),
+        };
+    }
+    // A lone character cannot form a byte.
+    if partial == 1 || config.padding {
+        return DecodePlan {
+            chars: group,
+            out: group / 4 * 3,
+            trailing: Err(Error::InvalidLength),
+        };
+    }
+    DecodePlan {
+        chars: len,
+        out: group / 4 * 3 + partial - 1,
+        trailing: Ok(()),
+    }
+}
+
+#[cfg(kani)]
+mod kani_verification_short {
+    use super::*;
+    use crate::STANDARD as TURBO_STANDARD;
+
+    /// Longest input any short kernel is handed.
+    const SHORT_MAX: usize = 64;
+
+    /// The plan never reads past the input, never writes past what the
+    /// decoders' callers size `dst` to, and its byte count is exactly what
+    /// `chars` characters decode to.
+    #[kani::proof]
+    fn check_short_decode_plan() {
+        let len: usize = kani::any();
+        kani::assume(len <= SHORT_MAX);
+        let config = Config {
+            url_safe: false,
+            padding: kani::any(),
+            lenient: false,
+        };
+        let input: [u8; SHORT_MAX] = kani::any();
+
+        let plan = plan_decode(config, &input[..len]);
+        assert!(plan.chars <= len, "plan reads past the input");
+        assert!(
+            plan.out <= TURBO_STANDARD.estimate_decoded_len(len),
+            "plan writes past the caller's buffer"
+        );
+        assert_ne!(plan.chars % 4, 1, "plan keeps a lone character");
+        assert_eq!(plan.out, plan.chars * 3 / 4, "byte count drifts from chars");
+    }
+
+    /// The encoded lengths match the engine's own, and the data characters
+    /// never exceed them.
+    #[kani::proof]
+    fn check_short_encoded_lens() {
+        let len: usize = kani::any();
+        kani::assume(len <= SHORT_MAX);
+        let padding: bool = kani::any();
+        let config = Config {
+            url_safe: false,
+            padding,
+            lenient: false,
+        };
+
+        let (chars, total) = encoded_lens(config, len);
+        let engine = if padding {
+            crate::STANDARD
+        } else {
+            crate::STANDARD_NO_PAD
+        };
+        assert_eq!(total, engine.encoded_len(len));
+        assert!(chars <= total && total - chars <= 2);
+    }
+}
@@ -0,0 +1,359 @@
+//! SSSE3 Base64, for x86 CPUs without AVX2 (Atom/Silvermont-class parts, and
+//! VMs that hide AVX from the guest).
+//!
+//! This is the AVX2 kernel's algorithm at half the width: the same `pshufb`
+//! reshuffle and multiply-based field split on encode, the same nibble-LUT
+//! validation and `pmaddubsw`/`pmaddwd` packing on decode. A 128-bit vector has
+//! no lanes to bridge, so the encoder loads each 12-byte group directly and
+//! needs neither the AVX2 kernel's offset load nor its permuted first round.
+//! Nothing here needs SSE4.1: the final validity test is a `pmovmskb` rather
+//! than `ptest`.
+
+use super::{PACK_L1, PACK_L2, PACK_SHUFFLE};
+use crate::{Config, Error};
+use core::hint::black_box;
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::{
+    __m128i, _mm_add_epi8, _mm_and_si128, _mm_cmpeq_epi8, _mm_cmpgt_epi8, _mm_loadu_si128,
+    _mm_madd_epi16, _mm_maddubs_epi16, _mm_movemask_epi8, _mm_mullo_epi16, _mm_or_si128,
+    _mm_set1_epi8, _mm_set1_epi32, _mm_setr_epi8, _mm_setzero_si128, _mm_shuffle_epi8,
+    _mm_srli_epi16, _mm_storeu_si128, _mm_sub_epi8, _mm_subs_epu8,
+};
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::{
+    __m128i, _mm_add_epi8, _mm_and_si128, _mm_cmpeq_epi8, _mm_cmpgt_epi8, _mm_loadu_si128,
+    _mm_madd_epi16, _mm_maddubs_epi16, _mm_movemask_epi8, _mm_mullo_epi16, _mm_or_si128,
+    _mm_set1_epi8, _mm_set1_epi32, _mm_setr_epi8, _mm_setzero_si128, _mm_shuffle_epi8,
+    _mm_srli_epi16, _mm_storeu_si128, _mm_sub_epi8, _mm_subs_epu8,
+};
+
+/// Rounds per iteration of the encoder's wide tier.
+const ENC_UNROLL: usize = 4;
+/// Vectors per iteration of the decoder's wide tier.
+const DEC_UNROLL: usize = 4;
+
+// Stride constants. The Kani index proofs in `verify` reason over this same
+// arithmetic symbolically, and import these rather than restating them, so a
+// stride that changes here changes the proofs too instead of silently drifting
+// out from under them.
+
+/// Input bytes an encode round consumes.
+const ENC_ROUND_IN: usize = 12;
+/// Characters an encode round writes.
+const ENC_ROUND_OUT: usize = 16;
+/// Bytes each encode load reads: a full vector, of which only the first
+/// [`ENC_ROUND_IN`] are consumed.
+const ENC_VEC: usize = 16;
+/// Bytes each encode load reads past what its round consumes, so no round may
+/// start within this many bytes of the end.
+const ENC_READ_AHEAD: usize = ENC_VEC - ENC_ROUND_IN;
+
+/// Input characters a single-vector decode pass consumes, which is also exactly
+/// what each of its loads reads.
+const DEC_BLOCK_IN: usize = 16;
+/// Bytes a single-vector decode pass advances `dst` by.
+const DEC_BLOCK_OUT: usize = 12;
+/// Characters every decode tier stops short of the end, so the final group —
+/// the only one that may carry `'='` — is always left to the scalar tail.
+const DEC_LEAD: usize = 4;
+
+/// Precomputed SSSE3 encode constants, factored out of [`encode_slice_ssse3`]
+/// so they are materialized once per call rather than once per round. These
+/// are the low lanes of the AVX2 kernel's constants (see
+/// `EncodeConstantsAvx2` for credit), except `reshuffle`, which reads from the
+/// start of the vector since there is no lead to skip.
+struct EncodeConstantsSsse3 {
+    reshuffle: __m128i,
+    align_mul: __m128i,
+    field_mask: __m128i,
+    field_mul: __m128i,
+    translate: __m128i,
+    c51: __m128i,
+    c25: __m128i,
+}
+
+#[target_feature(enable = "ssse3")]
+fn encode_constants_ssse3(config: Config) -> EncodeConstantsSsse3 {
+    let translate = if config.url_safe {
+        _mm_setr_epi8(
+            65, 71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -17, 32, 0, 0,
+        )
+    } else {
+        _mm_setr_epi8(
+            65, 71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -19, -16, 0, 0,
+        )
+    };
+
+    EncodeConstantsSsse3 {
+        reshuffle: _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10),
+        // Kept opaque for the same reason as in the AVX2 kernel: LLVM would
+        // otherwise strength-reduce the power-of-two multiplies into longer
+        // shift-and-blend sequences.
+        align_mul: black_box(_mm_set1_epi32(0x0010_0001)),
+        field_mask: _mm_set1_epi32(0x003F_03F0),
+        field_mul: black_box(_mm_set1_epi32(0x0100_0010)),
+        translate,
+        c51: _mm_set1_epi8(51),
+        c25: _mm_set1_epi8(25),
+    }
+}
+
+/// Encodes the first 12 of 16 raw input bytes into 16 Base64 characters; see
+/// `encode_vec_avx2` for how the two multiplies split the 6-bit fields.
+#[target_feature(enable = "ssse3")]
+fn encode_vec_ssse3(input: __m128i, k: &EncodeConstantsSsse3) -> __m128i {
+    let shuffled = _mm_shuffle_epi8(input, k.reshuffle);
+    let aligned = _mm_srli_epi16(_mm_mullo_epi16(shuffled, k.align_mul), 10);
+    let fields = _mm_mullo_epi16(_mm_and_si128(shuffled, k.field_mask), k.field_mul);
+    let indices = _mm_or_si128(aligned, fields);
+
+    let lut_idx = _mm_sub_epi8(
+        _mm_subs_epu8(indices, k.c51),
+        _mm_cmpgt_epi8(indices, k.c25),
+    );
+    _mm_add_epi8(indices, _mm_shuffle_epi8(k.translate, lut_idx))
+}
+
+/// Runs `rounds` encode rounds: each reads the 16 bytes at `src`, consumes the
+/// first 12, and writes 16 characters.
+///
+/// # Safety
+/// For every `i < rounds`, `src.add(12 * i)` must be valid for a 16-byte read
+/// and `dst.add(16 * i)` for a 16-byte write.
+#[target_feature(enable = "ssse3")]
+unsafe fn encode_rounds_ssse3(
+    src: *const u8,
+    dst: *mut u8,
+    rounds: usize,
+    k: &EncodeConstantsSsse3,
+) {
+    let mut src = src;
+    let mut dst = dst;
+    let mut remaining = rounds;
+
+    while remaining >= ENC_UNROLL {
+        let mut chunk = [_mm_setzero_si128(); ENC_UNROLL];
+        for (i, slot) in chunk.iter_mut().enumerate() {
+            *slot = unsafe { _mm_loadu_si128(src.add(ENC_ROUND_IN * i).cast::<__m128i>()) };
+        }
+        for (i, raw) in chunk.into_iter().enumerate() {
+            let chars = encode_vec_ssse3(raw, k);
+            unsafe { _mm_storeu_si128(dst.add(ENC_ROUND_OUT * i).cast::<__m128i>(), chars) };
+        }
+
+        src = unsafe { src.add(ENC_ROUND_IN * ENC_UNROLL) };
+        dst = unsafe { dst.add(ENC_ROUND_OUT * ENC_UNROLL) };
+        remaining -= ENC_UNROLL;
+    }
+
+    while remaining > 0 {
+        let raw = unsafe { _mm_loadu_si128(src.cast::<__m128i>()) };
+        let chars = encode_vec_ssse3(raw, k);
+        unsafe { _mm_storeu_si128(dst.cast::<__m128i>(), chars) };
+
+        src = unsafe { src.add(ENC_ROUND_IN) };
+        dst = unsafe { dst.add(ENC_ROUND_OUT) };
+        remaining -= 1;
+    }
+}
+
+#[target_feature(enable = "ssse3")]
+pub(crate) unsafe fn encode_slice_ssse3(config: &Config, input: &[u8], dst_slice: &mut [u8]) {
+    let len = input.len();
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+
+    let k = encode_constants_ssse3(*config);
+
+    if len >= ENC_VEC {
+        let rounds = (len - ENC_READ_AHEAD) / ENC_ROUND_IN;
+        unsafe { encode_rounds_ssse3(src, dst, rounds, &k) };
+
+        src = unsafe { src.add(ENC_ROUND_IN * rounds) };
+        dst = unsafe { dst.add(ENC_ROUND_OUT * rounds) };
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::encode(config, input, src, dst_slice, dst_off) };
+}
+
+/// Precomputed SSSE3 decode constants: the low lanes of the AVX2 kernel's
+/// nibble LUTs (see `DecodeConstantsAvx2` for their derivation and credit) and
+/// packing constants.
+struct DecodeConstantsSsse3 {
+    lut_lo: __m128i,
+    lut_hi: __m128i,
+    lut_shift: __m128i,
+    lut_roll: __m128i,
+    pack_l1: __m128i,
+    pack_l2: __m128i,
+    pack_shuffle: __m128i,
+    mask_nibble: __m128i,
+}
+
+#[target_feature(enable = "ssse3")]
+unsafe fn decode_constants_ssse3(config: &Config) -> DecodeConstantsSsse3 {
+    let (lut_lo, lut_hi, lut_shift, lut_roll) = if config.lenient {
+        let lut_lo = _mm_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3A, 0x3B, 0x3A,
+            0x3B, 0x32,
+        );
+        let lut_hi = _mm_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10,
+        );
+        let lut_shift = _mm_setr_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6, 0, 9);
+        let lut_roll = _mm_setr_epi8(
+            0, 0, 19, 4, -65, -65, -71, -71, 17, 0, -65, 16, -71, -65, -32, -71,
+        );
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    } else if config.url_safe {
+        let lut_lo = _mm_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3B, 0x3B, 0x3A,
+            0x3B, 0x33,
+        );
+        let lut_hi = _mm_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10,
+        );
+        let lut_shift = _mm_setr_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 4);
+        let lut_roll = _mm_setr_epi8(
+            0, 0, 17, 4, -65, -65, -71, -71, -65, -32, -71, 0, 0, 0, 0, 0,
+        );
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    } else {
+        let lut_lo = _mm_setr_epi8(
+            0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x1A, 0x1B, 0x1B,
+            0x1B, 0x1A,
+        );
+        let lut_hi = _mm_setr_epi8(
+            0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x08, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+            0x10, 0x10,
+        );
+        let lut_shift = _mm_setr_epi8(0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6);
+        let lut_roll = _mm_setr_epi8(0, 0, 19, 4, -65, -65, -71, -71, 16, 0, -65, 0, -71, 0, 0, 0);
+        (lut_lo, lut_hi, lut_shift, lut_roll)
+    };
+
+    // The shared packing constants are 32 bytes wide; both halves are the same
+    // 16-byte pattern, so the low one is all this kernel needs.
+    DecodeConstantsSsse3 {
+        lut_lo,
+        lut_hi,
+        lut_shift,
+        lut_roll,
+        pack_l1: unsafe { _mm_loadu_si128(PACK_L1.as_ptr().cast::<__m128i>()) },
+        pack_l2: unsafe { _mm_loadu_si128(PACK_L2.as_ptr().cast::<__m128i>()) },
+        pack_shuffle: unsafe { _mm_loadu_si128(PACK_SHUFFLE.as_ptr().cast::<__m128i>()) },
+        mask_nibble: _mm_set1_epi8(0x0F),
+    }
+}
+
+#[target_feature(enable = "ssse3")]
+pub(crate) unsafe fn decode_slice_ssse3(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) -> Result<usize, Error> {
+    let len = input.len();
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+
+    let DecodeConstantsSsse3 {
+        lut_lo,
+        lut_hi,
+        lut_shift,
+        lut_roll,
+        pack_l1,
+        pack_l2,
+        pack_shuffle,
+        mask_nibble,
+    } = unsafe { decode_constants_ssse3(config) };
+
+    // Validate + decode one vector, exactly as `decode_slice_avx2` does.
+    macro_rules! decode_vec {
+        ($input:expr) => {{
+            let hi_nibbles = _mm_and_si128(_mm_srli_epi16($input, 4), mask_nibble);
+            let lo_nibbles = _mm_and_si128($input, mask_nibble);
+
+            let lo = _mm_shuffle_epi8(lut_lo, lo_nibbles);
+            let hi = _mm_shuffle_epi8(lut_hi, hi_nibbles);
+            let err = _mm_and_si128(lo, hi);
+
+            let shift = _mm_shuffle_epi8(lut_shift, lo_nibbles);
+            let roll_idx = _mm_add_epi8(hi_nibbles, shift);
+            let roll = _mm_shuffle_epi8(lut_roll, roll_idx);
+            let indices = _mm_add_epi8($input, roll);
+
+            (indices, err)
+        }};
+    }
+
+    // 12 decoded bytes land in the low lanes; the 4 zeroed lanes above them are
+    // overwritten by the next store, or left in the caller's slack.
+    macro_rules! pack_and_store {
+        ($indices:expr, $dst_ptr:expr) => {{
+            let m = _mm_maddubs_epi16($indices, pack_l1);
+            let p = _mm_madd_epi16(m, pack_l2);
+            let out = _mm_shuffle_epi8(p, pack_shuffle);
+            unsafe { _mm_storeu_si128($dst_ptr.cast::<__m128i>(), out) };
+        }};
+    }
+
+    let safe_len = len.saturating_sub(DEC_LEAD);
+    let block_wide = DEC_BLOCK_IN * DEC_UNROLL;
+    let aligned_len_wide = safe_len - (safe_len % block_wide);
+    let aligned_len_16 = safe_len - (safe_len % DEC_BLOCK_IN);
+    let src_end_wide = unsafe { src.add(aligned_len_wide) };
+    let src_end_16 = unsafe { src.add(aligned_len_16) };
+
+    // Invalid characters are folded into one accumulator and reported after
+    // the loops, as in the AVX2 kernel.
+    let mut err_acc = _mm_setzero_si128();
+
+    // Wide tier: 64 input bytes -> 48 output.
+    while src < src_end_wide {
+        let mut decoded = [_mm_setzero_si128(); DEC_UNROLL];
+        for (i, slot) in decoded.iter_mut().enumerate() {
+            let raw = unsafe { _mm_loadu_si128(src.add(DEC_BLOCK_IN * i).cast::<__m128i>()) };
+            let (indices, err) = decode_vec!(raw);
+            *slot = indices;
+            err_acc = _mm_or_si128(err_acc, err);
+        }
+        for (i, indices) in decoded.into_iter().enumerate() {
+            let out = unsafe { dst.add(DEC_BLOCK_OUT * i) };
+            pack_and_store!(indices, out);
+        }
+
+        src = unsafe { src.add(DEC_BLOCK_IN * DEC_UNROLL) };
+        dst = unsafe { dst.add(DEC_BLOCK_OUT * DEC_UNROLL) };
+    }
+
+    // Single tier: 16 input bytes -> 12 output.
+    while src < src_end_16 {
+        let raw = unsafe { _mm_loadu_si128(src.cast::<__m128i>()) };
+        let (indices, err) = decode_vec!(raw);
+        err_acc = _mm_or_si128(err_acc, err);
+
+        pack_and_store!(indices, dst);
+
+        src = unsafe { src.add(DEC_BLOCK_IN) };
+        dst = unsafe { dst.add(DEC_BLOCK_OUT) };
+    }
+
+    // `ptest` is SSE4.1; a byte compare against zero plus `pmovmskb` is not.
+    if _mm_movemask_epi8(_mm_cmpeq_epi8(err_acc, _mm_setzero_si128())) != 0xFFFF {
+        return Err(Error::InvalidCharacter);
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::decode(config, input, src, dst_slice, dst_off) }
+}
+
+// Verification: Kani proofs, intrinsic models, model/hardware equivalence,
+// and the Miri + hardware coverage suites.
+#[cfg(any(kani, test))]
+mod verify;
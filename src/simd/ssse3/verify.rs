@@ -0,0 +1,867 @@
+//! SSSE3 verification: Kani proofs, Intel-pseudocode intrinsic model stubs,
+//! the model/hardware equivalence check, and the Miri + hardware coverage
+//! suites. Laid out like `avx2/verify.rs`, which this kernel mirrors.
+
+use super::*;
+
+#[cfg(kani)]
+mod kani_verification_ssse3 {
+    use super::*;
+    use crate::{Config, STANDARD as TURBO_STANDARD, STANDARD_NO_PAD as TURBO_STANDARD_NO_PAD};
+
+    // Only used inside `#[kani::stub(...)]` paths, which don't count as a use.
+    #[allow(unused_imports)]
+    use super::intrinsic_models as m;
+
+    // Layer 1 — index proofs: reason over a symbolic `len` and an arbitrary
+    // iteration index (no vectors), giving an induction (base/step/exit) that
+    // covers all N cheaply. Every stride is imported from the kernel module
+    // rather than restated. See the README's "Safety & Verification".
+
+    /// Largest `len` considered: above `usize::MAX / 4` the unpadded
+    /// `encoded_len`'s `len * 4` overflows, so the API can't size a buffer.
+    const MAX_LEN: usize = usize::MAX / 4;
+
+    // Encoder model, mirroring `encode_slice_ssse3`.
+    use super::super::{
+        ENC_READ_AHEAD, ENC_ROUND_IN, ENC_ROUND_OUT, ENC_UNROLL, ENC_VEC as ENC_LOAD,
+    };
+
+    fn enc_cap(len: usize, padding: bool) -> usize {
+        if padding {
+            TURBO_STANDARD.encoded_len(len)
+        } else {
+            TURBO_STANDARD_NO_PAD.encoded_len(len)
+        }
+    }
+
+    /// Symbolic `rounds` pinned to `(len - 4) / 12` via inequalities (cheaper
+    /// for CBMC than division; `check_enc_rounds_model` proves they agree).
+    fn any_enc_rounds(len: usize) -> usize {
+        let rounds: usize = kani::any();
+        kani::assume(rounds <= MAX_LEN / ENC_ROUND_IN);
+        kani::assume(ENC_ROUND_IN * rounds <= len - ENC_READ_AHEAD);
+        kani::assume(len - ENC_READ_AHEAD < ENC_ROUND_IN * (rounds + 1));
+        rounds
+    }
+
+    /// `(src_off, dst_off)` after `done` rounds.
+    fn enc_state(done: usize) -> (usize, usize) {
+        (ENC_ROUND_IN * done, ENC_ROUND_OUT * done)
+    }
+
+    /// Isolated so the suite's one non-power-of-two division owns its run.
+    #[kani::proof]
+    fn check_enc_rounds_model() {
+        let len: usize = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+
+        let rounds = any_enc_rounds(len);
+        assert_eq!(rounds, (len - ENC_READ_AHEAD) / ENC_ROUND_IN);
+        assert!(rounds >= 1);
+    }
+
+    /// Inductive step for the wide (4x-unrolled) tier, over an arbitrary
+    /// iteration.
+    #[kani::proof]
+    fn check_enc_wide_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+
+        let rounds = any_enc_rounds(len);
+        let cap = enc_cap(len, padding);
+
+        let done: usize = kani::any();
+        kani::assume(done <= rounds);
+        let remaining = rounds - done;
+        kani::assume(remaining >= ENC_UNROLL); // guard `while remaining >= 4`
+
+        let (src_off, dst_off) = enc_state(done);
+
+        // Widest body accesses: the `i = ENC_UNROLL - 1` load and store.
+        let last_src = ENC_ROUND_IN * (ENC_UNROLL - 1);
+        let last_dst = ENC_ROUND_OUT * (ENC_UNROLL - 1);
+        assert!(
+            src_off + last_src + ENC_LOAD <= len,
+            "wide load leaves input"
+        );
+        assert!(
+            dst_off + last_dst + ENC_ROUND_OUT <= cap,
+            "wide store leaves output"
+        );
+
+        let done_next = done + ENC_UNROLL;
+        assert_eq!(
+            (
+                src_off + ENC_ROUND_IN * ENC_UNROLL,
+                dst_off + ENC_ROUND_OUT * ENC_UNROLL
+            ),
+            enc_state(done_next)
+        );
+        assert_eq!(remaining - ENC_UNROLL, rounds - done_next);
+    }
+
+    /// Inductive step for the single-round tier.
+    #[kani::proof]
+    fn check_enc_single_step() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+
+        let rounds = any_enc_rounds(len);
+        let cap = enc_cap(len, padding);
+
+        let done: usize = kani::any();
+        kani::assume(done <= rounds);
+        let remaining = rounds - done;
+        kani::assume(remaining >= 1);
+
+        let (src_off, dst_off) = enc_state(done);
+        assert!(src_off + ENC_LOAD <= len, "single load leaves input");
+        assert!(dst_off + ENC_ROUND_OUT <= cap, "single store leaves output");
+
+        let done_next = done + 1;
+        assert_eq!(
+            (src_off + ENC_ROUND_IN, dst_off + ENC_ROUND_OUT),
+            enc_state(done_next)
+        );
+        assert_eq!(remaining - 1, rounds - done_next);
+    }
+
+    /// Exit case: the scalar handoff accounts for the rest.
+    #[kani::proof]
+    fn check_enc_tail_handoff() {
+        let len: usize = kani::any();
+        let padding: bool = kani::any();
+        kani::assume((ENC_LOAD..=MAX_LEN).contains(&len));
+
+        let rounds = any_enc_rounds(len);
+        let (src_off, dst_off) = enc_state(rounds);
+
+        // `rounds` caps at `(len - 4) / 12`, so the tail is non-empty (>= 4).
+        assert!(src_off < len);
+        let tail = len - src_off;
+        assert!(tail >= ENC_READ_AHEAD);
+
+        // Prefix + scalar tail is exactly the encoded length (no over/short write).
+        assert_eq!(
+            dst_off + enc_cap(tail, padding),
+            enc_cap(len, padding),
+            "prefix + tail must equal encoded length"
+        );
+    }
+
+    // Decoder model, mirroring `decode_slice_ssse3`.
+    use super::super::{
+        DEC_BLOCK_IN, DEC_BLOCK_IN as DEC_LOAD, DEC_BLOCK_OUT, DEC_LEAD, DEC_UNROLL,
+    };
+
+    /// Bytes `pack_and_store!` touches: one full vector, 4 wider than the 12
+    /// it advances.
+    const DEC_STORE_SPAN: usize = 16;
+    const DEC_WIDE_IN: usize = DEC_BLOCK_IN * DEC_UNROLL; // input bytes per wide-tier iteration
+    const DEC_WIDE_OUT: usize = DEC_BLOCK_OUT * DEC_UNROLL; // dst advance per wide-tier iteration
+
+    fn dec_cap(len: usize) -> usize {
+        TURBO_STANDARD.estimate_decoded_len(len)
+    }
+
+    /// The `aligned_len_wide` / `aligned_len_16` loop windows.
+    fn dec_windows(len: usize) -> (usize, usize) {
+        let safe = len.saturating_sub(DEC_LEAD);
+        (safe - safe % DEC_WIDE_IN, safe - safe % DEC_BLOCK_IN)
+    }
+
+    /// Inductive step for the decoder's wide tier, over an arbitrary iteration.
+    #[kani::proof]
+    fn check_dec_wide_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (aligned_wide, _) = dec_windows(len);
+        let cap = dec_cap(len);
+
+        let i: usize = kani::any();
+        kani::assume(i <= MAX_LEN / DEC_WIDE_IN);
+        let (src_off, dst_off) = (DEC_WIDE_IN * i, DEC_WIDE_OUT * i);
+        kani::assume(src_off < aligned_wide); // guard `src < src_end_wide`
+
+        let last_src = DEC_BLOCK_IN * (DEC_UNROLL - 1);
+        let last_dst = DEC_BLOCK_OUT * (DEC_UNROLL - 1);
+        assert!(
+            src_off + last_src + DEC_LOAD <= len,
+            "wide load leaves input"
+        );
+        assert!(
+            dst_off + last_dst + DEC_STORE_SPAN <= cap,
+            "wide store leaves output"
+        );
+
+        assert_eq!(
+            (src_off + DEC_WIDE_IN, dst_off + DEC_WIDE_OUT),
+            (DEC_WIDE_IN * (i + 1), DEC_WIDE_OUT * (i + 1))
+        );
+    }
+
+    /// Inductive step for the decoder's single-vector tier, entered from
+    /// wherever the wide tier stopped.
+    #[kani::proof]
+    fn check_dec_single_step() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (aligned_wide, aligned_block) = dec_windows(len);
+        let cap = dec_cap(len);
+        let wides = aligned_wide / DEC_WIDE_IN;
+
+        let j: usize = kani::any();
+        kani::assume(j <= MAX_LEN / DEC_BLOCK_IN);
+        let src_off = aligned_wide + DEC_BLOCK_IN * j;
+        let dst_off = DEC_WIDE_OUT * wides + DEC_BLOCK_OUT * j;
+        kani::assume(src_off < aligned_block); // guard `src < src_end_16`
+
+        assert!(src_off + DEC_LOAD <= len, "single load leaves input");
+        assert!(
+            dst_off + DEC_STORE_SPAN <= cap,
+            "single store leaves output"
+        );
+
+        assert_eq!(
+            (src_off + DEC_BLOCK_IN, dst_off + DEC_BLOCK_OUT),
+            (
+                aligned_wide + DEC_BLOCK_IN * (j + 1),
+                DEC_WIDE_OUT * wides + DEC_BLOCK_OUT * (j + 1)
+            )
+        );
+    }
+
+    /// Exit case: whatever the loops leave fits the space the caller
+    /// guaranteed, so the scalar decoder cannot overrun it.
+    #[kani::proof]
+    fn check_dec_tail_handoff() {
+        let len: usize = kani::any();
+        kani::assume(len <= MAX_LEN);
+
+        let (_, aligned_block) = dec_windows(len);
+        let cap = dec_cap(len);
+
+        let dst_off = DEC_BLOCK_OUT * (aligned_block / DEC_BLOCK_IN);
+        assert!(aligned_block <= len);
+        let tail = len - aligned_block;
+        assert!(
+            dst_off + dec_cap(tail) <= cap,
+            "scalar tail can overrun output"
+        );
+    }
+
+    // Layer 2 — kernel proofs: run the real code over symbolic bytes. Layer 1
+    // owns the loop arithmetic, so each reaches its kernel once. Buffers are
+    // the exact public-API capacities, so any real overrun fails.
+
+    /// Two rounds and a 5-byte scalar tail, so a round after the first runs
+    /// with the per-round stride Layer 1 asserts.
+    const ENC_KERNEL_LEN: usize = 29;
+    /// One single-vector pass + a 4-character scalar tail.
+    const DEC_KERNEL_LEN: usize = 20;
+
+    // Guard: a length below its tier's threshold would prove nothing but the
+    // scalar fallback. Fail the build instead.
+    const _: () = assert!(
+        ENC_KERNEL_LEN >= ENC_LOAD
+            && (ENC_KERNEL_LEN - ENC_READ_AHEAD) / ENC_ROUND_IN >= 2
+            && ENC_KERNEL_LEN % ENC_ROUND_IN != 0,
+        "ENC_KERNEL_LEN must run two rounds and leave an unaligned tail"
+    );
+    const _: () = assert!(
+        (DEC_KERNEL_LEN - DEC_LEAD) / DEC_BLOCK_IN == 1,
+        "DEC_KERNEL_LEN must run one single-vector decode pass"
+    );
+    // A length that is not a multiple of 4 can only ever decode to `Err` under a
+    // padded config, which would make the `Ok` half of the proof below vacuous.
+    const _: () = assert!(
+        DEC_KERNEL_LEN % 4 == 0,
+        "DEC_KERNEL_LEN must be able to decode successfully"
+    );
+
+    const ENC_KERNEL_CAP: usize = TURBO_STANDARD.encoded_len(ENC_KERNEL_LEN);
+    const ENC_KERNEL_DEC_CAP: usize = TURBO_STANDARD.estimate_decoded_len(ENC_KERNEL_CAP);
+    const DEC_KERNEL_CAP: usize = TURBO_STANDARD.estimate_decoded_len(DEC_KERNEL_LEN);
+
+    /// `Decode(Encode(x)) == x` over every 29-byte input.
+    fn roundtrip_kernel(url_safe: bool) {
+        let config = Config {
+            url_safe,
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; ENC_KERNEL_LEN] = kani::any();
+
+        let mut enc_buf = [0u8; ENC_KERNEL_CAP];
+        let mut dec_buf = [0u8; ENC_KERNEL_DEC_CAP];
+
+        unsafe {
+            encode_slice_ssse3(&config, &input, &mut enc_buf);
+            let dec_len = decode_slice_ssse3(&config, &enc_buf, &mut dec_buf)
+                .expect("valid encoding failed to decode");
+            assert_eq!(dec_len, ENC_KERNEL_LEN);
+            assert_eq!(&dec_buf[..dec_len], &input, "roundtrip mismatch");
+        }
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm_shuffle_epi8, m::_mm_shuffle_epi8_stub)]
+    #[kani::stub(_mm_subs_epu8, m::_mm_subs_epu8_stub)]
+    #[kani::stub(_mm_movemask_epi8, m::_mm_movemask_epi8_stub)]
+    #[kani::stub(_mm_maddubs_epi16, m::_mm_maddubs_epi16_stub)]
+    #[kani::stub(_mm_madd_epi16, m::_mm_madd_epi16_stub)]
+    #[kani::stub(_mm_mullo_epi16, m::_mm_mullo_epi16_stub)]
+    fn check_ssse3_roundtrip_standard() {
+        roundtrip_kernel(false);
+    }
+
+    #[kani::proof]
+    #[kani::stub(_mm_shuffle_epi8, m::_mm_shuffle_epi8_stub)]
+    #[kani::stub(_mm_subs_epu8, m::_mm_subs_epu8_stub)]
+    #[kani::stub(_mm_movemask_epi8, m::_mm_movemask_epi8_stub)]
+    #[kani::stub(_mm_maddubs_epi16, m::_mm_maddubs_epi16_stub)]
+    #[kani::stub(_mm_madd_epi16, m::_mm_madd_epi16_stub)]
+    #[kani::stub(_mm_mullo_epi16, m::_mm_mullo_epi16_stub)]
+    fn check_ssse3_roundtrip_url_safe() {
+        roundtrip_kernel(true);
+    }
+
+    /// The vectorized decoder agrees with the scalar one on every 20-character
+    /// input, acceptance and rejection alike. Error kinds are not compared, for
+    /// the reason given on `check_avx2_decode_matches_scalar`.
+    #[kani::proof]
+    #[kani::stub(_mm_shuffle_epi8, m::_mm_shuffle_epi8_stub)]
+    #[kani::stub(_mm_movemask_epi8, m::_mm_movemask_epi8_stub)]
+    #[kani::stub(_mm_maddubs_epi16, m::_mm_maddubs_epi16_stub)]
+    #[kani::stub(_mm_madd_epi16, m::_mm_madd_epi16_stub)]
+    fn check_ssse3_decode_matches_scalar() {
+        let config = Config {
+            url_safe: kani::any(),
+            padding: true,
+            lenient: false,
+        };
+        let input: [u8; DEC_KERNEL_LEN] = kani::any();
+
+        let mut simd_out = [0u8; DEC_KERNEL_CAP];
+        let mut scalar_out = [0u8; DEC_KERNEL_CAP];
+
+        let simd = unsafe { decode_slice_ssse3(&config, &input, &mut simd_out) };
+        let scalar = crate::scalar::decode_slice(&config, &input, &mut scalar_out);
+
+        match scalar {
+            Ok(n) => {
+                assert_eq!(simd, Ok(n), "scalar accepted an input the kernel rejected");
+                assert_eq!(
+                    &simd_out[..n],
+                    &scalar_out[..n],
+                    "kernel and scalar decoded to different bytes"
+                );
+            }
+            Err(_) => assert!(simd.is_err(), "kernel accepted an input scalar rejected"),
+        }
+    }
+}
+
+/// Rust models of the SSSE3/SSE2 intrinsics the Kani proofs stub out, each a
+/// line-for-line transcription of the Intel Intrinsics Guide (data version
+/// 3.6.9) `<operation>` pseudocode, following the conventions spelled out on
+/// `avx2::verify::intrinsic_models`: Intel's bit offsets kept verbatim and
+/// divided by 8 at the point of access, lines carrying no Intel text marked
+/// `NOTE:`.
+#[cfg(any(kani, test))]
+// Consumers reach these through `#[kani::stub(...)]` attribute arguments or
+// the hardware equivalence test only, so which look "used" depends on the
+// harness being compiled.
+#[allow(dead_code)]
+#[allow(non_snake_case)]
+// The transcription is more literal than idiomatic Rust would be, on purpose.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::missing_const_for_fn,
+    clippy::needless_late_init,
+    clippy::used_underscore_items
+)]
+pub(super) mod intrinsic_models {
+    use super::*;
+    use core::mem::transmute;
+
+    // NOTE: scaffolding, not from Intel. Reads bit `n` of a little-endian byte
+    // vector, for the places the pseudocode indexes a single bit.
+    fn bit(v: &[u8; 16], n: usize) -> u8 {
+        (v[n / 8] >> (n % 8)) & 1
+    }
+
+    // NOTE: scaffolding, not from Intel. The `SaturateU8` and `Saturate16`
+    // helpers the pseudocode calls by name.
+    fn SaturateU8(x: i16) -> u8 {
+        x.clamp(0, 255) as u8
+    }
+    fn Saturate16(x: i32) -> i16 {
+        x.clamp(-32768, 32767) as i16
+    }
+
+    // STUB: _mm_shuffle_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm_shuffle_epi8
+    pub(super) unsafe fn _mm_shuffle_epi8_stub(a: __m128i, b: __m128i) -> __m128i {
+        let a: [u8; 16] = unsafe { transmute(a) };
+        let b: [u8; 16] = unsafe { transmute(b) };
+        let mut dst = [0u8; 16];
+
+        // FOR j := 0 to 15
+        for j in 0..16 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	IF b[i+7] == 1
+            if bit(&b, i + 7) == 1 {
+                // 		dst[i+7:i] := 0
+                dst[i / 8] = 0;
+            // 	ELSE
+            } else {
+                // 		index[3:0] := b[i+3:i]
+                let index = usize::from(b[i / 8] & 0x0F);
+                // 		dst[i+7:i] := a[index*8+7:index*8]
+                dst[i / 8] = a[(index * 8) / 8];
+            }
+            // 	FI
+        }
+        // ENDFOR
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm_subs_epu8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm_subs_epu8
+    pub(super) unsafe fn _mm_subs_epu8_stub(a: __m128i, b: __m128i) -> __m128i {
+        let a: [u8; 16] = unsafe { transmute(a) };
+        let b: [u8; 16] = unsafe { transmute(b) };
+        let mut dst = [0u8; 16];
+
+        // FOR j := 0 to 15
+        for j in 0..16 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	dst[i+7:i] := SaturateU8(a[i+7:i] - b[i+7:i])
+            dst[i / 8] = SaturateU8(i16::from(a[i / 8]) - i16::from(b[i / 8]));
+        }
+        // ENDFOR
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm_movemask_epi8
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm_movemask_epi8
+    pub(super) unsafe fn _mm_movemask_epi8_stub(a: __m128i) -> i32 {
+        let a: [u8; 16] = unsafe { transmute(a) };
+        // NOTE: `dst` is the returned `int`; bits 16 and up stay zero.
+        let mut dst: i32 = 0;
+
+        // FOR j := 0 to 15
+        for j in 0..16 {
+            // 	i := j*8
+            let i = j * 8;
+            // 	dst[j] := a[i+7]
+            dst |= i32::from(bit(&a, i + 7)) << j;
+        }
+        // ENDFOR
+
+        dst
+    }
+
+    // STUB: _mm_maddubs_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm_maddubs_epi16
+    pub(super) unsafe fn _mm_maddubs_epi16_stub(a: __m128i, b: __m128i) -> __m128i {
+        // NOTE: `a` holds unsigned bytes, `b` signed ones.
+        let a: [u8; 16] = unsafe { transmute(a) };
+        let b: [i8; 16] = unsafe { transmute(b) };
+        let mut dst = [0i16; 8];
+
+        // FOR j := 0 to 7
+        for j in 0..8 {
+            // 	i := j*16
+            let i = j * 16;
+            // 	dst[i+15:i] := Saturate16( a[i+15:i+8]*b[i+15:i+8] + a[i+7:i]*b[i+7:i] )
+            dst[i / 16] = Saturate16(
+                i32::from(a[(i + 8) / 8]) * i32::from(b[(i + 8) / 8])
+                    + i32::from(a[i / 8]) * i32::from(b[i / 8]),
+            );
+        }
+        // ENDFOR
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm_madd_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm_madd_epi16
+    pub(super) unsafe fn _mm_madd_epi16_stub(a: __m128i, b: __m128i) -> __m128i {
+        let a: [i16; 8] = unsafe { transmute(a) };
+        let b: [i16; 8] = unsafe { transmute(b) };
+        let mut dst = [0i32; 4];
+
+        // FOR j := 0 to 3
+        for j in 0..4 {
+            // 	i := j*32
+            let i = j * 32;
+            // 	dst[i+31:i] := SignExtend32(a[i+31:i+16]*b[i+31:i+16]) + SignExtend32(a[i+15:i]*b[i+15:i])
+            // NOTE: the sum is `wrapping` because it lands in a 32-bit
+            // destination, which the two extreme products can overflow.
+            dst[i / 32] = (i32::from(a[(i + 16) / 16]) * i32::from(b[(i + 16) / 16]))
+                .wrapping_add(i32::from(a[i / 16]) * i32::from(b[i / 16]));
+        }
+        // ENDFOR
+
+        unsafe { transmute(dst) }
+    }
+
+    // STUB: _mm_mullo_epi16
+    // REFERENCE: https://www.intel.com/content/www/us/en/docs/intrinsics-guide/index.html#text=_mm_mullo_epi16
+    pub(super) unsafe fn _mm_mullo_epi16_stub(a: __m128i, b: __m128i) -> __m128i {
+        let a: [i16; 8] = unsafe { transmute(a) };
+        let b: [i16; 8] = unsafe { transmute(b) };
+        let mut dst = [0i16; 8];
+
+        // FOR j := 0 to 7
+        for j in 0..8 {
+            // 	i := j*16
+            let i = j * 16;
+            // 	tmp[31:0] := SignExtend32(a[i+15:i]) * SignExtend32(b[i+15:i])
+            let tmp: i32 = i32::from(a[i / 16]) * i32::from(b[i / 16]);
+            // 	dst[i+15:i] := tmp[15:0]
+            dst[i / 16] = tmp as i16;
+        }
+        // ENDFOR
+
+        unsafe { transmute(dst) }
+    }
+}
+
+/// Checks every model in [`intrinsic_models`] against the real instruction
+/// on SSSE3 hardware, under plain `cargo test`.
+#[cfg(test)]
+#[cfg(not(miri))]
+#[allow(clippy::used_underscore_items)] // calling the models is the point
+mod ssse3_stub_equivalence {
+    use super::intrinsic_models as model;
+    use super::*;
+    use core::mem::transmute;
+
+    /// Saturation and sign boundaries, the high bit that zeroes a shuffle
+    /// lane, index-shaped bytes, and deterministic noise.
+    fn probes() -> Vec<[u8; 16]> {
+        let byte = |i: usize| u8::try_from(i).expect("index below the 16-byte vector width");
+
+        let mut out = vec![[0x00; 16], [0xFF; 16], [0x80; 16], [0x7F; 16], [0x01; 16]];
+        out.push(core::array::from_fn(byte));
+        out.push(core::array::from_fn(|i| byte(i) | 0x80));
+        out.push(core::array::from_fn(|i| 0xFF - byte(i)));
+
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        for _ in 0..12 {
+            out.push(core::array::from_fn(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                u8::try_from(state >> 56).expect("shifted down to 8 bits")
+            }));
+        }
+        out
+    }
+
+    #[target_feature(enable = "ssse3")]
+    unsafe fn compare_all() {
+        let probes = probes();
+        // SAFETY: `__m128i` has no invalid bit patterns, so it and `[u8; 16]`
+        // are freely transmutable both ways.
+        let vec = |x: &[u8; 16]| unsafe { transmute::<[u8; 16], __m128i>(*x) };
+        let bytes = |v: __m128i| -> [u8; 16] { unsafe { transmute::<__m128i, [u8; 16]>(v) } };
+
+        // Each arm: `real(a, b)` must equal `model(a, b)` for every probe pair.
+        macro_rules! same {
+            ($real:ident, $model:ident) => {
+                for x in &probes {
+                    for y in &probes {
+                        assert_eq!(
+                            bytes($real(vec(x), vec(y))),
+                            bytes(unsafe { model::$model(vec(x), vec(y)) }),
+                            "{}: a={x:02x?} b={y:02x?}",
+                            stringify!($real)
+                        );
+                    }
+                }
+            };
+        }
+
+        same!(_mm_shuffle_epi8, _mm_shuffle_epi8_stub);
+        same!(_mm_subs_epu8, _mm_subs_epu8_stub);
+        same!(_mm_maddubs_epi16, _mm_maddubs_epi16_stub);
+        same!(_mm_madd_epi16, _mm_madd_epi16_stub);
+        same!(_mm_mullo_epi16, _mm_mullo_epi16_stub);
+        for x in &probes {
+            assert_eq!(
+                _mm_movemask_epi8(vec(x)),
+                unsafe { model::_mm_movemask_epi8_stub(vec(x)) },
+                "_mm_movemask_epi8: a={x:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn ssse3_models_match_hardware() {
+        if !is_x86_feature_detected!("ssse3") {
+            eprintln!("skipping: no SSSE3 on this machine");
+            return;
+        }
+        unsafe { compare_all() };
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_ssse3_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_encode};
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE};
+
+    /// Encode against the oracle and decode back, buffers sized as the public
+    /// API sizes them so MIRI sees the real caller's provenance.
+    fn check(config: &Config, oracle: &impl base64::Engine, len: usize) {
+        check_encode(config, oracle, encode_slice_ssse3, len);
+        check_decode(config, oracle, decode_slice_ssse3, len);
+    }
+
+    /// One raw length per distinct code path; the label names the path.
+    const TIER_LENGTHS: &[(usize, &str)] = &[
+        (0, "empty"),
+        (1, "scalar only"),
+        (12, "scalar only, decode too"),
+        (15, "encode: just under the SIMD guard"),
+        (16, "both: one single-tier round/pass + scalar tail"),
+        (40, "both: single-tier rounds/passes only"),
+        (52, "both: exactly one wide pass"),
+        (64, "both: one wide pass, then a single-tier round/pass"),
+        (301, "both: several wide passes plus single-tier rounds"),
+    ];
+
+    #[test]
+    fn miri_ssse3_standard() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        for &(len, tier) in TIER_LENGTHS {
+            println!("standard: len {len} ({tier})");
+            check(&config, &STANDARD, len);
+        }
+    }
+
+    #[test]
+    fn miri_ssse3_url_safe() {
+        let config = Config {
+            url_safe: true,
+            padding: true,
+            lenient: false,
+        };
+        for &(len, tier) in TIER_LENGTHS {
+            println!("url-safe: len {len} ({tier})");
+            check(&config, &URL_SAFE, len);
+        }
+    }
+
+    #[test]
+    fn miri_ssse3_no_padding() {
+        let config = Config {
+            url_safe: false,
+            padding: false,
+            lenient: false,
+        };
+        for &(len, tier) in TIER_LENGTHS {
+            println!("no-pad: len {len} ({tier})");
+            check(&config, &STANDARD_NO_PAD, len);
+        }
+    }
+
+    /// Invalid bytes must be caught in every tier, wherever they sit in a
+    /// pass, including the last lane before the accumulator is tested.
+    #[test]
+    fn miri_ssse3_decode_rejects_invalid() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        let mut dst = [0u8; 128];
+
+        for &(len, bad_at, where_) in &[
+            (20, 15, "single tier"),
+            (21, 20, "scalar tail"),
+            (68, 0, "wide tier, first lane"),
+            (68, 63, "wide tier, last lane"),
+            (84, 79, "wide tier then single tier, last lane"),
+        ] {
+            let mut input = vec![b'A'; len];
+            input[bad_at] = b'$';
+            let res = unsafe { decode_slice_ssse3(&config, &input, &mut dst) };
+            assert!(res.is_err(), "missed invalid byte in {where_}");
+        }
+    }
+}
+
+/// Exhaustive regression test for the nibble-lookup tables in
+/// [`decode_constants_ssse3`], guarding against transcription typos when they
+/// were cut down from the AVX2 ones.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod ssse3_decode_lut_exhaustive {
+    use super::*;
+
+    /// For every byte value, decode a 20-byte input of that byte (16 through
+    /// the SSSE3 fast path, 4 valid filler bytes into the scalar tail) and check
+    /// `decode_slice_ssse3` agrees with the scalar decoder on validity and value.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn check_all_byte_values(config: &Config) {
+        for candidate in 0u8..=255 {
+            let mut input = [candidate; 20];
+            input[16..].copy_from_slice(b"AAAA");
+
+            let mut ssse3_out = [0u8; 32];
+            let ssse3_result = unsafe { decode_slice_ssse3(config, &input, &mut ssse3_out) };
+
+            // Oracle: the first 16 bytes via the (separately tested) scalar path.
+            let mut scalar_out = [0u8; 32];
+            let scalar_result = crate::scalar::decode_slice(config, &input[..16], &mut scalar_out);
+
+            match scalar_result {
+                Ok(scalar_len) => {
+                    assert_eq!(
+                        ssse3_result,
+                        Ok(scalar_len + 3),
+                        "byte {candidate:#04x} ({candidate}): scalar accepted it"
+                    );
+                    assert_eq!(
+                        &ssse3_out[..scalar_len],
+                        &scalar_out[..scalar_len],
+                        "byte {candidate:#04x} ({candidate}): decoded value mismatch"
+                    );
+                }
+                Err(scalar_err) => {
+                    assert_eq!(
+                        ssse3_result,
+                        Err(scalar_err),
+                        "byte {candidate:#04x} ({candidate}): ssse3/scalar disagree on validity"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ssse3_lut_standard_matches_scalar() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        check_all_byte_values(&config);
+    }
+
+    #[test]
+    fn ssse3_lut_url_safe_matches_scalar() {
+        let config = Config {
+            url_safe: true,
+            padding: true,
+            lenient: false,
+        };
+        check_all_byte_values(&config);
+    }
+
+    #[test]
+    fn ssse3_lut_lenient_matches_scalar() {
+        let config = Config {
+            url_safe: false,
+            padding: false,
+            lenient: true,
+        };
+        check_all_byte_values(&config);
+    }
+}
+
+/// Length-boundary regression against the `base64` oracle at every length
+/// 0..=400, covering the `rounds = (len - 4) / 12` arithmetic and both tiers
+/// of each loop, plus a few large lengths.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod ssse3_length_sweep {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_encode};
+    use base64::engine::general_purpose::{
+        STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_STANDARD_NO_PAD, URL_SAFE as REF_URL_SAFE,
+    };
+
+    #[test]
+    fn ssse3_all_lengths_0_to_400() {
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for len in 0..=400 {
+                check_encode(&config, oracle, encode_slice_ssse3, len);
+                check_decode(&config, oracle, decode_slice_ssse3, len);
+            }
+        }
+    }
+
+    #[test]
+    fn ssse3_large_lengths() {
+        let config = Config {
+            url_safe: false,
+            padding: true,
+            lenient: false,
+        };
+        for len in [1_000, 10_000, 100_003] {
+            check_encode(&config, &REF_STANDARD, encode_slice_ssse3, len);
+            check_decode(&config, &REF_STANDARD, decode_slice_ssse3, len);
+        }
+    }
+}
+
+/// The lenient tables: both alphabets mixed, padded and not, at every length
+/// across the tiers, and against scalar's verdict on malformed input.
+#[cfg(test)]
+#[cfg(not(miri))]
+mod ssse3_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient;
+
+    #[test]
+    fn ssse3_decode_lenient_all_lengths() {
+        for len in (0..=300).chain([1_000, 4_097]) {
+            check_decode_lenient(decode_slice_ssse3, len);
+        }
+    }
+}
+
+#[cfg(all(test, miri))]
+mod miri_ssse3_lenient {
+    use super::*;
+    use crate::simd::testutil::check_decode_lenient;
+
+    /// The scalar tail alone, a single block, and a wide pass, each with a
+    /// scalar tail.
+    #[test]
+    fn miri_ssse3_decode_lenient_tier_boundaries() {
+        for len in [0, 1, 2, 3, 15, 17, 51] {
+            check_decode_lenient(decode_slice_ssse3, len);
+        }
+    }
+}
@@ -30,6 +30,44 @@ pub(crate) fn bytes(len: usize) -> Vec<u8> {
         .collect()
 }
 
+/// Whether the host CPU runs the AVX-512BW kernels. Says so on stderr when it
+/// does not, so the calling test can just return.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) fn has_avx512bw() -> bool {
+    let on = std::is_x86_feature_detected!("avx512f") && std::is_x86_feature_detected!("avx512bw");
+    if !on {
+        eprintln!("skipping: host CPU lacks AVX-512BW");
+    }
+    on
+}
+
+/// Whether the host CPU runs the AVX-512-VBMI kernels, saying so on stderr
+/// when it does not.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) fn has_avx512_vbmi() -> bool {
+    let on = std::is_x86_feature_detected!("avx512f")
+        && std::is_x86_feature_detected!("avx512bw")
+        && std::is_x86_feature_detected!("avx512vbmi");
+    if !on {
+        eprintln!("skipping: host CPU lacks AVX-512-VBMI");
+    }
+    on
+}
+
+/// Whether the host CPU runs the 256-bit AVX-512VL+VBMI kernels, saying so on
+/// stderr when it does not.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub(crate) fn has_avx512_vbmi_vl() -> bool {
+    let on = std::is_x86_feature_detected!("avx512f")
+        && std::is_x86_feature_detected!("avx512bw")
+        && std::is_x86_feature_detected!("avx512vl")
+        && std::is_x86_feature_detected!("avx512vbmi");
+    if !on {
+        eprintln!("skipping: host CPU lacks AVX-512VL+VBMI");
+    }
+    on
+}
+
 /// Encode `len` bytes and assert the SIMD output matches the oracle. The
 /// buffer is the exact encoded length, matching the real caller, so Miri
 /// catches any store overrun.
@@ -80,3 +118,534 @@ pub(crate) fn check_decode_exact(
         "exact-buffer decode mismatch at len {len}"
     );
 }
+
+/// Every config, paired with the oracle engine that matches it.
+fn configs() -> [(Config, base64::engine::GeneralPurpose); 4] {
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+    [
+        (
+            Config {
+                url_safe: false,
+                padding: true,
+                lenient: false,
+            },
+            STANDARD,
+        ),
+        (
+            Config {
+                url_safe: false,
+                padding: false,
+                lenient: false,
+            },
+            STANDARD_NO_PAD,
+        ),
+        (
+            Config {
+                url_safe: true,
+                padding: true,
+                lenient: false,
+            },
+            URL_SAFE,
+        ),
+        (
+            Config {
+                url_safe: true,
+                padding: false,
+                lenient: false,
+            },
+            URL_SAFE_NO_PAD,
+        ),
+    ]
+}
+
+/// Encode every length `0..=max` under every config through a short-input
+/// kernel, into a buffer of the exact encoded length.
+pub(crate) fn check_encode_short(encode: EncodeFn, max: usize) {
+    for (config, oracle) in configs() {
+        for len in 0..=max {
+            check_encode(&config, &oracle, encode, len);
+        }
+    }
+}
+
+/// Decode through a short-input kernel every valid encoding of at most `max`
+/// characters, into a buffer of the exact decoded length, then every length
+/// `0..=max` of `'A'`s with a bad byte, `'='` or a padding run planted in it.
+/// Those are held to the scalar kernel's verdict, error kind included.
+pub(crate) fn check_decode_short(decode: DecodeFn, max: usize) {
+    for (config, oracle) in configs() {
+        for len in (0..=max).take_while(|&len| oracle.encode(bytes(len)).len() <= max) {
+            check_decode_exact(&config, &oracle, decode, len);
+        }
+
+        for len in 0..=max {
+            let mut inputs = vec![vec![b'A'; len]];
+            for at in 0..len {
+                for bad in [b'*', 0x80, b'='] {
+                    let mut input = vec![b'A'; len];
+                    input[at] = bad;
+                    inputs.push(input);
+                }
+            }
+            for pad in 1..=len.min(2) {
+                let mut input = vec![b'A'; len];
+                input[len - pad..].fill(b'=');
+                inputs.push(input.clone());
+                input[len - 1] = b'A';
+                inputs.push(input);
+            }
+
+            for input in inputs {
+                let mut expected = vec![0u8; len];
+                let mut actual = vec![0u8; len];
+                let want = crate::scalar::decode_slice(&config, &input, &mut expected);
+                let got = unsafe { decode(&config, &input, &mut actual) };
+                assert_eq!(got, want, "verdict mismatch on {input:?}");
+                if let Ok(n) = want {
+                    assert_eq!(actual[..n], expected[..n], "decode mismatch on {input:?}");
+                }
+            }
+        }
+    }
+}
+
+/// Decode the encoding of `len` bytes under the lenient config, padded and
+/// unpadded, with the two alphabets mixed (every other `62`/`63` character
+/// swapped for its URL-safe twin). Then plant a bad character at every
+/// position, holding the kernel to the scalar verdict. `'='` is left out:
+/// scalar stops at the first padded group, so a mid-input one is a known
+/// divergence, not a lenient concern. The buffer carries the same margin as
+/// [`check_decode`]'s; see [`check_decode_lenient_exact`] for the tight one.
+pub(crate) fn check_decode_lenient(decode: DecodeFn, len: usize) {
+    decode_lenient_cases(decode, len, |chars| (chars / 4 + 1) * 3);
+}
+
+/// Like [`check_decode_lenient`] but into a buffer of the exact decoded
+/// length, for the masked-store kernels.
+pub(crate) fn check_decode_lenient_exact(decode: DecodeFn, len: usize) {
+    decode_lenient_cases(decode, len, |_| len);
+}
+
+fn decode_lenient_cases(decode: DecodeFn, len: usize, cap: impl Fn(usize) -> usize) {
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD};
+    let config = Config {
+        url_safe: false,
+        padding: false,
+        lenient: true,
+    };
+    let input = bytes(len);
+    for oracle in [STANDARD, STANDARD_NO_PAD] {
+        let mut chars = oracle.encode(&input).into_bytes();
+        for (i, c) in chars.iter_mut().enumerate() {
+            match *c {
+                b'+' if i % 2 == 0 => *c = b'-',
+                b'/' if i % 2 == 1 => *c = b'_',
+                _ => {}
+            }
+        }
+        let mut dst = vec![0u8; cap(chars.len())];
+        let n = unsafe { decode(&config, &chars, &mut dst) }.expect("lenient input failed");
+        assert_eq!(n, len, "lenient decode len {len}");
+        assert_eq!(dst[..n], input, "lenient decode mismatch at len {len}");
+
+        for at in 0..chars.len() {
+            for bad in [b'.', b' ', b'*', 0x80, 0xC1] {
+                let mut bad_input = chars.clone();
+                bad_input[at] = bad;
+                let mut expected = vec![0u8; dst.len()];
+                let want = crate::scalar::decode_slice(&config, &bad_input, &mut expected);
+                let got = unsafe { decode(&config, &bad_input, &mut dst) };
+                assert_eq!(got, want, "verdict mismatch on {bad_input:?}");
+                if let Ok(n) = want {
+                    assert_eq!(dst[..n], expected[..n], "decode mismatch on {bad_input:?}");
+                }
+            }
+        }
+    }
+}
+
+type HexEncodeFn = unsafe fn(bool, &[u8], &mut [u8]);
+type HexDecodeFn = unsafe fn(&[u8], &mut [u8]) -> Result<(), Error>;
+
+/// Hex-encode `len` bytes in both cases, then decode the digits back (as
+/// encoded, and with the case of every other digit flipped), all through
+/// buffers of the exact length so Miri catches any overrun. The `hex` crate
+/// is the oracle.
+pub(crate) fn check_hex(encode: HexEncodeFn, decode: HexDecodeFn, len: usize) {
+    let input = bytes(len);
+    for upper in [false, true] {
+        let expected = if upper {
+            hex::encode_upper(&input)
+        } else {
+            hex::encode(&input)
+        };
+        let mut digits = vec![0u8; 2 * len];
+        unsafe { encode(upper, &input, &mut digits) };
+        assert_eq!(
+            core::str::from_utf8(&digits).unwrap(),
+            expected,
+            "hex encode mismatch at len {len} (upper: {upper})"
+        );
+
+        for i in (0..digits.len()).step_by(2) {
+            digits[i] = digits[i].to_ascii_uppercase();
+            digits[i + 1] = digits[i + 1].to_ascii_lowercase();
+        }
+        let mut dst = vec![0u8; len];
+        unsafe { decode(&digits, &mut dst) }.expect("valid hex failed to decode");
+        assert_eq!(dst, input, "hex decode mismatch at len {len}");
+    }
+}
+
+/// Plants a bad digit at every position of `len` digits, and an odd
+/// trailing digit after them, holding the kernel to the scalar verdict.
+pub(crate) fn check_hex_rejects(decode: HexDecodeFn, len: usize) {
+    let digits = hex::encode(bytes(len / 2));
+    let mut inputs = vec![format!("{digits}a").into_bytes()];
+    for at in 0..digits.len() {
+        for bad in [b'g', b'G', b'/', b':', b'@', b'`', 0x80, 0xC6] {
+            let mut input = digits.clone().into_bytes();
+            input[at] = bad;
+            inputs.push(input);
+        }
+    }
+
+    for input in inputs {
+        let mut expected = vec![0u8; input.len() / 2];
+        let mut actual = vec![0u8; input.len() / 2];
+        let want = crate::hex::scalar::decode_slice(&input, &mut expected);
+        let got = unsafe { decode(&input, &mut actual) };
+        assert!(want.is_err(), "scalar accepted {input:?}");
+        assert_eq!(got, want, "verdict mismatch on {input:?}");
+    }
+}
+
+type Base32EncodeFn = unsafe fn(&crate::base32::Config, &[u8], &mut [u8]);
+type Base32DecodeFn = unsafe fn(&crate::base32::Config, &[u8], &mut [u8]) -> Result<usize, Error>;
+
+/// Every Base32 engine's config, paired with the `base32` crate alphabet that
+/// matches it.
+fn base32_configs() -> [(crate::base32::Config, base32::Alphabet); 6] {
+    use crate::base32::{BASE32, BASE32_NO_PAD, BASE32HEX, BASE32HEX_NO_PAD, CROCKFORD, ZBASE32};
+    [
+        (BASE32.config, base32::Alphabet::Rfc4648 { padding: true }),
+        (
+            BASE32_NO_PAD.config,
+            base32::Alphabet::Rfc4648 { padding: false },
+        ),
+        (
+            BASE32HEX.config,
+            base32::Alphabet::Rfc4648Hex { padding: true },
+        ),
+        (
+            BASE32HEX_NO_PAD.config,
+            base32::Alphabet::Rfc4648Hex { padding: false },
+        ),
+        (CROCKFORD.config, base32::Alphabet::Crockford),
+        (ZBASE32.config, base32::Alphabet::Z),
+    ]
+}
+
+/// Base32-encode `len` bytes under every config, then decode the oracle's
+/// encoding back, both through buffers of the exact length so Miri catches
+/// any overrun. The `base32` crate is the oracle.
+pub(crate) fn check_base32(encode: Base32EncodeFn, decode: Base32DecodeFn, len: usize) {
+    let input = bytes(len);
+    for (config, oracle) in base32_configs() {
+        let expected = base32::encode(oracle, &input);
+        let mut chars = vec![0u8; expected.len()];
+        unsafe { encode(&config, &input, &mut chars) };
+        assert_eq!(
+            core::str::from_utf8(&chars).unwrap(),
+            expected,
+            "base32 encode mismatch at len {len} ({config:?})"
+        );
+
+        let mut dst = vec![0u8; len];
+        let n = unsafe { decode(&config, expected.as_bytes(), &mut dst) }
+            .expect("valid base32 failed to decode");
+        assert_eq!(n, len, "base32 decode length at len {len} ({config:?})");
+        assert_eq!(
+            dst, input,
+            "base32 decode mismatch at len {len} ({config:?})"
+        );
+    }
+}
+
+/// Plants a bad character at every position of the encoding of `len` bytes
+/// under every config, and truncates it to every shorter length, holding the
+/// kernel to the scalar verdict, error kind included.
+pub(crate) fn check_base32_rejects(decode: Base32DecodeFn, len: usize) {
+    for (config, oracle) in base32_configs() {
+        let chars = base32::encode(oracle, &bytes(len)).into_bytes();
+        let mut inputs: Vec<Vec<u8>> = (0..chars.len()).map(|n| chars[..n].to_vec()).collect();
+        for at in 0..chars.len() {
+            for bad in [b'=', b'1', b'u', b'U', b'\0', 0x80, 0xC1] {
+                let mut input = chars.clone();
+                input[at] = bad;
+                inputs.push(input);
+            }
+        }
+
+        for input in inputs {
+            let cap = input.len() * 5 / 8;
+            let mut expected = vec![0u8; cap];
+            let mut actual = vec![0u8; cap];
+            let want = crate::base32::scalar::decode_slice(&config, &input, &mut expected);
+            let got = unsafe { decode(&config, &input, &mut actual) };
+            assert_eq!(got, want, "verdict mismatch on {input:?} ({config:?})");
+            if let Ok(n) = want {
+                assert_eq!(actual[..n], expected[..n], "decode mismatch on {input:?}");
+            }
+        }
+    }
+}
+
+type CryptEncodeFn = unsafe fn(crate::crypt::Scheme, &[u8], &mut [u8]);
+type CryptDecodeFn = unsafe fn(crate::crypt::Scheme, &[u8], &mut [u8]) -> Result<(), Error>;
+
+/// `input` encoded under `scheme`, by the `base64` crate. bcrypt is its
+/// `BCRYPT` alphabet, unpadded. crypt(3) reads each group little-endian: that
+/// is its `CRYPT` alphabet over each group's bytes reversed, with the group's
+/// characters reversed back. Zero-filling a partial group only reaches its
+/// last characters, which the length then cuts off.
+fn crypt_oracle(scheme: crate::crypt::Scheme, input: &[u8]) -> String {
+    use base64::engine::{GeneralPurpose, general_purpose::NO_PAD};
+    match scheme {
+        crate::crypt::Scheme::Bcrypt => {
+            GeneralPurpose::new(&base64::alphabet::BCRYPT, NO_PAD).encode(input)
+        }
+        crate::crypt::Scheme::Crypt => {
+            let mut groups = input.to_vec();
+            groups.resize(input.len().div_ceil(3) * 3, 0);
+            groups.chunks_mut(3).for_each(<[u8]>::reverse);
+            let mut chars = GeneralPurpose::new(&base64::alphabet::CRYPT, NO_PAD)
+                .encode(&groups)
+                .into_bytes();
+            chars.chunks_mut(4).for_each(<[u8]>::reverse);
+            chars.truncate((input.len() * 4).div_ceil(3));
+            String::from_utf8(chars).unwrap()
+        }
+    }
+}
+
+/// Encode `len` bytes under both schemes, then decode the oracle's encoding
+/// back, both through buffers of the exact length so Miri catches any
+/// overrun.
+pub(crate) fn check_crypt(encode: CryptEncodeFn, decode: CryptDecodeFn, len: usize) {
+    let input = bytes(len);
+    for scheme in crate::crypt::Scheme::ALL {
+        let expected = crypt_oracle(scheme, &input);
+        let mut chars = vec![0u8; expected.len()];
+        unsafe { encode(scheme, &input, &mut chars) };
+        assert_eq!(
+            core::str::from_utf8(&chars).unwrap(),
+            expected,
+            "crypt encode mismatch at len {len} ({scheme:?})"
+        );
+
+        let mut dst = vec![0u8; len];
+        unsafe { decode(scheme, expected.as_bytes(), &mut dst) }
+            .expect("valid crypt Base64 failed to decode");
+        assert_eq!(
+            dst, input,
+            "crypt decode mismatch at len {len} ({scheme:?})"
+        );
+    }
+}
+
+/// Plants a bad character at every position of the encoding of `len` bytes
+/// under both schemes, holding the kernel to the scalar verdict.
+pub(crate) fn check_crypt_rejects(decode: CryptDecodeFn, len: usize) {
+    for scheme in crate::crypt::Scheme::ALL {
+        let chars = crypt_oracle(scheme, &bytes(len)).into_bytes();
+        for at in 0..chars.len() {
+            for bad in [b'+', b'-', b'=', b'_', b'\0', 0x80, 0xAE] {
+                let mut input = chars.clone();
+                input[at] = bad;
+                let mut expected = vec![0u8; len];
+                let mut actual = vec![0u8; len];
+                let want = crate::crypt::scalar::decode_slice(scheme, &input, &mut expected);
+                let got = unsafe { decode(scheme, &input, &mut actual) };
+                assert!(want.is_err(), "scalar accepted {input:?}");
+                assert_eq!(got, want, "verdict mismatch on {input:?} ({scheme:?})");
+            }
+        }
+    }
+}
+
+type RemapFn = unsafe fn(&Config, &Config, &[u8], &mut [u8]) -> Result<(), Error>;
+type RemapInPlaceFn = unsafe fn(&Config, &Config, &mut [u8]) -> Result<(), Error>;
+
+/// Remaps the encoding of `len` bytes between every pair of alphabets, both
+/// into a buffer of the exact length, so Miri catches any overrun, and in
+/// place, then plants a bad character at every position, holding both forms
+/// to the scalar verdict.
+pub(crate) fn check_remap(remap: RemapFn, remap_in_place: RemapInPlaceFn, len: usize) {
+    use base64::engine::general_purpose::{STANDARD_NO_PAD, URL_SAFE_NO_PAD};
+    let input = bytes(len);
+    let encodings = [
+        STANDARD_NO_PAD.encode(&input).into_bytes(),
+        URL_SAFE_NO_PAD.encode(&input).into_bytes(),
+    ];
+    let config = |url_safe| Config {
+        url_safe,
+        padding: false,
+        lenient: false,
+    };
+    for (from, chars) in encodings.iter().enumerate() {
+        for (to, expected) in encodings.iter().enumerate() {
+            let (from_config, to_config) = (config(from == 1), config(to == 1));
+            let table = crate::transcode::remap_table_for(&from_config, &to_config);
+            let mut out = vec![0u8; chars.len()];
+            unsafe { remap(&from_config, &to_config, chars, &mut out) }
+                .expect("valid Base64 failed to remap");
+            assert_eq!(
+                &out, expected,
+                "remap mismatch at len {len} ({from} -> {to})"
+            );
+
+            let mut buf = chars.clone();
+            unsafe { remap_in_place(&from_config, &to_config, &mut buf) }
+                .expect("valid Base64 failed to remap");
+            assert_eq!(&buf, expected, "in-place remap mismatch at len {len}");
+
+            for at in 0..chars.len() {
+                for bad in [b'=', b'.', b' ', b"-+"[from], b"_/"[from], 0x80, 0xC1] {
+                    let mut input = chars.clone();
+                    input[at] = bad;
+                    let want =
+                        crate::transcode::scalar::remap(table, &input, &mut vec![0u8; input.len()]);
+                    assert!(want.is_err(), "scalar accepted {input:?}");
+                    let got = unsafe { remap(&from_config, &to_config, &input, &mut out) };
+                    assert_eq!(got, want, "verdict mismatch on {input:?}");
+                    let got = unsafe { remap_in_place(&from_config, &to_config, &mut input) };
+                    assert_eq!(got, want, "in-place verdict mismatch at {at}");
+                }
+            }
+        }
+    }
+}
+
+type Base64ToHexFn = unsafe fn(&Config, bool, &[u8], &mut [u8]) -> Result<(), Error>;
+type HexToBase64Fn = unsafe fn(&Config, &[u8], &mut [u8]) -> Result<(), Error>;
+
+/// Decodes `groups` whole Base64 groups straight to hex, under every config
+/// and in both cases, into a buffer of the exact length so Miri catches any
+/// overrun, then plants a bad character at every position, holding the kernel
+/// to the scalar verdict. The `base64` and `hex` crates are the oracles.
+pub(crate) fn check_base64_to_hex(kernel: Base64ToHexFn, groups: usize) {
+    let input = bytes(groups * 3);
+    for (config, oracle) in configs() {
+        let chars = oracle.encode(&input).into_bytes();
+        for upper in [false, true] {
+            let expected = if upper {
+                hex::encode_upper(&input)
+            } else {
+                hex::encode(&input)
+            };
+            let mut digits = vec![0u8; groups * 6];
+            unsafe { kernel(&config, upper, &chars, &mut digits) }.expect("valid Base64 failed");
+            assert_eq!(
+                core::str::from_utf8(&digits).unwrap(),
+                expected,
+                "Base64 -> hex mismatch at {groups} groups (upper: {upper})"
+            );
+        }
+
+        let mut digits = vec![0u8; groups * 6];
+        for at in 0..chars.len() {
+            for bad in [
+                b'=',
+                b'.',
+                b' ',
+                b"-+"[usize::from(config.url_safe)],
+                0x80,
+                0xC1,
+            ] {
+                let mut input = chars.clone();
+                input[at] = bad;
+                let want =
+                    crate::transcode::scalar::base64_to_hex(&config, false, &input, &mut digits);
+                let got = unsafe { kernel(&config, false, &input, &mut digits) };
+                assert!(want.is_err(), "scalar accepted {input:?}");
+                assert_eq!(got, want, "verdict mismatch on {input:?}");
+            }
+        }
+    }
+}
+
+/// Encodes `groups` whole 6-digit hex groups, in mixed case, straight to
+/// Base64 under every config, into a buffer of the exact length, then plants
+/// a bad digit at every position, holding the kernel to the scalar verdict.
+pub(crate) fn check_hex_to_base64(kernel: HexToBase64Fn, groups: usize) {
+    let input = bytes(groups * 3);
+    let mut digits = hex::encode(&input).into_bytes();
+    for d in digits.iter_mut().step_by(3) {
+        d.make_ascii_uppercase();
+    }
+    for (config, oracle) in configs() {
+        let mut chars = vec![0u8; groups * 4];
+        unsafe { kernel(&config, &digits, &mut chars) }.expect("valid hex failed");
+        assert_eq!(
+            core::str::from_utf8(&chars).unwrap(),
+            oracle.encode(&input),
+            "hex -> Base64 mismatch at {groups} groups"
+        );
+
+        for at in 0..digits.len() {
+            for bad in [b'g', b'G', b'/', b':', b' ', 0x80, 0xC6] {
+                let mut input = digits.clone();
+                input[at] = bad;
+                let want = crate::transcode::scalar::hex_to_base64(&config, &input, &mut chars);
+                let got = unsafe { kernel(&config, &input, &mut chars) };
+                assert!(want.is_err(), "scalar accepted {input:?}");
+                assert_eq!(got, want, "verdict mismatch on {input:?}");
+            }
+        }
+    }
+}
+
+type CompactFn = unsafe fn(&Config, &[u8], &mut [u8]) -> usize;
+
+/// Compacts `len` bytes under every config, the lenient one included: random
+/// bytes, mostly garbage, and the encoding of random bytes with garbage
+/// planted every `stride` bytes, from every byte to none. Each goes into a
+/// buffer of the input's length, so Miri catches any overrun, and is held to
+/// the scalar kernel's output.
+pub(crate) fn check_compact(compact: CompactFn, len: usize) {
+    let lenient = Config {
+        url_safe: false,
+        padding: false,
+        lenient: true,
+    };
+    let configs = configs().map(|(config, _)| config);
+    for config in configs.iter().chain([&lenient]) {
+        let chars = base64::engine::general_purpose::STANDARD
+            .encode(bytes(len))
+            .into_bytes();
+        let mut inputs = vec![bytes(len)];
+        for stride in [1, 2, 7, 63, 64, 65, usize::MAX] {
+            let mut input = chars[..len].to_vec();
+            for (i, c) in input.iter_mut().enumerate() {
+                if i % stride == stride / 2 {
+                    *c = [b'\n', b'>', b'"', 0x00, 0x80, 0xFF, b'.'][i % 7];
+                }
+            }
+            inputs.push(input);
+        }
+
+        for input in inputs {
+            let mut expected = vec![0u8; len];
+            let want = crate::garbage::scalar::compact(config, &input, &mut expected);
+            let mut actual = vec![0u8; len];
+            let got = unsafe { compact(config, &input, &mut actual) };
+            assert_eq!(got, want, "kept count mismatch on {input:?}");
+            assert_eq!(
+                actual[..got],
+                expected[..want],
+                "compact mismatch on {input:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,288 @@
+//! WebAssembly `simd128` kernel (feature `wasm-simd`), for builds with
+//! `-C target-feature=+simd128`. WASM has no runtime feature detection — a
+//! module that uses `v128` fails to validate on an engine without it — so this
+//! is compile-time dispatch, like NEON.
+//!
+//! The shapes are the SSSE3 kernel's: `i8x16_swizzle` is `pshufb` without the
+//! high-bit quirk, so the decode nibble tables carry over unchanged and the
+//! encode offsets only change order. What `simd128` lacks is `pmulhuw` and
+//! `pmaddubsw`, so the field extraction and the first packing step use lane
+//! shifts instead.
+
+use crate::{Config, Error};
+
+use core::arch::wasm32::{
+    i8x16, i8x16_eq, i8x16_gt, i8x16_shuffle, i8x16_splat, i8x16_swizzle, i32x4_dot_i16x8,
+    u8x16_add, u8x16_shr, u8x16_splat, u8x16_sub_sat, u16x8_shl, u16x8_shr, u16x8_splat,
+    u32x4_splat, v128, v128_and, v128_any_true, v128_bitselect, v128_load, v128_or, v128_store,
+    v128_store32_lane, v128_store64_lane,
+};
+
+/// Input bytes an encode round consumes.
+const ENC_ROUND_IN: usize = 12;
+/// Characters an encode round writes.
+const ENC_ROUND_OUT: usize = 16;
+/// Bytes each encode load reads: a full vector, of which only the first
+/// [`ENC_ROUND_IN`] are consumed.
+const ENC_VEC: usize = 16;
+/// Bytes each encode load reads past what its round consumes, so no round may
+/// start within this many bytes of the end.
+const ENC_READ_AHEAD: usize = ENC_VEC - ENC_ROUND_IN;
+
+/// Input characters a decode block consumes, which is also exactly what each
+/// of its loads reads.
+const DEC_BLOCK_IN: usize = 16;
+/// Bytes a decode block writes.
+const DEC_BLOCK_OUT: usize = 12;
+/// Characters the decode loop stops short of the end, so the final group —
+/// the only one that may carry `'='` — is always left to the scalar tail.
+const DEC_LEAD: usize = 4;
+
+// --- Encoder ---
+
+/// Encodes the first 12 bytes of `v` into 16 characters.
+#[inline]
+#[target_feature(enable = "simd128")]
+fn encode_vec(v: v128, translate: v128) -> v128 {
+    // Spread each 3-byte group over a 32-bit lane as `b1 b0 b2 b1`.
+    let v = i8x16_shuffle::<1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10>(v, v);
+
+    // Low 16-bit lane of each pair: the first field sits at bits 10..16 and
+    // the second at 4..10; high lane: the third at 6..12, the fourth at 0..6.
+    // SSSE3 lines them up with `pmulhuw`/`pmullw` by per-lane powers of two;
+    // here each lane half is shifted on its own and the halves blended.
+    let low_half = u32x4_splat(0x0000_FFFF);
+    let t0 = v128_and(v, u32x4_splat(0x0FC0_FC00));
+    let t1 = v128_bitselect(u16x8_shr(t0, 10), u16x8_shr(t0, 6), low_half);
+    let t2 = v128_and(v, u32x4_splat(0x003F_03F0));
+    let t3 = v128_bitselect(u16x8_shl(t2, 4), u16x8_shl(t2, 8), low_half);
+    let indices = v128_or(t1, t3);
+
+    // Index -> character: 0..=25 take slot 13 ('A'), 26..=51 slot 0, and
+    // 52..=63 slots 1..=12, whose offset is then added to the index.
+    let slot = v128_or(
+        u8x16_sub_sat(indices, u8x16_splat(51)),
+        v128_and(i8x16_gt(i8x16_splat(26), indices), u8x16_splat(13)),
+    );
+    u8x16_add(indices, i8x16_swizzle(translate, slot))
+}
+
+#[target_feature(enable = "simd128")]
+pub(crate) unsafe fn encode_slice_wasm(config: &Config, input: &[u8], dst_slice: &mut [u8]) {
+    let len = input.len();
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+
+    // The SSSE3 offsets, in `encode_vec`'s slot order.
+    let translate = if config.url_safe {
+        i8x16(
+            71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -17, 32, 65, 0, 0,
+        )
+    } else {
+        i8x16(
+            71, -4, -4, -4, -4, -4, -4, -4, -4, -4, -4, -19, -16, 65, 0, 0,
+        )
+    };
+
+    if len >= ENC_VEC {
+        let rounds = (len - ENC_READ_AHEAD) / ENC_ROUND_IN;
+        for _ in 0..rounds {
+            let raw = unsafe { v128_load(src.cast::<v128>()) };
+            unsafe { v128_store(dst.cast::<v128>(), encode_vec(raw, translate)) };
+
+            src = unsafe { src.add(ENC_ROUND_IN) };
+            dst = unsafe { dst.add(ENC_ROUND_OUT) };
+        }
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::encode(config, input, src, dst_slice, dst_off) };
+}
+
+// --- Decoder ---
+
+/// The SSSE3 kernel's nibble tables (see `DecodeConstantsAvx2` for their
+/// derivation and credit).
+struct DecodeConstantsWasm {
+    lut_lo: v128,
+    lut_hi: v128,
+    lut_roll: v128,
+    eq_char: v128,
+    eq_shift: v128,
+}
+
+#[target_feature(enable = "simd128")]
+fn decode_constants_wasm(config: Config) -> DecodeConstantsWasm {
+    let (lut_lo, lut_hi, lut_roll, eq_char, eq_shift) = if config.url_safe {
+        (
+            i8x16(
+                0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x3B, 0x3B, 0x3A,
+                0x3B, 0x33,
+            ),
+            i8x16(
+                0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x20, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+                0x10, 0x10,
+            ),
+            i8x16(0, 0, 17, 4, -65, -65, -71, -71, 0, 0, 0, 0, 0, -32, 0, 0),
+            b'_',
+            8i8,
+        )
+    } else {
+        (
+            i8x16(
+                0x15, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x13, 0x1A, 0x1B, 0x1B,
+                0x1B, 0x1A,
+            ),
+            i8x16(
+                0x10, 0x10, 0x01, 0x02, 0x04, 0x08, 0x04, 0x08, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10,
+                0x10, 0x10,
+            ),
+            i8x16(0, 16, 19, 4, -65, -65, -71, -71, 0, 0, 0, 0, 0, 0, 0, 0),
+            b'/',
+            -1i8,
+        )
+    };
+
+    DecodeConstantsWasm {
+        lut_lo,
+        lut_hi,
+        lut_roll,
+        eq_char: u8x16_splat(eq_char),
+        eq_shift: i8x16_splat(eq_shift),
+    }
+}
+
+/// Maps 16 characters to their 6-bit indices, plus a vector that is nonzero
+/// in every lane holding a character outside the alphabet.
+#[inline]
+#[target_feature(enable = "simd128")]
+fn decode_vec(input: v128, k: &DecodeConstantsWasm) -> (v128, v128) {
+    let hi_nibbles = u8x16_shr(input, 4);
+    let lo_nibbles = v128_and(input, u8x16_splat(0x0F));
+
+    let lo = i8x16_swizzle(k.lut_lo, lo_nibbles);
+    let hi = i8x16_swizzle(k.lut_hi, hi_nibbles);
+    let err = v128_and(lo, hi);
+
+    let eq = i8x16_eq(input, k.eq_char);
+    let roll_idx = u8x16_add(hi_nibbles, v128_and(eq, k.eq_shift));
+    let indices = u8x16_add(input, i8x16_swizzle(k.lut_roll, roll_idx));
+
+    (indices, err)
+}
+
+/// Packs 16 indices into 12 bytes and stores exactly those 12 at `dst`.
+///
+/// # Safety
+/// `dst` must be valid for 12 bytes of writes.
+#[inline]
+#[target_feature(enable = "simd128")]
+unsafe fn pack_and_store(indices: v128, dst: *mut u8) {
+    // `pmaddubsw` by `0x40, 0x01`: `a << 6 | b` in each 16-bit lane.
+    let low_byte = u16x8_splat(0x00FF);
+    let m = v128_or(
+        u16x8_shl(v128_and(indices, low_byte), 6),
+        u16x8_shr(indices, 8),
+    );
+    // `pmaddwd` by `0x1000, 0x0001`: 24 bits in each 32-bit lane.
+    let p = i32x4_dot_i16x8(m, u32x4_splat(0x0001_1000));
+    let out = i8x16_shuffle::<2, 1, 0, 6, 5, 4, 10, 9, 8, 14, 13, 12, 0, 0, 0, 0>(p, p);
+    // Two lane stores write the 12 bytes and nothing past them.
+    unsafe { v128_store64_lane::<0>(out, dst.cast::<u64>()) };
+    unsafe { v128_store32_lane::<2>(out, dst.add(8).cast::<u32>()) };
+}
+
+#[target_feature(enable = "simd128")]
+pub(crate) unsafe fn decode_slice_wasm(
+    config: &Config,
+    input: &[u8],
+    dst_slice: &mut [u8],
+) -> Result<usize, Error> {
+    let len = input.len();
+    let mut src = input.as_ptr();
+    let dst_start = dst_slice.as_mut_ptr();
+    let mut dst = dst_start;
+
+    let k = decode_constants_wasm(*config);
+
+    // Invalid characters are folded into one accumulator and reported after
+    // the loop, as in the SSSE3 kernel.
+    let mut err_acc = u8x16_splat(0);
+    let blocks = len.saturating_sub(DEC_LEAD) / DEC_BLOCK_IN;
+    for _ in 0..blocks {
+        let raw = unsafe { v128_load(src.cast::<v128>()) };
+        let (indices, err) = decode_vec(raw, &k);
+        err_acc = v128_or(err_acc, err);
+        unsafe { pack_and_store(indices, dst) };
+
+        src = unsafe { src.add(DEC_BLOCK_IN) };
+        dst = unsafe { dst.add(DEC_BLOCK_OUT) };
+    }
+
+    if v128_any_true(err_acc) {
+        return Err(Error::InvalidCharacter);
+    }
+
+    let dst_off = unsafe { dst.offset_from(dst_start) }.cast_unsigned();
+    unsafe { super::tail::decode(config, input, src, dst_slice, dst_off) }
+}
+
+#[cfg(test)]
+mod wasm_coverage {
+    use super::*;
+    use crate::simd::testutil::{check_decode, check_decode_exact, check_encode};
+    use base64::engine::general_purpose::{
+        STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_STANDARD_NO_PAD, URL_SAFE as REF_URL_SAFE,
+    };
+
+    #[test]
+    fn wasm_all_lengths_0_to_400() {
+        for (url_safe, padding, oracle) in [
+            (false, true, &REF_STANDARD),
+            (true, true, &REF_URL_SAFE),
+            (false, false, &REF_STANDARD_NO_PAD),
+        ] {
+            let config = Config {
+                url_safe,
+                padding,
+                lenient: false,
+            };
+            for len in 0..=400 {
+                check_encode(&config, oracle, encode_slice_wasm, len);
+                check_decode(&config, oracle, decode_slice_wasm, len);
+                // The lane stores never write past a block's 12 bytes.
+                check_decode_exact(&config, oracle, decode_slice_wasm, len);
+            }
+        }
+    }
+
+    /// Every byte value through the vector block, against the scalar decoder
+    /// on validity and value.
+    #[test]
+    fn wasm_decode_matches_scalar_on_every_byte() {
+        for url_safe in [false, true] {
+            let config = Config {
+                url_safe,
+                padding: true,
+                lenient: false,
+            };
+            for candidate in 0u8..=255 {
+                let mut input = [candidate; 20];
+                input[16..].copy_from_slice(b"AAAA");
+
+                let mut out = [0u8; 15];
+                let result = unsafe { decode_slice_wasm(&config, &input, &mut out) };
+
+                let mut scalar_out = [0u8; 12];
+                match crate::scalar::decode_slice(&config, &input[..16], &mut scalar_out) {
+                    Ok(n) => {
+                        assert_eq!(result, Ok(n + 3), "byte {candidate:#04x}");
+                        assert_eq!(&out[..n], &scalar_out[..n], "byte {candidate:#04x}");
+                    }
+                    Err(err) => assert_eq!(result, Err(err), "byte {candidate:#04x}"),
+                }
+            }
+        }
+    }
+}
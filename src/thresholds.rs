@@ -0,0 +1,411 @@
+//! Dispatch thresholds: the input lengths at which each SIMD kernel takes over
+//! from its fallback, and at which the widest kernels switch to non-temporal
+//! stores.
+//!
+//! Every threshold ships with a default measured on one or two machines. They
+//! are process-global and can be replaced at any time, either with values
+//! [`calibrate`] measures on the host or with a [`Thresholds`] saved from an
+//! earlier run. Installing one only moves the point at which an input changes
+//! kernel; every kernel still produces the same output at every length, so a
+//! badly tuned threshold costs speed, never correctness.
+
+use crate::Backend;
+#[cfg(unsafe_simd)]
+use crate::dispatch;
+
+/// Every backend, in declaration order, so `backend as usize` indexes it.
+const BACKENDS: [Backend; 9] = [
+    Backend::Scalar,
+    Backend::Ssse3,
+    Backend::Avx2,
+    Backend::Avx512Bw,
+    Backend::Avx512Vbmi256,
+    Backend::Avx512Vbmi,
+    Backend::Neon,
+    Backend::WasmSimd,
+    Backend::Portable,
+];
+
+/// Line name of the non-temporal threshold in the text form.
+const NON_TEMPORAL: &str = "non-temporal";
+
+/// A snapshot of the dispatch thresholds.
+///
+/// [`thresholds`] reads the installed ones, [`calibrate`] measures new ones,
+/// and [`set_thresholds`] installs a snapshot. To skip calibrating on every
+/// start, save one with its [`Display`](core::fmt::Display) form and parse it
+/// back with [`FromStr`](core::str::FromStr):
+///
+/// ```
+/// use base64_turbo::{Thresholds, set_thresholds, thresholds};
+///
+/// let saved = thresholds().to_string();
+/// // ... later, in another process on the same host:
+/// let loaded: Thresholds = saved.parse().unwrap();
+/// set_thresholds(&loaded);
+/// assert_eq!(thresholds(), loaded);
+/// ```
+///
+/// The text form is one line per compiled-in SIMD kernel, its name (see
+/// [`Backend::name`]) followed by its encode and decode thresholds, plus a
+/// `non-temporal` line where a kernel streams. Parsing starts from
+/// [`Thresholds::default`] and overrides whatever lines it finds; lines for
+/// kernels this build does not compile in are ignored, so a snapshot saved by
+/// a build with more features still loads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    /// `(encode, decode)` per backend, indexed as [`BACKENDS`]; `None` for
+    /// scalar and for kernels that are not compiled in.
+    kernels: [Option<(usize, usize)>; BACKENDS.len()],
+    /// `None` when no compiled-in kernel streams.
+    non_temporal: Option<usize>,
+}
+
+impl Thresholds {
+    /// The shortest input `backend`'s encoder is entered for, or `None` if its
+    /// kernel is not compiled in (or is the scalar one, which has no
+    /// threshold). Shorter inputs run the backend's short-input kernel where
+    /// it has one, and scalar otherwise.
+    #[inline]
+    #[must_use]
+    pub const fn encode_min(&self, backend: Backend) -> Option<usize> {
+        match self.kernels[backend as usize] {
+            Some((encode, _)) => Some(encode),
+            None => None,
+        }
+    }
+
+    /// The shortest input `backend`'s decoder is entered for; see
+    /// [`encode_min`](Self::encode_min).
+    #[inline]
+    #[must_use]
+    pub const fn decode_min(&self, backend: Backend) -> Option<usize> {
+        match self.kernels[backend as usize] {
+            Some((_, decode)) => Some(decode),
+            None => None,
+        }
+    }
+
+    /// The input length from which the AVX2 and AVX512-VBMI kernels write
+    /// with non-temporal stores, or `None` if neither is compiled in.
+    #[inline]
+    #[must_use]
+    pub const fn non_temporal_min(&self) -> Option<usize> {
+        self.non_temporal
+    }
+}
+
+impl Default for Thresholds {
+    /// The thresholds the crate ships with.
+    fn default() -> Self {
+        #[cfg_attr(not(unsafe_simd), allow(unused_mut))]
+        let mut defaults = Self {
+            kernels: [None; BACKENDS.len()],
+            non_temporal: None,
+        };
+        #[cfg(unsafe_simd)]
+        for backend in BACKENDS {
+            if let Some(kernels) = dispatch::table(backend) {
+                defaults.kernels[backend as usize] =
+                    Some((kernels.encode_min.default(), kernels.decode_min.default()));
+            }
+        }
+        #[cfg(any(x86_avx2, x86_avx512_vbmi))]
+        {
+            defaults.non_temporal = Some(crate::simd::NT_STORE_MIN_LEN);
+        }
+        defaults
+    }
+}
+
+impl core::fmt::Display for Thresholds {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for backend in BACKENDS {
+            if let Some((encode, decode)) = self.kernels[backend as usize] {
+                writeln!(f, "{backend} {encode} {decode}")?;
+            }
+        }
+        if let Some(len) = self.non_temporal {
+            writeln!(f, "{NON_TEMPORAL} {len}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for Thresholds {
+    type Err = ParseThresholdsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parsed = Self::default();
+        for (index, line) in s.lines().enumerate() {
+            let err = ParseThresholdsError { line: index + 1 };
+            let mut words = line.split_whitespace();
+            let Some(name) = words.next() else {
+                continue;
+            };
+            let mut lens = words.map(str::parse::<usize>);
+
+            if name == NON_TEMPORAL {
+                let (Some(Ok(len)), None) = (lens.next(), lens.next()) else {
+                    return Err(err);
+                };
+                if parsed.non_temporal.is_some() {
+                    parsed.non_temporal = Some(len);
+                }
+                continue;
+            }
+
+            let backend = BACKENDS
+                .into_iter()
+                .find(|backend| backend.name() == name)
+                .ok_or(err)?;
+            let (Some(Ok(encode)), Some(Ok(decode)), None) =
+                (lens.next(), lens.next(), lens.next())
+            else {
+                return Err(err);
+            };
+            let slot = &mut parsed.kernels[backend as usize];
+            if slot.is_some() {
+                *slot = Some((encode, decode));
+            }
+        }
+        Ok(parsed)
+    }
+}
+
+/// Returned when parsing [`Thresholds`] from text fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseThresholdsError {
+    line: usize,
+}
+
+impl ParseThresholdsError {
+    /// The 1-based line that could not be parsed.
+    #[inline]
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl core::fmt::Display for ParseThresholdsError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Invalid thresholds on line {}", self.line)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseThresholdsError {}
+
+/// The thresholds currently installed: the defaults, or whatever
+/// [`set_thresholds`] or [`calibrate`] installed last.
+#[must_use]
+pub fn thresholds() -> Thresholds {
+    #[cfg_attr(not(unsafe_simd), allow(unused_mut))]
+    let mut installed = Thresholds::default();
+    #[cfg(unsafe_simd)]
+    for backend in BACKENDS {
+        if let Some(kernels) = dispatch::table(backend) {
+            installed.kernels[backend as usize] =
+                Some((kernels.encode_min.get(), kernels.decode_min.get()));
+        }
+    }
+    #[cfg(any(x86_avx2, x86_avx512_vbmi))]
+    {
+        installed.non_temporal = Some(crate::simd::nt_store_min_len());
+    }
+    installed
+}
+
+/// Installs `thresholds` for every engine, pinned or not, from the next call.
+///
+/// Values are kept within what each kernel supports: a threshold above the
+/// longest input a backend's short-input kernel takes is lowered to it, and a
+/// non-temporal threshold below 64 KiB is raised to that. Read the result back
+/// with [`thresholds`].
+#[cfg_attr(not(unsafe_simd), allow(unused_variables))]
+// Only `const`-eligible in builds without a SIMD kernel.
+#[allow(clippy::missing_const_for_fn)]
+pub fn set_thresholds(thresholds: &Thresholds) {
+    #[cfg(unsafe_simd)]
+    for backend in BACKENDS {
+        if let (Some(kernels), Some((encode, decode))) = (
+            dispatch::table(backend),
+            thresholds.kernels[backend as usize],
+        ) {
+            kernels.encode_min.set(encode);
+            kernels.decode_min.set(decode);
+        }
+    }
+    #[cfg(any(x86_avx2, x86_avx512_vbmi))]
+    if let Some(len) = thresholds.non_temporal {
+        crate::simd::set_nt_store_min_len(len);
+    }
+}
+
+/// Measures the thresholds on this host, installs them, and returns what was
+/// installed.
+///
+/// Every compiled-in kernel the CPU supports is timed against its fallback
+/// over a sweep of short inputs, and its thresholds are set where it stops
+/// losing. If [`crate::active_backend`] is the AVX2 or AVX512-VBMI kernel, the
+/// non-temporal stores are also timed against ordinary ones, over inputs of
+/// 1 to 16 MiB. Kernels the CPU cannot run keep their current thresholds.
+///
+/// This takes on the order of a second in a release build, and runs the
+/// kernels on the calling thread. Other threads encoding meanwhile stay
+/// correct but may see the thresholds change under them, so call it at
+/// startup; to avoid paying for it on every start, save the result (see
+/// [`Thresholds`]) and install it with [`set_thresholds`] instead.
+#[cfg(feature = "std")]
+// Called to install the thresholds as often as to read them.
+#[allow(clippy::must_use_candidate)]
+pub fn calibrate() -> Thresholds {
+    #[cfg_attr(not(unsafe_simd), allow(unused_mut))]
+    let mut measured = thresholds();
+    #[cfg(unsafe_simd)]
+    for backend in BACKENDS {
+        if let (Some(kernels), true) = (dispatch::table(backend), backend.is_supported()) {
+            measured.kernels[backend as usize] =
+                Some((sweep::encode_min(kernels), sweep::decode_min(kernels)));
+        }
+    }
+    #[cfg(any(x86_avx2, x86_avx512_vbmi))]
+    if matches!(crate::active_backend(), Backend::Avx2 | Backend::Avx512Vbmi) {
+        measured.non_temporal = Some(sweep::non_temporal_min(dispatch::for_backend(
+            crate::active_backend(),
+        )));
+    }
+    set_thresholds(&measured);
+    thresholds()
+}
+
+/// The timing behind [`calibrate`]. Every kernel call here is on a table whose
+/// backend passed [`Backend::is_supported`], into a buffer the size the public
+/// API would give it.
+#[cfg(all(feature = "std", unsafe_simd))]
+mod sweep {
+    use crate::dispatch::{DecodeFn, EncodeFn, Kernels};
+    use crate::{Config, scalar};
+    use core::hint::black_box;
+    use std::time::{Duration, Instant};
+
+    /// Longest input the crossover sweeps time. Every default threshold sits
+    /// well below it.
+    const SWEEP_MAX: usize = 64;
+    /// Calls per timed batch, and batches per length; the fastest batch is
+    /// kept, which discards most interrupts and frequency changes.
+    const CALLS: u32 = 64;
+    const BATCHES: u32 = 5;
+
+    const PADDED: Config = Config {
+        url_safe: false,
+        padding: true,
+        lenient: false,
+    };
+    const UNPADDED: Config = Config {
+        url_safe: false,
+        padding: false,
+        lenient: false,
+    };
+
+    fn fastest(calls: u32, mut run: impl FnMut()) -> Duration {
+        (0..BATCHES)
+            .map(|_| {
+                let start = Instant::now();
+                for _ in 0..calls {
+                    run();
+                }
+                start.elapsed()
+            })
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// One past the longest swept length at which the kernel still loses to
+    /// its fallback, so that from there on it wins at every length timed.
+    fn crossover(lens: impl Iterator<Item = usize>, mut loses: impl FnMut(usize) -> bool) -> usize {
+        lens.filter(|&len| loses(len))
+            .last()
+            .map_or(0, |len| len + 1)
+    }
+
+    pub(super) fn encode_min(kernels: &Kernels) -> usize {
+        let input: [u8; SWEEP_MAX] = core::array::from_fn(|i| i.to_le_bytes()[0]);
+        let mut dst = [0u8; SWEEP_MAX.div_ceil(3) * 4];
+        let mut time = |encode: EncodeFn, len: usize| {
+            let (input, dst) = (&input[..len], &mut dst[..len.div_ceil(3) * 4]);
+            fastest(CALLS, || {
+                // SAFETY: see the module docs.
+                unsafe { encode(&PADDED, black_box(input), black_box(&mut *dst)) };
+            })
+        };
+        crossover(1..=SWEEP_MAX, |len| {
+            let fallback = match kernels.encode_short {
+                Some(short) if len < kernels.encode_min.max() => short,
+                _ => scalar::encode_slice,
+            };
+            time(kernels.encode, len) > time(fallback, len)
+        })
+    }
+
+    pub(super) fn decode_min(kernels: &Kernels) -> usize {
+        let input: [u8; SWEEP_MAX] = core::array::from_fn(|i| crate::STANDARD_ALPHABET[i % 64]);
+        let mut dst = [0u8; (SWEEP_MAX / 4 + 1) * 3];
+        let mut time = |decode: DecodeFn, len: usize| {
+            let (input, dst) = (&input[..len], &mut dst[..(len / 4 + 1) * 3]);
+            fastest(CALLS, || {
+                // SAFETY: see the module docs.
+                let _ = black_box(unsafe { decode(&UNPADDED, black_box(input), dst) });
+            })
+        };
+        // An unpadded input one past a whole group is invalid at any length.
+        let lens = (1..=SWEEP_MAX).filter(|len| len % 4 != 1);
+        crossover(lens, |len| {
+            let fallback = match kernels.decode_short {
+                Some(short) if len < kernels.decode_min.max() => short,
+                _ => scalar::decode_slice,
+            };
+            time(kernels.decode, len) > time(fallback, len)
+        })
+    }
+
+    /// The smallest swept size from which streaming wins at every larger one,
+    /// or twice the largest if it loses there. Sizes are input bytes, encoded
+    /// and then decoded back, so both directions weigh in.
+    #[cfg(any(x86_avx2, x86_avx512_vbmi))]
+    pub(super) fn non_temporal_min(kernels: &Kernels) -> usize {
+        const SIZES: [usize; 5] = [1 << 20, 2 << 20, 4 << 20, 8 << 20, 16 << 20];
+        const ALIGN: usize = 64;
+        let largest = SIZES[SIZES.len() - 1];
+
+        let input: Vec<u8> = (0..largest).map(|i| i.to_le_bytes()[0]).collect();
+        // The AVX2 decoder streams only into a 16-byte aligned destination, so
+        // both buffers start on a cache line.
+        let mut encoded = vec![0u8; largest.div_ceil(3) * 4 + ALIGN];
+        let mut decoded = vec![0u8; largest + ALIGN];
+        let encoded_at = encoded.as_ptr().align_offset(ALIGN);
+        let decoded_at = decoded.as_ptr().align_offset(ALIGN);
+
+        let mut time = |size: usize, streaming: bool| {
+            crate::simd::set_nt_store_min_len(if streaming { 0 } else { usize::MAX });
+            let chars = size.div_ceil(3) * 4;
+            let encoded = &mut encoded[encoded_at..][..chars];
+            let decoded = &mut decoded[decoded_at..][..(chars / 4 + 1) * 3];
+            fastest(1, || {
+                // SAFETY: see the module docs.
+                unsafe { (kernels.encode)(&PADDED, &input[..size], encoded) };
+                // SAFETY: see the module docs.
+                let _ = black_box(unsafe { (kernels.decode)(&PADDED, encoded, decoded) });
+            })
+        };
+
+        let mut min = SIZES[0];
+        for size in SIZES {
+            if time(size, true) > time(size, false) {
+                min = size * 2;
+            }
+        }
+        min
+    }
+}
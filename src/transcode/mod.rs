@@ -0,0 +1,468 @@
+//! Direct conversion between Base64 engines, and between Base64 and hex,
+//! without decoding to bytes.
+//!
+//! Every Base64 alphabet this crate has maps the same 6-bit value to the same
+//! position, so converting one to another is a character-for-character remap:
+//! one table lookup per character in scalar, one `vpermi2b` per 64 characters
+//! on AVX512-VBMI, and on AVX2 and NEON the decoder's nibble lookup straight
+//! into the encoder's translate step, validating as it goes. Only the final
+//! group or so changes shape, when padding is added or stripped; that is
+//! decoded and re-encoded on its own, through a stack buffer.
+//!
+//! Base64 and hex meet every 3 bytes, 4 characters to 6 digits, so the same
+//! split works for them: whole groups go through a kernel that decodes one
+//! format and encodes the other in registers (on AVX512-VBMI, AVX2 and NEON,
+//! the two formats' own vector steps back to back), and the final, possibly
+//! padded, group goes through the scalar codecs.
+
+// Every kernel takes `&Config`, and the helpers here pass theirs along.
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use crate::{Backend, Config, Engine, Error, STANDARD_ALPHABET, URL_SAFE_ALPHABET, hex};
+
+// Scalar kernels. The vector ones live with their Base64 siblings under
+// `crate::simd`.
+pub(crate) mod scalar;
+
+/// Compile-time remap table: each character of `from` to the one with the
+/// same value in `to`, `0xFF` for anything outside `from`.
+#[allow(clippy::cast_possible_truncation)] // every entry is a character, < 128
+pub(crate) const fn remap_table(from: &[u8; 64], to: &[u8; 64]) -> [u8; 256] {
+    let mut table = [0xFF; 256];
+    let mut i = 0;
+    while i < 64 {
+        table[from[i] as usize] = to[i];
+        i += 1;
+    }
+    table
+}
+
+/// A remap table for `LENIENT`: both alphabets' characters to the one with
+/// the same value in `to`.
+const fn lenient_remap_table(to: &[u8; 64]) -> [u8; 256] {
+    let mut table = remap_table(STANDARD_ALPHABET, to);
+    table[b'-' as usize] = to[62];
+    table[b'_' as usize] = to[63];
+    table
+}
+
+/// The remap tables, indexed by [`source_alphabet`] and the target's
+/// `url_safe`.
+pub(crate) static REMAP_TABLES: [[[u8; 256]; 2]; 3] = [
+    [
+        remap_table(STANDARD_ALPHABET, STANDARD_ALPHABET),
+        remap_table(STANDARD_ALPHABET, URL_SAFE_ALPHABET),
+    ],
+    [
+        remap_table(URL_SAFE_ALPHABET, STANDARD_ALPHABET),
+        remap_table(URL_SAFE_ALPHABET, URL_SAFE_ALPHABET),
+    ],
+    [
+        lenient_remap_table(STANDARD_ALPHABET),
+        lenient_remap_table(URL_SAFE_ALPHABET),
+    ],
+];
+
+/// Which row of [`REMAP_TABLES`] `from` decodes through: 0 for Standard, 1
+/// for URL-Safe, 2 for either.
+#[inline]
+const fn source_alphabet(from: &Config) -> usize {
+    if from.lenient {
+        2
+    } else {
+        from.url_safe as usize
+    }
+}
+
+/// The table that remaps `from`'s alphabet to `to`'s.
+#[inline]
+pub(crate) fn remap_table_for(from: &Config, to: &Config) -> &'static [u8; 256] {
+    &REMAP_TABLES[source_alphabet(from)][usize::from(to.url_safe)]
+}
+
+/// Where the remapped body of `len` characters ends: the scalar decoder's
+/// fast loop bound, a multiple of 8 that leaves at least 4 characters over.
+/// Padding is only recognized after it, so everything before it is whole
+/// groups of alphabet characters and remaps one for one.
+#[inline]
+const fn body_len(len: usize) -> usize {
+    len.saturating_sub(4) / 8 * 8
+}
+
+/// Decodes the final characters of an input under `from`, the part that may
+/// change length. At most 11 characters, so at most 8 bytes.
+///
+/// The scalar decoder stops at the first padded group and ignores whatever
+/// follows it, which here would silently drop those characters, so a padded
+/// group must be the last one.
+#[inline]
+fn decode_tail(from: Engine, tail: &[u8]) -> Result<([u8; 8], usize), Error> {
+    let mut bytes = [0u8; 8];
+    let n = crate::scalar::decode_slice(&from.config, tail, &mut bytes)?;
+    if let Some(pad) = tail.iter().position(|&c| c == b'=')
+        && (pad / 4 + 1) * 4 != tail.len()
+    {
+        return Err(Error::InvalidCharacter);
+    }
+    Ok((bytes, n))
+}
+
+/// Remaps `src`, in `from`'s alphabet, into `dst` in `to`'s, on `backend`'s
+/// kernel.
+#[inline]
+fn remap(
+    backend: Backend,
+    from: &Config,
+    to: &Config,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    match backend {
+        // SAFETY: the backend is supported, so the CPU has VBMI.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => unsafe { crate::simd::remap_avx512_vbmi(from, to, src, dst) },
+        // SAFETY: the backend is supported, so the CPU has AVX2 (AVX512BW and
+        // VBMI256 imply it).
+        #[cfg(x86_avx2)]
+        Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+            crate::simd::remap_avx2(from, to, src, dst)
+        },
+        // SAFETY: NEON is mandatory on aarch64.
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        Backend::Neon => unsafe { crate::simd::remap_neon(from, to, src, dst) },
+        _ => scalar::remap(remap_table_for(from, to), src, dst),
+    }
+}
+
+/// Remaps `buf` where it lies, from `from`'s alphabet to `to`'s, on
+/// `backend`'s kernel.
+#[inline]
+fn remap_in_place(
+    backend: Backend,
+    from: &Config,
+    to: &Config,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    match backend {
+        // SAFETY: as in `remap`.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => unsafe { crate::simd::remap_in_place_avx512_vbmi(from, to, buf) },
+        // SAFETY: as in `remap`.
+        #[cfg(x86_avx2)]
+        Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+            crate::simd::remap_in_place_avx2(from, to, buf)
+        },
+        // SAFETY: as in `remap`.
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        Backend::Neon => unsafe { crate::simd::remap_in_place_neon(from, to, buf) },
+        _ => scalar::remap_in_place(remap_table_for(from, to), buf),
+    }
+}
+
+/// Decodes whole Base64 groups straight to hex on `backend`'s kernel.
+#[inline]
+fn base64_to_hex_body(
+    backend: Backend,
+    from: Engine,
+    upper: bool,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    match backend {
+        // SAFETY: as in `remap`.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => unsafe {
+            crate::simd::base64_to_hex_avx512_vbmi(&from.config, upper, src, dst)
+        },
+        // SAFETY: as in `remap`.
+        #[cfg(x86_avx2)]
+        Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+            crate::simd::base64_to_hex_avx2(&from.config, upper, src, dst)
+        },
+        // SAFETY: as in `remap`.
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        Backend::Neon => unsafe { crate::simd::base64_to_hex_neon(&from.config, upper, src, dst) },
+        _ => scalar::base64_to_hex(&from.config, upper, src, dst),
+    }
+}
+
+/// Encodes whole 6-digit hex groups straight to Base64 on `backend`'s kernel.
+#[inline]
+fn hex_to_base64_body(
+    backend: Backend,
+    to: Engine,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    match backend {
+        // SAFETY: as in `remap`.
+        #[cfg(x86_avx512_vbmi)]
+        Backend::Avx512Vbmi => unsafe {
+            crate::simd::hex_to_base64_avx512_vbmi(&to.config, src, dst)
+        },
+        // SAFETY: as in `remap`.
+        #[cfg(x86_avx2)]
+        Backend::Avx2 | Backend::Avx512Bw | Backend::Avx512Vbmi256 => unsafe {
+            crate::simd::hex_to_base64_avx2(&to.config, src, dst)
+        },
+        // SAFETY: as in `remap`.
+        #[cfg(all(target_arch = "aarch64", feature = "neon"))]
+        Backend::Neon => unsafe { crate::simd::hex_to_base64_neon(&to.config, src, dst) },
+        _ => scalar::hex_to_base64(&to.config, src, dst),
+    }
+}
+
+/// Converts Base64 `input` from `from`'s alphabet and padding to `to`'s,
+/// writing the result into `output`, without decoding it to bytes first.
+///
+/// The result, and any error, is what `to.encode(from.decode(input)?)` would
+/// give, in a single pass and with no intermediate buffer: the body is remapped
+/// character for character (one `vpermi2b` per 64 characters on AVX512-VBMI,
+/// the decoder's lookup into the encoder's translate step on AVX2 and NEON,
+/// which `from`'s [`backend`](Engine::backend) selects; scalar elsewhere), and
+/// only the final group is re-encoded, to add or strip padding. The one
+/// difference: characters after a padded group are rejected, where the scalar
+/// decoder stops at the padding and ignores them.
+///
+/// # Returns
+///
+/// * `Ok(usize)`: The number of characters written to `output`.
+/// * `Err(Error)`: If the input is invalid or the buffer is too small.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidLength`] / [`Error::InvalidCharacter`] if `input`
+/// is not valid Base64 under `from`, and [`Error::BufferTooSmall`] if `output`
+/// cannot hold the result: at most `input.len() + 2` characters, and at most
+/// `input.len()` when `to` does not pad.
+///
+/// # Examples
+///
+/// ```
+/// use base64_turbo::{STANDARD, URL_SAFE_NO_PAD, transcode};
+///
+/// let mut buf = [0u8; 16];
+/// let n = transcode(&STANDARD, &URL_SAFE_NO_PAD, "+/+/ab8=", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"-_-_ab8");
+/// ```
+#[inline]
+pub fn transcode<T: AsRef<[u8]>>(
+    from: &Engine,
+    to: &Engine,
+    input: T,
+    output: &mut [u8],
+) -> Result<usize, Error> {
+    let input = input.as_ref();
+    let body = body_len(input.len());
+
+    // The tail decides the output length, so it goes first; its error still
+    // loses to a bad character in the body, as in `decode`.
+    let tail = decode_tail(*from, &input[body..]);
+    let req_len = body + tail.as_ref().map_or(0, |&(_, n)| to.encoded_len(n));
+    if output.len() < req_len {
+        return Err(Error::BufferTooSmall);
+    }
+
+    remap(
+        from.backend(),
+        &from.config,
+        &to.config,
+        &input[..body],
+        &mut output[..body],
+    )?;
+
+    let (bytes, n) = tail?;
+    crate::scalar::encode_slice(&to.config, &bytes[..n], &mut output[body..req_len]);
+    Ok(req_len)
+}
+
+/// [`transcode`] in place: converts the Base64 in `buf[..len]` from `from`'s
+/// alphabet and padding to `to`'s, and returns the new length.
+///
+/// The length only changes by the padding, so `buf` needs at most two bytes
+/// of room past `len`, and none when `to` does not pad. On error the contents
+/// of `buf[..len]` are unspecified.
+///
+/// # Errors
+///
+/// As [`transcode`]: [`Error::InvalidLength`] / [`Error::InvalidCharacter`] if
+/// `buf[..len]` is not valid Base64 under `from`, and [`Error::BufferTooSmall`]
+/// if `buf` cannot hold the result, or is shorter than `len` to begin with.
+///
+/// # Examples
+///
+/// ```
+/// use base64_turbo::{STANDARD, URL_SAFE_NO_PAD, transcode_in_place};
+///
+/// let mut buf = *b"-_-_ab8\0";
+/// let n = transcode_in_place(&URL_SAFE_NO_PAD, &STANDARD, &mut buf, 7).unwrap();
+/// assert_eq!(&buf[..n], b"+/+/ab8=");
+/// ```
+#[inline]
+pub fn transcode_in_place(
+    from: &Engine,
+    to: &Engine,
+    buf: &mut [u8],
+    len: usize,
+) -> Result<usize, Error> {
+    if len > buf.len() {
+        return Err(Error::BufferTooSmall);
+    }
+    let body = body_len(len);
+
+    // As in `transcode`. The tail is decoded out of `buf` before anything is
+    // written over it.
+    let tail = decode_tail(*from, &buf[body..len]);
+    let req_len = body + tail.as_ref().map_or(0, |&(_, n)| to.encoded_len(n));
+    if buf.len() < req_len {
+        return Err(Error::BufferTooSmall);
+    }
+
+    remap_in_place(from.backend(), &from.config, &to.config, &mut buf[..body])?;
+
+    let (bytes, n) = tail?;
+    crate::scalar::encode_slice(&to.config, &bytes[..n], &mut buf[body..req_len]);
+    Ok(req_len)
+}
+
+/// Converts Base64 `input` under `from` straight to hex under `to`, writing
+/// the digits into `output`, without decoding it to a buffer of bytes first.
+///
+/// The result, and any error, is what `to.encode(from.decode(input)?)` would
+/// give, with [`transcode`]'s one difference. The body is converted in
+/// registers on the vector backend `from`'s [`backend`](Engine::backend)
+/// selects, the Base64 decoder's lookup and pack steps feeding the hex
+/// encoder's nibble lookups:
+///
+/// * AVX512-VBMI: each 64 characters are packed to 48 bytes and expanded to
+///   96 digits.
+/// * AVX2, which AVX512BW and the 256-bit VBMI tier also take: each 32
+///   characters are packed to 24 bytes, 12 per 128-bit lane, and expanded to
+///   48 digits, stored a lane at a time.
+/// * NEON: each 16 characters are packed to 12 bytes and zipped out to 24
+///   digits.
+///
+/// Other backends use the scalar codecs throughout, and the final group
+/// always goes through them.
+///
+/// # Returns
+///
+/// * `Ok(usize)`: The number of digits written to `output`.
+/// * `Err(Error)`: If the input is invalid or the buffer is too small.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidLength`] / [`Error::InvalidCharacter`] if `input`
+/// is not valid Base64 under `from`, and [`Error::BufferTooSmall`] if `output`
+/// cannot hold twice its decoded length.
+///
+/// # Examples
+///
+/// ```
+/// use base64_turbo::{STANDARD, base64_to_hex, hex::HEX_LOWER};
+///
+/// let mut buf = [0u8; 8];
+/// let n = base64_to_hex(&STANDARD, &HEX_LOWER, "3q2+7w==", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"deadbeef");
+/// ```
+#[inline]
+pub fn base64_to_hex<T: AsRef<[u8]>>(
+    from: &Engine,
+    to: &hex::Engine,
+    input: T,
+    output: &mut [u8],
+) -> Result<usize, Error> {
+    let input = input.as_ref();
+    let body = body_len(input.len());
+    let digits = body / 4 * 6;
+
+    // As in `transcode`.
+    let tail = decode_tail(*from, &input[body..]);
+    let req_len = digits + tail.as_ref().map_or(0, |&(_, n)| to.encoded_len(n));
+    if output.len() < req_len {
+        return Err(Error::BufferTooSmall);
+    }
+
+    base64_to_hex_body(
+        from.backend(),
+        *from,
+        to.upper,
+        &input[..body],
+        &mut output[..digits],
+    )?;
+
+    let (bytes, n) = tail?;
+    hex::scalar::encode_slice(to.upper, &bytes[..n], &mut output[digits..req_len]);
+    Ok(req_len)
+}
+
+/// Converts hex `input`, in either case, straight to Base64 under `to`,
+/// writing the characters into `output`, without decoding it to a buffer of
+/// bytes first.
+///
+/// The result, and any error, is what `to.encode(from.decode(input)?)` would
+/// give. The body is converted in registers on the vector backend `from`'s
+/// [`backend`](hex::Engine::backend) selects, the hex decoder's range checks
+/// feeding the Base64 encoder's round:
+///
+/// * AVX512-VBMI: each 96 digits are decoded to 48 bytes and gathered
+///   straight into the encoder's layout, for 64 characters.
+/// * AVX2, which AVX512BW and the 256-bit VBMI tier also take: each 48 digits
+///   are paired into 24 bytes by `vpmaddubsw`, and one `vpermd` lays them out
+///   for the encoder's round, for 32 characters.
+/// * NEON: each 24 digits are split into high and low by an `uzp`, decoded to
+///   12 bytes, and encoded to 16 characters.
+///
+/// Other backends use the scalar codecs throughout, and the final, possibly
+/// padded, group always goes through them.
+///
+/// # Returns
+///
+/// * `Ok(usize)`: The number of characters written to `output`.
+/// * `Err(Error)`: If the input is invalid or the buffer is too small.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidLength`] if `input` has an odd length,
+/// [`Error::BufferTooSmall`] if `output` is shorter than `to`'s
+/// [`encoded_len`](Engine::encoded_len) of half of it, and
+/// [`Error::InvalidCharacter`] if `input` holds anything but hex digits.
+///
+/// # Examples
+///
+/// ```
+/// use base64_turbo::{URL_SAFE_NO_PAD, hex::HEX_LOWER, hex_to_base64};
+///
+/// let mut buf = [0u8; 8];
+/// let n = hex_to_base64(&HEX_LOWER, &URL_SAFE_NO_PAD, "DEADbeef", &mut buf).unwrap();
+/// assert_eq!(&buf[..n], b"3q2-7w");
+/// ```
+#[inline]
+pub fn hex_to_base64<T: AsRef<[u8]>>(
+    from: &hex::Engine,
+    to: &Engine,
+    input: T,
+    output: &mut [u8],
+) -> Result<usize, Error> {
+    let input = input.as_ref();
+    let len = input.len();
+    if !len.is_multiple_of(2) {
+        return Err(Error::InvalidLength);
+    }
+
+    let req_len = to.encoded_len(len / 2);
+    if output.len() < req_len {
+        return Err(Error::BufferTooSmall);
+    }
+
+    // Whole 3-byte groups, then the final 0 to 2 bytes with their padding.
+    let body = len / 6 * 6;
+    let chars = body / 6 * 4;
+    hex_to_base64_body(from.backend(), *to, &input[..body], &mut output[..chars])?;
+
+    let mut bytes = [0u8; 2];
+    let n = (len - body) / 2;
+    hex::scalar::decode_slice(&input[body..], &mut bytes)?;
+    crate::scalar::encode_slice(&to.config, &bytes[..n], &mut output[chars..req_len]);
+    Ok(req_len)
+}
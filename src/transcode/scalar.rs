@@ -0,0 +1,91 @@
+//! Scalar (non-SIMD) transcoding.
+//!
+//! **100% safe Rust.** The Base64 remap is one table lookup per character,
+//! with validation falling out of OR-ing the looked-up characters together:
+//! every valid one is ASCII, and anything outside the source alphabet maps to
+//! `0xFF`. Base64 to and from hex go a group at a time through the two
+//! formats' own scalar kernels, three bytes at once, so no buffer of bytes
+//! builds up between them. The VBMI kernels are tested against these.
+
+#![forbid(unsafe_code)]
+// `&Config`, as in the Base64 scalar kernel and the SIMD ones beside these.
+#![allow(clippy::trivially_copy_pass_by_ref)]
+
+use crate::{Config, Error};
+
+/// Remaps `src` into `dst`, which must be the same length, through `table`.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside the source
+/// alphabet. `dst` is then partly written.
+#[inline]
+pub(crate) fn remap(table: &[u8; 256], src: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    let mut bad = 0u8;
+    for (out, &c) in dst.iter_mut().zip(src) {
+        *out = table[usize::from(c)];
+        bad |= *out;
+    }
+    if bad < 0x80 {
+        Ok(())
+    } else {
+        Err(Error::InvalidCharacter)
+    }
+}
+
+/// Remaps `buf` where it lies, through `table`.
+///
+/// # Errors
+/// As [`remap`].
+#[inline]
+pub(crate) fn remap_in_place(table: &[u8; 256], buf: &mut [u8]) -> Result<(), Error> {
+    let mut bad = 0u8;
+    for c in buf.iter_mut() {
+        *c = table[usize::from(*c)];
+        bad |= *c;
+    }
+    if bad < 0x80 {
+        Ok(())
+    } else {
+        Err(Error::InvalidCharacter)
+    }
+}
+
+/// Decodes `src`, whole Base64 groups without padding, straight to hex in
+/// `dst`, which must hold `src.len() / 4 * 6` digits.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] for a character outside `config`'s
+/// alphabet, `'='` included. `dst` is then partly written.
+#[inline]
+pub(crate) fn base64_to_hex(
+    config: &Config,
+    upper: bool,
+    src: &[u8],
+    dst: &mut [u8],
+) -> Result<(), Error> {
+    let mut bytes = [0u8; 3];
+    for (chars, digits) in src.chunks_exact(4).zip(dst.chunks_exact_mut(6)) {
+        crate::scalar::decode_slice(config, chars, &mut bytes)?;
+        if chars[3] == b'=' {
+            return Err(Error::InvalidCharacter);
+        }
+        crate::hex::scalar::encode_slice(upper, &bytes, digits);
+    }
+    Ok(())
+}
+
+/// Encodes hex `src`, whole 6-digit groups in either case, straight to
+/// Base64 in `dst`, which must hold `src.len() / 6 * 4` characters.
+///
+/// # Errors
+/// Returns [`Error::InvalidCharacter`] if `src` holds anything but hex digits.
+/// `dst` is then partly written.
+#[inline]
+pub(crate) fn hex_to_base64(config: &Config, src: &[u8], dst: &mut [u8]) -> Result<(), Error> {
+    let mut bytes = [0u8; 3];
+    for (digits, chars) in src.chunks_exact(6).zip(dst.chunks_exact_mut(4)) {
+        crate::hex::scalar::decode_slice(digits, &mut bytes)?;
+        crate::scalar::encode_slice(config, &bytes, chars);
+    }
+    Ok(())
+}
@@ -0,0 +1,293 @@
+//! Helpers shared by the integration tests: random input, backend pinning, and
+//! the oracle, length, buffer and rejection checks every codec's `Engine` goes
+//! through. Each test file keeps only its codec's own oracle and edge cases.
+#![allow(dead_code)] // every test binary uses a different subset
+
+use base64_turbo::{Backend, Error, Unsupported, base32, base45, base85, crypt, hex, radix};
+use rand::{RngExt, rng};
+
+// ======================================================================
+// Input & Backends
+// ======================================================================
+
+pub(crate) fn random_bytes(len: usize) -> Vec<u8> {
+    let mut rng = rng();
+    (0..len).map(|_| rng.random()).collect()
+}
+
+/// Every backend, supported here or not.
+pub(crate) const BACKENDS: [Backend; 9] = [
+    Backend::Scalar,
+    Backend::Ssse3,
+    Backend::Avx2,
+    Backend::Avx512Bw,
+    Backend::Avx512Vbmi256,
+    Backend::Avx512Vbmi,
+    Backend::Neon,
+    Backend::WasmSimd,
+    Backend::Portable,
+];
+
+/// An engine pinned, through `with_backend`, to every backend this build and
+/// CPU support.
+pub(crate) fn pinned<E>(
+    with_backend: impl Fn(Backend) -> Result<E, Unsupported>,
+) -> impl Iterator<Item = (Backend, E)> {
+    BACKENDS.into_iter().filter_map(move |backend| {
+        let engine = with_backend(backend).ok();
+        if engine.is_none() {
+            println!("Skipping {backend} (unsupported on this build or CPU)");
+        }
+        engine.map(|engine| (backend, engine))
+    })
+}
+
+/// Runs `check` on random input of every length up to `max`.
+pub(crate) fn oracle_exhaustive_small(max: usize, mut check: impl FnMut(&[u8])) {
+    for len in 0..=max {
+        check(&random_bytes(len));
+    }
+}
+
+/// Runs `check` on random input a few 4 KiB pieces long, an odd 64 KiB, and
+/// about a megabyte.
+pub(crate) fn oracle_fuzz_large(mut check: impl FnMut(&[u8])) {
+    for len in [4096, 65_537, 1_000_003] {
+        check(&random_bytes(len));
+    }
+}
+
+// ======================================================================
+// Codecs
+// ======================================================================
+
+/// The API every codec's `Engine` shares, as the checks below drive it.
+pub(crate) trait Codec: Copy + core::fmt::Debug {
+    fn encoded_len(self, input_len: usize) -> usize;
+    fn decoded_len(self, input_len: usize) -> usize;
+    fn encode_into(self, input: &[u8], output: &mut [u8]) -> Result<usize, Error>;
+    fn decode_into(self, input: &[u8], output: &mut [u8]) -> Result<usize, Error>;
+    #[cfg(feature = "std")]
+    fn encode(self, input: &[u8]) -> String;
+    #[cfg(feature = "std")]
+    fn decode(self, input: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// A decode buffer length `chars` always fit in.
+    fn decode_buf_len(self, chars: &[u8]) -> usize {
+        self.decoded_len(chars.len())
+    }
+    /// Whether `encoded_len` is the encoding's length, not only a bound on it.
+    fn exact_encoded_len(self) -> bool {
+        true
+    }
+    /// Whether `decoded_len` is the input's length, not only a bound on it.
+    fn exact_decoded_len(self) -> bool {
+        false
+    }
+}
+
+/// What an allocating `encode` returns: a `String`, or for Base85, whose Z85
+/// cannot encode a partial group, a `Result` the checks expect to be `Ok`.
+#[cfg(feature = "std")]
+pub(crate) trait Encoded {
+    fn into_string(self) -> String;
+}
+
+#[cfg(feature = "std")]
+impl Encoded for String {
+    fn into_string(self) -> String {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl Encoded for Result<String, Error> {
+    fn into_string(self) -> String {
+        self.expect("encode failed")
+    }
+}
+
+/// Implements [`Codec`] for `$engine` by forwarding to its inherent methods,
+/// `$decoded_len` being its name for the decoded length; anything after a
+/// `;` overrides a default.
+macro_rules! codec {
+    ($engine:ty, $decoded_len:ident $(; $($overrides:tt)*)?) => {
+        impl Codec for $engine {
+            fn encoded_len(self, input_len: usize) -> usize {
+                <$engine>::encoded_len(&self, input_len)
+            }
+            fn decoded_len(self, input_len: usize) -> usize {
+                <$engine>::$decoded_len(&self, input_len)
+            }
+            fn encode_into(self, input: &[u8], output: &mut [u8]) -> Result<usize, Error> {
+                <$engine>::encode_into(&self, input, output)
+            }
+            fn decode_into(self, input: &[u8], output: &mut [u8]) -> Result<usize, Error> {
+                <$engine>::decode_into(&self, input, output)
+            }
+            #[cfg(feature = "std")]
+            fn encode(self, input: &[u8]) -> String {
+                Encoded::into_string(<$engine>::encode(&self, input))
+            }
+            #[cfg(feature = "std")]
+            fn decode(self, input: &[u8]) -> Result<Vec<u8>, Error> {
+                <$engine>::decode(&self, input)
+            }
+            $($($overrides)*)?
+        }
+    };
+}
+
+codec!(hex::Engine, decoded_len;
+    fn exact_decoded_len(self) -> bool {
+        true
+    }
+);
+codec!(base32::Engine, estimate_decoded_len);
+codec!(base45::Engine, decoded_len;
+    fn exact_decoded_len(self) -> bool {
+        true
+    }
+);
+codec!(crypt::Engine, decoded_len;
+    fn exact_decoded_len(self) -> bool {
+        true
+    }
+);
+codec!(base85::Engine, estimate_decoded_len;
+    // Only Ascii85 frames its encoding, and only it shortens zero groups.
+    fn exact_encoded_len(self) -> bool {
+        self.encoded_len(0) == 0
+    }
+    // Ascii85's `z` is the one way past the estimate: four bytes from one
+    // character.
+    #[allow(clippy::naive_bytecount)] // not worth a dependency here
+    fn decode_buf_len(self, chars: &[u8]) -> usize {
+        let zs = chars.iter().filter(|&&c| c == b'z').count();
+        self.estimate_decoded_len(chars.len()) + 4 * zs
+    }
+);
+codec!(radix::Engine, estimate_decoded_len;
+    fn exact_encoded_len(self) -> bool {
+        false
+    }
+);
+
+// ======================================================================
+// Checks
+// ======================================================================
+
+/// Verifies every API of `engine` against `expected`, the oracle's encoding
+/// of `input`.
+#[track_caller]
+pub(crate) fn assert_oracle_match(engine: impl Codec, input: &[u8], expected: &str) {
+    let mut enc_buf = vec![0u8; engine.encoded_len(input.len())];
+    let enc_len = engine
+        .encode_into(input, &mut enc_buf)
+        .expect("encode_into failed");
+    assert_eq!(
+        &enc_buf[..enc_len],
+        expected.as_bytes(),
+        "Slice Encode mismatch"
+    );
+
+    #[cfg(feature = "std")]
+    assert_eq!(engine.encode(input), expected, "Allocating Encode mismatch");
+
+    assert_decodes(engine, expected.as_bytes(), input);
+}
+
+/// Verifies both decode APIs of `engine` turn `chars` back into `input`.
+#[track_caller]
+pub(crate) fn assert_decodes(engine: impl Codec, chars: &[u8], input: &[u8]) {
+    let mut dec_buf = vec![0u8; engine.decode_buf_len(chars)];
+    let dec_len = engine
+        .decode_into(chars, &mut dec_buf)
+        .expect("decode_into failed");
+    assert_eq!(&dec_buf[..dec_len], input, "Slice Decode mismatch");
+
+    #[cfg(feature = "std")]
+    assert_eq!(
+        engine.decode(chars).expect("decode failed"),
+        input,
+        "Allocating Decode mismatch"
+    );
+}
+
+/// Verifies `engine`'s lengths against `oracle` for every input length up to
+/// `max`, exactly or as bounds, as [`Codec`] says they are.
+#[track_caller]
+pub(crate) fn assert_lens(engine: impl Codec, max: usize, oracle: impl Fn(&[u8]) -> String) {
+    for len in 0..=max {
+        let encoded = oracle(&random_bytes(len));
+        if engine.exact_encoded_len() {
+            assert_eq!(engine.encoded_len(len), encoded.len(), "{len}");
+        }
+        assert!(engine.encoded_len(len) >= encoded.len(), "{len}");
+        if engine.exact_decoded_len() {
+            assert_eq!(engine.decoded_len(encoded.len()), len, "{len}");
+        }
+        assert!(engine.decode_buf_len(encoded.as_bytes()) >= len, "{len}");
+    }
+}
+
+/// Verifies `engine` refuses to encode `input`, or decode it back, into a
+/// buffer one byte short.
+#[track_caller]
+pub(crate) fn assert_buffer_too_small(engine: impl Codec, input: &[u8]) {
+    let mut chars = vec![0u8; engine.encoded_len(input.len())];
+    let n = engine.encode_into(input, &mut chars).unwrap();
+    chars.truncate(n);
+
+    let mut buf = vec![0u8; n.max(input.len())];
+    assert_eq!(
+        engine.encode_into(input, &mut buf[..n - 1]),
+        Err(Error::BufferTooSmall),
+        "encode {n} chars"
+    );
+    assert_eq!(
+        engine.decode_into(&chars, &mut buf[..input.len() - 1]),
+        Err(Error::BufferTooSmall),
+        "decode {} bytes",
+        input.len()
+    );
+}
+
+/// Verifies `engine` rejects each of `chars` as [`Error::InvalidLength`].
+#[track_caller]
+pub(crate) fn assert_rejects_length(
+    engine: impl Codec,
+    chars: impl IntoIterator<Item: AsRef<[u8]>>,
+) {
+    let mut buf = [0u8; 256];
+    for chars in chars {
+        let chars = chars.as_ref();
+        assert_eq!(
+            engine.decode_into(chars, &mut buf),
+            Err(Error::InvalidLength),
+            "{engine:?}: {}",
+            String::from_utf8_lossy(chars)
+        );
+    }
+}
+
+/// Verifies `engine` rejects each of `bad` as [`Error::InvalidCharacter`],
+/// planted at the start, middle and end of `len` copies of `valid` for each
+/// of `lens`.
+#[track_caller]
+pub(crate) fn assert_rejects_chars(engine: impl Codec, lens: &[usize], valid: u8, bad: &[u8]) {
+    let mut buf = [0u8; 256];
+    for &len in lens {
+        for at in [0, len / 2, len - 1] {
+            for &c in bad {
+                let mut chars = vec![valid; len];
+                chars[at] = c;
+                assert_eq!(
+                    engine.decode_into(&chars, &mut buf),
+                    Err(Error::InvalidCharacter),
+                    "{engine:?}: {c:#04x} at {at} of {len}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! Integration tests verifying `base64_turbo::base32` against the reference `base32` crate.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64_turbo::Error;
+use base64_turbo::base32::{
+    BASE32, BASE32_NO_PAD, BASE32HEX, BASE32HEX_NO_PAD, CROCKFORD, Engine, ZBASE32,
+};
+use common::{
+    assert_buffer_too_small, assert_lens, assert_oracle_match, assert_rejects_chars,
+    assert_rejects_length, oracle_exhaustive_small, pinned, random_bytes,
+};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// Every engine, paired with the oracle alphabet that matches it.
+const ENGINES: [(Engine, base32::Alphabet); 6] = [
+    (BASE32, base32::Alphabet::Rfc4648 { padding: true }),
+    (BASE32_NO_PAD, base32::Alphabet::Rfc4648 { padding: false }),
+    (BASE32HEX, base32::Alphabet::Rfc4648Hex { padding: true }),
+    (
+        BASE32HEX_NO_PAD,
+        base32::Alphabet::Rfc4648Hex { padding: false },
+    ),
+    (CROCKFORD, base32::Alphabet::Crockford),
+    (ZBASE32, base32::Alphabet::Z),
+];
+
+/// Verifies every API of `engine` against the `base32` crate.
+#[track_caller]
+fn assert_base32_match(input: &[u8], engine: Engine, oracle: base32::Alphabet) {
+    assert_oracle_match(engine, input, &base32::encode(oracle, input));
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_base32_rfc4648_vectors() {
+    // RFC 4648 section 10.
+    let vectors: [(&[u8], &str, &str); 7] = [
+        (b"", "", ""),
+        (b"f", "MY======", "CO======"),
+        (b"fo", "MZXQ====", "CPNG===="),
+        (b"foo", "MZXW6===", "CPNMU==="),
+        (b"foob", "MZXW6YQ=", "CPNMUOG="),
+        (b"fooba", "MZXW6YTB", "CPNMUOJ1"),
+        (b"foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+    ];
+    for (data, base32, base32hex) in vectors {
+        let mut buf = [0u8; 16];
+        let n = BASE32.encode_into(data, &mut buf).unwrap();
+        assert_eq!(&buf[..n], base32.as_bytes());
+        let n = BASE32HEX.encode_into(data, &mut buf).unwrap();
+        assert_eq!(&buf[..n], base32hex.as_bytes());
+        let n = BASE32HEX.decode_into(base32hex, &mut buf).unwrap();
+        assert_eq!(&buf[..n], data);
+    }
+}
+
+#[test]
+fn test_base32_oracle_exhaustive_small() {
+    oracle_exhaustive_small(200, |data| {
+        for (engine, oracle) in ENGINES {
+            assert_base32_match(data, engine, oracle);
+        }
+    });
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_base32_oracle_fuzz_large() {
+    common::oracle_fuzz_large(|data| {
+        for (engine, oracle) in ENGINES {
+            assert_base32_match(data, engine, oracle);
+        }
+    });
+}
+
+#[test]
+fn test_base32_with_backend_matches_oracle() {
+    for (base, oracle) in ENGINES {
+        for (backend, engine) in pinned(|backend| base.with_backend(backend)) {
+            assert_eq!(engine.backend(), backend);
+
+            for len in (0..=100).chain([255, 1024, 4099]) {
+                assert_base32_match(&random_bytes(len), engine, oracle);
+            }
+        }
+    }
+}
+
+// ======================================================================
+// 2. Coverage: Alphabet Rules
+// ======================================================================
+
+#[test]
+fn test_base32_crockford_decode_is_lenient() {
+    let data = random_bytes(200);
+    let canonical = base32::encode(base32::Alphabet::Crockford, &data);
+    let mut buf = vec![0u8; data.len()];
+
+    let lower = canonical.to_ascii_lowercase();
+    let n = CROCKFORD.decode_into(&lower, &mut buf).unwrap();
+    assert_eq!(&buf[..n], data);
+
+    let aliased: String = canonical
+        .chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' => 'L',
+            c => c,
+        })
+        .collect();
+    let n = CROCKFORD.decode_into(&aliased, &mut buf).unwrap();
+    assert_eq!(&buf[..n], data);
+
+    // `U` is excluded from the alphabet outright.
+    assert_eq!(
+        CROCKFORD.decode_into("UU", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+}
+
+#[test]
+fn test_base32_other_alphabets_are_case_sensitive() {
+    let mut buf = [0u8; 8];
+    assert_eq!(
+        BASE32.decode_into("mzxw6ytb", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    assert_eq!(
+        BASE32HEX_NO_PAD.decode_into("cpnmuoj1", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    assert_eq!(
+        ZBASE32.decode_into("PB1SA5DX", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+}
+
+// ======================================================================
+// 3. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_base32_lens() {
+    for (engine, oracle) in ENGINES {
+        assert_lens(engine, 41, |data| base32::encode(oracle, data));
+    }
+    assert_eq!(BASE32_NO_PAD.estimate_decoded_len(7), 4);
+}
+
+#[test]
+fn test_base32_buffer_too_small() {
+    for (engine, _) in ENGINES {
+        for len in [1, 5, 6, 100] {
+            assert_buffer_too_small(engine, &random_bytes(len));
+        }
+    }
+}
+
+// ======================================================================
+// 4. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_base32_reject_invalid_length() {
+    // Padded input must come in whole groups.
+    assert_rejects_length(BASE32, ["MZXW6YT"]);
+    // No group ends 1, 3 or 6 characters in.
+    assert_rejects_length(BASE32_NO_PAD, ["M", "MZX", "MZXW6Y", "MZXW6YTBM"]);
+    // Nor may padding make a group of them.
+    assert_rejects_length(BASE32, ["M=======", "MZX=====", "MZXW6Y==", "========"]);
+}
+
+#[test]
+fn test_base32_reject_invalid_chars() {
+    let mut buf = [0u8; 128];
+    for (_, engine) in pinned(|backend| BASE32.with_backend(backend)) {
+        assert_rejects_chars(engine, &[8, 64, 72, 128], b'A', &[b'1', b'a', b' ', 0xC1]);
+        // Padding belongs only at the end.
+        assert_eq!(
+            engine.decode_into("MY======MZXW6YTB", &mut buf),
+            Err(Error::InvalidCharacter)
+        );
+        assert_eq!(
+            BASE32_NO_PAD.decode_into("MY======", &mut buf),
+            Err(Error::InvalidCharacter)
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Integration tests verifying `base64_turbo::base45` against the reference `base45` crate.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64_turbo::Error;
+use base64_turbo::base45::BASE45;
+use common::{
+    assert_buffer_too_small, assert_lens, assert_oracle_match, assert_rejects_chars,
+    assert_rejects_length, oracle_exhaustive_small, random_bytes,
+};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// Verifies every API against the `base45` crate.
+#[track_caller]
+fn assert_base45_match(input: &[u8]) {
+    assert_oracle_match(BASE45, input, &base45::encode(input));
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_base45_rfc9285_vectors() {
+    // RFC 9285 sections 4.3 and 4.4.
+    let vectors: [(&[u8], &str); 4] = [
+        (b"AB", "BB8"),
+        (b"Hello!!", "%69 VD92EX0"),
+        (b"base-45", "UJCLQE7W581"),
+        (b"ietf!", "QED8WEX0"),
+    ];
+    let mut buf = [0u8; 16];
+    for (data, chars) in vectors {
+        let n = BASE45.encode_into(data, &mut buf).unwrap();
+        assert_eq!(&buf[..n], chars.as_bytes());
+        let n = BASE45.decode_into(chars, &mut buf).unwrap();
+        assert_eq!(&buf[..n], data);
+    }
+}
+
+#[test]
+fn test_base45_oracle_exhaustive_small() {
+    oracle_exhaustive_small(200, assert_base45_match);
+}
+
+#[test]
+fn test_base45_every_group() {
+    // Every 16-bit group, and every final byte.
+    for n in 0..=u16::MAX {
+        assert_base45_match(&n.to_be_bytes());
+    }
+    for b in 0..=u8::MAX {
+        assert_base45_match(&[b]);
+    }
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_base45_oracle_fuzz_large() {
+    common::oracle_fuzz_large(assert_base45_match);
+}
+
+// ======================================================================
+// 2. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_base45_lens() {
+    assert_lens(BASE45, 41, |data| base45::encode(data));
+    assert_eq!(BASE45.decoded_len(4), 2);
+}
+
+#[test]
+fn test_base45_buffer_too_small() {
+    for len in [1, 2, 3, 100] {
+        assert_buffer_too_small(BASE45, &random_bytes(len));
+    }
+}
+
+// ======================================================================
+// 3. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_base45_reject_invalid_length() {
+    assert_rejects_length(BASE45, ["A", "BB8A", "QED8WEX0AB"]);
+}
+
+#[test]
+fn test_base45_reject_out_of_range_groups() {
+    let mut buf = [0u8; 16];
+    // "FGW" is 65535, the largest triplet; "GGW" is one past it, and ":::"
+    // (44 + 44 * 45 + 44 * 2025) the most any three characters reach.
+    assert_eq!(BASE45.decode_into("FGW", &mut buf), Ok(2));
+    assert_eq!(buf[..2], [0xFF, 0xFF]);
+    for chars in ["GGW", ":::", "BB8:::", "BB8GGWBB8"] {
+        assert_eq!(
+            BASE45.decode_into(chars, &mut buf),
+            Err(Error::InvalidCharacter),
+            "{chars}"
+        );
+    }
+    // A final pair is one byte: "U5" is 255, "V5" 256.
+    assert_eq!(BASE45.decode_into("U5", &mut buf), Ok(1));
+    assert_eq!(buf[0], 0xFF);
+    for chars in ["V5", "::", "BB8V5"] {
+        assert_eq!(
+            BASE45.decode_into(chars, &mut buf),
+            Err(Error::InvalidCharacter),
+            "{chars}"
+        );
+    }
+}
+
+#[test]
+fn test_base45_reject_invalid_chars() {
+    // Lowercase is not in the QR alphanumeric set, nor `#` or `=`.
+    assert_rejects_chars(
+        BASE45,
+        &[2, 3, 5, 30, 32],
+        b'0',
+        &[b'a', b'z', b'#', b'=', b'_', 0, 0x80, 0xFF],
+    );
+}
@@ -0,0 +1,325 @@
+//! Integration tests verifying `base64_turbo::base85` against the reference `z85` crate
+//! and Python's `base64.a85encode`/`b85encode`.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64_turbo::Error;
+use base64_turbo::base85::{ASCII85, Engine, RFC1924, Z85};
+use common::{
+    assert_buffer_too_small, assert_lens, assert_oracle_match, assert_rejects_chars,
+    oracle_exhaustive_small, random_bytes,
+};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+const Z85_SYMBOLS: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const RFC1924_SYMBOLS: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// The digits `input` encodes to, from the `z85` crate: zero-filled to whole
+/// groups, cut to `n + 1` digits for an `n`-byte final group, and mapped
+/// digit by digit onto `symbols`.
+fn oracle_digits(input: &[u8], symbols: &[u8; 85]) -> String {
+    let mut padded = input.to_vec();
+    padded.resize(input.len().div_ceil(4) * 4, 0);
+    let rem = input.len() % 4;
+    let digits = input.len() / 4 * 5 + if rem == 0 { 0 } else { rem + 1 };
+    z85::encode(&padded).as_bytes()[..digits]
+        .iter()
+        .map(|&c| char::from(symbols[Z85_SYMBOLS.iter().position(|&s| s == c).unwrap()]))
+        .collect()
+}
+
+fn z85_oracle(input: &[u8]) -> String {
+    oracle_digits(input, Z85_SYMBOLS)
+}
+
+fn rfc1924_oracle(input: &[u8]) -> String {
+    oracle_digits(input, RFC1924_SYMBOLS)
+}
+
+/// Ascii85 is the same digits on `!`..=`u`, framed in `<~ ~>`, with `z` for
+/// every whole zero group.
+fn ascii85_oracle(input: &[u8]) -> String {
+    let symbols: Vec<u8> = (b'!'..=b'u').collect();
+    let symbols: &[u8; 85] = symbols.as_slice().try_into().unwrap();
+
+    let mut ascii85 = String::from("<~");
+    for chunk in input.chunks(4) {
+        if chunk == [0; 4] {
+            ascii85.push('z');
+        } else {
+            ascii85.push_str(&oracle_digits(chunk, symbols));
+        }
+    }
+    ascii85.push_str("~>");
+    ascii85
+}
+
+/// An oracle encoder for one engine.
+type Oracle = fn(&[u8]) -> String;
+
+/// Every engine, paired with its oracle.
+const ENGINES: [(Engine, Oracle); 3] = [
+    (Z85, z85_oracle),
+    (ASCII85, ascii85_oracle),
+    (RFC1924, rfc1924_oracle),
+];
+
+/// Verifies every API of every engine against its oracle, and that Z85
+/// refuses a partial group.
+#[track_caller]
+fn assert_base85_match(input: &[u8]) {
+    for (engine, oracle) in ENGINES {
+        if engine.encode_into(input, &mut []) == Err(Error::InvalidLength) {
+            assert!(!input.len().is_multiple_of(4), "{engine:?}");
+            continue;
+        }
+        assert_oracle_match(engine, input, &oracle(input));
+    }
+    if !input.len().is_multiple_of(4) {
+        assert_eq!(Z85.encode_into(input, &mut []), Err(Error::InvalidLength));
+    }
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+#[cfg(feature = "std")]
+fn test_base85_z85_crate_whole_groups() {
+    for len in (0..=400).step_by(4) {
+        let data = random_bytes(len);
+        let expected = z85::encode(&data);
+        assert_eq!(Z85.encode(&data).unwrap(), expected);
+        assert_eq!(Z85.decode(&expected).unwrap(), data);
+    }
+    // ZeroMQ RFC 32's test vector.
+    assert_eq!(
+        Z85.encode(b"\x86\x4F\xD2\x6F\xB5\x59\xF7\x5B").unwrap(),
+        "HelloWorld"
+    );
+}
+
+#[test]
+fn test_base85_python_vectors() {
+    // `base64.a85encode(data, adobe=True)` and `base64.b85encode(data)`.
+    let vectors: [(&[u8], &str, &str); 10] = [
+        (b"", "<~~>", ""),
+        (b"h", "<~BE~>", "Xa"),
+        (b"he", "<~BOq~>", "Xk`"),
+        (b"hel", "<~BOtu~>", "Xk}~"),
+        (b"hell", "<~BOu!r~>", "Xk~0{"),
+        (b"hello", "<~BOu!rDZ~>", "Xk~0{Zv"),
+        (b"\0\0\0\0", "<~z~>", "00000"),
+        (b"\0\0\0", "<~!!!!~>", "0000"),
+        (b"\xff\xff\xff\xff", "<~s8W-!~>", "|NsC0"),
+        (
+            b"Man is distinguished",
+            "<~9jqo^BlbD-BleB1DJ+*+F(f,q~>",
+            "O<`^zX>%ZCX>)XGZfA9Ab7*B`",
+        ),
+    ];
+    let mut buf = [0u8; 64];
+    for (data, ascii85, rfc1924) in vectors {
+        let n = ASCII85.encode_into(data, &mut buf).unwrap();
+        assert_eq!(&buf[..n], ascii85.as_bytes());
+        let n = ASCII85.decode_into(ascii85, &mut buf).unwrap();
+        assert_eq!(&buf[..n], data);
+
+        let n = RFC1924.encode_into(data, &mut buf).unwrap();
+        assert_eq!(&buf[..n], rfc1924.as_bytes());
+        let n = RFC1924.decode_into(rfc1924, &mut buf).unwrap();
+        assert_eq!(&buf[..n], data);
+    }
+}
+
+#[test]
+fn test_base85_oracle_exhaustive_small() {
+    oracle_exhaustive_small(200, |data| {
+        assert_base85_match(data);
+        assert_base85_match(&vec![0; data.len()]);
+    });
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_base85_oracle_fuzz_large() {
+    common::oracle_fuzz_large(assert_base85_match);
+}
+
+// ======================================================================
+// 2. Coverage: Ascii85 Framing
+// ======================================================================
+
+#[test]
+fn test_base85_ascii85_decode_framing() {
+    let mut buf = [0u8; 32];
+    // PDF streams drop the `<~`, others the `~>` too, and whitespace may fall
+    // anywhere.
+    for framed in [
+        "<~87cURD]i,\"Ebo80~>",
+        "87cURD]i,\"Ebo80~>",
+        "<~87cURD]i,\"Ebo80",
+        "87cURD]i,\"Ebo80",
+        "  <~87cU\r\nRD]i,\n\"Ebo80~>\n",
+        "<~87cURD]i,\"Eb o80 ~>",
+    ] {
+        let n = ASCII85.decode_into(framed, &mut buf).unwrap();
+        assert_eq!(&buf[..n], b"Hello World!", "{framed:?}");
+    }
+
+    for empty in ["", "<~", "~>", "<~~>"] {
+        assert_eq!(ASCII85.decode_into(empty, &mut buf), Ok(0), "{empty:?}");
+    }
+    // A stray `~` is not framing.
+    assert_eq!(
+        ASCII85.decode_into("<~87cURD]i,~\"Ebo80~>", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+}
+
+#[test]
+fn test_base85_ascii85_z_shortcut() {
+    let mut buf = [0u8; 16];
+    let n = ASCII85.decode_into("<~zz~>", &mut buf).unwrap();
+    assert_eq!(&buf[..n], [0; 8]);
+
+    // Only between groups.
+    assert_eq!(
+        ASCII85.decode_into("<~BOzu!r~>", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    // The other alphabets read it as an ordinary digit.
+    assert_eq!(Z85.decode_into("0000z", &mut buf), Ok(4));
+    assert_eq!(buf[..4], 35u32.to_be_bytes());
+    assert_eq!(RFC1924.decode_into("0000z", &mut buf), Ok(4));
+    assert_eq!(buf[..4], 61u32.to_be_bytes());
+
+    // Four bytes per character outgrows the estimate, and is caught.
+    let mut buf = [0u8; 7];
+    assert_eq!(
+        ASCII85.decode_into("<~zz~>", &mut buf),
+        Err(Error::BufferTooSmall)
+    );
+}
+
+// ======================================================================
+// 3. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_base85_lens() {
+    for (engine, oracle) in ENGINES {
+        assert_lens(engine, 41, oracle);
+    }
+    assert_eq!(Z85.estimate_decoded_len(10), 8);
+    assert_eq!(RFC1924.estimate_decoded_len(7), 5);
+}
+
+#[test]
+fn test_base85_buffer_too_small() {
+    for (engine, _) in ENGINES {
+        for len in [4, 8, 100] {
+            assert_buffer_too_small(engine, &random_bytes(len));
+        }
+    }
+    for engine in [ASCII85, RFC1924] {
+        for len in [1, 5, 99] {
+            assert_buffer_too_small(engine, &random_bytes(len));
+        }
+    }
+    // Even empty input needs room for Ascii85's framing.
+    assert_eq!(
+        ASCII85.encode_into(b"", &mut [0u8; 3]),
+        Err(Error::BufferTooSmall)
+    );
+}
+
+// ======================================================================
+// 4. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_base85_z85_reject_partial_groups() {
+    let mut buf = [0u8; 16];
+    for len in [1, 2, 3, 5, 7, 9] {
+        assert_eq!(
+            Z85.encode_into(random_bytes(len), &mut buf),
+            Err(Error::InvalidLength),
+            "{len} bytes"
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(Z85.encode(random_bytes(len)), Err(Error::InvalidLength));
+    }
+    // Digits that would decode as partial groups elsewhere.
+    for digits in ["He", "Hel", "Hell", "HelloWo", "HelloWorl"] {
+        assert_eq!(Z85.decode_into(digits, &mut buf), Err(Error::InvalidLength));
+        assert!(RFC1924.decode_into(digits, &mut buf).is_ok(), "{digits}");
+    }
+}
+
+#[test]
+fn test_base85_reject_single_digit_group() {
+    let mut buf = [0u8; 16];
+    for digits in ["0", "HelloW"] {
+        assert_eq!(Z85.decode_into(digits, &mut buf), Err(Error::InvalidLength));
+        assert_eq!(
+            RFC1924.decode_into(digits, &mut buf),
+            Err(Error::InvalidLength)
+        );
+    }
+    assert_eq!(
+        ASCII85.decode_into("<~BOu!rD~>", &mut buf),
+        Err(Error::InvalidLength)
+    );
+}
+
+#[test]
+fn test_base85_reject_overflowing_groups() {
+    let mut buf = [0u8; 16];
+    // 85^5 - 1 is past 2^32 - 1; so is the largest 2-digit partial group.
+    assert_eq!(
+        Z85.decode_into("#####", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    assert_eq!(
+        RFC1924.decode_into("~~", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    assert_eq!(
+        ASCII85.decode_into("<~s8W-\"~>", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    // The largest that fits still decodes.
+    assert_eq!(ASCII85.decode_into("<~s8W-!~>", &mut buf), Ok(4));
+    assert_eq!(buf[..4], [0xFF; 4]);
+}
+
+#[test]
+fn test_base85_reject_invalid_chars() {
+    assert_rejects_chars(Z85, &[5, 10, 40], b'0', b"\"\\~,");
+    assert_rejects_chars(RFC1924, &[5, 10, 40], b'0', b"\"',.");
+
+    // Ascii85 needs its closing `~>` to get as far as the digits.
+    let mut buf = [0u8; 64];
+    for len in [5, 10, 40] {
+        for at in [0, len / 2, len - 1] {
+            for c in [b'v', b'~', b'{', 0x80] {
+                let mut chars = vec![b'0'; len];
+                chars[at] = c;
+                chars.extend(b"~>");
+                assert_eq!(
+                    ASCII85.decode_into(&chars, &mut buf),
+                    Err(Error::InvalidCharacter),
+                    "{c:#04x} at {at} of {len}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! The dispatch thresholds are process-global, so they get a test binary (and
+//! so a process) of their own, with a single test that walks them through
+//! every state in order.
+#![cfg(feature = "std")]
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+use base64::Engine as _;
+use base64::engine::general_purpose::{STANDARD as REF_STANDARD, STANDARD_NO_PAD as REF_NO_PAD};
+use base64_turbo::{Backend, STANDARD, STANDARD_NO_PAD, Thresholds, set_thresholds, thresholds};
+
+/// Every length on either side of every threshold the kernels clamp to, and a
+/// few past the streaming floor.
+const LENGTHS: &[usize] = &[
+    0, 1, 2, 3, 4, 5, 15, 16, 17, 20, 23, 24, 25, 31, 32, 33, 35, 36, 37, 47, 48, 49, 63, 64, 65,
+    100, 1_000, 70_000, 200_003,
+];
+
+/// Every backend the host can run.
+fn backends() -> impl Iterator<Item = Backend> {
+    [
+        Backend::Scalar,
+        Backend::Ssse3,
+        Backend::Avx2,
+        Backend::Avx512Bw,
+        Backend::Avx512Vbmi256,
+        Backend::Avx512Vbmi,
+        Backend::Neon,
+        Backend::WasmSimd,
+        Backend::Portable,
+    ]
+    .into_iter()
+    .filter(|b| b.is_supported())
+}
+
+/// Every backend the host can run, each pinned, round-trips against the
+/// oracle at every length in [`LENGTHS`].
+fn assert_round_trips(state: &str) {
+    for backend in backends() {
+        for (engine, oracle) in [(STANDARD, REF_STANDARD), (STANDARD_NO_PAD, REF_NO_PAD)] {
+            let engine = engine.with_backend(backend).unwrap();
+            for &len in LENGTHS {
+                let input: Vec<u8> = (0..len).map(|i| (i * 7 + len).to_le_bytes()[0]).collect();
+                let encoded = engine.encode(&input);
+                assert_eq!(
+                    encoded,
+                    oracle.encode(&input),
+                    "{state}: {backend} len {len}"
+                );
+                assert_eq!(
+                    engine.decode(&encoded).unwrap(),
+                    input,
+                    "{state}: {backend} len {len}"
+                );
+            }
+        }
+    }
+}
+
+/// Every backend the host can run, each pinned, round-trips inputs past the
+/// streaming floor through destinations that start off a 64-byte boundary, as
+/// allocations mostly do, so the streaming tiers' peeled heads are exercised.
+fn assert_round_trips_unaligned(state: &str) {
+    for backend in backends() {
+        let engine = STANDARD.with_backend(backend).unwrap();
+        for len in [70_000usize, 200_003] {
+            let input: Vec<u8> = (0..len).map(|i| (i * 7 + len).to_le_bytes()[0]).collect();
+            let expected = REF_STANDARD.encode(&input);
+            for shift in [1, 16, 33, 63] {
+                let mut buf = vec![0u8; expected.len() + 64 + shift];
+                let start = buf.as_ptr().align_offset(64) + shift;
+                let dst = &mut buf[start..start + expected.len()];
+                assert_eq!(engine.encode_into(&input, dst), Ok(expected.len()));
+                assert!(
+                    dst == expected.as_bytes(),
+                    "{state}: {backend} len {len} offset {shift}"
+                );
+
+                let cap = engine.estimate_decoded_len(expected.len());
+                let mut buf = vec![0u8; cap + 64 + shift];
+                let start = buf.as_ptr().align_offset(64) + shift;
+                let dst = &mut buf[start..start + cap];
+                assert_eq!(engine.decode_into(&expected, dst), Ok(len));
+                assert!(
+                    dst[..len] == input[..],
+                    "{state}: {backend} len {len} offset {shift}"
+                );
+            }
+        }
+    }
+}
+
+/// `text` with every number in it replaced by `len`.
+fn with_every_threshold(text: &str, len: usize) -> Thresholds {
+    let mut out = String::new();
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        out.push_str(words.next().unwrap());
+        for _ in words {
+            out.push(' ');
+            out.push_str(&len.to_string());
+        }
+        out.push('\n');
+    }
+    out.parse().unwrap()
+}
+
+#[test]
+fn test_thresholds_install_clamp_and_calibrate() {
+    // Nothing installed yet: the defaults, which survive a text round trip.
+    let defaults = Thresholds::default();
+    assert_eq!(thresholds(), defaults);
+    assert_eq!(defaults.to_string().parse::<Thresholds>(), Ok(defaults));
+    assert_eq!(defaults.encode_min(Backend::Scalar), None);
+    if Backend::Avx2.is_supported() {
+        assert_eq!(defaults.encode_min(Backend::Avx2), Some(32));
+        assert_eq!(defaults.decode_min(Backend::Avx2), Some(36));
+        assert_eq!(defaults.non_temporal_min(), Some(4 << 20));
+    }
+
+    // Parsing keeps the defaults for lines it does not find, and pins down the
+    // line it cannot read.
+    assert_eq!("\n  \n".parse::<Thresholds>(), Ok(defaults));
+    assert_eq!("avx2 1 2 3".parse::<Thresholds>().unwrap_err().line(), 1);
+    assert_eq!(
+        "avx2 1 2\nsse9 1 2"
+            .parse::<Thresholds>()
+            .unwrap_err()
+            .line(),
+        2
+    );
+    assert_eq!(
+        "non-temporal x".parse::<Thresholds>().unwrap_err().line(),
+        1
+    );
+
+    // Every kernel entered for every input, and streaming from its floor.
+    let lowest = with_every_threshold(&defaults.to_string(), 0);
+    set_thresholds(&lowest);
+    let installed = thresholds();
+    if let Some(len) = installed.non_temporal_min() {
+        assert_eq!(len, 64 << 10, "streaming floor");
+    }
+    assert_round_trips("lowest");
+    assert_round_trips_unaligned("lowest");
+
+    // No kernel entered at all: the ones with short-input kernels are held to
+    // what those take.
+    let highest = with_every_threshold(&defaults.to_string(), usize::MAX);
+    set_thresholds(&highest);
+    let installed = thresholds();
+    if Backend::Avx2.is_supported() {
+        assert_eq!(installed.encode_min(Backend::Avx2), Some(49));
+        assert_eq!(installed.decode_min(Backend::Avx2), Some(65));
+    }
+    if Backend::Ssse3.is_supported() {
+        assert_eq!(installed.encode_min(Backend::Ssse3), Some(usize::MAX));
+    }
+    assert_round_trips("highest");
+
+    set_thresholds(&defaults);
+    assert_eq!(thresholds(), defaults);
+
+    // Whatever the host measures is installed, reported, and still correct.
+    let measured = base64_turbo::calibrate();
+    assert_eq!(thresholds(), measured);
+    assert_eq!(measured.to_string().parse::<Thresholds>(), Ok(measured));
+    assert_round_trips("calibrated");
+}
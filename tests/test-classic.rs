@@ -1,7 +1,7 @@
 //! Integration tests verifying `base64-turbo`'s output against the reference `base64` crate.
 #![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
 
-use base64_turbo::{Engine, Error, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64_turbo::{Backend, Engine, Error, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
 
 // Reference Crate for Oracle Verification
 use base64::{
@@ -454,6 +454,22 @@ fn test_unstable_apis() {
         assert_eq!(&dec[..len], &input, "Scalar Safe Decode");
     }
 
+    // --- SSSE3 ---
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "ssse3"))]
+    if std::is_x86_feature_detected!("ssse3") {
+        unsafe {
+            let mut dst = vec![0u8; STANDARD.encoded_len(input.len())];
+            STANDARD.encode_ssse3(&input, &mut dst);
+            assert_eq!(&dst, expected.as_bytes(), "SSSE3 Unsafe Encode");
+
+            let mut dec = vec![0u8; STANDARD.estimate_decoded_len(dst.len())];
+            let len = STANDARD.decode_ssse3(&dst, &mut dec).unwrap();
+            assert_eq!(&dec[..len], &input, "SSSE3 Unsafe Decode");
+        }
+    } else {
+        println!("Skipping SSSE3 Unstable test (hardware unsupported)");
+    }
+
     // --- AVX2 ---
     #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx2"))]
     if std::is_x86_feature_detected!("avx2") {
@@ -470,6 +486,22 @@ fn test_unstable_apis() {
         println!("Skipping AVX2 Unstable test (hardware unsupported)");
     }
 
+    // --- AVX-512BW ---
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "avx512bw"))]
+    if std::is_x86_feature_detected!("avx512f") && std::is_x86_feature_detected!("avx512bw") {
+        unsafe {
+            let mut dst = vec![0u8; STANDARD.encoded_len(input.len())];
+            STANDARD.encode_avx512bw(&input, &mut dst);
+            assert_eq!(&dst, expected.as_bytes(), "AVX512BW Unsafe Encode");
+
+            let mut dec = vec![0u8; STANDARD.estimate_decoded_len(dst.len())];
+            let len = STANDARD.decode_avx512bw(&dst, &mut dec).unwrap();
+            assert_eq!(&dec[..len], &input, "AVX512BW Unsafe Decode");
+        }
+    } else {
+        println!("Skipping AVX512BW Unstable test (hardware unsupported)");
+    }
+
     // --- AVX-512-VBMI ---
     #[cfg(all(
         any(target_arch = "x86", target_arch = "x86_64"),
@@ -492,6 +524,29 @@ fn test_unstable_apis() {
         println!("Skipping AVX512-VBMI Unstable test (hardware unsupported)");
     }
 
+    // --- AVX-512-VBMI, 256-bit ---
+    #[cfg(all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        feature = "avx512-vbmi"
+    ))]
+    if std::is_x86_feature_detected!("avx512f")
+        && std::is_x86_feature_detected!("avx512bw")
+        && std::is_x86_feature_detected!("avx512vl")
+        && std::is_x86_feature_detected!("avx512vbmi")
+    {
+        unsafe {
+            let mut dst = vec![0u8; STANDARD.encoded_len(input.len())];
+            STANDARD.encode_avx512_vbmi_256(&input, &mut dst);
+            assert_eq!(&dst, expected.as_bytes(), "AVX512-VBMI-256 Unsafe Encode");
+
+            let mut dec = vec![0u8; STANDARD.estimate_decoded_len(dst.len())];
+            let len = STANDARD.decode_avx512_vbmi_256(&dst, &mut dec).unwrap();
+            assert_eq!(&dec[..len], &input, "AVX512-VBMI-256 Unsafe Decode");
+        }
+    } else {
+        println!("Skipping AVX512-VBMI-256 Unstable test (hardware unsupported)");
+    }
+
     // --- NEON ---
     #[cfg(target_arch = "aarch64")]
     #[cfg(feature = "neon")]
@@ -505,3 +560,100 @@ fn test_unstable_apis() {
         assert_eq!(&dec[..len], &input, "NEON Unsafe Decode");
     }
 }
+
+/// WASM has no runtime detection: a `simd128` build only runs where it is supported.
+#[test]
+#[cfg(all(wasm_simd, feature = "unstable"))]
+fn test_unstable_wasm_apis() {
+    let input = random_bytes(1024);
+    let expected = REF_STANDARD.encode(&input);
+
+    unsafe {
+        let mut dst = vec![0u8; STANDARD.encoded_len(input.len())];
+        STANDARD.encode_wasm(&input, &mut dst);
+        assert_eq!(&dst, expected.as_bytes(), "WASM Unsafe Encode");
+
+        let mut dec = vec![0u8; STANDARD.estimate_decoded_len(dst.len())];
+        let len = STANDARD.decode_wasm(&dst, &mut dec).unwrap();
+        assert_eq!(&dec[..len], &input, "WASM Unsafe Decode");
+    }
+}
+
+/// The portable kernel is safe to call on any CPU, but only exists on nightly.
+#[test]
+#[cfg(all(portable_simd, feature = "unstable"))]
+#[cfg(not(miri))]
+fn test_unstable_portable_apis() {
+    let input = random_bytes(1024);
+    let expected = REF_STANDARD.encode(&input);
+
+    let mut dst = vec![0u8; STANDARD.encoded_len(input.len())];
+    STANDARD.encode_portable(&input, &mut dst);
+    assert_eq!(&dst, expected.as_bytes(), "Portable Safe Encode");
+
+    let mut dec = vec![0u8; STANDARD.estimate_decoded_len(dst.len())];
+    let len = STANDARD.decode_portable(&dst, &mut dec).unwrap();
+    assert_eq!(&dec[..len], &input, "Portable Safe Decode");
+}
+
+// ======================================================================
+// 11. Coverage: Backend Selection
+// ======================================================================
+
+#[test]
+fn test_active_backend_is_supported() {
+    let active = base64_turbo::active_backend();
+    assert!(active.is_supported(), "active backend {active} unsupported");
+    assert_eq!(STANDARD.backend(), active);
+}
+
+#[test]
+fn test_with_backend_matches_oracle() {
+    for backend in [
+        Backend::Scalar,
+        Backend::Ssse3,
+        Backend::Avx2,
+        Backend::Avx512Bw,
+        Backend::Avx512Vbmi256,
+        Backend::Avx512Vbmi,
+        Backend::Neon,
+        Backend::WasmSimd,
+        Backend::Portable,
+    ] {
+        let Ok(engine) = STANDARD.with_backend(backend) else {
+            println!("Skipping {backend} (unsupported on this build or CPU)");
+            continue;
+        };
+        assert_eq!(engine.backend(), backend);
+
+        // Straddle every kernel's length threshold, then run its wide tiers.
+        for len in (0..=80).chain([255, 1024, 4099]) {
+            let data = random_bytes(len);
+            assert_oracle_match(&data, engine, &REF_STANDARD);
+        }
+    }
+}
+
+#[test]
+fn test_with_backend_reports_unsupported() {
+    for backend in [
+        Backend::Scalar,
+        Backend::Ssse3,
+        Backend::Avx2,
+        Backend::Avx512Bw,
+        Backend::Avx512Vbmi256,
+        Backend::Avx512Vbmi,
+        Backend::Neon,
+        Backend::WasmSimd,
+        Backend::Portable,
+    ] {
+        match URL_SAFE.with_backend(backend) {
+            Ok(_) => assert!(backend.is_supported()),
+            Err(e) => {
+                assert!(!backend.is_supported());
+                assert_eq!(e.backend(), backend);
+                assert!(format!("{e}").contains(backend.name()));
+            }
+        }
+    }
+}
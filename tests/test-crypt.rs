@@ -0,0 +1,172 @@
+//! Integration tests verifying `base64_turbo::crypt` against the reference `base64` crate.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64::Engine as _;
+use base64::engine::{GeneralPurpose, general_purpose::NO_PAD};
+use base64_turbo::crypt::{BCRYPT, CRYPT, Engine};
+use common::{
+    assert_buffer_too_small, assert_lens, assert_oracle_match, assert_rejects_chars,
+    assert_rejects_length, oracle_exhaustive_small, pinned, random_bytes,
+};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// bcrypt is the `base64` crate's `BCRYPT` alphabet, unpadded.
+fn bcrypt_oracle(input: &[u8]) -> String {
+    GeneralPurpose::new(&base64::alphabet::BCRYPT, NO_PAD).encode(input)
+}
+
+/// crypt(3) reads each group little-endian: the `base64` crate's `CRYPT`
+/// alphabet over each group's bytes reversed, with the group's characters
+/// reversed back. Zero-filling a partial group only reaches its last
+/// characters, which the length then cuts off.
+fn crypt_oracle(input: &[u8]) -> String {
+    let mut groups = input.to_vec();
+    groups.resize(input.len().div_ceil(3) * 3, 0);
+    groups.chunks_mut(3).for_each(<[u8]>::reverse);
+    let mut chars = GeneralPurpose::new(&base64::alphabet::CRYPT, NO_PAD)
+        .encode(&groups)
+        .into_bytes();
+    chars.chunks_mut(4).for_each(<[u8]>::reverse);
+    chars.truncate((input.len() * 4).div_ceil(3));
+    String::from_utf8(chars).unwrap()
+}
+
+/// An oracle encoder for one engine.
+type Oracle = fn(&[u8]) -> String;
+
+/// Both engines, paired with their oracles.
+const ENGINES: [(Engine, Oracle); 2] = [(CRYPT, crypt_oracle), (BCRYPT, bcrypt_oracle)];
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_crypt_bit_order() {
+    // One byte: crypt(3) writes its low six bits first, bcrypt its high six.
+    let mut buf = [0u8; 2];
+    assert_eq!(CRYPT.encode_into([0xFF], &mut buf), Ok(2));
+    assert_eq!(&buf, b"z1");
+    assert_eq!(BCRYPT.encode_into([0xFF], &mut buf), Ok(2));
+    assert_eq!(&buf, b"9u");
+
+    let mut buf = [0u8; 4];
+    assert_eq!(CRYPT.encode_into(b"\0\x01\x02", &mut buf), Ok(4));
+    assert_eq!(&buf, b".2U.");
+    assert_eq!(BCRYPT.encode_into(b"\0\x01\x02", &mut buf), Ok(4));
+    assert_eq!(&buf, b"..CA");
+}
+
+#[test]
+fn test_crypt_bcrypt_salt() {
+    // The salt of `$2a$10$N9qo8uLOickgx2ZMRZoMyeIjZAgcfl7p92ldGxad68LJZdL17lhWy`.
+    let salt = b"\x3f\xfb\x2a\xfb\x03\x50\x91\xe9\xa2\xcf\x86\xce\x4d\xba\x8e\xd2";
+    let mut buf = [0u8; 22];
+    assert_eq!(
+        BCRYPT.decode_into("N9qo8uLOickgx2ZMRZoMye", &mut buf),
+        Ok(16)
+    );
+    assert_eq!(&buf[..16], salt);
+    assert_eq!(BCRYPT.encode_into(salt, &mut buf), Ok(22));
+    assert_eq!(&buf, b"N9qo8uLOickgx2ZMRZoMye");
+}
+
+#[test]
+fn test_crypt_oracle_exhaustive_small() {
+    oracle_exhaustive_small(200, |data| {
+        for (engine, oracle) in ENGINES {
+            assert_oracle_match(engine, data, &oracle(data));
+        }
+    });
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_crypt_oracle_fuzz_large() {
+    common::oracle_fuzz_large(|data| {
+        for (engine, oracle) in ENGINES {
+            assert_oracle_match(engine, data, &oracle(data));
+        }
+    });
+}
+
+#[test]
+fn test_crypt_with_backend_matches_oracle() {
+    for (base, oracle) in ENGINES {
+        for (backend, engine) in pinned(|backend| base.with_backend(backend)) {
+            assert_eq!(engine.backend(), backend);
+
+            for len in (0..=100).chain([255, 1024, 4099]) {
+                let data = random_bytes(len);
+                assert_oracle_match(engine, &data, &oracle(&data));
+            }
+        }
+    }
+}
+
+// ======================================================================
+// 2. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_crypt_lens() {
+    for (engine, oracle) in ENGINES {
+        assert_lens(engine, 50, oracle);
+    }
+    // The digests of md5-crypt, sha256-crypt and sha512-crypt.
+    assert_eq!(CRYPT.encoded_len(16), 22);
+    assert_eq!(CRYPT.encoded_len(32), 43);
+    assert_eq!(CRYPT.encoded_len(64), 86);
+}
+
+#[test]
+fn test_crypt_buffer_too_small() {
+    for (engine, _) in ENGINES {
+        for len in [1, 3, 4, 100] {
+            assert_buffer_too_small(engine, &random_bytes(len));
+        }
+    }
+}
+
+#[test]
+fn test_crypt_ignores_spare_bits() {
+    // A final pair carries 12 bits for 8: the top four of crypt(3)'s second
+    // character, and the low four of bcrypt's, are not data.
+    let mut buf = [0u8; 1];
+    assert_eq!(CRYPT.decode_into("zz", &mut buf), Ok(1));
+    assert_eq!(buf, [0xFF]);
+    assert_eq!(BCRYPT.decode_into("9z", &mut buf), Ok(1));
+    assert_eq!(buf, [0xFF]);
+}
+
+// ======================================================================
+// 3. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_crypt_reject_invalid_length() {
+    for engine in [CRYPT, BCRYPT] {
+        assert_rejects_length(engine, ["c", "cJ4Pg", "cJ4Pgx46r"]);
+    }
+    // Length is checked before characters.
+    assert_rejects_length(CRYPT, ["="]);
+}
+
+#[test]
+fn test_crypt_reject_invalid_chars() {
+    for base in [CRYPT, BCRYPT] {
+        for (_, engine) in pinned(|backend| base.with_backend(backend)) {
+            assert_rejects_chars(
+                engine,
+                &[2, 4, 64, 67, 130],
+                b'.',
+                &[b'+', b'-', b'_', b'=', b' ', 0xAE],
+            );
+        }
+    }
+}
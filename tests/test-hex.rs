@@ -0,0 +1,122 @@
+//! Integration tests verifying `base64_turbo::hex` against the reference `hex` crate.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64_turbo::hex::{Engine, HEX_LOWER, HEX_UPPER};
+use common::{
+    BACKENDS, assert_buffer_too_small, assert_decodes, assert_lens, assert_oracle_match,
+    assert_rejects_chars, assert_rejects_length, oracle_exhaustive_small, pinned, random_bytes,
+};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// Verifies every API of `engine` against the `hex` crate, and that decode
+/// takes the other case too.
+#[track_caller]
+fn assert_hex_match(input: &[u8], engine: Engine, upper: bool) {
+    let (expected, other_case) = if upper {
+        (hex::encode_upper(input), hex::encode(input))
+    } else {
+        (hex::encode(input), hex::encode_upper(input))
+    };
+    assert_oracle_match(engine, input, &expected);
+    assert_decodes(engine, other_case.as_bytes(), input);
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_hex_oracle_exhaustive_small() {
+    oracle_exhaustive_small(300, |data| {
+        assert_hex_match(data, HEX_LOWER, false);
+        assert_hex_match(data, HEX_UPPER, true);
+    });
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_hex_oracle_fuzz_large() {
+    common::oracle_fuzz_large(|data| {
+        assert_hex_match(data, HEX_LOWER, false);
+        assert_hex_match(data, HEX_UPPER, true);
+    });
+}
+
+#[test]
+fn test_hex_with_backend_matches_oracle() {
+    // Hex runs on every backend this build and CPU support.
+    for backend in BACKENDS {
+        assert_eq!(
+            HEX_LOWER.with_backend(backend).is_ok(),
+            backend.is_supported()
+        );
+    }
+    for (backend, engine) in pinned(|backend| HEX_LOWER.with_backend(backend)) {
+        assert_eq!(engine.backend(), backend);
+
+        for len in (0..=80).chain([255, 1024, 4099]) {
+            let data = random_bytes(len);
+            assert_hex_match(&data, engine, false);
+            assert_hex_match(&data, HEX_UPPER.with_backend(backend).unwrap(), true);
+        }
+    }
+}
+
+// ======================================================================
+// 2. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_hex_lens() {
+    assert_lens(HEX_LOWER, 50, |data| hex::encode(data));
+    assert_eq!(HEX_LOWER.encoded_len(0), 0);
+    assert_eq!(HEX_LOWER.encoded_len(7), 14);
+    assert_eq!(HEX_UPPER.decoded_len(14), 7);
+    assert_eq!(HEX_UPPER.decoded_len(15), 7);
+}
+
+#[test]
+fn test_hex_empty_input() {
+    let mut buf = [0u8; 0];
+    assert_eq!(HEX_LOWER.encode_into(b"", &mut buf), Ok(0));
+    assert_eq!(HEX_LOWER.decode_into(b"", &mut buf), Ok(0));
+
+    #[cfg(feature = "std")]
+    {
+        assert_eq!(HEX_LOWER.encode(b""), "");
+        assert_eq!(HEX_LOWER.decode(b""), Ok(Vec::new()));
+    }
+}
+
+#[test]
+fn test_hex_buffer_too_small() {
+    for len in [1, 4, 33, 100] {
+        assert_buffer_too_small(HEX_LOWER, &random_bytes(len));
+    }
+}
+
+// ======================================================================
+// 3. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_hex_reject_odd_length() {
+    assert_rejects_length(HEX_LOWER, [1, 3, 63, 65, 129].map(|len| vec![b'a'; len]));
+}
+
+#[test]
+fn test_hex_reject_invalid_chars() {
+    for (_, engine) in pinned(|backend| HEX_LOWER.with_backend(backend)) {
+        assert_rejects_chars(
+            engine,
+            &[2, 32, 64, 66, 130],
+            b'0',
+            &[b'g', b'G', b'/', b':', b'@', b'`', b' ', 0xE6],
+        );
+    }
+}
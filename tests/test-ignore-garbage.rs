@@ -0,0 +1,264 @@
+//! Integration tests verifying `Engine::decode_ignore_garbage_into` against the reference
+//! `base64` crate, and against a strict decode of the input with its garbage filtered out.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64::Engine as _;
+use base64::engine::general_purpose;
+use base64_turbo::{
+    Backend, Engine, Error, LENIENT, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD,
+};
+use common::random_bytes;
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// An oracle encoder for one engine.
+type Oracle = fn(&[u8]) -> String;
+
+/// Every engine, paired with an oracle whose output it decodes.
+const ENGINES: [(Engine, Oracle); 5] = [
+    (STANDARD, |x| general_purpose::STANDARD.encode(x)),
+    (STANDARD_NO_PAD, |x| {
+        general_purpose::STANDARD_NO_PAD.encode(x)
+    }),
+    (URL_SAFE, |x| general_purpose::URL_SAFE.encode(x)),
+    (URL_SAFE_NO_PAD, |x| {
+        general_purpose::URL_SAFE_NO_PAD.encode(x)
+    }),
+    (LENIENT, |x| general_purpose::URL_SAFE.encode(x)),
+];
+
+/// `base` pinned to every backend this build and CPU support.
+fn pinned(base: Engine) -> impl Iterator<Item = (Backend, Engine)> {
+    common::pinned(move |backend| base.with_backend(backend))
+}
+
+/// Whether `engine` keeps `c`: an alphabet character, or `'='`.
+fn keeps(engine: Engine, c: u8) -> bool {
+    let mut buf = [0u8; 6];
+    c == b'=' || engine.decode_into([c, b'A', b'A', b'A'], &mut buf).is_ok()
+}
+
+/// `engine.decode_ignore_garbage_into` on a buffer of the estimated length,
+/// trimmed to what it wrote.
+fn decode(engine: Engine, input: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = vec![0u8; engine.estimate_decoded_len(input.len())];
+    let n = engine.decode_ignore_garbage_into(input, &mut out)?;
+    out.truncate(n);
+    Ok(out)
+}
+
+/// A strict decode of `input` with every byte `engine` would drop filtered
+/// out first.
+fn reference(engine: Engine, input: &[u8]) -> Result<Vec<u8>, Error> {
+    let kept: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|&c| keeps(engine, c))
+        .collect();
+    let mut out = vec![0u8; engine.estimate_decoded_len(kept.len())];
+    let n = engine.decode_into(&kept, &mut out)?;
+    out.truncate(n);
+    Ok(out)
+}
+
+/// `chars` with a byte `engine` drops planted before every `stride`-th one.
+fn with_garbage(engine: Engine, chars: &[u8], stride: usize) -> Vec<u8> {
+    let junk: Vec<u8> = (0..=255).filter(|&c| !keeps(engine, c)).collect();
+    let mut out = Vec::with_capacity(chars.len() * 2);
+    for (i, &c) in chars.iter().enumerate() {
+        if i % stride == 0 {
+            out.push(junk[i % junk.len()]);
+        }
+        out.push(c);
+    }
+    out
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_ignore_garbage_oracle_all_engines() {
+    for (base, oracle) in ENGINES {
+        for (backend, engine) in pinned(base) {
+            for len in (0..=150).chain([3_069, 3_072, 3_075, 30_001]) {
+                let data = random_bytes(len);
+                let chars = oracle(&data).into_bytes();
+                for stride in [1, 2, 3, 64, 1_000, usize::MAX] {
+                    let input = with_garbage(engine, &chars, stride);
+                    assert_eq!(
+                        decode(engine, &input).unwrap(),
+                        data,
+                        "{backend}: len {len}, stride {stride}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ignore_garbage_mime_lines() {
+    let data = random_bytes(10_000);
+    let chars = general_purpose::STANDARD.encode(&data);
+    let mut quoted = Vec::new();
+    for line in chars.as_bytes().chunks(76) {
+        quoted.extend_from_slice(b"> ");
+        quoted.extend_from_slice(line);
+        quoted.extend_from_slice(b"\r\n");
+    }
+    for (backend, engine) in pinned(STANDARD) {
+        assert_eq!(decode(engine, &quoted).unwrap(), data, "{backend}");
+    }
+}
+
+#[test]
+fn test_ignore_garbage_foreign_alphabet_is_garbage() {
+    // '-' and '_' are garbage to STANDARD, '+' and '/' to URL_SAFE, and
+    // neither to LENIENT.
+    assert_eq!(decode(STANDARD, b"-QUJD_").unwrap(), b"ABC");
+    assert_eq!(decode(URL_SAFE, b"+QUJD/").unwrap(), b"ABC");
+    assert_eq!(decode(LENIENT, b"-QUJD_"), reference(LENIENT, b"-QUJD_"));
+    assert_eq!(decode(LENIENT, b"-QUJD_").map(|b| b.len()), Ok(4));
+}
+
+// ======================================================================
+// 2. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_ignore_garbage_reject_matches_reference() {
+    for (base, oracle) in ENGINES {
+        for (backend, engine) in pinned(base) {
+            for len in [1, 2, 5, 47, 48, 49, 3_071, 3_072, 3_073, 3_074] {
+                let chars = oracle(&random_bytes(len)).into_bytes();
+                // Every length of the encoding, so every one of the final
+                // group's shapes, whole or cut short.
+                for cut in [1, 2, 3, 4, 5] {
+                    let input = with_garbage(engine, &chars[..chars.len().saturating_sub(cut)], 5);
+                    assert_eq!(
+                        decode(engine, &input),
+                        reference(engine, &input),
+                        "{backend}: len {len}, cut {cut}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ignore_garbage_random_bytes_match_scalar() {
+    for (base, _) in ENGINES {
+        let scalar = base.with_backend(Backend::Scalar).unwrap();
+        for (backend, engine) in pinned(base) {
+            for len in [0, 1, 63, 64, 65, 4_095, 4_096, 4_097, 20_000] {
+                let input = random_bytes(len);
+                assert_eq!(
+                    decode(engine, &input),
+                    decode(scalar, &input),
+                    "{backend}: {len}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ignore_garbage_reject_data_after_padding() {
+    // The padded group must be the last, on either side of the 4 KiB pieces
+    // the input is compacted in.
+    let mut body = vec![b'A'; 4_100];
+    for at in [2, 4_090, 4_094, 4_095, 4_096, 4_097] {
+        body[at] = b'=';
+        let input = with_garbage(STANDARD, &body, 7);
+        for (backend, engine) in pinned(STANDARD) {
+            assert_eq!(
+                decode(engine, &input),
+                Err(Error::InvalidCharacter),
+                "{backend}: {at}"
+            );
+        }
+        body[at] = b'A';
+    }
+}
+
+#[test]
+fn test_ignore_garbage_only_garbage() {
+    for (backend, engine) in pinned(STANDARD) {
+        for len in [0, 1, 64, 5_000] {
+            assert_eq!(
+                decode(engine, &vec![b'\n'; len]),
+                Ok(Vec::new()),
+                "{backend}"
+            );
+        }
+    }
+}
+
+// ======================================================================
+// 3. Coverage: Buffers
+// ======================================================================
+
+#[test]
+fn test_ignore_garbage_empty_input() {
+    // As for `decode_into`, empty input needs no room at all.
+    for (backend, engine) in pinned(STANDARD) {
+        assert_eq!(
+            engine.decode_ignore_garbage_into("", &mut []),
+            Ok(0),
+            "{backend}"
+        );
+    }
+}
+
+#[test]
+fn test_ignore_garbage_buffer_too_small() {
+    // The bound is on the bytes decoded, not on the input, garbage and all.
+    for (backend, engine) in pinned(STANDARD) {
+        let input = b"\n\n\n\nQUJD";
+        let mut buf = [0u8; 3];
+        assert_eq!(
+            engine.decode_ignore_garbage_into(input, &mut buf[..2]),
+            Err(Error::BufferTooSmall),
+            "{backend}"
+        );
+        assert_eq!(
+            engine.decode_ignore_garbage_into(input, &mut buf),
+            Ok(3),
+            "{backend}"
+        );
+        assert_eq!(&buf, b"ABC");
+    }
+}
+
+#[test]
+fn test_ignore_garbage_exact_buffer() {
+    // Exactly the decoded length, and one short, on either side of the 4 KiB
+    // pieces and for every final group's shape.
+    for (base, oracle) in ENGINES {
+        for (backend, engine) in pinned(base) {
+            for len in [1, 2, 3, 4, 47, 48, 3_071, 3_072, 3_073, 3_074, 10_000] {
+                let data = random_bytes(len);
+                let input = with_garbage(engine, oracle(&data).as_bytes(), 3);
+                let mut out = vec![0u8; len];
+                assert_eq!(
+                    engine.decode_ignore_garbage_into(&input, &mut out),
+                    Ok(len),
+                    "{backend}: {len}"
+                );
+                assert_eq!(out, data, "{backend}: {len}");
+                assert_eq!(
+                    engine.decode_ignore_garbage_into(&input, &mut out[..len - 1]),
+                    Err(Error::BufferTooSmall),
+                    "{backend}: {len}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! Integration tests verifying the `base64_turbo::LENIENT` engine against the reference `base64`
+//! crate, on every backend.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64::Engine as _;
+use base64::engine::general_purpose;
+use base64_turbo::hex::HEX_LOWER;
+use base64_turbo::{
+    Backend, Engine, Error, LENIENT, STANDARD, STANDARD_NO_PAD, URL_SAFE, base64_to_hex,
+    thresholds, transcode,
+};
+use common::{pinned, random_bytes};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// `LENIENT` pinned to every backend this build and CPU support.
+fn engines() -> impl Iterator<Item = (Backend, Engine)> {
+    pinned(|backend| LENIENT.with_backend(backend))
+}
+
+/// `engine.decode_into` on a buffer of the estimated length, trimmed to what
+/// it wrote.
+fn decode(engine: Engine, input: impl AsRef<[u8]>) -> Result<Vec<u8>, Error> {
+    let input = input.as_ref();
+    let mut out = vec![0u8; engine.estimate_decoded_len(input.len())];
+    let n = engine.decode_into(input, &mut out)?;
+    out.truncate(n);
+    Ok(out)
+}
+
+/// The padded standard encoding of `data`, with the two alphabets mixed:
+/// every other `+` or `/` swapped for its URL-safe twin.
+fn mixed(data: &[u8]) -> String {
+    general_purpose::STANDARD
+        .encode(data)
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '+' if i % 2 == 0 => '-',
+            '/' if i % 2 == 1 => '_',
+            c => c,
+        })
+        .collect()
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_lenient_decodes_both_alphabets() {
+    for (backend, engine) in engines() {
+        for len in (0..=200).chain([1024, 4099, 100_003]) {
+            let data = random_bytes(len);
+            for input in [
+                general_purpose::STANDARD.encode(&data),
+                general_purpose::URL_SAFE.encode(&data),
+                mixed(&data),
+            ] {
+                assert_eq!(decode(engine, &input).unwrap(), data, "{backend}: {len}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_lenient_padding_optional() {
+    for (backend, engine) in engines() {
+        for len in 0..=100 {
+            let data = random_bytes(len);
+            let input = mixed(&data);
+            let unpadded = input.trim_end_matches('=');
+            assert_eq!(decode(engine, unpadded).unwrap(), data, "{backend}: {len}");
+        }
+    }
+}
+
+/// The nibble-LUT tiers decode `LENIENT` on their own kernels, not through
+/// scalar: every input is at least the backend's `decode_min` long.
+#[test]
+fn test_lenient_nibble_kernels() {
+    let tiers = [
+        Backend::Avx512Bw,
+        Backend::Avx2,
+        Backend::Ssse3,
+        Backend::Neon,
+    ];
+    for (backend, engine) in engines().filter(|(backend, _)| tiers.contains(backend)) {
+        let min = thresholds().decode_min(backend).unwrap();
+        for len in (min.div_ceil(4) * 3..).take(200).chain([4099, 100_003]) {
+            let data = random_bytes(len);
+            let input = mixed(&data);
+            assert!(input.len() >= min, "{backend}: {len} is below decode_min");
+            assert_eq!(decode(engine, &input).unwrap(), data, "{backend}: {len}");
+            let unpadded = input.trim_end_matches('=');
+            assert_eq!(decode(engine, unpadded).unwrap(), data, "{backend}: {len}");
+        }
+    }
+}
+
+#[test]
+fn test_lenient_encodes_standard_no_pad() {
+    for len in 0..=100 {
+        let data = random_bytes(len);
+        let mut lenient = vec![0u8; LENIENT.encoded_len(len)];
+        let mut strict = vec![0u8; STANDARD_NO_PAD.encoded_len(len)];
+        let n = LENIENT.encode_into(&data, &mut lenient).unwrap();
+        let m = STANDARD_NO_PAD.encode_into(&data, &mut strict).unwrap();
+        assert_eq!(lenient[..n], strict[..m], "{len}");
+    }
+}
+
+// ======================================================================
+// 2. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_lenient_reject_matches_scalar() {
+    let data = random_bytes(150);
+    let valid = mixed(&data).into_bytes();
+    let scalar = LENIENT.with_backend(Backend::Scalar).unwrap();
+    for (backend, engine) in engines() {
+        for at in [0, 1, 63, 64, 100, valid.len() - 5, valid.len() - 1] {
+            for bad in [b'.', b' ', b'*', b'\n', 0x80, 0xFF] {
+                let mut input = valid.clone();
+                input[at] = bad;
+                let expected = decode(scalar, &input);
+                assert!(expected.is_err(), "scalar accepted {input:?}");
+                assert_eq!(decode(engine, &input), expected, "{backend}: {at}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_strict_engines_stay_strict() {
+    let mut buf = [0u8; 9];
+    assert_eq!(
+        STANDARD.decode_into("-_+/-_+/", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+    assert_eq!(
+        URL_SAFE.decode_into("-_+/-_+/", &mut buf),
+        Err(Error::InvalidCharacter)
+    );
+}
+
+// ======================================================================
+// 3. Coverage: Transcoding
+// ======================================================================
+
+#[test]
+fn test_lenient_transcode_source() {
+    for len in 0..=100 {
+        let data = random_bytes(len);
+        let input = mixed(&data);
+
+        let expected = general_purpose::URL_SAFE.encode(&data);
+        let mut out = vec![0u8; expected.len()];
+        let n = transcode(&LENIENT, &URL_SAFE, &input, &mut out).expect("transcode failed");
+        assert_eq!(&out[..n], expected.as_bytes(), "{len}");
+
+        let expected = hex::encode(&data);
+        let mut out = vec![0u8; expected.len()];
+        let n = base64_to_hex(&LENIENT, &HEX_LOWER, &input, &mut out).expect("to hex failed");
+        assert_eq!(&out[..n], expected.as_bytes(), "{len}");
+    }
+}
@@ -0,0 +1,98 @@
+//! The backend cap is process-global and locked in on first use, so it gets a
+//! test binary (and so a process) of its own, with a single test that sets it
+//! before anything else can trigger detection.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+use base64_turbo::{Backend, STANDARD, active_backend, set_max_backend};
+
+/// Whether the target guarantees the best x86 kernel compiled in, which,
+/// without `std`, fixes the tier at compile time and leaves nothing to cap.
+const STATIC_TIER: bool = cfg!(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(
+        all(
+            feature = "avx512-vbmi",
+            target_feature = "avx512f",
+            target_feature = "avx512bw",
+            target_feature = "avx512vbmi"
+        ),
+        all(
+            not(feature = "avx512-vbmi"),
+            feature = "avx512bw",
+            target_feature = "avx512f",
+            target_feature = "avx512bw"
+        ),
+        all(
+            not(feature = "avx512-vbmi"),
+            not(feature = "avx512bw"),
+            feature = "avx2",
+            target_feature = "avx2"
+        ),
+        all(
+            not(feature = "avx512-vbmi"),
+            not(feature = "avx512bw"),
+            not(feature = "avx2"),
+            feature = "ssse3",
+            target_feature = "ssse3"
+        )
+    )
+));
+
+#[test]
+fn test_max_backend_caps_detection() {
+    if STATIC_TIER && !cfg!(feature = "std") {
+        let active = active_backend();
+        assert_ne!(active, Backend::Scalar);
+        assert_eq!(set_max_backend(Backend::Scalar), Err(active));
+        return;
+    }
+
+    // x86 detection reads the variable when it first runs, and the lower of
+    // the two caps wins, so the `Avx512Vbmi` cap below cannot undo it.
+    // Without `std` there is no environment, so the cap itself goes to scalar.
+    let x86 = cfg!(any(target_arch = "x86", target_arch = "x86_64"));
+    #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), feature = "std"))]
+    // SAFETY: this is the only test in the binary, so no other thread is
+    // reading the environment concurrently.
+    unsafe {
+        std::env::set_var("BASE64_TURBO_MAX_BACKEND", " Scalar ");
+    }
+    let cap = if x86 && !cfg!(feature = "std") {
+        Backend::Scalar
+    } else {
+        Backend::Avx512Vbmi
+    };
+
+    // Off x86 detection, the portable kernel ranks below every other, so only
+    // a scalar cap rules it out.
+    let x86_detect = x86
+        && cfg!(any(
+            feature = "ssse3",
+            feature = "avx2",
+            feature = "avx512bw",
+            feature = "avx512-vbmi"
+        ));
+    let expected = if cap != Backend::Scalar && !x86_detect && Backend::Portable.is_supported() {
+        Backend::Portable
+    } else {
+        Backend::Scalar
+    };
+
+    assert_eq!(set_max_backend(cap), Ok(()));
+    assert_eq!(active_backend(), expected);
+    assert_eq!(STANDARD.backend(), expected);
+    let mut buf = [0u8; 88];
+    let n = STANDARD.encode_into([0xFB; 64], &mut buf).unwrap();
+    assert_eq!(&buf[..n], ("+/v7".repeat(21) + "+w==").as_bytes());
+
+    // Pinned engines are the caller's explicit choice and ignore the cap.
+    if Backend::Avx2.is_supported() {
+        let avx2 = STANDARD.with_backend(Backend::Avx2).unwrap();
+        assert_eq!(avx2.backend(), Backend::Avx2);
+    }
+
+    // On x86 the choice is now locked in; a later cap is refused.
+    if x86_detect {
+        assert_eq!(set_max_backend(Backend::Avx2), Err(Backend::Scalar));
+    }
+}
@@ -0,0 +1,176 @@
+//! Integration tests verifying `base64_turbo::radix` against the reference `bs58` and
+//! `base62` crates.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64_turbo::radix::{BASE58, BASE58_FLICKR, BASE58_RIPPLE, BASE62, Engine};
+use common::{
+    assert_buffer_too_small, assert_lens, assert_oracle_match, assert_rejects_chars,
+    oracle_exhaustive_small, random_bytes,
+};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// The Base58 engines, paired with the `bs58` alphabet that matches each.
+const BASE58_ENGINES: [(Engine, &bs58::Alphabet); 3] = [
+    (BASE58, bs58::Alphabet::BITCOIN),
+    (BASE58_RIPPLE, bs58::Alphabet::RIPPLE),
+    (BASE58_FLICKR, bs58::Alphabet::FLICKR),
+];
+
+/// Verifies every Base58 engine against `bs58`.
+#[track_caller]
+fn assert_bs58_match(input: &[u8]) {
+    for (engine, alphabet) in BASE58_ENGINES {
+        let expected = bs58::encode(input).with_alphabet(alphabet).into_string();
+        assert_oracle_match(engine, input, &expected);
+    }
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_radix_base58_oracle_exhaustive_small() {
+    oracle_exhaustive_small(200, assert_bs58_match);
+}
+
+#[test]
+fn test_radix_base58_leading_zeros() {
+    for zeros in 0..=20 {
+        for len in [0, 1, 7, 8, 9, 33] {
+            let mut data = vec![0u8; zeros];
+            data.extend(random_bytes(len).iter().map(|&b| b | 1));
+            assert_bs58_match(&data);
+        }
+    }
+    let mut buf = [0u8; 16];
+    let n = BASE58.encode_into(b"\0\0\0", &mut buf).unwrap();
+    assert_eq!(&buf[..n], b"111");
+}
+
+#[test]
+fn test_radix_base58_limb_edges() {
+    // Inputs whose digits or bytes fill whole limbs, or just spill over.
+    for len in [7, 8, 9, 15, 16, 17, 63, 64, 65] {
+        assert_bs58_match(&vec![0xFF; len]);
+        let mut one = vec![0u8; len];
+        one[0] = 1;
+        assert_bs58_match(&one);
+    }
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_radix_base58_oracle_fuzz_large() {
+    for len in [1024, 4099] {
+        assert_bs58_match(&random_bytes(len));
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_radix_base62_oracle() {
+    // `base62` encodes integers: a big-endian input without leading zeros is
+    // the same number.
+    for len in 1..=16 {
+        for _ in 0..100 {
+            let mut data = random_bytes(len);
+            data[0] |= 1;
+            let mut word = [0u8; 16];
+            word[16 - len..].copy_from_slice(&data);
+            let expected = base62::encode(u128::from_be_bytes(word));
+            assert_oracle_match(BASE62, &data, &expected);
+        }
+    }
+    assert_oracle_match(BASE62, &u128::MAX.to_be_bytes(), &base62::encode(u128::MAX));
+}
+
+#[test]
+fn test_radix_base62_round_trip() {
+    for len in (0..=200).chain([1024, 4099]) {
+        let mut data = random_bytes(len);
+        if len > 4 {
+            data[..len / 4].fill(0);
+        }
+        let mut enc_buf = vec![0u8; BASE62.encoded_len(len)];
+        let enc_len = BASE62.encode_into(&data, &mut enc_buf).unwrap();
+        let mut dec_buf = vec![0u8; BASE62.estimate_decoded_len(enc_len)];
+        let dec_len = BASE62
+            .decode_into(&enc_buf[..enc_len], &mut dec_buf)
+            .unwrap();
+        assert_eq!(&dec_buf[..dec_len], data, "len {len}");
+    }
+}
+
+// ======================================================================
+// 2. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_radix_lens() {
+    for (engine, alphabet) in BASE58_ENGINES {
+        assert_lens(engine, 100, |data| {
+            bs58::encode(data).with_alphabet(alphabet).into_string()
+        });
+    }
+    for len in 0..=100 {
+        let zeros = vec![0u8; len];
+        let ones = vec![0xFF; len];
+        for engine in [BASE58, BASE62] {
+            for data in [&zeros, &ones] {
+                let mut buf = vec![0u8; engine.encoded_len(len)];
+                let n = engine.encode_into(data, &mut buf).unwrap();
+                assert!(engine.estimate_decoded_len(n) >= len);
+            }
+        }
+    }
+    // A 32-byte key is at most 44 characters, and 44 decode to 32 bytes.
+    assert_eq!(BASE58.encoded_len(32), 44);
+    assert_eq!(BASE58.encode_into([0xFF; 32], &mut [0u8; 44]), Ok(44));
+    assert!(BASE58.estimate_decoded_len(44) >= 32);
+}
+
+#[test]
+fn test_radix_encoded_len_is_exact_max() {
+    // All `0xFF` is the largest number of each length, and encodes into a
+    // buffer of exactly `encoded_len`, filling it.
+    for len in 0..=300 {
+        let ones = vec![0xFF; len];
+        for engine in [BASE58, BASE62] {
+            let mut buf = vec![0u8; engine.encoded_len(len)];
+            assert_eq!(engine.encode_into(&ones, &mut buf), Ok(buf.len()), "{len}");
+        }
+        let expected = bs58::encode(&ones).into_string();
+        assert_eq!(BASE58.encoded_len(len), expected.len(), "{len}");
+    }
+}
+
+#[test]
+fn test_radix_buffer_too_small() {
+    for engine in [BASE58, BASE62] {
+        for len in [1, 5, 32, 100] {
+            assert_buffer_too_small(engine, &random_bytes(len));
+        }
+    }
+}
+
+// ======================================================================
+// 3. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_radix_reject_invalid_chars() {
+    for (engine, bad) in [
+        (BASE58, b"0OIl+/\0\x80"),
+        (BASE58_RIPPLE, b"0OIl+/\0\x80"),
+        (BASE58_FLICKR, b"0OIl+/\0\x80"),
+        (BASE62, b"+/-_= \0\x80"),
+    ] {
+        assert_rejects_chars(engine, &[1, 10, 11, 50], b'2', bad);
+    }
+}
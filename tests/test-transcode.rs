@@ -0,0 +1,402 @@
+//! Integration tests verifying `base64_turbo::transcode` and the Base64/hex conversions against
+//! the reference `base64` and `hex` crates.
+#![allow(clippy::unwrap_used, clippy::expect_used, missing_docs)]
+
+mod common;
+
+use base64::Engine as _;
+use base64::engine::general_purpose;
+use base64_turbo::hex::{HEX_LOWER, HEX_UPPER};
+use base64_turbo::{
+    Engine, Error, STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD, base64_to_hex,
+    hex_to_base64, transcode, transcode_in_place,
+};
+use common::{pinned, random_bytes};
+
+// ======================================================================
+// Helpers
+// ======================================================================
+
+/// An oracle encoder for one engine.
+type Oracle = fn(&[u8]) -> String;
+
+/// The four Base64 engines, paired with their oracles.
+const ENGINES: [(Engine, Oracle); 4] = [
+    (STANDARD, |x| general_purpose::STANDARD.encode(x)),
+    (STANDARD_NO_PAD, |x| {
+        general_purpose::STANDARD_NO_PAD.encode(x)
+    }),
+    (URL_SAFE, |x| general_purpose::URL_SAFE.encode(x)),
+    (URL_SAFE_NO_PAD, |x| {
+        general_purpose::URL_SAFE_NO_PAD.encode(x)
+    }),
+];
+
+/// `to.encode(from.decode(input)?)`, which `transcode` must equal, error
+/// included.
+fn reference(from: Engine, to: Engine, input: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![0u8; from.estimate_decoded_len(input.len())];
+    let n = from.decode_into(input, &mut bytes)?;
+    let mut chars = vec![0u8; to.encoded_len(n)];
+    let m = to.encode_into(&bytes[..n], &mut chars)?;
+    chars.truncate(m);
+    Ok(chars)
+}
+
+/// Checks both forms of `from` -> `to` on `input` against [`reference`].
+#[track_caller]
+fn assert_reference_match(from: Engine, to: Engine, input: &[u8]) {
+    let expected = reference(from, to, input);
+
+    let mut out = vec![0u8; input.len() + 2];
+    let got = transcode(&from, &to, input, &mut out).map(|n| out[..n].to_vec());
+    assert_eq!(got, expected, "transcode mismatch");
+
+    let mut buf = input.to_vec();
+    buf.resize(input.len() + 2, 0);
+    let got = transcode_in_place(&from, &to, &mut buf, input.len()).map(|n| buf[..n].to_vec());
+    assert_eq!(got, expected, "transcode_in_place mismatch");
+}
+
+// ======================================================================
+// 1. Coverage: Oracle Matching
+// ======================================================================
+
+#[test]
+fn test_transcode_oracle_all_pairs() {
+    for len in 0..=200 {
+        let data = random_bytes(len);
+        for (from, from_oracle) in ENGINES {
+            let input = from_oracle(&data);
+            for (to, to_oracle) in ENGINES {
+                let expected = to_oracle(&data);
+                let mut out = vec![0u8; expected.len()];
+                let n = transcode(&from, &to, &input, &mut out).expect("transcode failed");
+                assert_eq!(&out[..n], expected.as_bytes(), "{len}");
+                assert_reference_match(from, to, input.as_bytes());
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_transcode_oracle_fuzz_large() {
+    for len in [4096, 65_537, 1_000_003] {
+        let data = random_bytes(len);
+        for (from, from_oracle) in [ENGINES[0], ENGINES[3]] {
+            let input = from_oracle(&data);
+            for (to, to_oracle) in ENGINES {
+                let expected = to_oracle(&data);
+                let mut out = vec![0u8; expected.len()];
+                let n = transcode(&from, &to, &input, &mut out).expect("transcode failed");
+                assert_eq!(&out[..n], expected.as_bytes());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_transcode_with_backend_matches_oracle() {
+    for (base, from_oracle) in ENGINES {
+        for (backend, from) in pinned(|backend| base.with_backend(backend)) {
+            for len in (0..=100).chain([255, 1024, 4099]) {
+                let data = random_bytes(len);
+                let input = from_oracle(&data);
+                for (to, to_oracle) in ENGINES {
+                    let mut buf = input.clone().into_bytes();
+                    buf.resize(input.len() + 2, 0);
+                    let n = transcode_in_place(&from, &to, &mut buf, input.len())
+                        .expect("transcode_in_place failed");
+                    assert_eq!(&buf[..n], to_oracle(&data).as_bytes(), "{backend}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_transcode_padding_changes_length() {
+    let mut buf = *b"aGk\0";
+    assert_eq!(
+        transcode_in_place(&URL_SAFE_NO_PAD, &URL_SAFE, &mut buf, 3),
+        Ok(4)
+    );
+    assert_eq!(&buf, b"aGk=");
+    assert_eq!(
+        transcode_in_place(&URL_SAFE, &STANDARD_NO_PAD, &mut buf, 4),
+        Ok(3)
+    );
+    assert_eq!(&buf[..3], b"aGk");
+}
+
+// ======================================================================
+// 2. Coverage: Lengths & Buffers
+// ======================================================================
+
+#[test]
+fn test_transcode_buffer_too_small() {
+    // 7 characters become 8 with padding.
+    let mut out = [0u8; 7];
+    assert_eq!(
+        transcode(&URL_SAFE_NO_PAD, &STANDARD, "-_-_ab8", &mut out),
+        Err(Error::BufferTooSmall)
+    );
+    let mut buf = *b"-_-_ab8";
+    assert_eq!(
+        transcode_in_place(&URL_SAFE_NO_PAD, &STANDARD, &mut buf, 7),
+        Err(Error::BufferTooSmall)
+    );
+    // Without padding to add, the input's own length is enough.
+    assert_eq!(
+        transcode_in_place(&URL_SAFE_NO_PAD, &STANDARD_NO_PAD, &mut buf, 7),
+        Ok(7)
+    );
+    assert_eq!(&buf, b"+/+/ab8");
+}
+
+#[test]
+fn test_transcode_in_place_len_past_buf() {
+    let mut buf = *b"QUJD";
+    assert_eq!(
+        transcode_in_place(&STANDARD, &URL_SAFE, &mut buf, 5),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(&buf, b"QUJD");
+}
+
+// ======================================================================
+// 3. Coverage: Invalid Input
+// ======================================================================
+
+#[test]
+fn test_transcode_reject_matches_decode() {
+    let data = random_bytes(150);
+    for (base, from_oracle) in ENGINES {
+        for (_, from) in pinned(|backend| base.with_backend(backend)) {
+            let valid = from_oracle(&data).into_bytes();
+            for at in [0, 1, 63, 64, 100, valid.len() - 5, valid.len() - 1] {
+                for bad in [b'=', b'.', b' ', b'-', b'/', 0xAE] {
+                    let mut input = valid.clone();
+                    input[at] = bad;
+                    if bad == b'=' && at == valid.len() - 5 {
+                        // Padding before the final group: see below.
+                        continue;
+                    }
+                    for (to, _) in ENGINES {
+                        assert_reference_match(from, to, &input);
+                    }
+                }
+            }
+            for len in [1, 5, 66, 199] {
+                for (to, _) in ENGINES {
+                    assert_reference_match(from, to, &valid[..len]);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_transcode_reject_foreign_alphabet() {
+    let mut out = [0u8; 8];
+    assert_eq!(
+        transcode(&URL_SAFE, &STANDARD, "+/+/ab8=", &mut out),
+        Err(Error::InvalidCharacter)
+    );
+    assert_eq!(
+        transcode(&STANDARD, &URL_SAFE, "-_-_ab8=", &mut out),
+        Err(Error::InvalidCharacter)
+    );
+}
+
+#[test]
+fn test_transcode_reject_data_after_padding() {
+    // The scalar decoder stops at the first padded group; transcoding must not
+    // drop what follows it.
+    let mut out = [0u8; 16];
+    for (backend, from) in pinned(|backend| STANDARD.with_backend(backend)) {
+        for input in ["QUI=QUJD", "QQ==QUJD", "QUJDQUJDQUI=QUJD", "QUJDQUJDQQ==QQ"] {
+            assert_eq!(
+                transcode(&from, &URL_SAFE, input, &mut out),
+                Err(Error::InvalidCharacter),
+                "{backend}: {input}"
+            );
+        }
+    }
+}
+
+// ======================================================================
+// 4. Coverage: Base64 <-> Hex
+// ======================================================================
+
+/// Checks `base64_to_hex` against `hex.encode(from.decode(input)?)`, error
+/// included.
+#[track_caller]
+fn assert_base64_to_hex_match(from: Engine, to: base64_turbo::hex::Engine, input: &[u8]) {
+    let mut bytes = vec![0u8; from.estimate_decoded_len(input.len())];
+    let expected = from.decode_into(input, &mut bytes).map(|n| {
+        let mut digits = vec![0u8; to.encoded_len(n)];
+        to.encode_into(&bytes[..n], &mut digits).unwrap();
+        digits
+    });
+
+    let mut out = vec![0u8; input.len() / 4 * 6 + 6];
+    let got = base64_to_hex(&from, &to, input, &mut out).map(|n| out[..n].to_vec());
+    assert_eq!(got, expected, "base64_to_hex mismatch");
+}
+
+/// Checks `hex_to_base64` against `to.encode(hex.decode(input)?)`, error
+/// included.
+#[track_caller]
+fn assert_hex_to_base64_match(from: base64_turbo::hex::Engine, to: Engine, input: &[u8]) {
+    let mut bytes = vec![0u8; from.decoded_len(input.len())];
+    let expected = from.decode_into(input, &mut bytes).map(|n| {
+        let mut chars = vec![0u8; to.encoded_len(n)];
+        to.encode_into(&bytes[..n], &mut chars).unwrap();
+        chars
+    });
+
+    let mut out = vec![0u8; input.len() / 6 * 4 + 4];
+    let got = hex_to_base64(&from, &to, input, &mut out).map(|n| out[..n].to_vec());
+    assert_eq!(got, expected, "hex_to_base64 mismatch");
+}
+
+#[test]
+fn test_hex_oracle_all_pairs() {
+    for len in 0..=200 {
+        let data = random_bytes(len);
+        for (engine, oracle) in ENGINES {
+            let chars = oracle(&data);
+            for (hex_engine, digits) in [
+                (HEX_LOWER, hex::encode(&data)),
+                (HEX_UPPER, hex::encode_upper(&data)),
+            ] {
+                let mut out = vec![0u8; digits.len()];
+                let n = base64_to_hex(&engine, &hex_engine, &chars, &mut out)
+                    .expect("base64_to_hex failed");
+                assert_eq!(&out[..n], digits.as_bytes(), "{len}");
+
+                let mut out = vec![0u8; chars.len()];
+                let n = hex_to_base64(&hex_engine, &engine, &digits, &mut out)
+                    .expect("hex_to_base64 failed");
+                assert_eq!(&out[..n], chars.as_bytes(), "{len}");
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(not(miri))]
+fn test_hex_oracle_fuzz_large() {
+    for len in [4096, 65_537, 1_000_003] {
+        let data = random_bytes(len);
+        let digits = hex::encode(&data);
+        for (engine, oracle) in [ENGINES[0], ENGINES[3]] {
+            let chars = oracle(&data);
+            let mut out = vec![0u8; digits.len()];
+            let n = base64_to_hex(&engine, &HEX_LOWER, &chars, &mut out).unwrap();
+            assert_eq!(&out[..n], digits.as_bytes());
+
+            let mut out = vec![0u8; chars.len()];
+            let n = hex_to_base64(&HEX_LOWER, &engine, &digits, &mut out).unwrap();
+            assert_eq!(&out[..n], chars.as_bytes());
+        }
+    }
+}
+
+#[test]
+fn test_hex_with_backend_matches_oracle() {
+    for (base, oracle) in ENGINES {
+        for (backend, engine) in pinned(|backend| base.with_backend(backend)) {
+            let hex_engine = HEX_UPPER.with_backend(backend).unwrap();
+            for len in (0..=100).chain([255, 1024, 4099]) {
+                let data = random_bytes(len);
+                let chars = oracle(&data);
+                let digits = hex::encode_upper(&data);
+
+                let mut out = vec![0u8; digits.len()];
+                let n = base64_to_hex(&engine, &hex_engine, &chars, &mut out).unwrap();
+                assert_eq!(&out[..n], digits.as_bytes(), "{backend}");
+
+                let mut out = vec![0u8; chars.len()];
+                let n = hex_to_base64(&hex_engine, &engine, &digits, &mut out).unwrap();
+                assert_eq!(&out[..n], chars.as_bytes(), "{backend}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hex_trace_ids() {
+    // A W3C trace ID and a SHA-256 digest, as their APIs send them.
+    let mut buf = [0u8; 64];
+    let n = base64_to_hex(&STANDARD, &HEX_LOWER, "S/kvNXezTaajzpKdDg5HNg==", &mut buf).unwrap();
+    assert_eq!(&buf[..n], b"4bf92f3577b34da6a3ce929d0e0e4736");
+    let n = hex_to_base64(
+        &HEX_LOWER,
+        &STANDARD,
+        "4bf92f3577b34da6a3ce929d0e0e4736",
+        &mut buf,
+    )
+    .unwrap();
+    assert_eq!(&buf[..n], b"S/kvNXezTaajzpKdDg5HNg==");
+
+    let digest = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    let n = hex_to_base64(&HEX_LOWER, &URL_SAFE_NO_PAD, digest, &mut buf).unwrap();
+    assert_eq!(&buf[..n], b"47DEQpj8HBSa-_TImW-5JCeuQeRkm5NMpJWZG3hSuFU");
+}
+
+#[test]
+fn test_hex_buffer_too_small() {
+    let mut buf = [0u8; 7];
+    assert_eq!(
+        base64_to_hex(&STANDARD, &HEX_LOWER, "3q2+7w==", &mut buf),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(
+        hex_to_base64(&HEX_LOWER, &STANDARD, "deadbeef", &mut buf),
+        Err(Error::BufferTooSmall)
+    );
+    // Unpadded, the same bytes fit.
+    assert_eq!(
+        hex_to_base64(&HEX_LOWER, &STANDARD_NO_PAD, "deadbeef", &mut buf),
+        Ok(6)
+    );
+}
+
+#[test]
+fn test_hex_reject_matches_decode() {
+    let data = random_bytes(150);
+    for (backend, hex_engine) in pinned(|backend| HEX_LOWER.with_backend(backend)) {
+        for (base, oracle) in ENGINES {
+            let engine = base.with_backend(backend).unwrap();
+            let chars = oracle(&data).into_bytes();
+            for at in [0, 1, 63, 64, 100, chars.len() - 5, chars.len() - 1] {
+                for bad in [b'=', b'.', b' ', b'-', b'/', 0xAE] {
+                    if bad == b'=' && at == chars.len() - 5 {
+                        continue; // Padding before the final group, as above.
+                    }
+                    let mut input = chars.clone();
+                    input[at] = bad;
+                    assert_base64_to_hex_match(engine, hex_engine, &input);
+                }
+            }
+            for len in [1, 5, 66, 199] {
+                assert_base64_to_hex_match(engine, hex_engine, &chars[..len]);
+            }
+
+            let digits = hex::encode(&data).into_bytes();
+            for at in [0, 1, 63, 64, 95, 96, 200, digits.len() - 1] {
+                for bad in [b'g', b'G', b'/', b' ', 0xAE] {
+                    let mut input = digits.clone();
+                    input[at] = bad;
+                    assert_hex_to_base64_match(hex_engine, engine, &input);
+                }
+            }
+            for len in [1, 3, 97, 299] {
+                assert_hex_to_base64_match(hex_engine, engine, &digits[..len]);
+            }
+        }
+    }
+}